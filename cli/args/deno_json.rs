@@ -1,11 +1,17 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 
+use std::borrow::Cow;
+use std::collections::HashMap;
 use std::collections::HashSet;
 
+use deno_core::anyhow::Context;
+use deno_core::error::AnyError;
 use deno_core::serde_json;
 use deno_semver::jsr::JsrDepPackageReq;
 use deno_semver::jsr::JsrPackageReqReference;
 use deno_semver::npm::NpmPackageReqReference;
+use indexmap::IndexMap;
+use serde::Deserialize;
 
 pub fn deno_json_deps(
   config: &deno_config::ConfigFile,
@@ -49,3 +55,89 @@ fn values_to_set<'a>(
   }
   entries
 }
+
+/// A task of the configuration file along with the tasks that have to run
+/// before it.
+#[derive(Clone, Debug)]
+pub struct TaskConfig {
+  pub task: deno_config::Task,
+  pub dependencies: Vec<String>,
+  /// Globs of the files the task reads. When set, the task is skipped if
+  /// these files didn't change since a previous run.
+  pub inputs: Vec<String>,
+  /// Globs of the files the task writes, which are restored from the cache
+  /// when the task is skipped.
+  pub outputs: Vec<String>,
+}
+
+/// The object form of a task, like:
+///
+/// ```jsonc
+/// "build": {
+///   "command": "deno run -A build.ts",
+///   "dependencies": ["codegen", "lint"],
+///   "inputs": ["src/**/*.ts"],
+///   "outputs": ["dist/"]
+/// }
+/// ```
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TaskObject {
+  #[serde(default)]
+  command: String,
+  #[serde(default)]
+  dependencies: Vec<String>,
+  #[serde(default)]
+  inputs: Vec<String>,
+  #[serde(default)]
+  outputs: Vec<String>,
+}
+
+pub fn resolve_tasks_config(
+  config: &deno_config::ConfigFile,
+) -> Result<IndexMap<String, TaskConfig>, AnyError> {
+  let mut task_objects = HashMap::new();
+  let mut config = Cow::Borrowed(config);
+  if let Some(serde_json::Value::Object(tasks)) = &config.json.tasks {
+    if tasks.values().any(|value| value.is_object()) {
+      // replace the object form of the tasks with their command, which is
+      // what the configuration file knows how to resolve
+      let mut tasks = tasks.clone();
+      for (name, value) in tasks.iter_mut() {
+        if value.is_object() {
+          let mut task = serde_json::from_value::<TaskObject>(value.take())
+            .with_context(|| format!("Invalid task \"{}\".", name))?;
+          *value = serde_json::Value::String(std::mem::take(&mut task.command));
+          task_objects.insert(name.clone(), task);
+        }
+      }
+      config.to_mut().json.tasks = Some(serde_json::Value::Object(tasks));
+    }
+  }
+  let tasks = config.resolve_tasks_config()?;
+  Ok(
+    tasks
+      .into_iter()
+      .map(|(name, task)| {
+        let task_object = task_objects.remove(&name);
+        let (dependencies, inputs, outputs) = match task_object {
+          Some(task_object) => (
+            task_object.dependencies,
+            task_object.inputs,
+            task_object.outputs,
+          ),
+          None => Default::default(),
+        };
+        (
+          name,
+          TaskConfig {
+            task,
+            dependencies,
+            inputs,
+            outputs,
+          },
+        )
+      })
+      .collect(),
+  )
+}
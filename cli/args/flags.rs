@@ -50,6 +50,17 @@ pub struct AddFlags {
   pub packages: Vec<String>,
 }
 
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct RemoveFlags {
+  pub packages: Vec<String>,
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct OutdatedFlags {
+  pub update: bool,
+  pub latest: bool,
+}
+
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
 pub struct BenchFlags {
   pub files: FileFlags,
@@ -59,11 +70,27 @@ pub struct BenchFlags {
   pub watch: Option<WatchFlags>,
 }
 
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum BundlePlatform {
+  Browser,
+  #[default]
+  Deno,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BundleSourceMap {
+  External,
+  Inline,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct BundleFlags {
   pub source_file: String,
   pub out_file: Option<String>,
   pub watch: Option<WatchFlags>,
+  pub minify: bool,
+  pub source_map: Option<BundleSourceMap>,
+  pub platform: BundlePlatform,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -74,6 +101,7 @@ pub struct CacheFlags {
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct CheckFlags {
   pub files: Vec<String>,
+  pub watch: Option<WatchFlags>,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -84,6 +112,8 @@ pub struct CompileFlags {
   pub target: Option<String>,
   pub no_terminal: bool,
   pub include: Vec<String>,
+  pub compress: bool,
+  pub code_cache: bool,
 }
 
 impl CompileFlags {
@@ -136,12 +166,35 @@ pub struct DocHtmlFlag {
   pub output: String,
 }
 
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DocMarkdownFlag {
+  pub name: Option<String>,
+  pub output: String,
+  /// Whether to write a file per symbol instead of per module.
+  pub per_symbol: bool,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DocManFlag {
+  pub name: Option<String>,
+  pub output: String,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DocCoverageFlag {
+  /// fail when the total coverage is below this percentage
+  pub min_coverage: Option<u8>,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct DocFlags {
   pub private: bool,
   pub json: bool,
   pub lint: bool,
   pub html: Option<DocHtmlFlag>,
+  pub markdown: Option<DocMarkdownFlag>,
+  pub man: Option<DocManFlag>,
+  pub coverage: Option<DocCoverageFlag>,
   pub source_files: DocSourceFileFlag,
   pub filter: Option<String>,
 }
@@ -175,12 +228,29 @@ impl FmtFlags {
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct InitFlags {
   pub dir: Option<String>,
+  pub template: Option<String>,
+  pub yes: bool,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum InfoGraphFormat {
+  Dot,
+  Mermaid,
+  Html,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct InfoFlags {
   pub json: bool,
   pub file: Option<String>,
+  pub unused_exports: bool,
+  pub standalone: bool,
+  pub extract: Option<String>,
+  pub format: Option<InfoGraphFormat>,
+  pub cycles: bool,
+  pub sizes: bool,
+  pub depth: Option<usize>,
+  pub exclude: Vec<String>,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -192,10 +262,14 @@ pub struct InstallFlagsGlobal {
   pub force: bool,
 }
 
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InstallFlagsLocal {
+  pub frozen: bool,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum InstallKind {
-  #[allow(unused)]
-  Local,
+  Local(InstallFlagsLocal),
   Global(InstallFlagsGlobal),
 }
 
@@ -317,6 +391,10 @@ pub struct WatchFlagsWithPaths {
 pub struct TaskFlags {
   pub cwd: Option<String>,
   pub task: Option<String>,
+  pub parallel: bool,
+  pub sequential: bool,
+  pub recursive: bool,
+  pub filter: Option<String>,
 }
 
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
@@ -331,6 +409,7 @@ pub enum TestReporterConfig {
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
 pub struct TestFlags {
   pub doc: bool,
+  pub run_doc: bool,
   pub no_run: bool,
   pub coverage_dir: Option<String>,
   pub fail_fast: Option<NonZeroUsize>,
@@ -373,6 +452,7 @@ pub struct PublishFlags {
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum DenoSubcommand {
   Add(AddFlags),
+  Remove(RemoveFlags),
   Bench(BenchFlags),
   Bundle(BundleFlags),
   Cache(CacheFlags),
@@ -390,6 +470,7 @@ pub enum DenoSubcommand {
   Uninstall(UninstallFlags),
   Lsp,
   Lint(LintFlags),
+  Outdated(OutdatedFlags),
   Repl(ReplFlags),
   Run(RunFlags),
   Serve(ServeFlags),
@@ -513,6 +594,7 @@ pub struct Flags {
   pub inspect_brk: Option<SocketAddr>,
   pub inspect_wait: Option<SocketAddr>,
   pub inspect: Option<SocketAddr>,
+  pub inspect_dap: Option<SocketAddr>,
   pub location: Option<Url>,
   pub lock_write: bool,
   pub lock: Option<String>,
@@ -911,10 +993,23 @@ impl Flags {
           .to_file_path()
           .ok()
       }
-      Task(_) | Check(_) | Coverage(_) | Cache(_) | Info(_) | Eval(_)
-      | Test(_) | Bench(_) | Repl(_) | Compile(_) | Publish(_) => {
-        std::env::current_dir().ok()
-      }
+      Task(_)
+      | Check(_)
+      | Coverage(_)
+      | Cache(_)
+      | Info(_)
+      | Eval(_)
+      | Test(_)
+      | Bench(_)
+      | Repl(_)
+      | Compile(_)
+      | Publish(_)
+      | Remove(_)
+      | Outdated(_)
+      | Install(InstallFlags {
+        kind: InstallKind::Local(_),
+        ..
+      }) => std::env::current_dir().ok(),
       Add(_) | Bundle(_) | Completions(_) | Doc(_) | Fmt(_) | Init(_)
       | Install(_) | Uninstall(_) | Jupyter(_) | Lsp | Lint(_) | Types
       | Upgrade(_) | Vendor(_) => None,
@@ -971,6 +1066,14 @@ impl Flags {
         }),
       ..
     })
+    | DenoSubcommand::Check(CheckFlags {
+      watch:
+        Some(WatchFlags {
+          exclude: excluded_paths,
+          ..
+        }),
+      ..
+    })
     | DenoSubcommand::Bundle(BundleFlags {
       watch:
         Some(WatchFlags {
@@ -1159,6 +1262,7 @@ pub fn flags_from_vec(args: Vec<OsString>) -> clap::error::Result<Flags> {
       "jupyter" => jupyter_parse(&mut flags, &mut m),
       "lint" => lint_parse(&mut flags, &mut m),
       "lsp" => lsp_parse(&mut flags, &mut m),
+      "outdated" => outdated_parse(&mut flags, &mut m),
       "repl" => repl_parse(&mut flags, &mut m),
       "run" => run_parse(&mut flags, &mut m, app)?,
       "serve" => serve_parse(&mut flags, &mut m, app)?,
@@ -1169,6 +1273,7 @@ pub fn flags_from_vec(args: Vec<OsString>) -> clap::error::Result<Flags> {
       "upgrade" => upgrade_parse(&mut flags, &mut m),
       "vendor" => vendor_parse(&mut flags, &mut m),
       "publish" => publish_parse(&mut flags, &mut m),
+      "remove" => remove_parse(&mut flags, &mut m),
       _ => unreachable!(),
     }
   } else {
@@ -1318,7 +1423,9 @@ fn clap_root() -> Command {
         .subcommand(uninstall_subcommand())
         .subcommand(lsp_subcommand())
         .subcommand(lint_subcommand())
+        .subcommand(outdated_subcommand())
         .subcommand(publish_subcommand())
+        .subcommand(remove_subcommand())
         .subcommand(repl_subcommand())
         .subcommand(task_subcommand())
         .subcommand(test_subcommand())
@@ -1354,6 +1461,66 @@ You can add multiple dependencies at once:
     })
 }
 
+fn remove_subcommand() -> Command {
+  Command::new("remove")
+    .alias("rm")
+    .about("Remove dependencies")
+    .long_about(
+      "Remove dependencies from the configuration file and the package.json,
+and prune the packages that are no longer used from the lockfile.
+
+  deno remove @std/path
+
+You can remove multiple dependencies at once:
+
+  deno remove @std/path npm:chalk
+",
+    )
+    .defer(|cmd| {
+      cmd.arg(
+        Arg::new("packages")
+          .help("List of packages to remove")
+          .required(true)
+          .num_args(1..)
+          .action(ArgAction::Append),
+      )
+    })
+}
+
+fn outdated_subcommand() -> Command {
+  Command::new("outdated")
+    .about("Find outdated dependencies")
+    .long_about(
+      "Find the dependencies of the configuration file and the package.json
+that have newer versions in the jsr and npm registries.
+
+  deno outdated
+
+Update the version constraints to the newest versions they allow, or with
+--latest to the latest versions:
+
+  deno outdated --update
+  deno outdated --update --latest
+",
+    )
+    .defer(|cmd| {
+      cmd
+        .arg(
+          Arg::new("update")
+            .long("update")
+            .help("Update the version constraints of the outdated dependencies")
+            .action(ArgAction::SetTrue),
+        )
+        .arg(
+          Arg::new("latest")
+            .long("latest")
+            .requires("update")
+            .help("Update to the latest versions, even when they don't match the version constraints")
+            .action(ArgAction::SetTrue),
+        )
+    })
+}
+
 fn bench_subcommand() -> Command {
   Command::new("bench")
     .about("Run benchmarks")
@@ -1417,22 +1584,29 @@ glob {*_,*.,}bench.{js,mjs,ts,mts,jsx,tsx}:
 
 fn bundle_subcommand() -> Command {
   Command::new("bundle")
-    .about("Bundle module and dependencies into single file")
+    .about("Bundle module and dependencies into an ES module")
     .long_about(
-      "⚠️ Warning: `deno bundle` is deprecated and will be removed in Deno 2.0.
-Use an alternative bundler like \"deno_emit\", \"esbuild\" or \"rollup\" instead.
-
-Output a single JavaScript file with all dependencies.
+      "Output an ES module with the given module and all its dependencies, for
+use in browsers, workers or Deno.
 
   deno bundle jsr:@std/http/file-server file_server.bundle.js
 
-If no output file is given, the output is written to standard output:
+Dynamically imported modules are written to separate chunks next to the
+output file, which are loaded when they're imported.
+
+If no output file is given, the output is written to standard output and
+dynamically imported modules are included in it:
 
-  deno bundle jsr:@std/http/file-server",
+  deno bundle jsr:@std/http/file-server
+
+Use '--platform=browser' to also bundle npm packages. Node built-in modules
+and modules that can't be resolved are replaced by modules that throw when
+they're imported, since they're not available in the browser.
+
+  deno bundle --platform=browser --minify --source-map main.ts dist/main.js",
     )
     .defer(|cmd| {
       compile_args(cmd)
-        .hide(true)
         .arg(check_arg(true))
         .arg(
           Arg::new("source_file")
@@ -1440,6 +1614,34 @@ If no output file is given, the output is written to standard output:
             .value_hint(ValueHint::FilePath),
         )
         .arg(Arg::new("out_file").value_hint(ValueHint::FilePath))
+        .arg(
+          Arg::new("minify")
+            .long("minify")
+            .help("Remove comments and whitespace from the output")
+            .action(ArgAction::SetTrue),
+        )
+        .arg(
+          Arg::new("source-map")
+            .long("source-map")
+            .help("Output a source map, written to a separate file by default")
+            .value_parser(["external", "inline"])
+            .num_args(0..=1)
+            .require_equals(true)
+            .default_missing_value("external"),
+        )
+        .arg(
+          Arg::new("platform")
+            .long("platform")
+            .help("The platform the bundle is for")
+            .long_help(
+              "The platform the bundle is for. For 'deno', npm packages and Node
+built-in modules are imported at runtime. For 'browser', npm packages are
+included in the bundle.",
+            )
+            .value_parser(["browser", "deno"])
+            .require_equals(true)
+            .default_value("deno"),
+        )
         .arg(watch_arg(false))
         .arg(watch_exclude_arg())
         .arg(no_clear_screen_arg())
@@ -1497,6 +1699,15 @@ Unless --reload is specified, this command will not re-download already cached d
           .conflicts_with("no-remote")
           .hide(true)
       )
+      .arg(
+        watch_arg(false)
+          .long_help(
+            "Watch for file changes and type-check again automatically.
+Changes to files that the given modules don't depend on are skipped.",
+          ),
+      )
+      .arg(no_clear_screen_arg())
+      .arg(watch_exclude_arg())
       .arg(
         Arg::new("file")
           .num_args(1..)
@@ -1537,15 +1748,20 @@ supported in canary.
       .arg(
         Arg::new("include")
           .long("include")
-          .help("Additional module to include in the module graph")
+          .help("Additional module or directory of assets to include in the executable")
           .long_help(
             "Includes an additional module in the compiled executable's module
     graph. Use this flag if a dynamically imported module or a web worker main
     module fails to load in the executable. This flag can be passed multiple
-    times, to include multiple additional modules.",
+    times, to include multiple additional modules.
+
+    When a directory is passed, its files are embedded in the executable as
+    assets instead. They can be read at runtime with APIs like Deno.readFile
+    and Deno.readDir, at the same paths relative to the entrypoint as when
+    compiling.",
           )
           .action(ArgAction::Append)
-          .value_hint(ValueHint::FilePath),
+          .value_hint(ValueHint::AnyPath),
       )
       .arg(
         Arg::new("output")
@@ -1573,6 +1789,28 @@ supported in canary.
           .help("Hide terminal on Windows")
           .action(ArgAction::SetTrue),
       )
+      .arg(
+        Arg::new("compress")
+          .long("compress")
+          .help("Compress the embedded modules and files with zstd")
+          .long_help(
+            "Compresses the embedded modules, npm packages and assets with
+zstd. This makes the executable smaller at the cost of decompressing
+them when they are first loaded.",
+          )
+          .action(ArgAction::SetTrue),
+      )
+      .arg(
+        Arg::new("code-cache")
+          .long("code-cache")
+          .help("Embed a V8 code cache of the modules in the executable")
+          .long_help(
+            "Compiles the modules ahead of time and embeds the resulting V8
+code cache in the executable, so they don't need to be parsed again
+on every start. Only supported when compiling for the current target.",
+          )
+          .action(ArgAction::SetTrue),
+      )
       .arg(executable_ext_arg())
       .arg(env_file_arg())
       .arg(script_arg().required(true).trailing_var_arg(true))
@@ -1724,6 +1962,15 @@ Output documentation in HTML format:
     deno doc --html --name=\"My library\" ./main.ts ./dev.ts
     deno doc --html --name=\"My library\" --output=./documentation/ ./path/to/module.ts
 
+Output documentation as Markdown files, one per module or one per symbol:
+
+    deno doc --markdown --output=./wiki/ ./path/to/module.ts
+    deno doc --markdown=symbol --output=./wiki/ ./path/to/module.ts
+
+Output man pages for the exported functions:
+
+    deno doc --man --name=\"My library\" --output=./man/ ./path/to/module.ts
+
 Output private documentation to standard output:
 
     deno doc --private ./path/to/module.ts
@@ -1736,6 +1983,11 @@ Lint a module for documentation diagnostics:
 
     deno doc --lint ./path/to/module.ts
 
+Report the documentation coverage of a module and fail when it's below 80%:
+
+    deno doc --lint --min-coverage=80 ./path/to/module.ts
+    deno doc --lint --coverage --json ./path/to/module.ts
+
 Target a specific symbol:
 
     deno doc ./path/to/module.ts MyClass.someField
@@ -1766,6 +2018,23 @@ Show documentation for runtime built-ins:
             .action(ArgAction::SetTrue)
             .conflicts_with("json")
         )
+        .arg(
+          Arg::new("markdown")
+            .long("markdown")
+            .help("Output documentation as Markdown files, one per module or one per symbol")
+            .num_args(0..=1)
+            .require_equals(true)
+            .value_parser(["module", "symbol"])
+            .default_missing_value("module")
+            .conflicts_with_all(["json", "html"])
+        )
+        .arg(
+          Arg::new("man")
+            .long("man")
+            .help("Output man pages for the exported functions")
+            .action(ArgAction::SetTrue)
+            .conflicts_with_all(["json", "html", "markdown"])
+        )
         .arg(
           Arg::new("name")
             .long("name")
@@ -1776,7 +2045,7 @@ Show documentation for runtime built-ins:
         .arg(
           Arg::new("output")
             .long("output")
-            .help("Directory for HTML documentation output")
+            .help("Directory for HTML, Markdown or man page output")
             .action(ArgAction::Set)
             .require_equals(true)
             .value_hint(ValueHint::DirPath)
@@ -1795,7 +2064,9 @@ Show documentation for runtime built-ins:
             .required(false)
             .conflicts_with("json")
             .conflicts_with("lint")
-            .conflicts_with("html"),
+            .conflicts_with("html")
+            .conflicts_with("markdown")
+            .conflicts_with("man"),
         )
         .arg(
           Arg::new("lint")
@@ -1803,6 +2074,25 @@ Show documentation for runtime built-ins:
             .help("Output documentation diagnostics.")
             .action(ArgAction::SetTrue),
         )
+        .arg(
+          Arg::new("coverage")
+            .long("coverage")
+            .help("Report the percentage of exported symbols, parameters and return values that are documented")
+            .long_help("Report the percentage of exported symbols, parameters and return values that are documented, per module and in total. Documentation diagnostics no longer fail the command, use --min-coverage to fail below a threshold instead.")
+            .requires("lint")
+            .conflicts_with_all(["html", "markdown", "man"])
+            .action(ArgAction::SetTrue),
+        )
+        .arg(
+          Arg::new("min-coverage")
+            .long("min-coverage")
+            .help("Report the documentation coverage and fail when it's below the given percentage")
+            .value_name("PERCENT")
+            .require_equals(true)
+            .value_parser(value_parser!(u8).range(0..=100))
+            .requires("lint")
+            .conflicts_with_all(["html", "markdown", "man"]),
+        )
         // TODO(nayeemrmn): Make `--builtin` a proper option. Blocked by
         // https://github.com/clap-rs/clap/issues/1794. Currently `--builtin` is
         // just a possible value of `source_file` so leading hyphens must be
@@ -1813,7 +2103,13 @@ Show documentation for runtime built-ins:
             .num_args(1..)
             .action(ArgAction::Append)
             .value_hint(ValueHint::FilePath)
-            .required_if_eq_any([("html", "true"), ("lint", "true")]),
+            .required_if_eq_any([
+              ("html", "true"),
+              ("lint", "true"),
+              ("markdown", "module"),
+              ("markdown", "symbol"),
+              ("man", "true"),
+            ]),
         )
     })
 }
@@ -1987,12 +2283,49 @@ Ignore formatting a file by adding an ignore comment at the top of the file:
 fn init_subcommand() -> Command {
   Command::new("init")
     .about("Initialize a new project")
+    .long_about(
+      "Initialize a new project.
+
+  deno init
+  deno init my_project
+
+Start from a template with --template. The built-in templates are
+'library' (a package for publishing to JSR), 'serve' (an HTTP app for
+'deno serve'), 'cli' (a command line tool) and 'npm' (an npm-compatible
+package):
+
+  deno init --template=serve my_app
+
+A template can also be a JSR package, a git repository or a local directory.
+The {{name}} and {{author}} variables in its files are substituted:
+
+  deno init --template=jsr:@scope/template
+  deno init --template=https://github.com/user/template.git
+  deno init --template=./path/to/template
+
+The package name and author are prompted for, unless --yes is passed.",
+    )
     .defer(|cmd| {
-      cmd.arg(
-        Arg::new("dir")
-          .required(false)
-          .value_hint(ValueHint::DirPath),
-      )
+      cmd
+        .arg(
+          Arg::new("dir")
+            .required(false)
+            .value_hint(ValueHint::DirPath),
+        )
+        .arg(
+          Arg::new("template")
+            .long("template")
+            .help("The template to create the project from")
+            .value_name("TEMPLATE")
+            .value_hint(ValueHint::Other),
+        )
+        .arg(
+          Arg::new("yes")
+            .long("yes")
+            .short('y')
+            .help("Use the default values instead of prompting")
+            .action(ArgAction::SetTrue),
+        )
     })
 }
 
@@ -2044,14 +2377,123 @@ TypeScript compiler cache: Subdirectory containing TS compiler output.",
           .long("json")
           .help("UNSTABLE: Outputs the information in JSON format")
           .action(ArgAction::SetTrue),
+      )
+      .arg(
+        Arg::new("unused-exports")
+          .long("unused-exports")
+          .help("UNSTABLE: Show the exports of local modules that are not used by any other module. Defaults to the \"exports\" of the configuration file as entry points when no file is given")
+          .conflicts_with("location")
+          .action(ArgAction::SetTrue),
+      )
+      .arg(
+        Arg::new("standalone")
+          .long("standalone")
+          .help("UNSTABLE: Show the contents of an executable produced by 'deno compile'")
+          .long_help(
+            "UNSTABLE: Show the contents of an executable produced by 'deno compile':
+the embedded modules, npm packages and files, as well as the permissions,
+V8 flags, import map and unstable features it runs with.
+
+  deno info --standalone ./my_cli",
+          )
+          .requires("file")
+          .conflicts_with_all(["location", "unused-exports"])
+          .action(ArgAction::SetTrue),
+      )
+      .arg(
+        Arg::new("extract")
+          .long("extract")
+          .help("UNSTABLE: Write the contents of the executable to the given directory")
+          .value_name("DIR")
+          .requires("standalone")
+          .value_hint(ValueHint::DirPath),
+      )
+      .arg(
+        Arg::new("format")
+          .long("format")
+          .help("UNSTABLE: Output the module graph in the given format")
+          .long_help(
+            "UNSTABLE: Output the module graph as a Graphviz 'dot' graph, a Mermaid
+flowchart or an HTML page that renders the flowchart.
+
+Modules are coloured by their origin (local, remote, jsr, npm or node) and
+sized by their byte size. Dynamic imports are shown as dashed arrows and
+imports that are part of an import cycle are shown in red.
+
+  deno info --format=dot main.ts | dot -Tsvg > graph.svg
+  deno info --format=html --depth=2 --exclude=npm:* main.ts > graph.html",
+          )
+          .value_parser(["dot", "mermaid", "html"])
+          .require_equals(true)
+          .requires("file")
+          .conflicts_with_all(["json", "unused-exports", "standalone"]),
+      )
+      .arg(
+        Arg::new("cycles")
+          .long("cycles")
+          .help("UNSTABLE: List the import cycles of the module graph")
+          .requires("file")
+          .conflicts_with_all(["json", "format", "unused-exports", "standalone"])
+          .action(ArgAction::SetTrue),
+      )
+      .arg(
+        Arg::new("sizes")
+          .long("sizes")
+          .help("UNSTABLE: Show the size of the code loaded by the given file")
+          .long_help(
+            "UNSTABLE: Show the size of the code loaded by the given file: the emitted
+JavaScript of each module and everything it imports, the size of each jsr and
+npm package, and the packages that are resolved at multiple versions. The
+minified and gzip sizes are estimates.
+
+Use '--format=html' to output a treemap of the modules.
+
+  deno info --sizes main.ts
+  deno info --sizes --format=html main.ts > sizes.html",
+          )
+          .requires("file")
+          .conflicts_with_all(["cycles", "unused-exports", "standalone"])
+          .action(ArgAction::SetTrue),
+      )
+      .arg(
+        Arg::new("depth")
+          .long("depth")
+          .help("UNSTABLE: Only show the modules up to this many imports away from the given file")
+          .value_name("DEPTH")
+          .require_equals(true)
+          .value_parser(value_parser!(usize))
+          .requires("file")
+          .conflicts_with_all(["json", "unused-exports", "standalone"]),
+      )
+      .arg(
+        Arg::new("exclude")
+          .long("exclude")
+          .help("UNSTABLE: Don't show the modules matching these patterns, where '*' matches any characters, or the dependencies only imported through them. For example: --exclude=npm:*")
+          .value_name("PATTERNS")
+          .num_args(1..)
+          .use_value_delimiter(true)
+          .require_equals(true)
+          .requires("file")
+          .conflicts_with_all(["json", "unused-exports", "standalone"]),
       ))
 }
 
 fn install_subcommand() -> Command {
   Command::new("install")
-    .about("Install script as an executable")
+    .about("Install dependencies or a script as an executable")
     .long_about(
-        "Installs a script as an executable in the installation root's bin directory.
+        "Installs the dependencies of the project when no script is given.
+
+  deno install
+  deno install --frozen
+
+Every jsr, npm and remote dependency referenced by the \"imports\" of the
+configuration file or import map, package.json and the lockfile is
+downloaded to the global cache, and to the local node_modules directory
+when \"nodeModulesDir\" is enabled. With --frozen, the install fails instead
+of updating an out of date lockfile.
+
+Installs a script as an executable in the installation root's bin directory.
 
   deno install --global --allow-net --allow-read jsr:@std/http/file-server
   deno install -g https://examples.deno.land/color-logging.ts
@@ -2078,24 +2520,27 @@ The installation root is determined, in order of precedence:
   - $HOME/.deno
 
 These must be added to the path manually if required.")
-    .defer(|cmd| runtime_args(cmd, true, true).arg(Arg::new("cmd").required(true).num_args(1..).value_hint(ValueHint::FilePath))
+    .defer(|cmd| runtime_args(cmd, true, true).arg(Arg::new("cmd").required(false).num_args(1..).value_hint(ValueHint::FilePath))
       .arg(check_arg(true))
       .arg(
         Arg::new("name")
           .long("name")
           .short('n')
           .help("Executable file name")
+          .requires("cmd")
           .required(false))
       .arg(
         Arg::new("root")
           .long("root")
           .help("Installation root")
+          .requires("cmd")
           .value_hint(ValueHint::DirPath))
       .arg(
         Arg::new("force")
           .long("force")
           .short('f')
           .help("Forcefully overwrite existing installation")
+          .requires("cmd")
           .action(ArgAction::SetTrue))
       )
       .arg(
@@ -2103,6 +2548,14 @@ These must be added to the path manually if required.")
           .long("global")
           .short('g')
           .help("Install a package or script as a globally available executable")
+          .requires("cmd")
+          .action(ArgAction::SetTrue)
+      )
+      .arg(
+        Arg::new("frozen")
+          .long("frozen")
+          .help("Error out if the lockfile is out of date instead of updating it")
+          .conflicts_with("cmd")
           .action(ArgAction::SetTrue)
       )
       .arg(env_file_arg())
@@ -2423,7 +2876,44 @@ fn task_subcommand() -> Command {
     .long_about(
       "Run a task defined in the configuration file
 
-  deno task build",
+  deno task build
+
+Tasks in the configuration file can depend on other tasks, which run before
+them, independent ones at the same time:
+
+  \"tasks\": {
+    \"build\": {
+      \"command\": \"deno run -A build.ts\",
+      \"dependencies\": [\"codegen\", \"lint\"]
+    }
+  }
+
+Tasks that declare their \"inputs\" are skipped when their input files, command
+and referenced environment variables didn't change since a previous run. Their
+\"outputs\" are then restored from the cache:
+
+  \"tasks\": {
+    \"codegen\": {
+      \"command\": \"deno run -A codegen.ts\",
+      \"inputs\": [\"schema/\"],
+      \"outputs\": [\"gen/\"]
+    }
+  }
+
+In a workspace, run a task in every member that defines it, or in the members
+matching a pattern. Members run after the members they import:
+
+  deno task --recursive test
+  deno task --filter=@scope/* test
+
+Run several tasks at the same time with --parallel, or one after another with
+--sequential. Arguments after a -- are then forwarded to the given tasks:
+
+  deno task --parallel dev:server dev:client
+  deno task --sequential build test -- --verbose
+
+Without either flag, \"deno task build test\" keeps running only the build task,
+with test forwarded to it as an argument, for backwards compatibility.",
     )
     .defer(|cmd| {
       cmd
@@ -2437,6 +2927,34 @@ fn task_subcommand() -> Command {
             .help("Specify the directory to run the task in")
             .value_hint(ValueHint::DirPath),
         )
+        .arg(
+          Arg::new("parallel")
+            .long("parallel")
+            .help("Run the given tasks at the same time")
+            .conflicts_with("sequential")
+            .action(ArgAction::SetTrue),
+        )
+        .arg(
+          Arg::new("sequential")
+            .long("sequential")
+            .help("Run the given tasks one after another")
+            .action(ArgAction::SetTrue),
+        )
+        .arg(
+          Arg::new("recursive")
+            .long("recursive")
+            .short('r')
+            .help("Run the task in every workspace member that defines it")
+            .conflicts_with("cwd")
+            .action(ArgAction::SetTrue),
+        )
+        .arg(
+          Arg::new("filter")
+            .long("filter")
+            .value_name("PATTERN")
+            .help("Run the task in the workspace members whose package or directory name matches the pattern, where * matches any characters")
+            .conflicts_with("cwd"),
+        )
     })
 }
 
@@ -2490,6 +3008,26 @@ Directory arguments are expanded to all contained files matching the glob
       Arg::new("doc")
         .long("doc")
         .help("Type-check code blocks in JSDoc and Markdown")
+        .long_help(
+          "Type-check code blocks in JSDoc and Markdown. Code blocks marked
+`ignore` are skipped.",
+        )
+        .action(ArgAction::SetTrue),
+    )
+    .arg(
+      Arg::new("run-doc")
+        .long("run-doc")
+        .help("Also run the code blocks in JSDoc and Markdown as tests")
+        .long_help(
+          "Also run the code blocks in JSDoc and Markdown as tests.
+
+An expression followed by a `// => expected` comment asserts its result, and
+an `output` block right after a code block asserts its console output.
+Code blocks marked `no_run` or `no-run` are only type-checked and `throws`
+ones pass when they throw. Code blocks of declaration files are only
+type-checked.",
+        )
+        .requires("doc")
         .action(ArgAction::SetTrue),
     )
     .arg(
@@ -2533,6 +3071,7 @@ Directory arguments are expanded to all contained files matching the glob
         .conflicts_with("inspect")
         .conflicts_with("inspect-wait")
         .conflicts_with("inspect-brk")
+        .conflicts_with("inspect-dap")
         .help("Collect coverage profile data into DIR. If DIR is not specified, it uses 'coverage/'."),
     )
     .arg(
@@ -3173,6 +3712,18 @@ fn inspect_args(app: Command) -> Command {
         .require_equals(true)
         .value_parser(value_parser!(SocketAddr)),
     )
+    .arg(
+      Arg::new("inspect-dap")
+        .long("inspect-dap")
+        .value_name("HOST_AND_PORT")
+        .help(
+          "Activate a Debug Adapter Protocol server on host:port, wait for a debugger to connect and break at the start of user script",
+        )
+        .num_args(0..=1)
+        .require_equals(true)
+        .value_parser(value_parser!(SocketAddr))
+        .conflicts_with_all(["inspect", "inspect-brk", "inspect-wait"]),
+    )
 }
 
 static IMPORT_MAP_HELP: &str = concat!(
@@ -3559,6 +4110,17 @@ fn add_parse(flags: &mut Flags, matches: &mut ArgMatches) {
   flags.subcommand = DenoSubcommand::Add(AddFlags { packages });
 }
 
+fn remove_parse(flags: &mut Flags, matches: &mut ArgMatches) {
+  let packages = matches.remove_many::<String>("packages").unwrap().collect();
+  flags.subcommand = DenoSubcommand::Remove(RemoveFlags { packages });
+}
+
+fn outdated_parse(flags: &mut Flags, matches: &mut ArgMatches) {
+  let update = matches.get_flag("update");
+  let latest = matches.get_flag("latest");
+  flags.subcommand = DenoSubcommand::Outdated(OutdatedFlags { update, latest });
+}
+
 fn bench_parse(flags: &mut Flags, matches: &mut ArgMatches) {
   flags.type_check_mode = TypeCheckMode::Local;
 
@@ -3617,10 +4179,24 @@ fn bundle_parse(flags: &mut Flags, matches: &mut ArgMatches) {
 
   ext_arg_parse(flags, matches);
 
+  let source_map = matches.remove_one::<String>("source-map").map(
+    |source_map| match source_map.as_str() {
+      "inline" => BundleSourceMap::Inline,
+      _ => BundleSourceMap::External,
+    },
+  );
+  let platform = match matches.remove_one::<String>("platform").as_deref() {
+    Some("browser") => BundlePlatform::Browser,
+    _ => BundlePlatform::Deno,
+  };
+
   flags.subcommand = DenoSubcommand::Bundle(BundleFlags {
     source_file,
     out_file,
     watch: watch_arg_parse(matches),
+    minify: matches.get_flag("minify"),
+    source_map,
+    platform,
   });
 }
 
@@ -3637,7 +4213,10 @@ fn check_parse(flags: &mut Flags, matches: &mut ArgMatches) {
   if matches.get_flag("all") || matches.get_flag("remote") {
     flags.type_check_mode = TypeCheckMode::All;
   }
-  flags.subcommand = DenoSubcommand::Check(CheckFlags { files });
+  flags.subcommand = DenoSubcommand::Check(CheckFlags {
+    files,
+    watch: watch_arg_parse(matches),
+  });
 }
 
 fn compile_parse(flags: &mut Flags, matches: &mut ArgMatches) {
@@ -3650,6 +4229,8 @@ fn compile_parse(flags: &mut Flags, matches: &mut ArgMatches) {
   let output = matches.remove_one::<String>("output");
   let target = matches.remove_one::<String>("target");
   let no_terminal = matches.get_flag("no-terminal");
+  let compress = matches.get_flag("compress");
+  let code_cache = matches.get_flag("code-cache");
   let include = match matches.remove_many::<String>("include") {
     Some(f) => f.collect(),
     None => vec![],
@@ -3663,6 +4244,8 @@ fn compile_parse(flags: &mut Flags, matches: &mut ArgMatches) {
     target,
     no_terminal,
     include,
+    compress,
+    code_cache,
   });
 }
 
@@ -3764,21 +4347,35 @@ fn doc_parse(flags: &mut Flags, matches: &mut ArgMatches) {
   let lint = matches.get_flag("lint");
   let json = matches.get_flag("json");
   let filter = matches.remove_one::<String>("filter");
-  let html = if matches.get_flag("html") {
-    let name = matches.remove_one::<String>("name");
-    let output = matches
-      .remove_one::<String>("output")
-      .unwrap_or(String::from("./docs/"));
-    Some(DocHtmlFlag { name, output })
-  } else {
-    None
-  };
+  let name = matches.remove_one::<String>("name");
+  let output = matches
+    .remove_one::<String>("output")
+    .unwrap_or(String::from("./docs/"));
+  let html = matches.get_flag("html").then(|| DocHtmlFlag {
+    name: name.clone(),
+    output: output.clone(),
+  });
+  let markdown =
+    matches
+      .remove_one::<String>("markdown")
+      .map(|layout| DocMarkdownFlag {
+        name: name.clone(),
+        output: output.clone(),
+        per_symbol: layout == "symbol",
+      });
+  let man = matches.get_flag("man").then(|| DocManFlag { name, output });
+  let min_coverage = matches.remove_one::<u8>("min-coverage");
+  let coverage = (matches.get_flag("coverage") || min_coverage.is_some())
+    .then_some(DocCoverageFlag { min_coverage });
 
   flags.subcommand = DenoSubcommand::Doc(DocFlags {
     source_files,
     json,
     lint,
     html,
+    markdown,
+    man,
+    coverage,
     filter,
     private,
   });
@@ -3848,6 +4445,8 @@ fn fmt_parse(flags: &mut Flags, matches: &mut ArgMatches) {
 fn init_parse(flags: &mut Flags, matches: &mut ArgMatches) {
   flags.subcommand = DenoSubcommand::Init(InitFlags {
     dir: matches.remove_one::<String>("dir"),
+    template: matches.remove_one::<String>("template"),
+    yes: matches.get_flag("yes"),
   });
 }
 
@@ -3862,9 +4461,29 @@ fn info_parse(flags: &mut Flags, matches: &mut ArgMatches) {
   no_remote_arg_parse(flags, matches);
   no_npm_arg_parse(flags, matches);
   let json = matches.get_flag("json");
+  let unused_exports = matches.get_flag("unused-exports");
+  let standalone = matches.get_flag("standalone");
   flags.subcommand = DenoSubcommand::Info(InfoFlags {
     file: matches.remove_one::<String>("file"),
     json,
+    unused_exports,
+    standalone,
+    extract: matches.remove_one::<String>("extract"),
+    format: matches.remove_one::<String>("format").map(|format| {
+      match format.as_str() {
+        "dot" => InfoGraphFormat::Dot,
+        "mermaid" => InfoGraphFormat::Mermaid,
+        "html" => InfoGraphFormat::Html,
+        _ => unreachable!(),
+      }
+    }),
+    cycles: matches.get_flag("cycles"),
+    sizes: matches.get_flag("sizes"),
+    depth: matches.remove_one::<usize>("depth"),
+    exclude: matches
+      .remove_many::<String>("exclude")
+      .map(|values| values.collect())
+      .unwrap_or_default(),
   });
 }
 
@@ -3876,22 +4495,28 @@ fn install_parse(flags: &mut Flags, matches: &mut ArgMatches) {
   let force = matches.get_flag("force");
   let global = matches.get_flag("global");
   let name = matches.remove_one::<String>("name");
-  let mut cmd_values = matches.remove_many::<String>("cmd").unwrap();
-
-  let module_url = cmd_values.next().unwrap();
-  let args = cmd_values.collect();
+  let frozen = matches.get_flag("frozen");
+
+  let kind = match matches.remove_many::<String>("cmd") {
+    Some(mut cmd_values) => {
+      let module_url = cmd_values.next().unwrap();
+      let args = cmd_values.collect();
+      InstallKind::Global(InstallFlagsGlobal {
+        name,
+        module_url,
+        args,
+        root,
+        force,
+      })
+    }
+    None => InstallKind::Local(InstallFlagsLocal { frozen }),
+  };
 
   flags.subcommand = DenoSubcommand::Install(InstallFlags {
     // TODO(bartlomieju): remove once `deno install` supports both local and
     // global installs
     global,
-    kind: InstallKind::Global(InstallFlagsGlobal {
-      name,
-      module_url,
-      args,
-      root,
-      force,
-    }),
+    kind,
   });
 }
 
@@ -4081,6 +4706,10 @@ fn task_parse(flags: &mut Flags, matches: &mut ArgMatches) {
   let mut task_flags = TaskFlags {
     cwd: matches.remove_one::<String>("cwd"),
     task: None,
+    parallel: matches.get_flag("parallel"),
+    sequential: matches.get_flag("sequential"),
+    recursive: matches.get_flag("recursive"),
+    filter: matches.remove_one::<String>("filter"),
   };
 
   if let Some((task, mut matches)) = matches.remove_subcommand() {
@@ -4124,6 +4753,7 @@ fn test_parse(flags: &mut Flags, matches: &mut ArgMatches) {
     );
   }
   let doc = matches.get_flag("doc");
+  let run_doc = matches.get_flag("run-doc");
   let allow_none = matches.get_flag("allow-none");
   let filter = matches.remove_one::<String>("filter");
 
@@ -4203,6 +4833,7 @@ fn test_parse(flags: &mut Flags, matches: &mut ArgMatches) {
   flags.subcommand = DenoSubcommand::Test(TestFlags {
     no_run,
     doc,
+    run_doc,
     coverage_dir: matches.remove_one::<String>("coverage"),
     fail_fast,
     files: FileFlags { include, ignore },
@@ -4439,6 +5070,15 @@ fn inspect_arg_parse(flags: &mut Flags, matches: &mut ArgMatches) {
   } else {
     None
   };
+  flags.inspect_dap = if matches.contains_id("inspect-dap") {
+    Some(
+      matches
+        .remove_one::<SocketAddr>("inspect-dap")
+        .unwrap_or_else(default),
+    )
+  } else {
+    None
+  };
 }
 
 fn import_map_arg_parse(flags: &mut Flags, matches: &mut ArgMatches) {
@@ -5973,6 +6613,7 @@ mod tests {
       Flags {
         subcommand: DenoSubcommand::Check(CheckFlags {
           files: svec!["script.ts"],
+          watch: None,
         }),
         type_check_mode: TypeCheckMode::Local,
         ..Flags::default()
@@ -5986,6 +6627,7 @@ mod tests {
         Flags {
           subcommand: DenoSubcommand::Check(CheckFlags {
             files: svec!["script.ts"],
+            watch: None,
           }),
           type_check_mode: TypeCheckMode::All,
           ..Flags::default()
@@ -6006,6 +6648,40 @@ mod tests {
     }
   }
 
+  #[test]
+  fn check_watch() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "check",
+      "--watch",
+      "--no-clear-screen",
+      "--watch-exclude=gen.ts",
+      "script.ts"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Check(CheckFlags {
+          files: svec!["script.ts"],
+          watch: Some(WatchFlags {
+            hmr: false,
+            no_clear_screen: true,
+            exclude: svec!["gen.ts"],
+          }),
+        }),
+        type_check_mode: TypeCheckMode::Local,
+        ..Flags::default()
+      }
+    );
+
+    let r =
+      flags_from_vec(svec!["deno", "check", "--no-clear-screen", "script.ts"]);
+    assert_eq!(
+      r.unwrap_err().kind(),
+      clap::error::ErrorKind::MissingRequiredArgument
+    );
+  }
+
   #[test]
   fn info() {
     let r = flags_from_vec(svec!["deno", "info", "script.ts"]);
@@ -6015,6 +6691,14 @@ mod tests {
         subcommand: DenoSubcommand::Info(InfoFlags {
           json: false,
           file: Some("script.ts".to_string()),
+          unused_exports: false,
+          standalone: false,
+          extract: None,
+          format: None,
+          cycles: false,
+          sizes: false,
+          depth: None,
+          exclude: vec![],
         }),
         ..Flags::default()
       }
@@ -6027,6 +6711,14 @@ mod tests {
         subcommand: DenoSubcommand::Info(InfoFlags {
           json: false,
           file: Some("script.ts".to_string()),
+          unused_exports: false,
+          standalone: false,
+          extract: None,
+          format: None,
+          cycles: false,
+          sizes: false,
+          depth: None,
+          exclude: vec![],
         }),
         reload: true,
         ..Flags::default()
@@ -6040,6 +6732,14 @@ mod tests {
         subcommand: DenoSubcommand::Info(InfoFlags {
           json: true,
           file: Some("script.ts".to_string()),
+          unused_exports: false,
+          standalone: false,
+          extract: None,
+          format: None,
+          cycles: false,
+          sizes: false,
+          depth: None,
+          exclude: vec![],
         }),
         ..Flags::default()
       }
@@ -6051,7 +6751,15 @@ mod tests {
       Flags {
         subcommand: DenoSubcommand::Info(InfoFlags {
           json: false,
-          file: None
+          file: None,
+          unused_exports: false,
+          standalone: false,
+          extract: None,
+          format: None,
+          cycles: false,
+          sizes: false,
+          depth: None,
+          exclude: vec![],
         }),
         ..Flags::default()
       }
@@ -6063,7 +6771,61 @@ mod tests {
       Flags {
         subcommand: DenoSubcommand::Info(InfoFlags {
           json: true,
-          file: None
+          file: None,
+          unused_exports: false,
+          standalone: false,
+          extract: None,
+          format: None,
+          cycles: false,
+          sizes: false,
+          depth: None,
+          exclude: vec![],
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "info", "--unused-exports"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          json: false,
+          file: None,
+          unused_exports: true,
+          standalone: false,
+          extract: None,
+          format: None,
+          cycles: false,
+          sizes: false,
+          depth: None,
+          exclude: vec![],
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "info",
+      "--unused-exports",
+      "--json",
+      "mod.ts"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          json: true,
+          file: Some("mod.ts".to_string()),
+          unused_exports: true,
+          standalone: false,
+          extract: None,
+          format: None,
+          cycles: false,
+          sizes: false,
+          depth: None,
+          exclude: vec![],
         }),
         ..Flags::default()
       }
@@ -6082,7 +6844,15 @@ mod tests {
       Flags {
         subcommand: DenoSubcommand::Info(InfoFlags {
           json: false,
-          file: None
+          file: None,
+          unused_exports: false,
+          standalone: false,
+          extract: None,
+          format: None,
+          cycles: false,
+          sizes: false,
+          depth: None,
+          exclude: vec![],
         }),
         config_flag: ConfigFlag::Path("tsconfig.json".to_owned()),
         no_npm: true,
@@ -6092,6 +6862,179 @@ mod tests {
     );
   }
 
+  #[test]
+  fn info_graph() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "info",
+      "--format=dot",
+      "--depth=2",
+      "--exclude=npm:*,https://esm.sh/*",
+      "main.ts"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          json: false,
+          file: Some("main.ts".to_string()),
+          unused_exports: false,
+          standalone: false,
+          extract: None,
+          format: Some(InfoGraphFormat::Dot),
+          cycles: false,
+          sizes: false,
+          depth: Some(2),
+          exclude: svec!["npm:*", "https://esm.sh/*"],
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "info", "--cycles", "main.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          json: false,
+          file: Some("main.ts".to_string()),
+          unused_exports: false,
+          standalone: false,
+          extract: None,
+          format: None,
+          cycles: true,
+          sizes: false,
+          depth: None,
+          exclude: vec![],
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "info", "--format=mermaid"]);
+    assert!(r.is_err());
+    let r = flags_from_vec(svec!["deno", "info", "--format=svg", "main.ts"]);
+    assert!(r.is_err());
+    let r = flags_from_vec(svec![
+      "deno",
+      "info",
+      "--format=html",
+      "--json",
+      "main.ts"
+    ]);
+    assert!(r.is_err());
+    let r =
+      flags_from_vec(svec!["deno", "info", "--cycles", "--json", "main.ts"]);
+    assert!(r.is_err());
+  }
+
+  #[test]
+  fn info_sizes() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "info",
+      "--sizes",
+      "--format=html",
+      "main.ts"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          json: false,
+          file: Some("main.ts".to_string()),
+          unused_exports: false,
+          standalone: false,
+          extract: None,
+          format: Some(InfoGraphFormat::Html),
+          cycles: false,
+          sizes: true,
+          depth: None,
+          exclude: vec![],
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r =
+      flags_from_vec(svec!["deno", "info", "--sizes", "--json", "main.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          json: true,
+          file: Some("main.ts".to_string()),
+          unused_exports: false,
+          standalone: false,
+          extract: None,
+          format: None,
+          cycles: false,
+          sizes: true,
+          depth: None,
+          exclude: vec![],
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "info", "--sizes"]);
+    assert!(r.is_err());
+    let r =
+      flags_from_vec(svec!["deno", "info", "--sizes", "--cycles", "main.ts"]);
+    assert!(r.is_err());
+  }
+
+  #[test]
+  fn info_standalone() {
+    let r = flags_from_vec(svec!["deno", "info", "--standalone", "my_cli"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          json: false,
+          file: Some("my_cli".to_string()),
+          unused_exports: false,
+          standalone: true,
+          extract: None,
+          format: None,
+          cycles: false,
+          sizes: false,
+          depth: None,
+          exclude: vec![],
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "info",
+      "--standalone",
+      "--extract",
+      "out",
+      "--json",
+      "my_cli"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          json: true,
+          file: Some("my_cli".to_string()),
+          unused_exports: false,
+          standalone: true,
+          extract: Some("out".to_string()),
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "info", "--standalone"]);
+    assert!(r.is_err());
+    let r = flags_from_vec(svec!["deno", "info", "--extract", "out", "my_cli"]);
+    assert!(r.is_err());
+  }
+
   #[test]
   fn tsconfig() {
     let r =
@@ -6359,6 +7302,37 @@ mod tests {
     );
   }
 
+  #[test]
+  fn repl_with_check() {
+    let r = flags_from_vec(svec!["deno", "repl", "--check"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Repl(ReplFlags {
+          eval_files: None,
+          eval: None,
+          is_default_command: false,
+        }),
+        type_check_mode: TypeCheckMode::Local,
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "repl", "--check=all"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Repl(ReplFlags {
+          eval_files: None,
+          eval: None,
+          is_default_command: false,
+        }),
+        type_check_mode: TypeCheckMode::All,
+        ..Flags::default()
+      }
+    );
+  }
+
   #[test]
   fn repl_with_eval_flag() {
     #[rustfmt::skip]
@@ -6924,6 +7898,9 @@ mod tests {
           source_file: "source.ts".to_string(),
           out_file: None,
           watch: Default::default(),
+          minify: false,
+          source_map: None,
+          platform: BundlePlatform::Deno,
         }),
         type_check_mode: TypeCheckMode::Local,
         ..Flags::default()
@@ -6949,6 +7926,9 @@ mod tests {
           source_file: "source.ts".to_string(),
           out_file: Some("bundle.js".to_string()),
           watch: Default::default(),
+          minify: false,
+          source_map: None,
+          platform: BundlePlatform::Deno,
         }),
         permissions: PermissionFlags {
           allow_write: Some(vec![]),
@@ -6972,6 +7952,9 @@ mod tests {
           source_file: "source.ts".to_string(),
           out_file: Some("bundle.js".to_string()),
           watch: Default::default(),
+          minify: false,
+          source_map: None,
+          platform: BundlePlatform::Deno,
         }),
         type_check_mode: TypeCheckMode::Local,
         permissions: PermissionFlags {
@@ -6999,6 +7982,9 @@ mod tests {
           source_file: "source.ts".to_string(),
           out_file: None,
           watch: Default::default(),
+          minify: false,
+          source_map: None,
+          platform: BundlePlatform::Deno,
         }),
         type_check_mode: TypeCheckMode::Local,
         lock_write: true,
@@ -7019,6 +8005,9 @@ mod tests {
           source_file: "source.ts".to_string(),
           out_file: None,
           watch: Default::default(),
+          minify: false,
+          source_map: None,
+          platform: BundlePlatform::Deno,
         }),
         type_check_mode: TypeCheckMode::Local,
         ..Flags::default()
@@ -7037,6 +8026,9 @@ mod tests {
           source_file: "script.ts".to_string(),
           out_file: None,
           watch: Default::default(),
+          minify: false,
+          source_map: None,
+          platform: BundlePlatform::Deno,
         }),
         type_check_mode: TypeCheckMode::None,
         ..Flags::default()
@@ -7054,6 +8046,9 @@ mod tests {
           source_file: "source.ts".to_string(),
           out_file: None,
           watch: Some(Default::default()),
+          minify: false,
+          source_map: None,
+          platform: BundlePlatform::Deno,
         }),
         type_check_mode: TypeCheckMode::Local,
         ..Flags::default()
@@ -7081,6 +8076,9 @@ mod tests {
             no_clear_screen: true,
             exclude: vec![],
           }),
+          minify: false,
+          source_map: None,
+          platform: BundlePlatform::Deno,
         }),
         type_check_mode: TypeCheckMode::Local,
         ..Flags::default()
@@ -7088,6 +8086,64 @@ mod tests {
     )
   }
 
+  #[test]
+  fn bundle_with_options() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "bundle",
+      "--minify",
+      "--source-map",
+      "--platform=browser",
+      "source.ts",
+      "bundle.js"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Bundle(BundleFlags {
+          source_file: "source.ts".to_string(),
+          out_file: Some("bundle.js".to_string()),
+          watch: Default::default(),
+          minify: true,
+          source_map: Some(BundleSourceMap::External),
+          platform: BundlePlatform::Browser,
+        }),
+        type_check_mode: TypeCheckMode::Local,
+        permissions: PermissionFlags {
+          allow_write: Some(vec![]),
+          ..Default::default()
+        },
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "bundle",
+      "--source-map=inline",
+      "source.ts"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Bundle(BundleFlags {
+          source_file: "source.ts".to_string(),
+          out_file: None,
+          watch: Default::default(),
+          minify: false,
+          source_map: Some(BundleSourceMap::Inline),
+          platform: BundlePlatform::Deno,
+        }),
+        type_check_mode: TypeCheckMode::Local,
+        ..Flags::default()
+      }
+    );
+
+    let r =
+      flags_from_vec(svec!["deno", "bundle", "--platform=node", "source.ts"]);
+    assert!(r.is_err());
+  }
+
   #[test]
   fn run_import_map() {
     let r = flags_from_vec(svec![
@@ -7123,6 +8179,14 @@ mod tests {
         subcommand: DenoSubcommand::Info(InfoFlags {
           file: Some("script.ts".to_string()),
           json: false,
+          unused_exports: false,
+          standalone: false,
+          extract: None,
+          format: None,
+          cycles: false,
+          sizes: false,
+          depth: None,
+          exclude: vec![],
         }),
         import_map_path: Some("import_map.json".to_owned()),
         ..Flags::default()
@@ -7166,6 +8230,9 @@ mod tests {
           private: false,
           json: false,
           html: None,
+          markdown: None,
+          man: None,
+          coverage: None,
           lint: false,
           filter: None,
         }),
@@ -7325,32 +8392,69 @@ mod tests {
   }
 
   #[test]
-  fn install_with_flags() {
-    #[rustfmt::skip]
-    let r = flags_from_vec(svec!["deno", "install", "--import-map", "import_map.json", "--no-remote", "--config", "tsconfig.json", "--no-check", "--unsafely-ignore-certificate-errors", "--reload", "--lock", "lock.json", "--lock-write", "--cert", "example.crt", "--cached-only", "--allow-read", "--allow-net", "--v8-flags=--help", "--seed", "1", "--inspect=127.0.0.1:9229", "--name", "file_server", "--root", "/foo", "--force", "--env=.example.env", "jsr:@std/http/file-server", "foo", "bar"]);
+  fn install_local() {
+    let r = flags_from_vec(svec!["deno", "install"]);
     assert_eq!(
       r.unwrap(),
       Flags {
         subcommand: DenoSubcommand::Install(InstallFlags {
-          kind: InstallKind::Global(InstallFlagsGlobal {
-            name: Some("file_server".to_string()),
-            module_url: "jsr:@std/http/file-server".to_string(),
-            args: svec!["foo", "bar"],
-            root: Some("/foo".to_string()),
-            force: true,
-          }),
+          kind: InstallKind::Local(InstallFlagsLocal { frozen: false }),
           global: false,
         }),
-        import_map_path: Some("import_map.json".to_string()),
-        no_remote: true,
-        config_flag: ConfigFlag::Path("tsconfig.json".to_owned()),
-        type_check_mode: TypeCheckMode::None,
-        reload: true,
-        lock: Some(String::from("lock.json")),
-        lock_write: true,
-        ca_data: Some(CaData::File("example.crt".to_string())),
-        cached_only: true,
-        v8_flags: svec!["--help", "--random-seed=1"],
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "install", "--frozen"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Install(InstallFlags {
+          kind: InstallKind::Local(InstallFlagsLocal { frozen: true }),
+          global: false,
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "install", "-g"]);
+    assert!(r.is_err());
+    let r = flags_from_vec(svec![
+      "deno",
+      "install",
+      "--frozen",
+      "jsr:@std/http/file-server"
+    ]);
+    assert!(r.is_err());
+  }
+
+  #[test]
+  fn install_with_flags() {
+    #[rustfmt::skip]
+    let r = flags_from_vec(svec!["deno", "install", "--import-map", "import_map.json", "--no-remote", "--config", "tsconfig.json", "--no-check", "--unsafely-ignore-certificate-errors", "--reload", "--lock", "lock.json", "--lock-write", "--cert", "example.crt", "--cached-only", "--allow-read", "--allow-net", "--v8-flags=--help", "--seed", "1", "--inspect=127.0.0.1:9229", "--name", "file_server", "--root", "/foo", "--force", "--env=.example.env", "jsr:@std/http/file-server", "foo", "bar"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Install(InstallFlags {
+          kind: InstallKind::Global(InstallFlagsGlobal {
+            name: Some("file_server".to_string()),
+            module_url: "jsr:@std/http/file-server".to_string(),
+            args: svec!["foo", "bar"],
+            root: Some("/foo".to_string()),
+            force: true,
+          }),
+          global: false,
+        }),
+        import_map_path: Some("import_map.json".to_string()),
+        no_remote: true,
+        config_flag: ConfigFlag::Path("tsconfig.json".to_owned()),
+        type_check_mode: TypeCheckMode::None,
+        reload: true,
+        lock: Some(String::from("lock.json")),
+        lock_write: true,
+        ca_data: Some(CaData::File("example.crt".to_string())),
+        cached_only: true,
+        v8_flags: svec!["--help", "--random-seed=1"],
         seed: Some(1),
         inspect: Some("127.0.0.1:9229".parse().unwrap()),
         unsafely_ignore_certificate_errors: Some(vec![]),
@@ -8049,6 +9153,7 @@ mod tests {
         subcommand: DenoSubcommand::Test(TestFlags {
           no_run: true,
           doc: false,
+          run_doc: false,
           fail_fast: None,
           filter: Some("- foo".to_string()),
           allow_none: true,
@@ -8136,6 +9241,7 @@ mod tests {
           no_run: false,
           reporter: Default::default(),
           doc: false,
+          run_doc: false,
           fail_fast: None,
           filter: None,
           allow_none: false,
@@ -8172,6 +9278,7 @@ mod tests {
         subcommand: DenoSubcommand::Test(TestFlags {
           no_run: false,
           doc: false,
+          run_doc: false,
           fail_fast: Some(NonZeroUsize::new(3).unwrap()),
           filter: None,
           allow_none: false,
@@ -8213,6 +9320,7 @@ mod tests {
         subcommand: DenoSubcommand::Test(TestFlags {
           no_run: false,
           doc: false,
+          run_doc: false,
           fail_fast: None,
           filter: None,
           allow_none: false,
@@ -8239,6 +9347,30 @@ mod tests {
     );
   }
 
+  #[test]
+  fn test_run_doc() {
+    let r = flags_from_vec(svec!["deno", "test", "--doc", "--run-doc"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Test(TestFlags {
+          doc: true,
+          run_doc: true,
+          ..Default::default()
+        }),
+        permissions: PermissionFlags {
+          no_prompt: true,
+          ..Default::default()
+        },
+        type_check_mode: TypeCheckMode::Local,
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "test", "--run-doc"]);
+    assert!(r.is_err());
+  }
+
   #[test]
   fn test_reporter() {
     let r = flags_from_vec(svec!["deno", "test", "--reporter=pretty"]);
@@ -8348,6 +9480,7 @@ mod tests {
         subcommand: DenoSubcommand::Test(TestFlags {
           no_run: false,
           doc: false,
+          run_doc: false,
           fail_fast: None,
           filter: None,
           allow_none: false,
@@ -8382,6 +9515,7 @@ mod tests {
         subcommand: DenoSubcommand::Test(TestFlags {
           no_run: false,
           doc: false,
+          run_doc: false,
           fail_fast: None,
           filter: None,
           allow_none: false,
@@ -8415,6 +9549,7 @@ mod tests {
         subcommand: DenoSubcommand::Test(TestFlags {
           no_run: false,
           doc: false,
+          run_doc: false,
           fail_fast: None,
           filter: None,
           allow_none: false,
@@ -8450,6 +9585,7 @@ mod tests {
         subcommand: DenoSubcommand::Test(TestFlags {
           no_run: false,
           doc: false,
+          run_doc: false,
           fail_fast: None,
           filter: None,
           allow_none: false,
@@ -8515,6 +9651,9 @@ mod tests {
           source_file: "source.ts".to_string(),
           out_file: None,
           watch: Default::default(),
+          minify: false,
+          source_map: None,
+          platform: BundlePlatform::Deno,
         }),
         type_check_mode: TypeCheckMode::Local,
         ca_data: Some(CaData::File("example.crt".to_owned())),
@@ -8579,6 +9718,14 @@ mod tests {
         subcommand: DenoSubcommand::Info(InfoFlags {
           json: false,
           file: Some("https://example.com".to_string()),
+          unused_exports: false,
+          standalone: false,
+          extract: None,
+          format: None,
+          cycles: false,
+          sizes: false,
+          depth: None,
+          exclude: vec![],
         }),
         ca_data: Some(CaData::File("example.crt".to_owned())),
         ..Flags::default()
@@ -8596,6 +9743,9 @@ mod tests {
           private: false,
           json: true,
           html: None,
+          markdown: None,
+          man: None,
+          coverage: None,
           lint: false,
           source_files: DocSourceFileFlag::Paths(svec!["path/to/module.ts"]),
           filter: None,
@@ -8625,6 +9775,9 @@ mod tests {
             name: Some("My library".to_string()),
             output: String::from("./docs/"),
           }),
+          markdown: None,
+          man: None,
+          coverage: None,
           source_files: DocSourceFileFlag::Paths(svec!["path/to/module.ts"]),
           filter: None,
         }),
@@ -8651,6 +9804,9 @@ mod tests {
             name: Some("My library".to_string()),
             output: String::from("./foo"),
           }),
+          markdown: None,
+          man: None,
+          coverage: None,
           lint: true,
           source_files: DocSourceFileFlag::Paths(svec!["path/to/module.ts"]),
           filter: None,
@@ -8677,6 +9833,9 @@ mod tests {
           private: false,
           json: false,
           html: None,
+          markdown: None,
+          man: None,
+          coverage: None,
           lint: false,
           source_files: DocSourceFileFlag::Paths(vec![
             "path/to/module.ts".to_string()
@@ -8695,6 +9854,9 @@ mod tests {
           private: false,
           json: false,
           html: None,
+          markdown: None,
+          man: None,
+          coverage: None,
           lint: false,
           source_files: Default::default(),
           filter: None,
@@ -8718,6 +9880,9 @@ mod tests {
           lint: false,
           json: false,
           html: None,
+          markdown: None,
+          man: None,
+          coverage: None,
           source_files: DocSourceFileFlag::Builtin,
           filter: Some("Deno.Listener".to_string()),
         }),
@@ -8741,6 +9906,9 @@ mod tests {
           lint: false,
           json: false,
           html: None,
+          markdown: None,
+          man: None,
+          coverage: None,
           source_files: DocSourceFileFlag::Paths(svec!["path/to/module.js"]),
           filter: None,
         }),
@@ -8764,6 +9932,9 @@ mod tests {
           lint: false,
           json: false,
           html: None,
+          markdown: None,
+          man: None,
+          coverage: None,
           source_files: DocSourceFileFlag::Paths(vec![
             "path/to/module.js".to_string(),
             "path/to/module2.js".to_string()
@@ -8788,6 +9959,9 @@ mod tests {
           private: false,
           json: false,
           html: None,
+          markdown: None,
+          man: None,
+          coverage: None,
           lint: false,
           source_files: DocSourceFileFlag::Paths(vec![
             "path/to/module.js".to_string(),
@@ -8817,6 +9991,9 @@ mod tests {
           lint: true,
           json: false,
           html: None,
+          markdown: None,
+          man: None,
+          coverage: None,
           source_files: DocSourceFileFlag::Paths(vec![
             "path/to/module.js".to_string(),
             "path/to/module2.js".to_string()
@@ -8828,6 +10005,170 @@ mod tests {
     );
   }
 
+  #[test]
+  fn doc_markdown_and_man() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "doc",
+      "--markdown",
+      "--output=./wiki",
+      "path/to/module.ts"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Doc(DocFlags {
+          private: false,
+          json: false,
+          lint: false,
+          html: None,
+          markdown: Some(DocMarkdownFlag {
+            name: None,
+            output: String::from("./wiki"),
+            per_symbol: false,
+          }),
+          man: None,
+          coverage: None,
+          source_files: DocSourceFileFlag::Paths(svec!["path/to/module.ts"]),
+          filter: None,
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "doc",
+      "--markdown=symbol",
+      "path/to/module.ts"
+    ]);
+    assert_eq!(
+      r.unwrap().subcommand,
+      DenoSubcommand::Doc(DocFlags {
+        private: false,
+        json: false,
+        lint: false,
+        html: None,
+        markdown: Some(DocMarkdownFlag {
+          name: None,
+          output: String::from("./docs/"),
+          per_symbol: true,
+        }),
+        man: None,
+        coverage: None,
+        source_files: DocSourceFileFlag::Paths(svec!["path/to/module.ts"]),
+        filter: None,
+      })
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "doc",
+      "--man",
+      "--name=My library",
+      "path/to/module.ts"
+    ]);
+    assert_eq!(
+      r.unwrap().subcommand,
+      DenoSubcommand::Doc(DocFlags {
+        private: false,
+        json: false,
+        lint: false,
+        html: None,
+        markdown: None,
+        man: Some(DocManFlag {
+          name: Some("My library".to_string()),
+          output: String::from("./docs/"),
+        }),
+        coverage: None,
+        source_files: DocSourceFileFlag::Paths(svec!["path/to/module.ts"]),
+        filter: None,
+      })
+    );
+
+    let r = flags_from_vec(svec!["deno", "doc", "--markdown"]);
+    assert!(r.is_err());
+    let r = flags_from_vec(svec!["deno", "doc", "--man"]);
+    assert!(r.is_err());
+    let r = flags_from_vec(svec![
+      "deno",
+      "doc",
+      "--markdown=wiki",
+      "path/to/module.ts"
+    ]);
+    assert!(r.is_err());
+    let r = flags_from_vec(svec![
+      "deno",
+      "doc",
+      "--markdown",
+      "--html",
+      "path/to/module.ts"
+    ]);
+    assert!(r.is_err());
+  }
+
+  #[test]
+  fn doc_coverage() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "doc",
+      "--lint",
+      "--min-coverage=80",
+      "path/to/module.ts"
+    ]);
+    assert_eq!(
+      r.unwrap().subcommand,
+      DenoSubcommand::Doc(DocFlags {
+        private: false,
+        json: false,
+        lint: true,
+        html: None,
+        markdown: None,
+        man: None,
+        coverage: Some(DocCoverageFlag {
+          min_coverage: Some(80),
+        }),
+        source_files: DocSourceFileFlag::Paths(svec!["path/to/module.ts"]),
+        filter: None,
+      })
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "doc",
+      "--lint",
+      "--coverage",
+      "--json",
+      "path/to/module.ts"
+    ]);
+    assert_eq!(
+      r.unwrap().subcommand,
+      DenoSubcommand::Doc(DocFlags {
+        private: false,
+        json: true,
+        lint: true,
+        html: None,
+        markdown: None,
+        man: None,
+        coverage: Some(DocCoverageFlag { min_coverage: None }),
+        source_files: DocSourceFileFlag::Paths(svec!["path/to/module.ts"]),
+        filter: None,
+      })
+    );
+
+    let r =
+      flags_from_vec(svec!["deno", "doc", "--coverage", "path/to/module.ts"]);
+    assert!(r.is_err());
+    let r = flags_from_vec(svec![
+      "deno",
+      "doc",
+      "--lint",
+      "--min-coverage=101",
+      "path/to/module.ts"
+    ]);
+    assert!(r.is_err());
+  }
+
   #[test]
   fn inspect_default_host() {
     let r = flags_from_vec(svec!["deno", "run", "--inspect", "foo.js"]);
@@ -8878,6 +10219,42 @@ mod tests {
     );
   }
 
+  #[test]
+  fn inspect_dap() {
+    let r = flags_from_vec(svec!["deno", "run", "--inspect-dap", "foo.js"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "foo.js".to_string(),
+        )),
+        inspect_dap: Some("127.0.0.1:9229".parse().unwrap()),
+        code_cache_enabled: true,
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "test",
+      "--inspect-dap=127.0.0.1:4711",
+      "foo_test.ts"
+    ]);
+    assert_eq!(
+      r.unwrap().inspect_dap,
+      Some("127.0.0.1:4711".parse().unwrap())
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--inspect-dap",
+      "--inspect-brk",
+      "foo.js"
+    ]);
+    assert!(r.is_err());
+  }
+
   #[test]
   fn compile() {
     let r = flags_from_vec(svec![
@@ -8895,7 +10272,37 @@ mod tests {
           args: vec![],
           target: None,
           no_terminal: false,
-          include: vec![]
+          include: vec![],
+          compress: false,
+          code_cache: false,
+        }),
+        type_check_mode: TypeCheckMode::Local,
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn compile_with_compress_and_code_cache() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "compile",
+      "--compress",
+      "--code-cache",
+      "main.ts"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Compile(CompileFlags {
+          source_file: "main.ts".to_string(),
+          output: None,
+          args: vec![],
+          target: None,
+          no_terminal: false,
+          include: vec![],
+          compress: true,
+          code_cache: true,
         }),
         type_check_mode: TypeCheckMode::Local,
         ..Flags::default()
@@ -8917,7 +10324,9 @@ mod tests {
           args: svec!["foo", "bar", "-p", "8080"],
           target: None,
           no_terminal: true,
-          include: vec![]
+          include: vec![],
+          compress: false,
+          code_cache: false,
         }),
         import_map_path: Some("import_map.json".to_string()),
         no_remote: true,
@@ -9120,6 +10529,10 @@ mod tests {
         subcommand: DenoSubcommand::Task(TaskFlags {
           cwd: None,
           task: Some("build".to_string()),
+          parallel: false,
+          sequential: false,
+          recursive: false,
+          filter: None,
         }),
         argv: svec!["hello", "world"],
         ..Flags::default()
@@ -9133,6 +10546,10 @@ mod tests {
         subcommand: DenoSubcommand::Task(TaskFlags {
           cwd: None,
           task: Some("build".to_string()),
+          parallel: false,
+          sequential: false,
+          recursive: false,
+          filter: None,
         }),
         ..Flags::default()
       }
@@ -9145,12 +10562,107 @@ mod tests {
         subcommand: DenoSubcommand::Task(TaskFlags {
           cwd: Some("foo".to_string()),
           task: Some("build".to_string()),
+          parallel: false,
+          sequential: false,
+          recursive: false,
+          filter: None,
         }),
         ..Flags::default()
       }
     );
   }
 
+  #[test]
+  fn task_subcommand_parallel() {
+    let r = flags_from_vec(svec!["deno", "task", "--parallel", "a", "b"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Task(TaskFlags {
+          cwd: None,
+          task: Some("a".to_string()),
+          parallel: true,
+          sequential: false,
+          recursive: false,
+          filter: None,
+        }),
+        argv: svec!["b"],
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn task_subcommand_sequential() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "task",
+      "--sequential",
+      "a",
+      "b",
+      "--",
+      "1"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Task(TaskFlags {
+          cwd: None,
+          task: Some("a".to_string()),
+          parallel: false,
+          sequential: true,
+          recursive: false,
+          filter: None,
+        }),
+        argv: svec!["b", "--", "1"],
+        ..Flags::default()
+      }
+    );
+
+    let r =
+      flags_from_vec(svec!["deno", "task", "--parallel", "--sequential", "a"]);
+    r.unwrap_err();
+  }
+
+  #[test]
+  fn task_subcommand_recursive() {
+    let r = flags_from_vec(svec!["deno", "task", "-r", "build"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Task(TaskFlags {
+          cwd: None,
+          task: Some("build".to_string()),
+          parallel: false,
+          sequential: false,
+          recursive: true,
+          filter: None,
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "task", "--filter=@scope/*", "build"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Task(TaskFlags {
+          cwd: None,
+          task: Some("build".to_string()),
+          parallel: false,
+          sequential: false,
+          recursive: false,
+          filter: Some("@scope/*".to_string()),
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r =
+      flags_from_vec(svec!["deno", "task", "--recursive", "--cwd", "a", "b"]);
+    r.unwrap_err();
+  }
+
   #[test]
   fn task_subcommand_double_hyphen() {
     let r = flags_from_vec(svec![
@@ -9169,6 +10681,10 @@ mod tests {
         subcommand: DenoSubcommand::Task(TaskFlags {
           cwd: None,
           task: Some("build".to_string()),
+          parallel: false,
+          sequential: false,
+          recursive: false,
+          filter: None,
         }),
         argv: svec!["--", "hello", "world"],
         config_flag: ConfigFlag::Path("deno.json".to_owned()),
@@ -9185,6 +10701,10 @@ mod tests {
         subcommand: DenoSubcommand::Task(TaskFlags {
           cwd: Some("foo".to_string()),
           task: Some("build".to_string()),
+          parallel: false,
+          sequential: false,
+          recursive: false,
+          filter: None,
         }),
         argv: svec!["--", "hello", "world"],
         ..Flags::default()
@@ -9202,6 +10722,10 @@ mod tests {
         subcommand: DenoSubcommand::Task(TaskFlags {
           cwd: None,
           task: Some("build".to_string()),
+          parallel: false,
+          sequential: false,
+          recursive: false,
+          filter: None,
         }),
         argv: svec!["--"],
         ..Flags::default()
@@ -9218,6 +10742,10 @@ mod tests {
         subcommand: DenoSubcommand::Task(TaskFlags {
           cwd: None,
           task: Some("build".to_string()),
+          parallel: false,
+          sequential: false,
+          recursive: false,
+          filter: None,
         }),
         argv: svec!["-1", "--test"],
         ..Flags::default()
@@ -9234,6 +10762,10 @@ mod tests {
         subcommand: DenoSubcommand::Task(TaskFlags {
           cwd: None,
           task: Some("build".to_string()),
+          parallel: false,
+          sequential: false,
+          recursive: false,
+          filter: None,
         }),
         argv: svec!["--test"],
         ..Flags::default()
@@ -9252,6 +10784,10 @@ mod tests {
         subcommand: DenoSubcommand::Task(TaskFlags {
           cwd: None,
           task: Some("build".to_string()),
+          parallel: false,
+          sequential: false,
+          recursive: false,
+          filter: None,
         }),
         unstable_config: UnstableConfig {
           legacy_flag_enabled: true,
@@ -9272,6 +10808,10 @@ mod tests {
         subcommand: DenoSubcommand::Task(TaskFlags {
           cwd: None,
           task: None,
+          parallel: false,
+          sequential: false,
+          recursive: false,
+          filter: None,
         }),
         ..Flags::default()
       }
@@ -9287,6 +10827,10 @@ mod tests {
         subcommand: DenoSubcommand::Task(TaskFlags {
           cwd: None,
           task: None,
+          parallel: false,
+          sequential: false,
+          recursive: false,
+          filter: None,
         }),
         config_flag: ConfigFlag::Path("deno.jsonc".to_string()),
         ..Flags::default()
@@ -9303,6 +10847,10 @@ mod tests {
         subcommand: DenoSubcommand::Task(TaskFlags {
           cwd: None,
           task: None,
+          parallel: false,
+          sequential: false,
+          recursive: false,
+          filter: None,
         }),
         config_flag: ConfigFlag::Path("deno.jsonc".to_string()),
         ..Flags::default()
@@ -9481,7 +11029,11 @@ mod tests {
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Init(InitFlags { dir: None }),
+        subcommand: DenoSubcommand::Init(InitFlags {
+          dir: None,
+          template: None,
+          yes: false,
+        }),
         ..Flags::default()
       }
     );
@@ -9492,6 +11044,22 @@ mod tests {
       Flags {
         subcommand: DenoSubcommand::Init(InitFlags {
           dir: Some(String::from("foo")),
+          template: None,
+          yes: false,
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r =
+      flags_from_vec(svec!["deno", "init", "--template=serve", "--yes", "foo"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Init(InitFlags {
+          dir: Some(String::from("foo")),
+          template: Some(String::from("serve")),
+          yes: true,
         }),
         ..Flags::default()
       }
@@ -9501,7 +11069,11 @@ mod tests {
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Init(InitFlags { dir: None }),
+        subcommand: DenoSubcommand::Init(InitFlags {
+          dir: None,
+          template: None,
+          yes: false,
+        }),
         log_level: Some(Level::Error),
         ..Flags::default()
       }
@@ -9623,4 +11195,62 @@ mod tests {
       }
     );
   }
+
+  #[test]
+  fn remove_subcommand() {
+    let r = flags_from_vec(svec!["deno", "remove"]);
+    r.unwrap_err();
+
+    let r = flags_from_vec(svec!["deno", "remove", "@david/which"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Remove(RemoveFlags {
+          packages: svec!["@david/which"],
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "rm", "@david/which", "npm:chalk"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Remove(RemoveFlags {
+          packages: svec!["@david/which", "npm:chalk"],
+        }),
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn outdated_subcommand() {
+    let r = flags_from_vec(svec!["deno", "outdated"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Outdated(OutdatedFlags {
+          update: false,
+          latest: false,
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "outdated", "--update", "--latest"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Outdated(OutdatedFlags {
+          update: true,
+          latest: true,
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "outdated", "--latest"]);
+    r.unwrap_err();
+  }
 }
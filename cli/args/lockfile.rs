@@ -8,6 +8,8 @@ use crate::args::ConfigFile;
 use crate::Flags;
 
 use super::DenoSubcommand;
+use super::InstallFlags;
+use super::InstallKind;
 
 pub use deno_lockfile::Lockfile;
 pub use deno_lockfile::LockfileError;
@@ -19,7 +21,10 @@ pub fn discover(
   if flags.no_lock
     || matches!(
       flags.subcommand,
-      DenoSubcommand::Install(_) | DenoSubcommand::Uninstall(_)
+      DenoSubcommand::Install(InstallFlags {
+        kind: InstallKind::Global(_),
+        ..
+      }) | DenoSubcommand::Uninstall(_)
     )
   {
     return Ok(None);
@@ -48,6 +48,7 @@ use deno_runtime::deno_tls::rustls;
 use deno_runtime::deno_tls::rustls::RootCertStore;
 use deno_runtime::deno_tls::rustls_pemfile;
 use deno_runtime::deno_tls::webpki_roots;
+use deno_runtime::inspector_server::InspectorProtocol;
 use deno_runtime::inspector_server::InspectorServer;
 use deno_runtime::permissions::PermissionsOptions;
 use deno_terminal::colors;
@@ -77,6 +78,7 @@ use deno_config::glob::PathOrPatternSet;
 use deno_config::FmtConfig;
 use deno_config::LintConfig;
 use deno_config::TestConfig;
+use deno_config::WorkspaceMemberConfig;
 
 pub fn npm_registry_url() -> &'static Url {
   static NPM_REGISTRY_DEFAULT_URL: Lazy<Url> = Lazy::new(|| {
@@ -360,6 +362,7 @@ fn resolve_fmt_options(
 pub struct TestOptions {
   pub files: FilePatterns,
   pub doc: bool,
+  pub run_doc: bool,
   pub no_run: bool,
   pub fail_fast: Option<NonZeroUsize>,
   pub allow_none: bool,
@@ -390,6 +393,7 @@ impl TestOptions {
         .concurrent_jobs
         .unwrap_or_else(|| NonZeroUsize::new(1).unwrap()),
       doc: test_flags.doc,
+      run_doc: test_flags.run_doc,
       fail_fast: test_flags.fail_fast,
       filter: test_flags.filter,
       no_run: test_flags.no_run,
@@ -1218,6 +1222,14 @@ impl CliOptions {
   pub fn resolve_inspector_server(
     &self,
   ) -> Result<Option<InspectorServer>, AnyError> {
+    if let Some(host) = self.flags.inspect_dap {
+      return Ok(Some(InspectorServer::with_protocol(
+        host,
+        version::get_user_agent(),
+        InspectorProtocol::Dap,
+      )?));
+    }
+
     let maybe_inspect_host = self
       .flags
       .inspect
@@ -1237,9 +1249,9 @@ impl CliOptions {
 
   pub fn resolve_tasks_config(
     &self,
-  ) -> Result<IndexMap<String, deno_config::Task>, AnyError> {
+  ) -> Result<IndexMap<String, deno_json::TaskConfig>, AnyError> {
     if let Some(config_file) = &self.maybe_config_file {
-      config_file.resolve_tasks_config()
+      deno_json::resolve_tasks_config(config_file)
     } else if self.maybe_package_json.is_some() {
       Ok(Default::default())
     } else {
@@ -1247,6 +1259,36 @@ impl CliOptions {
     }
   }
 
+  /// Resolves the tasks of every member of the workspace.
+  pub fn resolve_workspace_tasks_config(
+    &self,
+  ) -> Result<
+    Vec<(
+      &WorkspaceMemberConfig,
+      IndexMap<String, deno_json::TaskConfig>,
+    )>,
+    AnyError,
+  > {
+    let Some(workspace_config) = &self.maybe_workspace_config else {
+      bail!("Running tasks in workspace members requires a workspace. Add a \"workspaces\" field to the root configuration file.");
+    };
+    workspace_config
+      .members
+      .iter()
+      .map(|member| {
+        let tasks_config = deno_json::resolve_tasks_config(&member.config_file)
+          .with_context(|| {
+            format!(
+              "Failed to resolve the tasks of '{}' workspace member at '{}'",
+              member.member_name,
+              member.config_file.specifier.as_str()
+            )
+          })?;
+        Ok((member, tasks_config))
+      })
+      .collect()
+  }
+
   /// Return the JSX import source configuration.
   pub fn to_maybe_jsx_import_source_config(
     &self,
@@ -1490,15 +1532,18 @@ impl CliOptions {
     }
   }
 
-  /// If the --inspect or --inspect-brk flags are used.
+  /// If the --inspect, --inspect-brk or --inspect-dap flags are used.
   pub fn is_inspecting(&self) -> bool {
     self.flags.inspect.is_some()
       || self.flags.inspect_brk.is_some()
       || self.flags.inspect_wait.is_some()
+      || self.flags.inspect_dap.is_some()
   }
 
+  /// The debug adapter breaks on the first statement so that the editor can
+  /// install its breakpoints before any user code runs.
   pub fn inspect_brk(&self) -> Option<SocketAddr> {
-    self.flags.inspect_brk
+    self.flags.inspect_brk.or(self.flags.inspect_dap)
   }
 
   pub fn inspect_wait(&self) -> Option<SocketAddr> {
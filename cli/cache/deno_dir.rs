@@ -3,6 +3,8 @@
 use once_cell::sync::OnceCell;
 
 use super::DiskCache;
+use crate::util::checksum;
+use deno_core::ModuleSpecifier;
 
 use std::env;
 use std::path::PathBuf;
@@ -148,15 +150,30 @@ impl DenoDir {
     self.root.join("v8_code_cache_v1")
   }
 
-  /// Path used for the REPL history file.
+  /// Folder used for caching the outputs of tasks.
+  pub fn task_cache_folder_path(&self) -> PathBuf {
+    // bump this version name to invalidate the entire cache
+    self.root.join("task_cache_v1")
+  }
+
+  /// Path used for the REPL history file. Each project, identified by its
+  /// configuration file, has its own history file.
   /// Can be overridden or disabled by setting `DENO_REPL_HISTORY` environment variable.
-  pub fn repl_history_file_path(&self) -> Option<PathBuf> {
+  pub fn repl_history_file_path(
+    &self,
+    maybe_config_specifier: Option<&ModuleSpecifier>,
+  ) -> Option<PathBuf> {
     if let Some(deno_repl_history) = env::var_os("DENO_REPL_HISTORY") {
       if deno_repl_history.is_empty() {
         None
       } else {
         Some(PathBuf::from(deno_repl_history))
       }
+    } else if let Some(config_specifier) = maybe_config_specifier {
+      Some(self.root.join("repl_history").join(format!(
+        "{}.txt",
+        checksum::gen(&[config_specifier.as_str().as_bytes()])
+      )))
     } else {
       Some(self.root.join("deno_history.txt"))
     }
@@ -0,0 +1,166 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+use std::path::PathBuf;
+
+use deno_core::anyhow::bail;
+use deno_core::anyhow::Context;
+use deno_core::error::AnyError;
+use deno_core::serde_json;
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::CACHE_PERM;
+use crate::util::checksum;
+use crate::util::fs::atomic_write_file;
+
+const MANIFEST_FILE_NAME: &str = "manifest.json";
+const OUTPUTS_DIR_NAME: &str = "outputs";
+
+#[derive(Debug, Serialize, Deserialize)]
+struct TaskCacheManifest {
+  outputs: Vec<TaskCacheOutput>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct TaskCacheOutput {
+  /// Path of the output file relative to the directory of the task.
+  path: String,
+  checksum: String,
+}
+
+/// Stores the output files of the runs of tasks by a hash of their inputs,
+/// so that a task whose inputs didn't change doesn't need to run again.
+pub struct TaskCache {
+  location: PathBuf,
+}
+
+impl TaskCache {
+  pub fn new(location: PathBuf) -> Self {
+    Self { location }
+  }
+
+  /// Restores the output files of a previous run with the given hash into
+  /// `cwd`, leaving the files that are intact untouched. Returns `false`
+  /// when there was no such run.
+  pub fn restore(&self, hash: &str, cwd: &Path) -> Result<bool, AnyError> {
+    let entry_path = self.location.join(hash);
+    let manifest_text =
+      match fs::read_to_string(entry_path.join(MANIFEST_FILE_NAME)) {
+        Ok(text) => text,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(false),
+        Err(err) => return Err(err.into()),
+      };
+    let Ok(manifest) =
+      serde_json::from_str::<TaskCacheManifest>(&manifest_text)
+    else {
+      // treat a corrupt entry as a cache miss, it's replaced after the run
+      return Ok(false);
+    };
+
+    for output in &manifest.outputs {
+      let path = cwd.join(&output.path);
+      let is_intact = fs::read(&path)
+        .map(|bytes| checksum::gen(&[bytes]) == output.checksum)
+        .unwrap_or(false);
+      if is_intact {
+        continue;
+      }
+      let cached_path = entry_path.join(OUTPUTS_DIR_NAME).join(&output.path);
+      if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+      }
+      fs::copy(&cached_path, &path).with_context(|| {
+        format!("Failed restoring '{}' from the task cache", path.display())
+      })?;
+    }
+    Ok(true)
+  }
+
+  /// Stores the output files of a successful run with the given hash.
+  pub fn save(
+    &self,
+    hash: &str,
+    cwd: &Path,
+    output_files: &[PathBuf],
+  ) -> Result<(), AnyError> {
+    let entry_path = self.location.join(hash);
+    let outputs_path = entry_path.join(OUTPUTS_DIR_NAME);
+    // remove any entry left behind by an interrupted save
+    match fs::remove_dir_all(&entry_path) {
+      Ok(()) => {}
+      Err(err) if err.kind() == ErrorKind::NotFound => {}
+      Err(err) => return Err(err.into()),
+    }
+
+    let mut outputs = Vec::with_capacity(output_files.len());
+    for file_path in output_files {
+      let Ok(relative_path) = file_path.strip_prefix(cwd) else {
+        bail!(
+          "Task output '{}' is outside of the task's directory.",
+          file_path.display()
+        );
+      };
+      let bytes = fs::read(file_path)?;
+      let cached_path = outputs_path.join(relative_path);
+      fs::create_dir_all(cached_path.parent().unwrap())?;
+      fs::copy(file_path, &cached_path)?;
+      outputs.push(TaskCacheOutput {
+        path: relative_path.to_string_lossy().to_string(),
+        checksum: checksum::gen(&[bytes]),
+      });
+    }
+
+    // the manifest is written last, so an entry is only used once complete
+    fs::create_dir_all(&entry_path)?;
+    let manifest = serde_json::to_string(&TaskCacheManifest { outputs })?;
+    atomic_write_file(
+      &entry_path.join(MANIFEST_FILE_NAME),
+      manifest,
+      CACHE_PERM,
+    )?;
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use test_util::TempDir;
+
+  #[test]
+  fn saves_and_restores_outputs() {
+    let temp_dir = TempDir::new();
+    let cache = TaskCache::new(temp_dir.path().join("cache").to_path_buf());
+    let cwd = temp_dir.path().join("project").to_path_buf();
+    let output_path = cwd.join("dist").join("out.js");
+    fs::create_dir_all(output_path.parent().unwrap()).unwrap();
+    fs::write(&output_path, "console.log(1);").unwrap();
+
+    assert!(!cache.restore("hash", &cwd).unwrap());
+    cache.save("hash", &cwd, &[output_path.clone()]).unwrap();
+
+    // intact outputs are left alone
+    assert!(cache.restore("hash", &cwd).unwrap());
+    assert_eq!(fs::read_to_string(&output_path).unwrap(), "console.log(1);");
+
+    // changed or removed outputs are restored
+    fs::write(&output_path, "changed").unwrap();
+    assert!(cache.restore("hash", &cwd).unwrap());
+    assert_eq!(fs::read_to_string(&output_path).unwrap(), "console.log(1);");
+    fs::remove_dir_all(cwd.join("dist")).unwrap();
+    assert!(cache.restore("hash", &cwd).unwrap());
+    assert_eq!(fs::read_to_string(&output_path).unwrap(), "console.log(1);");
+
+    assert!(!cache.restore("other", &cwd).unwrap());
+    assert!(cache
+      .save(
+        "other",
+        &cwd,
+        &[temp_dir.path().join("outside.js").to_path_buf()]
+      )
+      .is_err());
+  }
+}
@@ -120,6 +120,27 @@ impl Emitter {
     }
   }
 
+  /// Emits the source without a source map, which is useful for measuring
+  /// the size of the emitted code. The emit isn't cached.
+  pub fn emit_parsed_source_without_source_map(
+    &self,
+    specifier: &ModuleSpecifier,
+    media_type: MediaType,
+    source: &Arc<str>,
+  ) -> Result<String, AnyError> {
+    let parsed_source = self.parsed_source_cache.remove_or_parse_module(
+      specifier,
+      source.clone(),
+      media_type,
+    )?;
+    let mut options = self.emit_options.clone();
+    options.source_map = SourceMapOption::None;
+    let transpiled_source = parsed_source
+      .transpile(&self.transpile_options, &options)?
+      .into_source();
+    Ok(transpiled_source.text)
+  }
+
   /// Expects a file URL, panics otherwise.
   pub async fn load_and_emit_for_hmr(
     &self,
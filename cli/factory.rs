@@ -47,6 +47,7 @@ use crate::standalone::DenoCompileBinaryWriter;
 use crate::tools::check::TypeChecker;
 use crate::tools::coverage::CoverageCollector;
 use crate::tools::run::hmr::HmrRunner;
+use crate::util::file_watcher::has_config_changed;
 use crate::util::file_watcher::WatcherCommunicator;
 use crate::util::fs::canonicalize_path_maybe_not_exists;
 use crate::util::progress_bar::ProgressBar;
@@ -59,6 +60,7 @@ use deno_core::error::AnyError;
 use deno_core::futures::FutureExt;
 use deno_core::parking_lot::Mutex;
 use deno_core::FeatureChecker;
+use deno_core::ModuleSpecifier;
 
 use deno_graph::GraphKind;
 use deno_lockfile::WorkspaceMemberConfig;
@@ -71,6 +73,7 @@ use deno_runtime::inspector_server::InspectorServer;
 use import_map::ImportMap;
 use log::warn;
 use std::future::Future;
+use std::rc::Rc;
 use std::sync::Arc;
 
 pub struct CliFactoryBuilder {
@@ -106,6 +109,54 @@ impl CliFactoryBuilder {
   }
 }
 
+/// Keeps a factory across the restarts of a file watcher, so that its
+/// services and caches are reused instead of being created on each change.
+#[derive(Default)]
+pub struct WatcherFactory {
+  factory: Option<Rc<CliFactory>>,
+}
+
+impl WatcherFactory {
+  /// Gets the factory for a restart with the changed paths.
+  ///
+  /// The factory is created again when the options it was built with are
+  /// stale, which is when the configuration changed or when the dependencies
+  /// were reloaded on the first run. Otherwise the parsed sources of the
+  /// changed files, and the module graph and in-memory files of the previous
+  /// run are dropped. Local files are read from the disk on each load, so
+  /// they aren't cached otherwise.
+  pub fn get(
+    &mut self,
+    flags: Flags,
+    watcher_communicator: Arc<WatcherCommunicator>,
+    changed_paths: Option<&[PathBuf]>,
+  ) -> Result<Rc<CliFactory>, AnyError> {
+    if let (Some(factory), Some(changed_paths)) = (&self.factory, changed_paths)
+    {
+      let cli_options = factory.cli_options();
+      if !cli_options.reload_flag()
+        && !has_config_changed(cli_options, changed_paths)
+      {
+        let parsed_source_cache = factory.parsed_source_cache();
+        for path in changed_paths {
+          if let Ok(specifier) = ModuleSpecifier::from_file_path(path) {
+            parsed_source_cache.free(&specifier);
+          }
+        }
+        factory.graph_container().clear();
+        factory.file_fetcher()?.clear_memory_files();
+        return Ok(factory.clone());
+      }
+    }
+    let factory = Rc::new(
+      CliFactoryBuilder::new()
+        .build_from_flags_for_watcher(flags, watcher_communicator)?,
+    );
+    self.factory = Some(factory.clone());
+    Ok(factory)
+  }
+}
+
 struct Deferred<T>(once_cell::unsync::OnceCell<T>);
 
 impl<T> Default for Deferred<T> {
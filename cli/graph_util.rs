@@ -23,7 +23,6 @@ use crate::util::sync::TaskQueuePermit;
 use deno_runtime::fs_util::specifier_to_file_path;
 
 use deno_config::WorkspaceMemberConfig;
-use deno_core::anyhow::bail;
 use deno_core::error::custom_error;
 use deno_core::error::AnyError;
 use deno_core::parking_lot::Mutex;
@@ -337,7 +336,7 @@ impl ModuleGraphCreator {
     self.module_graph_builder.graph_valid(graph)
   }
 
-  async fn type_check_graph(
+  pub async fn type_check_graph(
     &self,
     graph: ModuleGraph,
   ) -> Result<Arc<ModuleGraph>, AnyError> {
@@ -348,6 +347,7 @@ impl ModuleGraphCreator {
         check::CheckOptions {
           build_fast_check_graph: true,
           lib: self.options.ts_type_lib_window(),
+          log_checked_roots: true,
           log_ignored_options: true,
           reload: self.options.reload_flag(),
           type_check_mode: self.options.type_check_mode(),
@@ -672,23 +672,6 @@ impl ModuleGraphBuilder {
   }
 }
 
-pub fn error_for_any_npm_specifier(
-  graph: &ModuleGraph,
-) -> Result<(), AnyError> {
-  for module in graph.modules() {
-    match module {
-      Module::Npm(module) => {
-        bail!("npm specifiers have not yet been implemented for this subcommand (https://github.com/denoland/deno/issues/15960). Found: {}", module.specifier)
-      }
-      Module::Node(module) => {
-        bail!("Node specifiers have not yet been implemented for this subcommand (https://github.com/denoland/deno/issues/15960). Found: node:{}", module.module_name)
-      }
-      Module::Js(_) | Module::Json(_) | Module::External(_) => {}
-    }
-  }
-  Ok(())
-}
-
 /// Adds more explanatory information to a resolution error.
 pub fn enhanced_resolution_error_message(error: &ResolutionError) -> String {
   let mut message = format!("{error}");
@@ -795,6 +778,13 @@ impl ModuleGraphContainer {
   pub fn graph(&self) -> Arc<ModuleGraph> {
     self.inner.read().clone()
   }
+
+  /// Replaces the graph with an empty one, so that the modules are loaded
+  /// again, e.g. after they changed on a watcher restart.
+  pub fn clear(&self) {
+    let mut inner = self.inner.write();
+    *inner = Arc::new(ModuleGraph::new(inner.graph_kind()));
+  }
 }
 
 /// Gets if any of the specified root's "file:" dependents are in the
@@ -829,6 +819,26 @@ pub fn has_graph_root_local_dependent_changed(
   false
 }
 
+/// Gets the roots of the graph with a "file:" dependent in the changed
+/// paths of a watcher restart, or all the roots on the first run.
+pub fn graph_roots_with_changed_dependents(
+  graph: &ModuleGraph,
+  changed_paths: Option<Vec<PathBuf>>,
+) -> Vec<ModuleSpecifier> {
+  let Some(changed_paths) = changed_paths else {
+    return graph.roots.clone();
+  };
+  let changed_paths = changed_paths.into_iter().collect::<HashSet<_>>();
+  graph
+    .roots
+    .iter()
+    .filter(|root| {
+      has_graph_root_local_dependent_changed(graph, root, &changed_paths)
+    })
+    .cloned()
+    .collect()
+}
+
 /// A permit for updating the module graph. When complete and
 /// everything looks fine, calling `.commit()` will store the
 /// new graph in the ModuleGraphContainer.
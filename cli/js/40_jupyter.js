@@ -337,7 +337,11 @@ async function formatInner(obj, raw) {
 internals.jupyter = { formatInner };
 
 function enableJupyter() {
-  const { op_jupyter_broadcast } = core.ops;
+  const {
+    op_jupyter_broadcast,
+    op_jupyter_comm_register,
+    op_jupyter_comm_unregister,
+  } = core.ops;
 
   async function broadcast(
     msgType,
@@ -386,6 +390,177 @@ function enableJupyter() {
 
   internals.jupyter.broadcastResult = broadcastResult;
 
+  /** Open comms by comm id. */
+  const comms = new Map();
+  /** Callbacks for comms opened by the frontend, by target name. */
+  const commTargets = new Map();
+
+  const _dispatchMessage = Symbol("dispatchMessage");
+  const _dispatchClose = Symbol("dispatchClose");
+
+  /**
+   * One end of a Jupyter comm, a channel for custom messages between the
+   * kernel and the frontend. Comms back interactive widgets.
+   */
+  class Comm {
+    #id;
+    #targetName;
+    #closed = false;
+    #messageListeners = [];
+    #closeListeners = [];
+
+    constructor(id, targetName) {
+      this.#id = id;
+      this.#targetName = targetName;
+      comms.set(id, this);
+      op_jupyter_comm_register(id, targetName);
+    }
+
+    get id() {
+      return this.#id;
+    }
+
+    get targetName() {
+      return this.#targetName;
+    }
+
+    get closed() {
+      return this.#closed;
+    }
+
+    /** Sends a message with the given data to the frontend. */
+    async send(data = {}, { metadata = {}, buffers = [] } = {}) {
+      if (this.#closed) {
+        throw new Error(`Comm ${this.#id} is closed`);
+      }
+      await broadcast("comm_msg", { comm_id: this.#id, data }, {
+        metadata,
+        buffers,
+      });
+    }
+
+    /** Closes the comm, notifying the frontend. */
+    async close(data = {}, { metadata = {}, buffers = [] } = {}) {
+      if (this.#closed) {
+        return;
+      }
+      this.#markClosed();
+      await broadcast("comm_close", { comm_id: this.#id, data }, {
+        metadata,
+        buffers,
+      });
+    }
+
+    /** Adds a listener for messages sent by the frontend. */
+    onMessage(listener) {
+      this.#messageListeners.push(listener);
+    }
+
+    /** Adds a listener called when the frontend closes the comm. */
+    onClose(listener) {
+      this.#closeListeners.push(listener);
+    }
+
+    #markClosed() {
+      this.#closed = true;
+      comms.delete(this.#id);
+      op_jupyter_comm_unregister(this.#id);
+    }
+
+    async [_dispatchMessage](data, options) {
+      for (const listener of this.#messageListeners) {
+        await callListener(() => listener(data, options));
+      }
+    }
+
+    async [_dispatchClose](data, options) {
+      if (this.#closed) {
+        return;
+      }
+      this.#markClosed();
+      for (const listener of this.#closeListeners) {
+        await callListener(() => listener(data, options));
+      }
+    }
+  }
+
+  /** Errors thrown by user listeners shouldn't take down the kernel. */
+  async function callListener(fn) {
+    try {
+      await fn();
+    } catch (err) {
+      console.error(err);
+    }
+  }
+
+  /**
+   * Opens a comm from the kernel side, for example with the
+   * `"jupyter.widget"` target to create a widget model.
+   */
+  async function openComm(
+    targetName,
+    data = {},
+    { metadata = {}, buffers = [] } = {},
+  ) {
+    const comm = new Comm(crypto.randomUUID(), targetName);
+    await broadcast("comm_open", {
+      comm_id: comm.id,
+      target_name: targetName,
+      data,
+    }, { metadata, buffers });
+    return comm;
+  }
+
+  /**
+   * Registers a callback for comms the frontend opens with the given target
+   * name. Comms opened for targets without a callback are closed.
+   */
+  function registerCommTarget(targetName, callback) {
+    commTargets.set(targetName, callback);
+  }
+
+  /**
+   * Called by the kernel for `comm_open`, `comm_msg` and `comm_close`
+   * messages from the frontend. Returns whether the message was handled.
+   */
+  async function handleCommMessage(msgType, content, metadata, buffers) {
+    const options = {
+      metadata,
+      buffers: buffers.map((buffer) => new Uint8Array(buffer)),
+    };
+    switch (msgType) {
+      case "comm_open": {
+        const callback = commTargets.get(content.target_name);
+        if (callback === undefined) {
+          return false;
+        }
+        const comm = new Comm(content.comm_id, content.target_name);
+        await callListener(() => callback(comm, content.data, options));
+        return true;
+      }
+      case "comm_msg": {
+        const comm = comms.get(content.comm_id);
+        if (comm === undefined) {
+          return false;
+        }
+        await comm[_dispatchMessage](content.data, options);
+        return true;
+      }
+      case "comm_close": {
+        const comm = comms.get(content.comm_id);
+        if (comm === undefined) {
+          return false;
+        }
+        await comm[_dispatchClose](content.data, options);
+        return true;
+      }
+      default:
+        return false;
+    }
+  }
+
+  internals.jupyter.handleCommMessage = handleCommMessage;
+
   /**
    * Display function for Jupyter Deno Kernel.
    * Mimics the behavior of IPython's `display(obj, raw=True)` function to allow
@@ -415,6 +590,8 @@ function enableJupyter() {
   globalThis.Deno.jupyter = {
     broadcast,
     display,
+    openComm,
+    registerCommTarget,
     format,
     md,
     html,
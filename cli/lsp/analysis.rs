@@ -5,10 +5,12 @@ use super::diagnostics::DiagnosticSource;
 use super::documents::Documents;
 use super::language_server;
 use super::resolver::LspResolver;
+use super::text::LineIndex;
 use super::tsc;
 
 use crate::args::jsr_url;
 use crate::tools::lint::create_linter;
+use crate::util::path::relative_specifier;
 use deno_runtime::fs_util::specifier_to_file_path;
 
 use deno_ast::SourceRange;
@@ -24,6 +26,7 @@ use deno_core::serde_json::json;
 use deno_core::ModuleSpecifier;
 use deno_lint::diagnostic::LintDiagnostic;
 use deno_lint::rules::LintRule;
+use deno_lockfile::Lockfile;
 use deno_runtime::deno_node::NpmResolver;
 use deno_runtime::deno_node::PathClean;
 use deno_semver::jsr::JsrPackageNvReference;
@@ -41,6 +44,7 @@ use std::cmp::Ordering;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::path::Path;
+use std::str::FromStr;
 use tower_lsp::lsp_types as lsp;
 use tower_lsp::lsp_types::Position;
 use tower_lsp::lsp_types::Range;
@@ -1147,6 +1151,204 @@ pub fn source_range_to_lsp_range(
   }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PackageKind {
+  Jsr,
+  Npm,
+}
+
+/// A `jsr:` or `npm:` specifier along with the version that its package
+/// requirement was resolved to in the lockfile.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LockedPackage {
+  pub kind: PackageKind,
+  pub req_ref: PackageReqReference,
+  pub version: Version,
+}
+
+impl LockedPackage {
+  /// Returns the specifier with its version requirement replaced by a caret
+  /// range of `version`.
+  pub fn specifier_for_version(&self, version: &Version) -> Option<String> {
+    let req =
+      PackageReq::from_str(&format!("{}@^{}", self.req_ref.req.name, version))
+        .ok()?;
+    let req_ref = PackageReqReference {
+      req,
+      sub_path: self.req_ref.sub_path.clone(),
+    };
+    Some(match self.kind {
+      PackageKind::Jsr => JsrPackageReqReference::new(req_ref).to_string(),
+      PackageKind::Npm => NpmPackageReqReference::new(req_ref).to_string(),
+    })
+  }
+}
+
+fn parse_package_req_reference(
+  specifier: &ModuleSpecifier,
+) -> Option<(PackageKind, PackageReqReference)> {
+  if let Ok(req_ref) = JsrPackageReqReference::from_specifier(specifier) {
+    Some((PackageKind::Jsr, req_ref.into_inner()))
+  } else if let Ok(req_ref) = NpmPackageReqReference::from_specifier(specifier)
+  {
+    Some((PackageKind::Npm, req_ref.into_inner()))
+  } else {
+    None
+  }
+}
+
+pub fn get_locked_package(
+  lockfile: &Lockfile,
+  specifier: &ModuleSpecifier,
+) -> Option<LockedPackage> {
+  let (kind, req_ref) = parse_package_req_reference(specifier)?;
+  let prefix = match kind {
+    PackageKind::Jsr => "jsr:",
+    PackageKind::Npm => "npm:",
+  };
+  let nv = lockfile
+    .content
+    .packages
+    .specifiers
+    .get(&format!("{}{}", prefix, req_ref.req))?;
+  let nv = PackageNv::from_str(nv.strip_prefix(prefix)?).ok()?;
+  Some(LockedPackage {
+    kind,
+    req_ref,
+    version: nv.version,
+  })
+}
+
+/// Returns the newest version that is not a pre-release, if it is newer than
+/// the locked version.
+pub fn get_outdated_version<'a>(
+  locked: &LockedPackage,
+  versions: &'a [Version],
+) -> Option<&'a Version> {
+  versions
+    .iter()
+    .filter(|v| v.pre.is_empty())
+    .max()
+    .filter(|latest| **latest > locked.version)
+}
+
+/// Computes the edits to a deno.json or import map text that are required
+/// when files are renamed. Relative specifiers in the `imports`, `scopes` and
+/// `exports` entries that point at a renamed file, or into a renamed
+/// directory, are rewritten relative to `base`.
+pub fn get_json_edits_for_file_renames(
+  text: &str,
+  base: &ModuleSpecifier,
+  renames: &[(ModuleSpecifier, ModuleSpecifier)],
+) -> Result<Vec<lsp::TextEdit>, AnyError> {
+  get_json_string_edits(text, &["imports", "scopes", "exports"], |value| {
+    if !value.starts_with("./") && !value.starts_with("../") {
+      return None;
+    }
+    let specifier = base.join(value).ok()?;
+    let new_specifier = renames.iter().find_map(|(old, new)| {
+      if &specifier == old {
+        return Some(new.clone());
+      }
+      // the renamed specifier is a directory
+      let old_dir = format!("{}/", old.as_str().trim_end_matches('/'));
+      let suffix = specifier.as_str().strip_prefix(&old_dir)?;
+      let new_dir = format!("{}/", new.as_str().trim_end_matches('/'));
+      ModuleSpecifier::parse(&format!("{new_dir}{suffix}")).ok()
+    })?;
+    relative_specifier(base, &new_specifier)
+  })
+}
+
+/// Computes the edits to a deno.json or import map text that update the
+/// `imports` and `scopes` entries which map to the same package requirement
+/// as `locked` to a caret range of `version`.
+pub fn get_json_edits_for_package_version(
+  text: &str,
+  locked: &LockedPackage,
+  version: &Version,
+) -> Result<Vec<lsp::TextEdit>, AnyError> {
+  get_json_string_edits(text, &["imports", "scopes"], |value| {
+    // directory mappings like `"@std/path/": "jsr:@std/path@^1/"`
+    let (value, trailing_slash) = match value.strip_suffix('/') {
+      Some(value) => (value, "/"),
+      None => (value, ""),
+    };
+    let specifier = ModuleSpecifier::parse(value).ok()?;
+    let (kind, req_ref) = parse_package_req_reference(&specifier)?;
+    if kind != locked.kind || req_ref.req != locked.req_ref.req {
+      return None;
+    }
+    let mapped = LockedPackage {
+      kind,
+      req_ref,
+      version: locked.version.clone(),
+    };
+    let new_specifier = mapped.specifier_for_version(version)?;
+    Some(format!("{new_specifier}{trailing_slash}"))
+  })
+}
+
+/// Replaces the string values found under the given top level `keys` of a
+/// JSON text with what `get_new_value` returns for them, if anything.
+fn get_json_string_edits(
+  text: &str,
+  keys: &[&str],
+  mut get_new_value: impl FnMut(&str) -> Option<String>,
+) -> Result<Vec<lsp::TextEdit>, AnyError> {
+  use jsonc_parser::ast::Value;
+
+  fn collect_string_lits<'a>(
+    value: &'a Value<'a>,
+    lits: &mut Vec<&'a jsonc_parser::ast::StringLit<'a>>,
+  ) {
+    match value {
+      Value::StringLit(lit) => lits.push(lit),
+      Value::Object(obj) => {
+        for prop in &obj.properties {
+          collect_string_lits(&prop.value, lits);
+        }
+      }
+      _ => {}
+    }
+  }
+
+  let ast =
+    jsonc_parser::parse_to_ast(text, &Default::default(), &Default::default())?;
+  let Some(Value::Object(obj)) = &ast.value else {
+    return Ok(Vec::new());
+  };
+  let mut lits = Vec::new();
+  for key in keys {
+    if let Some(prop) = obj.get(key) {
+      collect_string_lits(&prop.value, &mut lits);
+    }
+  }
+
+  let line_index = LineIndex::new(text);
+  let mut edits = Vec::new();
+  for lit in lits {
+    let Some(new_text) = get_new_value(lit.value.as_ref()) else {
+      continue;
+    };
+    // skip the quotes
+    let start = lit.range.start + 1;
+    let end = lit.range.end - 1;
+    let to_position = |offset: usize| {
+      let utf16_offset = text[..offset].encode_utf16().count() as u32;
+      line_index.position_tsc(utf16_offset.into())
+    };
+    edits.push(lsp::TextEdit {
+      range: Range {
+        start: to_position(start),
+        end: to_position(end),
+      },
+      new_text: new_text.replace('"', "\\\""),
+    });
+  }
+  Ok(edits)
+}
+
 #[cfg(test)]
 mod tests {
   use std::path::PathBuf;
@@ -1274,4 +1476,175 @@ mod tests {
       "\u{a0}foo"
     );
   }
+
+  #[test]
+  fn test_get_locked_package() {
+    let mut lockfile =
+      Lockfile::with_lockfile_content(PathBuf::from("/deno.lock"), "{}", false)
+        .unwrap();
+    lockfile.insert_package_specifier(
+      "npm:chalk@^5".to_string(),
+      "npm:chalk@5.3.0".to_string(),
+    );
+    let locked = get_locked_package(
+      &lockfile,
+      &ModuleSpecifier::parse("npm:chalk@^5/ansi").unwrap(),
+    )
+    .unwrap();
+    assert_eq!(locked.kind, PackageKind::Npm);
+    assert_eq!(locked.version, Version::parse_standard("5.3.0").unwrap());
+    assert_eq!(
+      locked
+        .specifier_for_version(&Version::parse_standard("6.0.1").unwrap())
+        .unwrap(),
+      "npm:chalk@^6.0.1/ansi"
+    );
+    let versions = ["6.0.0-beta.1", "5.3.0", "5.2.0"]
+      .iter()
+      .map(|v| Version::parse_standard(v).unwrap())
+      .collect::<Vec<_>>();
+    assert_eq!(get_outdated_version(&locked, &versions), None);
+    let versions = ["6.0.0", "5.3.0"]
+      .iter()
+      .map(|v| Version::parse_standard(v).unwrap())
+      .collect::<Vec<_>>();
+    assert_eq!(
+      get_outdated_version(&locked, &versions),
+      Some(&Version::parse_standard("6.0.0").unwrap())
+    );
+    assert!(get_locked_package(
+      &lockfile,
+      &ModuleSpecifier::parse("npm:chalk@^4").unwrap()
+    )
+    .is_none());
+  }
+
+  #[test]
+  fn test_get_json_edits_for_file_renames() {
+    let base = ModuleSpecifier::parse("file:///project/deno.json").unwrap();
+    let text = r#"{
+  "imports": {
+    "@/": "./src/",
+    "utils": "./src/utils.ts",
+    "std/": "jsr:@std/"
+  },
+  "exports": {
+    ".": "./mod.ts",
+    "./utils": "./src/utils.ts"
+  }
+}"#;
+    let edits = get_json_edits_for_file_renames(
+      text,
+      &base,
+      &[(
+        ModuleSpecifier::parse("file:///project/src/utils.ts").unwrap(),
+        ModuleSpecifier::parse("file:///project/lib/utils.ts").unwrap(),
+      )],
+    )
+    .unwrap();
+    assert_eq!(
+      edits,
+      vec![
+        lsp::TextEdit {
+          range: Range {
+            start: Position {
+              line: 3,
+              character: 14,
+            },
+            end: Position {
+              line: 3,
+              character: 28,
+            },
+          },
+          new_text: "./lib/utils.ts".to_string(),
+        },
+        lsp::TextEdit {
+          range: Range {
+            start: Position {
+              line: 8,
+              character: 16,
+            },
+            end: Position {
+              line: 8,
+              character: 30,
+            },
+          },
+          new_text: "./lib/utils.ts".to_string(),
+        },
+      ]
+    );
+
+    let edits = get_json_edits_for_file_renames(
+      text,
+      &base,
+      &[(
+        ModuleSpecifier::parse("file:///project/src").unwrap(),
+        ModuleSpecifier::parse("file:///project/lib").unwrap(),
+      )],
+    )
+    .unwrap();
+    assert_eq!(
+      edits
+        .iter()
+        .map(|e| e.new_text.as_str())
+        .collect::<Vec<_>>(),
+      vec!["./lib/", "./lib/utils.ts", "./lib/utils.ts"]
+    );
+  }
+
+  #[test]
+  fn test_get_json_edits_for_package_version() {
+    let text = r#"{
+  "imports": {
+    "@std/path": "jsr:@std/path@^0.220.0",
+    "@std/path/": "jsr:@std/path@^0.220.0/",
+    "@std/fs": "jsr:@std/fs@^0.220.0",
+    "path": "npm:@std/path@^0.220.0"
+  }
+}"#;
+    let locked = LockedPackage {
+      kind: PackageKind::Jsr,
+      req_ref: JsrPackageReqReference::from_str("jsr:@std/path@^0.220.0/join")
+        .unwrap()
+        .into_inner(),
+      version: Version::parse_standard("0.220.1").unwrap(),
+    };
+    let edits = get_json_edits_for_package_version(
+      text,
+      &locked,
+      &Version::parse_standard("1.0.0").unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+      edits,
+      vec![
+        lsp::TextEdit {
+          range: Range {
+            start: Position {
+              line: 2,
+              character: 18,
+            },
+            end: Position {
+              line: 2,
+              character: 40,
+            },
+          },
+          new_text: "jsr:@std/path@^1.0.0".to_string(),
+        },
+        lsp::TextEdit {
+          range: Range {
+            start: Position {
+              line: 3,
+              character: 19,
+            },
+            end: Position {
+              line: 3,
+              character: 42,
+            },
+          },
+          new_text: "jsr:@std/path@^1.0.0/".to_string(),
+        },
+      ]
+    );
+  }
 }
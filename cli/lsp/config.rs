@@ -94,6 +94,28 @@ pub struct DenoCompletionSettings {
   pub imports: ImportCompletionSettings,
 }
 
+#[derive(Debug, Default, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DenoInlayHintsSettings {
+  #[serde(default)]
+  pub package_versions: InlayHintsPackageVersionsOptions,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct InlayHintsPackageVersionsOptions {
+  /// Flag for hinting the version that a `jsr:` or `npm:` dependency was
+  /// locked to.
+  #[serde(default = "is_true")]
+  pub enabled: bool,
+}
+
+impl Default for InlayHintsPackageVersionsOptions {
+  fn default() -> Self {
+    Self { enabled: true }
+  }
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub struct ClassMemberSnippets {
@@ -489,6 +511,11 @@ pub struct WorkspaceSettings {
   pub code_lens: CodeLensSettings,
 
   /// A flag that indicates if internal debug logging should be made available.
+  /// Deno specific inlay hints. The TypeScript ones are configured under
+  /// `javascript.inlayHints` and `typescript.inlayHints`.
+  #[serde(default)]
+  pub inlay_hints: DenoInlayHintsSettings,
+
   #[serde(default)]
   pub internal_debug: bool,
 
@@ -546,6 +573,7 @@ impl Default for WorkspaceSettings {
       config: None,
       import_map: None,
       code_lens: Default::default(),
+      inlay_hints: Default::default(),
       internal_debug: false,
       internal_inspect: Default::default(),
       log_file: false,
@@ -1791,6 +1819,9 @@ mod tests {
           references_all_functions: false,
           test: true,
         },
+        inlay_hints: DenoInlayHintsSettings {
+          package_versions: InlayHintsPackageVersionsOptions { enabled: true },
+        },
         internal_debug: false,
         internal_inspect: InspectSetting::Bool(false),
         log_file: false,
@@ -1954,6 +1985,23 @@ mod tests {
     );
   }
 
+  #[test]
+  fn test_package_versions_inlay_hints() {
+    let settings = WorkspaceSettings::from_raw_settings(
+      json!({
+        "inlayHints": {
+          "packageVersions": { "enabled": false },
+          "parameterTypes": { "enabled": true },
+        },
+      }),
+      json!({}),
+      json!({}),
+    );
+    assert!(!settings.inlay_hints.package_versions.enabled);
+    // the deprecated tsc settings under `deno.inlayHints` still apply
+    assert!(settings.typescript.inlay_hints.parameter_types.enabled);
+  }
+
   #[tokio::test]
   async fn config_enable_via_config_file_detection() {
     let root_uri = resolve_url("file:///root/").unwrap();
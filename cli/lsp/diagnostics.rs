@@ -255,6 +255,55 @@ impl TsDiagnosticsStore {
   }
 }
 
+/// Moves the diagnostics of the virtual documents for embedded regions over to
+/// their host documents, merging them with any of the host's own diagnostics.
+fn map_embedded_diagnostics(
+  diagnostics: DiagnosticVec,
+  snapshot: &StateSnapshot,
+) -> DiagnosticVec {
+  let mut records: Vec<DiagnosticRecord> =
+    Vec::with_capacity(diagnostics.len());
+  let mut host_indexes: HashMap<ModuleSpecifier, usize> = HashMap::new();
+  for record in diagnostics {
+    let Some(embedded_doc) =
+      snapshot.documents.get_embedded_document(&record.specifier)
+    else {
+      records.push(record);
+      continue;
+    };
+    let host_version = snapshot
+      .documents
+      .get(&embedded_doc.host_specifier)
+      .and_then(|d| d.maybe_lsp_version());
+    let host_diagnostics =
+      record
+        .versioned
+        .diagnostics
+        .into_iter()
+        .map(|mut diagnostic| {
+          diagnostic.range = embedded_doc.to_host_range(diagnostic.range);
+          diagnostic
+        });
+    match host_indexes.get(&embedded_doc.host_specifier) {
+      Some(index) => records[*index]
+        .versioned
+        .diagnostics
+        .extend(host_diagnostics),
+      None => {
+        host_indexes.insert(embedded_doc.host_specifier.clone(), records.len());
+        records.push(DiagnosticRecord {
+          specifier: embedded_doc.host_specifier.clone(),
+          versioned: VersionedDiagnostics {
+            version: host_version,
+            diagnostics: host_diagnostics.collect(),
+          },
+        });
+      }
+    }
+  }
+  records
+}
+
 pub fn should_send_diagnostic_batch_index_notifications() -> bool {
   crate::args::has_flag_env_var(
     "DENO_DONT_USE_INTERNAL_LSP_DIAGNOSTIC_SYNC_FLAG",
@@ -519,7 +568,7 @@ impl DiagnosticsServer {
                     messages_len = diagnostics_publisher
                       .publish(
                         DiagnosticSource::Ts,
-                        diagnostics,
+                        map_embedded_diagnostics(diagnostics, &snapshot),
                         &url_map,
                         &token,
                       )
@@ -557,6 +606,7 @@ impl DiagnosticsServer {
                   }
                   let mark = performance.mark("lsp.update_diagnostics_deps");
                   let diagnostics = spawn_blocking({
+                    let snapshot = snapshot.clone();
                     let token = token.clone();
                     move || generate_deno_diagnostics(&snapshot, &config, token)
                   })
@@ -568,7 +618,7 @@ impl DiagnosticsServer {
                     messages_len = diagnostics_publisher
                       .publish(
                         DiagnosticSource::Deno,
-                        diagnostics,
+                        map_embedded_diagnostics(diagnostics, &snapshot),
                         &url_map,
                         &token,
                       )
@@ -606,6 +656,7 @@ impl DiagnosticsServer {
                   }
                   let mark = performance.mark("lsp.update_diagnostics_lint");
                   let diagnostics = spawn_blocking({
+                    let snapshot = snapshot.clone();
                     let token = token.clone();
                     move || generate_lint_diagnostics(&snapshot, &config, token)
                   })
@@ -617,7 +668,7 @@ impl DiagnosticsServer {
                     messages_len = diagnostics_publisher
                       .publish(
                         DiagnosticSource::Lint,
-                        diagnostics,
+                        map_embedded_diagnostics(diagnostics, &snapshot),
                         &url_map,
                         &token,
                       )
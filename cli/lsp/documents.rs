@@ -3,6 +3,9 @@
 use super::cache::calculate_fs_version;
 use super::cache::LSP_DISALLOW_GLOBAL_TO_LOCAL_COPY;
 use super::config::Config;
+use super::embedded::extractor_for_document;
+use super::embedded::get_embedded_documents;
+use super::embedded::EmbeddedDocument;
 use super::resolver::LspResolver;
 use super::testing::TestCollector;
 use super::testing::TestModule;
@@ -617,6 +620,20 @@ impl Document {
     self.open_data.as_ref()?.maybe_parsed_source.clone()
   }
 
+  /// Like `maybe_parsed_source()`, but parses the documents which aren't open.
+  pub fn parse(&self) -> Option<deno_ast::ParsedSource> {
+    if !media_type_is_diagnosable(self.media_type) {
+      return None;
+    }
+    match self.maybe_parsed_source() {
+      Some(result) => result.ok(),
+      None => {
+        parse_source(&self.specifier, self.text_info.clone(), self.media_type)
+          .ok()
+      }
+    }
+  }
+
   pub async fn maybe_test_module(&self) -> Option<Arc<TestModule>> {
     self.maybe_test_module_fut.clone()?.await
   }
@@ -856,6 +873,9 @@ pub struct Documents {
   has_injected_types_node_package: bool,
   /// If --unstable-sloppy-imports is enabled.
   unstable_sloppy_imports: bool,
+  /// The virtual documents of the regions embedded in open documents, keyed
+  /// by the specifier of the host document.
+  embedded_docs: HashMap<ModuleSpecifier, Arc<Vec<EmbeddedDocument>>>,
 }
 
 impl Documents {
@@ -870,6 +890,7 @@ impl Documents {
       npm_specifier_reqs: Default::default(),
       has_injected_types_node_package: false,
       unstable_sloppy_imports: false,
+      embedded_docs: Default::default(),
     }
   }
 
@@ -902,6 +923,7 @@ impl Documents {
     self.file_system_docs.set_dirty(true);
 
     self.open_docs.insert(specifier, document.clone());
+    self.update_embedded_documents(&document);
     self.dirty = true;
     document
   }
@@ -928,6 +950,7 @@ impl Documents {
     self.dirty = true;
     let doc = doc.with_change(version, changes)?;
     self.open_docs.insert(doc.specifier().clone(), doc.clone());
+    self.update_embedded_documents(&doc);
     Ok(doc)
   }
 
@@ -951,6 +974,7 @@ impl Documents {
   pub fn close(&mut self, specifier: &ModuleSpecifier) {
     if let Some(document) = self.open_docs.remove(specifier) {
       let document = document.closed(&self.cache);
+      self.update_embedded_documents(&document);
       self
         .file_system_docs
         .docs
@@ -960,6 +984,71 @@ impl Documents {
     }
   }
 
+  /// Replaces the virtual documents of the regions embedded in a document.
+  /// Only open documents have embedded documents.
+  fn update_embedded_documents(&mut self, host: &Document) {
+    if let Some(embedded_docs) = self.embedded_docs.remove(host.specifier()) {
+      for embedded_doc in embedded_docs.iter() {
+        self.open_docs.remove(&embedded_doc.specifier);
+      }
+    }
+    if !host.is_open() || host.is_diagnosable() {
+      return;
+    }
+    let Some(extractor) =
+      extractor_for_document(host.specifier(), host.maybe_language_id())
+    else {
+      return;
+    };
+    let embedded_docs =
+      get_embedded_documents(host.specifier(), &host.content(), extractor);
+    if embedded_docs.is_empty() {
+      return;
+    }
+    for embedded_doc in &embedded_docs {
+      let document = Document::new(
+        embedded_doc.specifier.clone(),
+        embedded_doc.text.clone(),
+        host.maybe_lsp_version(),
+        Some(embedded_doc.language_id()),
+        None,
+        self.resolver.clone(),
+        self.config.clone(),
+        &self.cache,
+      );
+      self
+        .open_docs
+        .insert(embedded_doc.specifier.clone(), document);
+    }
+    self
+      .embedded_docs
+      .insert(host.specifier().clone(), Arc::new(embedded_docs));
+  }
+
+  /// Returns the virtual documents of the regions embedded in an open
+  /// document.
+  pub fn embedded_documents(
+    &self,
+    host_specifier: &ModuleSpecifier,
+  ) -> Option<Arc<Vec<EmbeddedDocument>>> {
+    self.embedded_docs.get(host_specifier).cloned()
+  }
+
+  /// Returns the embedded region for the specifier of a virtual document.
+  pub fn get_embedded_document(
+    &self,
+    specifier: &ModuleSpecifier,
+  ) -> Option<&EmbeddedDocument> {
+    // virtual documents are always named after their host
+    let (host, _) = specifier.as_str().rsplit_once('$')?;
+    let host = ModuleSpecifier::parse(host).ok()?;
+    self
+      .embedded_docs
+      .get(&host)?
+      .iter()
+      .find(|d| &d.specifier == specifier)
+  }
+
   pub fn release(&self, specifier: &ModuleSpecifier) {
     self.file_system_docs.remove_document(specifier);
     self.file_system_docs.set_dirty(true);
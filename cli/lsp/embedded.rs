@@ -0,0 +1,416 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+//! Support for JavaScript and TypeScript regions embedded in documents that
+//! the language server doesn't otherwise understand, like the `<script>`
+//! blocks of component files or fenced code blocks in Markdown. Every region
+//! is exposed as a virtual document which is type checked like any other open
+//! document, with positions mapped back to the host document.
+
+use std::ops::Range;
+use std::sync::Arc;
+
+use deno_ast::MediaType;
+use deno_core::ModuleSpecifier;
+use tower_lsp::lsp_types as lsp;
+
+use super::documents::LanguageId;
+use super::text::LineIndex;
+
+/// A byte range of a host document containing a JavaScript or TypeScript
+/// module.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EmbeddedRegion {
+  pub media_type: MediaType,
+  pub range: Range<usize>,
+}
+
+pub trait EmbeddedRegionExtractor: Send + Sync {
+  fn extract(&self, text: &str) -> Vec<EmbeddedRegion>;
+}
+
+/// Extracts `<script>` blocks, as found in HTML and the component files of
+/// Vue, Svelte and Astro. The frontmatter of Astro components is extracted as
+/// well.
+pub struct ScriptTagExtractor {
+  frontmatter: bool,
+}
+
+impl EmbeddedRegionExtractor for ScriptTagExtractor {
+  fn extract(&self, text: &str) -> Vec<EmbeddedRegion> {
+    let mut regions = Vec::new();
+    if self.frontmatter {
+      let frontmatter_regex =
+        lazy_regex::regex!(r"(?m)\A---\r?\n([\s\S]*?)^---");
+      if let Some(body) =
+        frontmatter_regex.captures(text).and_then(|c| c.get(1))
+      {
+        regions.push(EmbeddedRegion {
+          media_type: MediaType::TypeScript,
+          range: body.range(),
+        });
+      }
+    }
+    let script_regex =
+      lazy_regex::regex!(r"(?s)<!--.*?-->|<script\b([^>]*)>(.*?)</script>");
+    let lang_regex = lazy_regex::regex!(r#"\blang\s*=\s*["']?([A-Za-z]+)"#);
+    let type_regex = lazy_regex::regex!(r#"\btype\s*=\s*["']?([A-Za-z/+-]+)"#);
+    for captures in script_regex.captures_iter(text) {
+      // skip scripts inside of comments
+      let (Some(attributes), Some(body)) = (captures.get(1), captures.get(2))
+      else {
+        continue;
+      };
+      let attributes = attributes.as_str();
+      if attributes.contains("src=") {
+        continue;
+      }
+      if let Some(kind) = type_regex.captures(attributes).and_then(|c| c.get(1))
+      {
+        if !matches!(
+          kind.as_str(),
+          "module" | "text/javascript" | "application/javascript"
+        ) {
+          continue;
+        }
+      }
+      let media_type = match lang_regex
+        .captures(attributes)
+        .and_then(|c| c.get(1))
+        .map(|l| l.as_str())
+      {
+        None | Some("js" | "javascript") => MediaType::JavaScript,
+        Some("jsx") => MediaType::Jsx,
+        Some("ts" | "typescript") => MediaType::TypeScript,
+        Some("tsx") => MediaType::Tsx,
+        Some(_) => continue,
+      };
+      regions.push(EmbeddedRegion {
+        media_type,
+        range: body.range(),
+      });
+    }
+    regions
+  }
+}
+
+/// Extracts fenced code blocks of Markdown documents.
+pub struct MarkdownCodeBlockExtractor;
+
+impl EmbeddedRegionExtractor for MarkdownCodeBlockExtractor {
+  fn extract(&self, text: &str) -> Vec<EmbeddedRegion> {
+    // Like for `deno test --doc`, code blocks in HTML comments are skipped by
+    // matching the comments without any capturing groups.
+    let blocks_regex =
+      lazy_regex::regex!(r"(?s)<!--.*?-->|```([^\r\n]*)\r?\n([\S\s]*?)```");
+    blocks_regex
+      .captures_iter(text)
+      .filter_map(|captures| {
+        let attributes = captures.get(1)?.as_str();
+        let body = captures.get(2)?;
+        let mut attributes = attributes.split(' ');
+        let media_type = match attributes.next() {
+          Some("js" | "javascript") => MediaType::JavaScript,
+          Some("mjs") => MediaType::Mjs,
+          Some("jsx") => MediaType::Jsx,
+          Some("ts" | "typescript") => MediaType::TypeScript,
+          Some("mts") => MediaType::Mts,
+          Some("tsx") => MediaType::Tsx,
+          _ => return None,
+        };
+        if attributes.any(|a| a == "ignore") {
+          return None;
+        }
+        Some(EmbeddedRegion {
+          media_type,
+          range: body.range(),
+        })
+      })
+      .collect()
+  }
+}
+
+/// Returns the extractor for the regions embedded in the document, if the
+/// document can contain any.
+pub fn extractor_for_document(
+  specifier: &ModuleSpecifier,
+  language_id: Option<LanguageId>,
+) -> Option<&'static dyn EmbeddedRegionExtractor> {
+  static SCRIPT_TAG: ScriptTagExtractor =
+    ScriptTagExtractor { frontmatter: false };
+  static ASTRO: ScriptTagExtractor = ScriptTagExtractor { frontmatter: true };
+  if language_id == Some(LanguageId::Markdown) {
+    return Some(&MarkdownCodeBlockExtractor);
+  }
+  let extension = specifier
+    .path()
+    .rsplit_once('.')
+    .map(|(_, extension)| extension.to_lowercase())?;
+  match extension.as_str() {
+    "md" | "markdown" | "mdx" => Some(&MarkdownCodeBlockExtractor),
+    "html" | "htm" | "vue" | "svelte" => Some(&SCRIPT_TAG),
+    "astro" => Some(&ASTRO),
+    _ => None,
+  }
+}
+
+/// A virtual document for a region embedded in a host document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EmbeddedDocument {
+  pub specifier: ModuleSpecifier,
+  pub host_specifier: ModuleSpecifier,
+  pub media_type: MediaType,
+  pub text: Arc<str>,
+  /// The position in the host document where the region starts.
+  start: lsp::Position,
+  /// The position in the host document where the region ends.
+  end: lsp::Position,
+}
+
+impl EmbeddedDocument {
+  pub fn language_id(&self) -> LanguageId {
+    match self.media_type {
+      MediaType::Jsx => LanguageId::Jsx,
+      MediaType::TypeScript | MediaType::Mts => LanguageId::TypeScript,
+      MediaType::Tsx => LanguageId::Tsx,
+      _ => LanguageId::JavaScript,
+    }
+  }
+
+  pub fn contains_host_position(&self, position: &lsp::Position) -> bool {
+    self.start <= *position && *position <= self.end
+  }
+
+  pub fn to_embedded_position(&self, position: lsp::Position) -> lsp::Position {
+    if position.line == self.start.line {
+      lsp::Position {
+        line: 0,
+        character: position.character.saturating_sub(self.start.character),
+      }
+    } else {
+      lsp::Position {
+        line: position.line - self.start.line,
+        character: position.character,
+      }
+    }
+  }
+
+  pub fn to_host_position(&self, position: lsp::Position) -> lsp::Position {
+    if position.line == 0 {
+      lsp::Position {
+        line: self.start.line,
+        character: self.start.character + position.character,
+      }
+    } else {
+      lsp::Position {
+        line: self.start.line + position.line,
+        character: position.character,
+      }
+    }
+  }
+
+  pub fn to_host_range(&self, range: lsp::Range) -> lsp::Range {
+    lsp::Range {
+      start: self.to_host_position(range.start),
+      end: self.to_host_position(range.end),
+    }
+  }
+
+  /// Maps the edits of a completion item for the virtual document, including
+  /// the auto-import edits added on resolve, to the host document.
+  pub fn to_host_completion_item(&self, item: &mut lsp::CompletionItem) {
+    match &mut item.text_edit {
+      Some(lsp::CompletionTextEdit::Edit(edit)) => {
+        edit.range = self.to_host_range(edit.range);
+      }
+      Some(lsp::CompletionTextEdit::InsertAndReplace(edit)) => {
+        edit.insert = self.to_host_range(edit.insert);
+        edit.replace = self.to_host_range(edit.replace);
+      }
+      None => {}
+    }
+    self.to_host_additional_text_edits(item);
+  }
+
+  pub fn to_host_additional_text_edits(&self, item: &mut lsp::CompletionItem) {
+    for edit in item.additional_text_edits.iter_mut().flatten() {
+      edit.range = self.to_host_range(edit.range);
+    }
+  }
+}
+
+/// Extracts the embedded documents of a host document.
+pub fn get_embedded_documents(
+  host_specifier: &ModuleSpecifier,
+  text: &str,
+  extractor: &dyn EmbeddedRegionExtractor,
+) -> Vec<EmbeddedDocument> {
+  let line_index = LineIndex::new(text);
+  let to_position = |offset: usize| {
+    let utf16_offset = text[..offset].encode_utf16().count() as u32;
+    line_index.position_tsc(utf16_offset.into())
+  };
+  extractor
+    .extract(text)
+    .into_iter()
+    .filter_map(|region| {
+      let start = to_position(region.range.start);
+      let end = to_position(region.range.end);
+      let specifier = ModuleSpecifier::parse(&format!(
+        "{}${}-{}{}",
+        host_specifier,
+        start.line + 1,
+        end.line + 1,
+        region.media_type.as_ts_extension(),
+      ))
+      .ok()?;
+      Some(EmbeddedDocument {
+        specifier,
+        host_specifier: host_specifier.clone(),
+        media_type: region.media_type,
+        text: text[region.range].into(),
+        start,
+        end,
+      })
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_script_tag_extractor() {
+    let text = r#"<template><p>{{ a }}</p></template>
+<script setup lang="ts">
+const a: number = 1;
+</script>
+<script src="./foo.js"></script>
+<!-- <script>ignored()</script> -->
+<script type="application/json">{}</script>
+<script>let b = 2;</script>
+"#;
+    let regions = ScriptTagExtractor { frontmatter: false }.extract(text);
+    assert_eq!(
+      regions
+        .iter()
+        .map(|r| (r.media_type, &text[r.range.clone()]))
+        .collect::<Vec<_>>(),
+      vec![
+        (MediaType::TypeScript, "\nconst a: number = 1;\n"),
+        (MediaType::JavaScript, "let b = 2;"),
+      ]
+    );
+
+    let text = "---\nconst title: string = \"a\";\n---\n<h1>{title}</h1>\n";
+    let regions = ScriptTagExtractor { frontmatter: true }.extract(text);
+    assert_eq!(
+      regions
+        .iter()
+        .map(|r| (r.media_type, &text[r.range.clone()]))
+        .collect::<Vec<_>>(),
+      vec![(MediaType::TypeScript, "const title: string = \"a\";\n")]
+    );
+  }
+
+  #[test]
+  fn test_markdown_code_block_extractor() {
+    let text = "# Title\n\n```ts\nconst a = 1;\n```\n\n```sh\nls\n```\n\n```js ignore\nfoo();\n```\n";
+    let regions = MarkdownCodeBlockExtractor.extract(text);
+    assert_eq!(
+      regions
+        .iter()
+        .map(|r| (r.media_type, &text[r.range.clone()]))
+        .collect::<Vec<_>>(),
+      vec![(MediaType::TypeScript, "const a = 1;\n")]
+    );
+  }
+
+  #[test]
+  fn test_embedded_document_positions() {
+    let host = ModuleSpecifier::parse("file:///a/App.vue").unwrap();
+    let text =
+      "<div/>\n<script lang=\"ts\">const a = 1;\nconst b = a;\n</script>\n";
+    let documents = get_embedded_documents(
+      &host,
+      text,
+      extractor_for_document(&host, None).unwrap(),
+    );
+    assert_eq!(documents.len(), 1);
+    let document = &documents[0];
+    assert_eq!(document.specifier.as_str(), "file:///a/App.vue$2-4.ts");
+    assert_eq!(document.language_id(), LanguageId::TypeScript);
+    assert_eq!(&*document.text, "const a = 1;\nconst b = a;\n");
+    let host_position = lsp::Position {
+      line: 1,
+      character: 24,
+    };
+    assert!(document.contains_host_position(&host_position));
+    let position = document.to_embedded_position(host_position);
+    assert_eq!(
+      position,
+      lsp::Position {
+        line: 0,
+        character: 6,
+      }
+    );
+    assert_eq!(document.to_host_position(position), host_position);
+    let position = lsp::Position {
+      line: 1,
+      character: 10,
+    };
+    assert_eq!(
+      document.to_host_position(position),
+      lsp::Position {
+        line: 2,
+        character: 10,
+      }
+    );
+    assert!(!document.contains_host_position(&lsp::Position {
+      line: 0,
+      character: 3,
+    }));
+  }
+
+  #[test]
+  fn test_embedded_document_completion_item() {
+    let host = ModuleSpecifier::parse("file:///a/README.md").unwrap();
+    let text = "# Title\n\n```ts\nconst a = 1;\n```\n";
+    let documents = get_embedded_documents(
+      &host,
+      text,
+      extractor_for_document(&host, None).unwrap(),
+    );
+    let document = &documents[0];
+    let range = |line, start, end| lsp::Range {
+      start: lsp::Position {
+        line,
+        character: start,
+      },
+      end: lsp::Position {
+        line,
+        character: end,
+      },
+    };
+    let mut item = lsp::CompletionItem {
+      text_edit: Some(lsp::CompletionTextEdit::Edit(lsp::TextEdit {
+        range: range(0, 6, 7),
+        new_text: "b".to_string(),
+      })),
+      additional_text_edits: Some(vec![lsp::TextEdit {
+        range: range(0, 0, 0),
+        new_text: "import { b } from \"./b.ts\";\n".to_string(),
+      }]),
+      ..Default::default()
+    };
+    document.to_host_completion_item(&mut item);
+    assert_eq!(
+      item.text_edit,
+      Some(lsp::CompletionTextEdit::Edit(lsp::TextEdit {
+        range: range(3, 6, 7),
+        new_text: "b".to_string(),
+      }))
+    );
+    assert_eq!(item.additional_text_edits.unwrap()[0].range, range(3, 0, 0));
+  }
+}
@@ -2,6 +2,7 @@
 
 use base64::Engine;
 use deno_ast::MediaType;
+use deno_ast::SourcePos;
 use deno_core::anyhow::anyhow;
 use deno_core::error::AnyError;
 use deno_core::resolve_url;
@@ -16,6 +17,7 @@ use deno_graph::Resolution;
 use deno_runtime::deno_tls::rustls::RootCertStore;
 use deno_runtime::deno_tls::RootCertStoreProvider;
 use deno_semver::jsr::JsrPackageReqReference;
+use deno_semver::Version;
 use indexmap::IndexSet;
 use log::error;
 use serde::Deserialize;
@@ -38,9 +40,15 @@ use tower_lsp::lsp_types::request::*;
 use tower_lsp::lsp_types::*;
 
 use super::analysis::fix_ts_import_changes;
+use super::analysis::get_json_edits_for_file_renames;
+use super::analysis::get_json_edits_for_package_version;
+use super::analysis::get_locked_package;
+use super::analysis::get_outdated_version;
 use super::analysis::ts_changes_to_edit;
 use super::analysis::CodeActionCollection;
 use super::analysis::CodeActionData;
+use super::analysis::LockedPackage;
+use super::analysis::PackageKind;
 use super::analysis::TsResponseImportMapper;
 use super::cache;
 use super::capabilities;
@@ -62,6 +70,7 @@ use super::documents::Document;
 use super::documents::Documents;
 use super::documents::DocumentsFilter;
 use super::documents::LanguageId;
+use super::embedded::EmbeddedDocument;
 use super::jsr::CliJsrSearchApi;
 use super::logging::lsp_log;
 use super::logging::lsp_warn;
@@ -74,6 +83,7 @@ use super::performance::PerformanceMark;
 use super::refactor;
 use super::registries::ModuleRegistry;
 use super::resolver::LspResolver;
+use super::search::PackageSearchApi;
 use super::testing;
 use super::text;
 use super::tsc;
@@ -102,6 +112,7 @@ use crate::lsp::tsc::file_text_changes_to_workspace_edit;
 use crate::lsp::urls::LspUrlKind;
 use crate::tools::fmt::format_file;
 use crate::tools::fmt::format_parsed_source;
+use crate::tools::info::analyze_module_exports;
 use crate::tools::upgrade::check_for_upgrades_for_lsp;
 use crate::tools::upgrade::upgrade_check_enabled;
 use crate::util::fs::remove_dir_all_if_exists;
@@ -355,6 +366,12 @@ impl LanguageServer {
     self.0.read().await.task_definitions()
   }
 
+  pub async fn unused_exports_request(
+    &self,
+  ) -> LspResult<Vec<lsp_custom::UnusedExport>> {
+    self.0.read().await.unused_exports().await
+  }
+
   pub async fn test_run_request(
     &self,
     params: Option<Value>,
@@ -1023,6 +1040,35 @@ impl Inner {
     Ok(())
   }
 
+  /// Returns the specifiers of the virtual documents for the regions embedded
+  /// in an open document.
+  fn embedded_specifiers(
+    &self,
+    specifier: &ModuleSpecifier,
+  ) -> Vec<ModuleSpecifier> {
+    self
+      .documents
+      .embedded_documents(specifier)
+      .map(|docs| docs.iter().map(|d| d.specifier.clone()).collect())
+      .unwrap_or_default()
+  }
+
+  /// Returns the virtual document of the embedded region containing the
+  /// position of a host document.
+  fn embedded_document_at(
+    &self,
+    uri: &Url,
+    position: &Position,
+  ) -> Option<EmbeddedDocument> {
+    let specifier = self.url_map.normalize_url(uri, LspUrlKind::File);
+    self
+      .documents
+      .embedded_documents(&specifier)?
+      .iter()
+      .find(|d| d.contains_host_position(position))
+      .cloned()
+  }
+
   fn did_open(
     &mut self,
     specifier: &ModuleSpecifier,
@@ -1051,7 +1097,13 @@ impl Inner {
       params.text_document.language_id.parse().unwrap(),
       params.text_document.text.into(),
     );
-    self.project_changed([(document.specifier(), ChangeKind::Opened)], false);
+    let embedded_specifiers = self.embedded_specifiers(specifier);
+    self.project_changed(
+      [(document.specifier(), ChangeKind::Opened)]
+        .into_iter()
+        .chain(embedded_specifiers.iter().map(|s| (s, ChangeKind::Opened))),
+      false,
+    );
 
     self.performance.measure(mark);
     document
@@ -1062,6 +1114,7 @@ impl Inner {
     let specifier = self
       .url_map
       .normalize_url(&params.text_document.uri, LspUrlKind::File);
+    let old_embedded_specifiers = self.embedded_specifiers(&specifier);
     match self.documents.change(
       &specifier,
       params.text_document.version,
@@ -1077,6 +1130,29 @@ impl Inner {
           self.diagnostics_server.invalidate(&[specifier]);
           self.send_diagnostics_update();
           self.send_testing_update();
+        } else {
+          let embedded_specifiers = self.embedded_specifiers(&specifier);
+          if !old_embedded_specifiers.is_empty()
+            || !embedded_specifiers.is_empty()
+          {
+            let changes = old_embedded_specifiers
+              .iter()
+              .filter(|s| !embedded_specifiers.contains(s))
+              .map(|s| (s, ChangeKind::Closed))
+              .chain(embedded_specifiers.iter().map(|s| {
+                if old_embedded_specifiers.contains(s) {
+                  (s, ChangeKind::Modified)
+                } else {
+                  (s, ChangeKind::Opened)
+                }
+              }))
+              .collect::<Vec<_>>();
+            self.project_changed(changes, false);
+            self.refresh_npm_specifiers().await;
+            self.diagnostics_server.invalidate(&old_embedded_specifiers);
+            self.diagnostics_server.invalidate(&embedded_specifiers);
+            self.send_diagnostics_update();
+          }
         }
       }
       Err(err) => error!("{:#}", err),
@@ -1113,8 +1189,18 @@ impl Inner {
       self.send_diagnostics_update();
       self.send_testing_update();
     }
+    let embedded_specifiers = self.embedded_specifiers(&specifier);
     self.documents.close(&specifier);
-    self.project_changed([(&specifier, ChangeKind::Closed)], false);
+    self.project_changed(
+      [(&specifier, ChangeKind::Closed)]
+        .into_iter()
+        .chain(embedded_specifiers.iter().map(|s| (s, ChangeKind::Closed))),
+      false,
+    );
+    if !embedded_specifiers.is_empty() {
+      self.diagnostics_server.invalidate(&embedded_specifiers);
+      self.send_diagnostics_update();
+    }
     self.performance.measure(mark);
   }
 
@@ -1393,7 +1479,17 @@ impl Inner {
     }
   }
 
-  async fn hover(&self, params: HoverParams) -> LspResult<Option<Hover>> {
+  async fn hover(&self, mut params: HoverParams) -> LspResult<Option<Hover>> {
+    let position_params = &mut params.text_document_position_params;
+    let maybe_embedded_doc = self.embedded_document_at(
+      &position_params.text_document.uri,
+      &position_params.position,
+    );
+    if let Some(embedded_doc) = &maybe_embedded_doc {
+      position_params.text_document.uri = embedded_doc.specifier.clone();
+      position_params.position =
+        embedded_doc.to_embedded_position(position_params.position);
+    }
     let specifier = self.url_map.normalize_url(
       &params.text_document_position_params.text_document.uri,
       LspUrlKind::File,
@@ -1444,6 +1540,22 @@ impl Inner {
         ),
         (true, true, _) => unreachable!("{}", json!(params)),
       };
+      let value = if let Some(locked) = dep
+        .get_code()
+        .and_then(|code| self.get_locked_package(&specifier, code))
+      {
+        let latest = match self.get_outdated_version(&locked).await {
+          Ok(Some(version)) => format!("{version} _(outdated)_"),
+          Ok(None) => format!("{} _(latest)_", locked.version),
+          Err(_) => "_unknown_".to_string(),
+        };
+        format!(
+          "{value}\n**Locked Version**: {}\n\n**Latest Version**: {latest}\n",
+          locked.version
+        )
+      } else {
+        value
+      };
       let value = if let Some(docs) = self.module_registry.get_hover(&dep).await
       {
         format!("{value}\n\n---\n\n{docs}")
@@ -1467,6 +1579,13 @@ impl Inner {
         .await?;
       maybe_quick_info.map(|qi| qi.to_hover(line_index, self))
     };
+    let hover = match (hover, &maybe_embedded_doc) {
+      (Some(mut hover), Some(embedded_doc)) => {
+        hover.range = hover.range.map(|r| embedded_doc.to_host_range(r));
+        Some(hover)
+      }
+      (hover, _) => hover,
+    };
     self.performance.measure(mark);
     Ok(hover)
   }
@@ -1507,6 +1626,155 @@ impl Inner {
     }
   }
 
+  fn get_locked_package(
+    &self,
+    referrer: &ModuleSpecifier,
+    specifier: &ModuleSpecifier,
+  ) -> Option<LockedPackage> {
+    let lockfile = self
+      .config
+      .tree
+      .data_for_specifier(referrer)
+      .and_then(|d| d.lockfile.as_ref())?;
+    let lockfile = lockfile.lock();
+    get_locked_package(&lockfile, specifier)
+  }
+
+  /// Gets the specifier and text of the import map that applies to
+  /// `referrer`, which is the config file itself when it has an `imports`
+  /// field. Unsaved changes of an open document are included.
+  fn import_map_text(
+    &self,
+    referrer: &ModuleSpecifier,
+  ) -> Option<(ModuleSpecifier, String)> {
+    let import_map = self
+      .config
+      .tree
+      .data_for_specifier(referrer)?
+      .import_map
+      .as_ref()?;
+    let specifier = import_map.base_url();
+    if specifier.scheme() != "file" {
+      return None;
+    }
+    let text = match self.documents.get(specifier).filter(|d| d.is_open()) {
+      Some(document) => document.content().to_string(),
+      None => {
+        std::fs::read_to_string(specifier_to_file_path(specifier).ok()?).ok()?
+      }
+    };
+    Some((specifier.clone(), text))
+  }
+
+  /// Gets the newest version of a locked package when it's newer than the
+  /// locked version. The versions of a package are cached by the search
+  /// APIs once they were fetched.
+  async fn get_outdated_version(
+    &self,
+    locked: &LockedPackage,
+  ) -> Result<Option<Version>, AnyError> {
+    let name = &locked.req_ref.req.name;
+    let versions = match locked.kind {
+      PackageKind::Jsr => self.jsr_search_api.versions(name).await,
+      PackageKind::Npm => self.npm_search_api.versions(name).await,
+    };
+    let versions = versions.inspect_err(|err| {
+      lsp_warn!("Unable to get versions of \"{}\": {:#}", name, err);
+    })?;
+    Ok(get_outdated_version(locked, &versions).cloned())
+  }
+
+  /// Hints the version each `jsr:` and `npm:` dependency was locked to, which
+  /// also covers bare specifiers mapped to them by an import map.
+  fn package_version_inlay_hints(
+    &self,
+    specifier: &ModuleSpecifier,
+    asset_or_doc: &AssetOrDocument,
+    range: &Range,
+  ) -> Vec<InlayHint> {
+    let Some(document) = asset_or_doc.document() else {
+      return vec![];
+    };
+    let mut hints = vec![];
+    for dep in document.dependencies().values() {
+      let Some(locked) = dep
+        .get_code()
+        .and_then(|code| self.get_locked_package(specifier, code))
+      else {
+        continue;
+      };
+      for import in &dep.imports {
+        let position = to_lsp_range(&import.range).end;
+        if position < range.start || position > range.end {
+          continue;
+        }
+        hints.push(InlayHint {
+          position,
+          label: InlayHintLabel::String(locked.version.to_string()),
+          kind: None,
+          text_edits: None,
+          tooltip: Some(InlayHintTooltip::String(
+            "Version resolved from the lockfile".to_string(),
+          )),
+          padding_left: Some(true),
+          padding_right: None,
+          data: None,
+        });
+      }
+    }
+    hints
+  }
+
+  /// Offers to bump an outdated `jsr:` or `npm:` specifier at the requested
+  /// range to the latest version. Bare specifiers are updated where they are
+  /// mapped in the import map.
+  async fn update_package_version_action(
+    &self,
+    specifier: &ModuleSpecifier,
+    asset_or_doc: &AssetOrDocument,
+    range: &Range,
+  ) -> Option<CodeAction> {
+    let (raw_specifier, dep, dep_range) =
+      asset_or_doc.get_maybe_dependency(&range.start)?;
+    let code = dep.get_code()?;
+    let locked = self.get_locked_package(specifier, code)?;
+    let latest = self.get_outdated_version(&locked).await.ok()??;
+    let mut changes = HashMap::new();
+    if ModuleSpecifier::parse(&raw_specifier).ok().as_ref() == Some(code) {
+      let mut edit_range = to_lsp_range(&dep_range);
+      // keep the quotes
+      edit_range.start.character += 1;
+      edit_range.end.character -= 1;
+      changes.insert(
+        specifier.clone(),
+        vec![TextEdit {
+          range: edit_range,
+          new_text: locked.specifier_for_version(&latest)?,
+        }],
+      );
+    } else {
+      let (json_specifier, text) = self.import_map_text(specifier)?;
+      let edits = get_json_edits_for_package_version(&text, &locked, &latest)
+        .inspect_err(|err| {
+          lsp_warn!("Unable to update \"{}\": {:#}", json_specifier, err);
+        })
+        .ok()?;
+      if edits.is_empty() {
+        return None;
+      }
+      changes.insert(json_specifier, edits);
+    }
+    Some(CodeAction {
+      title: format!("Update \"{}\" to {}", locked.req_ref.req.name, latest),
+      kind: Some(CodeActionKind::REFACTOR_REWRITE),
+      edit: Some(WorkspaceEdit {
+        changes: Some(changes),
+        ..Default::default()
+      }),
+      ..Default::default()
+    })
+  }
+
   async fn code_action(
     &self,
     params: CodeActionParams,
@@ -1658,6 +1926,23 @@ impl Inner {
       all_actions.extend(code_actions.get_response());
     }
 
+    // only look up the registry when the client asked for this kind
+    let includes_rewrite = params.context.only.as_ref().map_or(true, |only| {
+      only.iter().any(|kind| {
+        CodeActionKind::REFACTOR_REWRITE
+          .as_str()
+          .starts_with(kind.as_str())
+      })
+    });
+    if includes_rewrite {
+      if let Some(action) = self
+        .update_package_version_action(&specifier, &asset_or_doc, &params.range)
+        .await
+      {
+        all_actions.push(CodeActionOrCommand::CodeAction(action));
+      }
+    }
+
     // Refactor
     let only = params
       .context
@@ -2093,8 +2378,18 @@ impl Inner {
 
   async fn completion(
     &self,
-    params: CompletionParams,
+    mut params: CompletionParams,
   ) -> LspResult<Option<CompletionResponse>> {
+    let position_params = &mut params.text_document_position;
+    let maybe_embedded_doc = self.embedded_document_at(
+      &position_params.text_document.uri,
+      &position_params.position,
+    );
+    if let Some(embedded_doc) = &maybe_embedded_doc {
+      position_params.text_document.uri = embedded_doc.specifier.clone();
+      position_params.position =
+        embedded_doc.to_embedded_position(position_params.position);
+    }
     let specifier = self.url_map.normalize_url(
       &params.text_document_position.text_document.uri,
       LspUrlKind::File,
@@ -2185,6 +2480,16 @@ impl Inner {
         );
       }
     };
+    if let Some(embedded_doc) = &maybe_embedded_doc {
+      let items = match &mut response {
+        Some(CompletionResponse::Array(items)) => Some(items),
+        Some(CompletionResponse::List(list)) => Some(&mut list.items),
+        None => None,
+      };
+      for item in items.into_iter().flatten() {
+        embedded_doc.to_host_completion_item(item);
+      }
+    }
     self.performance.measure(mark);
     Ok(response)
   }
@@ -2232,7 +2537,7 @@ impl Inner {
         match result {
           Ok(maybe_completion_info) => {
             if let Some(completion_info) = maybe_completion_info {
-              completion_info
+              let mut item = completion_info
                 .as_completion_item(&params, data, specifier, self)
                 .map_err(|err| {
                   error!(
@@ -2240,7 +2545,14 @@ impl Inner {
                     err
                   );
                   LspError::internal_error()
-                })?
+                })?;
+              // the item's text edit was already mapped by the completion
+              if let Some(embedded_doc) =
+                self.documents.get_embedded_document(specifier)
+              {
+                embedded_doc.to_host_additional_text_edits(&mut item);
+              }
+              item
             } else {
               error!(
                 "Received an undefined response from tsc for completion details."
@@ -2722,6 +3034,7 @@ impl Inner {
     params: RenameFilesParams,
   ) -> LspResult<Option<WorkspaceEdit>> {
     let mut changes = vec![];
+    let mut renames = vec![];
     for rename in params.files {
       let old_specifier = self.url_map.normalize_url(
         &resolve_url(&rename.old_uri).unwrap(),
@@ -2743,16 +3056,18 @@ impl Inner {
         .fmt_options_for_specifier(&old_specifier)
         .options)
         .into();
+      let new_specifier = self.url_map.normalize_url(
+        &resolve_url(&rename.new_uri).unwrap(),
+        LspUrlKind::File,
+      );
+      renames.push((old_specifier.clone(), new_specifier.clone()));
       changes.extend(
         self
           .ts_server
           .get_edits_for_file_rename(
             self.snapshot(),
             old_specifier,
-            self.url_map.normalize_url(
-              &resolve_url(&rename.new_uri).unwrap(),
-              LspUrlKind::File,
-            ),
+            new_specifier,
             format_code_settings,
             tsc::UserPreferences {
               allow_text_changes_in_new_files: Some(true),
@@ -2762,7 +3077,76 @@ impl Inner {
           .await?,
       );
     }
-    file_text_changes_to_workspace_edit(&changes, self)
+    let mut workspace_edit =
+      file_text_changes_to_workspace_edit(&changes, self)?;
+    let config_edits = self.config_file_edits_for_file_renames(&renames);
+    if let Some(DocumentChanges::Operations(ops)) = workspace_edit
+      .as_mut()
+      .and_then(|edit| edit.document_changes.as_mut())
+    {
+      ops.extend(config_edits.into_iter().map(DocumentChangeOperation::Edit));
+    }
+    Ok(workspace_edit)
+  }
+
+  /// Tsserver only updates the imports of modules, so the specifiers in the
+  /// config files and import maps of every scope are updated here.
+  fn config_file_edits_for_file_renames(
+    &self,
+    renames: &[(ModuleSpecifier, ModuleSpecifier)],
+  ) -> Vec<TextDocumentEdit> {
+    if renames.is_empty() {
+      return vec![];
+    }
+    let mut json_specifiers = BTreeSet::new();
+    for config_data in self.config.tree.data_by_scope().values() {
+      if let Some(config_file) = &config_data.config_file {
+        json_specifiers.insert(config_file.specifier.clone());
+      }
+      if let Some(import_map) = &config_data.import_map {
+        if import_map.base_url().scheme() == "file" {
+          json_specifiers.insert(import_map.base_url().clone());
+        }
+      }
+    }
+    let mut document_edits = vec![];
+    for specifier in json_specifiers {
+      let maybe_document = self
+        .documents
+        .get(&specifier)
+        .filter(|document| document.is_open());
+      let text = match &maybe_document {
+        Some(document) => document.content().to_string(),
+        None => {
+          let Ok(path) = specifier_to_file_path(&specifier) else {
+            continue;
+          };
+          let Ok(text) = std::fs::read_to_string(path) else {
+            continue;
+          };
+          text
+        }
+      };
+      let edits =
+        match get_json_edits_for_file_renames(&text, &specifier, renames) {
+          Ok(edits) => edits,
+          Err(err) => {
+            lsp_warn!("Unable to update \"{}\": {:#}", specifier, err);
+            continue;
+          }
+        };
+      if edits.is_empty() {
+        continue;
+      }
+      document_edits.push(TextDocumentEdit {
+        text_document: OptionalVersionedTextDocumentIdentifier {
+          uri: specifier,
+          version: maybe_document.and_then(|d| d.maybe_lsp_version()),
+        },
+        edits: edits.into_iter().map(OneOf::Left).collect(),
+      });
+    }
+    document_edits
   }
 
   async fn symbol(
@@ -3024,6 +3408,13 @@ impl tower_lsp::LanguageServer for LanguageServer {
       inner.diagnostics_server.invalidate(&[specifier]);
       inner.send_diagnostics_update();
       inner.send_testing_update();
+    } else {
+      let embedded_specifiers = inner.embedded_specifiers(&specifier);
+      if !embedded_specifiers.is_empty() {
+        inner.refresh_npm_specifiers().await;
+        inner.diagnostics_server.invalidate(&embedded_specifiers);
+        inner.send_diagnostics_update();
+      }
     }
   }
 
@@ -3374,7 +3765,10 @@ impl Inner {
     for config_file in self.config.tree.config_files() {
       if let Some(tasks) = json!(&config_file.json.tasks).as_object() {
         for (name, value) in tasks {
-          let Some(command) = value.as_str() else {
+          let Some(command) = value
+            .as_str()
+            .or_else(|| value.get("command").and_then(|c| c.as_str()))
+          else {
             continue;
           };
           result.push(TaskDefinition {
@@ -3400,6 +3794,103 @@ impl Inner {
     Ok(result)
   }
 
+  /// Finds the exports of the workspace's modules which no other module
+  /// references. The entry points of the configuration files' `exports` are
+  /// used by dependents, so they and the modules they re-export with
+  /// `export *` are skipped.
+  async fn unused_exports(&self) -> LspResult<Vec<lsp_custom::UnusedExport>> {
+    let mark = self.performance.mark("lsp.unused_exports");
+    let mut entry_points = self
+      .config
+      .tree
+      .config_files()
+      .into_iter()
+      .filter_map(|c| c.resolve_export_value_urls().ok())
+      .flatten()
+      .collect::<HashSet<_>>();
+    let mut pending = entry_points.iter().cloned().collect::<Vec<_>>();
+    while let Some(specifier) = pending.pop() {
+      let Some(document) = self.documents.get(&specifier) else {
+        continue;
+      };
+      let Some(parsed_source) = document.parse() else {
+        continue;
+      };
+      for star_reexport in analyze_module_exports(&parsed_source).star_reexports
+      {
+        let Some(resolved) = document
+          .dependencies()
+          .get(&star_reexport)
+          .and_then(|d| d.get_code())
+        else {
+          continue;
+        };
+        if entry_points.insert(resolved.clone()) {
+          pending.push(resolved.clone());
+        }
+      }
+    }
+
+    let snapshot = self.snapshot();
+    let mut unused_exports = Vec::new();
+    for document in self.documents.documents(DocumentsFilter::AllDiagnosable) {
+      let specifier = document.specifier();
+      if specifier.scheme() != "file"
+        || entry_points.contains(specifier)
+        || !self.config.specifier_enabled(specifier)
+      {
+        continue;
+      }
+      let Some(parsed_source) = document.parse() else {
+        continue;
+      };
+      let line_index = document.line_index();
+      let text_info = parsed_source.text_info();
+      // lsp columns are counted in utf-16 code units
+      let to_position = |pos: SourcePos| {
+        let byte_index = pos.as_byte_index(text_info.range().start);
+        let utf16_offset =
+          text_info.text_str()[..byte_index].encode_utf16().count() as u32;
+        line_index.position_utf16(utf16_offset.into())
+      };
+      for symbol in analyze_module_exports(&parsed_source).exports {
+        let range = Range {
+          start: to_position(symbol.range.start),
+          end: to_position(symbol.range.end),
+        };
+        let Some(symbols) = self
+          .ts_server
+          .find_references(
+            snapshot.clone(),
+            specifier.clone(),
+            line_index.offset_tsc(range.start)?,
+          )
+          .await?
+        else {
+          continue;
+        };
+        let is_used = symbols
+          .iter()
+          .flat_map(|s| &s.references)
+          .filter_map(|r| resolve_url(&r.entry.document_span.file_name).ok())
+          .any(|s| &s != specifier);
+        if !is_used {
+          unused_exports.push(lsp_custom::UnusedExport {
+            uri: self
+              .url_map
+              .normalize_specifier(specifier)
+              .map(|u| u.into_url())
+              .unwrap_or_else(|_| specifier.clone()),
+            name: symbol.name,
+            range,
+          });
+        }
+      }
+    }
+    self.performance.measure(mark);
+    Ok(unused_exports)
+  }
+
   async fn inlay_hint(
     &self,
     params: InlayHintParams,
@@ -3409,41 +3900,60 @@ impl Inner {
       .normalize_url(&params.text_document.uri, LspUrlKind::File);
     if !self.is_diagnosable(&specifier)
       || !self.config.specifier_enabled(&specifier)
-      || !self.config.enabled_inlay_hints_for_specifier(&specifier)
     {
       return Ok(None);
     }
+    let tsc_enabled = self.config.enabled_inlay_hints_for_specifier(&specifier);
+    let package_versions_enabled = self
+      .config
+      .workspace_settings_for_specifier(&specifier)
+      .inlay_hints
+      .package_versions
+      .enabled;
+    if !tsc_enabled && !package_versions_enabled {
+      return Ok(None);
+    }
 
     let mark = self.performance.mark_with_args("lsp.inlay_hint", &params);
     let asset_or_doc = self.get_asset_or_document(&specifier)?;
-    let line_index = asset_or_doc.line_index();
-    let text_span =
-      tsc::TextSpan::from_range(&params.range, line_index.clone()).map_err(
-        |err| {
-          error!("Failed to convert range to text_span: {:#}", err);
-          LspError::internal_error()
-        },
-      )?;
-    let maybe_inlay_hints = self
-      .ts_server
-      .provide_inlay_hints(
-        self.snapshot(),
-        specifier.clone(),
-        text_span,
-        tsc::UserPreferences::from_config_for_specifier(
-          &self.config,
-          &specifier,
-        ),
-      )
-      .await?;
-    let maybe_inlay_hints = maybe_inlay_hints.map(|hints| {
-      hints
-        .iter()
-        .map(|hint| hint.to_lsp(line_index.clone()))
-        .collect()
-    });
+    let mut inlay_hints = vec![];
+    if tsc_enabled {
+      let line_index = asset_or_doc.line_index();
+      let text_span =
+        tsc::TextSpan::from_range(&params.range, line_index.clone()).map_err(
+          |err| {
+            error!("Failed to convert range to text_span: {:#}", err);
+            LspError::internal_error()
+          },
+        )?;
+      let maybe_inlay_hints = self
+        .ts_server
+        .provide_inlay_hints(
+          self.snapshot(),
+          specifier.clone(),
+          text_span,
+          tsc::UserPreferences::from_config_for_specifier(
+            &self.config,
+            &specifier,
+          ),
+        )
+        .await?;
+      inlay_hints.extend(
+        maybe_inlay_hints
+          .unwrap_or_default()
+          .iter()
+          .map(|hint| hint.to_lsp(line_index.clone())),
+      );
+    }
+    if package_versions_enabled {
+      inlay_hints.extend(self.package_version_inlay_hints(
+        &specifier,
+        &asset_or_doc,
+        &params.range,
+      ));
+    }
     self.performance.measure(mark);
-    Ok(maybe_inlay_hints)
+    Ok(Some(inlay_hints).filter(|hints| !hints.is_empty()))
   }
 
   async fn reload_import_registries(&mut self) -> LspResult<Option<Value>> {
@@ -6,6 +6,7 @@ use tower_lsp::lsp_types as lsp;
 
 pub const PERFORMANCE_REQUEST: &str = "deno/performance";
 pub const TASK_REQUEST: &str = "deno/taskDefinitions";
+pub const UNUSED_EXPORTS_REQUEST: &str = "deno/unusedExports";
 pub const VIRTUAL_TEXT_DOCUMENT: &str = "deno/virtualTextDocument";
 pub const LATEST_DIAGNOSTIC_BATCH_INDEX: &str =
   "deno/internalLatestDiagnosticBatchIndex";
@@ -20,6 +21,14 @@ pub struct TaskDefinition {
   pub source_uri: lsp::Url,
 }
 
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnusedExport {
+  pub uri: lsp::Url,
+  pub name: String,
+  pub range: lsp::Range,
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 pub struct RegistryStateNotificationParams {
   pub origin: String,
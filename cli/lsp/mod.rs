@@ -21,6 +21,7 @@ mod completions;
 mod config;
 mod diagnostics;
 mod documents;
+mod embedded;
 mod jsr;
 pub mod language_server;
 mod logging;
@@ -59,6 +60,10 @@ pub async fn start() -> Result<(), AnyError> {
   // TODO(nayeemrmn): Rename this to `deno/taskDefinitions` in vscode_deno and
   // remove this alias.
   .custom_method("deno/task", LanguageServer::task_definitions)
+  .custom_method(
+    lsp_custom::UNUSED_EXPORTS_REQUEST,
+    LanguageServer::unused_exports_request,
+  )
   .custom_method(testing::TEST_RUN_REQUEST, LanguageServer::test_run_request)
   .custom_method(
     testing::TEST_RUN_CANCEL_REQUEST,
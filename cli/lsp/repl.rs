@@ -18,8 +18,11 @@ use tower_lsp::lsp_types::CompletionTriggerKind;
 use tower_lsp::lsp_types::DidChangeTextDocumentParams;
 use tower_lsp::lsp_types::DidCloseTextDocumentParams;
 use tower_lsp::lsp_types::DidOpenTextDocumentParams;
+use tower_lsp::lsp_types::HoverContents;
+use tower_lsp::lsp_types::HoverParams;
 use tower_lsp::lsp_types::InitializeParams;
 use tower_lsp::lsp_types::InitializedParams;
+use tower_lsp::lsp_types::MarkedString;
 use tower_lsp::lsp_types::PartialResultParams;
 use tower_lsp::lsp_types::Position;
 use tower_lsp::lsp_types::Range;
@@ -115,6 +118,28 @@ impl ReplLanguageServer {
     self.pending_text = String::new();
   }
 
+  /// Replaces the text of all the committed inputs.
+  pub async fn set_committed_text(&mut self, text: String) {
+    self.check_cwd_change().await;
+    self.document_version += 1;
+    self
+      .language_server
+      .did_change(DidChangeTextDocumentParams {
+        text_document: VersionedTextDocumentIdentifier {
+          uri: self.get_document_specifier(),
+          version: self.document_version,
+        },
+        content_changes: vec![TextDocumentContentChangeEvent {
+          range: None,
+          range_length: None,
+          text: text.clone(),
+        }],
+      })
+      .await;
+    self.document_text = text;
+    self.pending_text = String::new();
+  }
+
   pub async fn completions(
     &mut self,
     line_text: &str,
@@ -192,6 +217,60 @@ impl ReplLanguageServer {
       .collect()
   }
 
+  /// Gets the hover documentation for the symbol at the position in the line
+  /// as markdown.
+  pub async fn hover(
+    &mut self,
+    line_text: &str,
+    position: usize,
+  ) -> Option<String> {
+    self.did_change(line_text).await;
+    let text_info = deno_ast::SourceTextInfo::from_string(format!(
+      "{}{}",
+      self.document_text, self.pending_text
+    ));
+    let position =
+      text_info.range().start + self.document_text.len() + position;
+    let line_and_column = text_info.line_and_column_index(position);
+    let hover = self
+      .language_server
+      .hover(HoverParams {
+        text_document_position_params: TextDocumentPositionParams {
+          text_document: TextDocumentIdentifier {
+            uri: self.get_document_specifier(),
+          },
+          position: Position {
+            line: line_and_column.line_index as u32,
+            character: line_and_column.column_index as u32,
+          },
+        },
+        work_done_progress_params: WorkDoneProgressParams {
+          work_done_token: None,
+        },
+      })
+      .await
+      .ok()??;
+    let marked_string_to_markdown =
+      |marked_string: MarkedString| match marked_string {
+        MarkedString::String(text) => text,
+        MarkedString::LanguageString(code) => {
+          format!("```{}\n{}\n```", code.language, code.value)
+        }
+      };
+    let documentation = match hover.contents {
+      HoverContents::Scalar(marked_string) => {
+        marked_string_to_markdown(marked_string)
+      }
+      HoverContents::Array(marked_strings) => marked_strings
+        .into_iter()
+        .map(marked_string_to_markdown)
+        .collect::<Vec<_>>()
+        .join("\n\n"),
+      HoverContents::Markup(markup) => markup.value,
+    };
+    Some(documentation)
+  }
+
   async fn did_change(&mut self, new_text: &str) {
     self.check_cwd_change().await;
     let new_text = if new_text.ends_with('\n') {
@@ -299,6 +378,7 @@ pub fn get_repl_workspace_settings() -> WorkspaceSettings {
     cache_on_save: false,
     import_map: None,
     code_lens: Default::default(),
+    inlay_hints: Default::default(),
     internal_debug: false,
     internal_inspect: Default::default(),
     log_file: false,
@@ -228,6 +228,7 @@ impl TestRun {
         .iter()
         .map(|s| (s.clone(), test::TestMode::Executable))
         .collect(),
+      false,
     )
     .await?;
 
@@ -304,6 +305,7 @@ impl TestRun {
               filter,
               shuffle: None,
               trace_leaks: false,
+              doc_test: false,
             },
           ))
         }
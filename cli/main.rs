@@ -94,6 +94,12 @@ async fn run_subcommand(flags: Flags) -> Result<i32, AnyError> {
     DenoSubcommand::Add(add_flags) => spawn_subcommand(async {
       tools::registry::add(flags, add_flags).await
     }),
+    DenoSubcommand::Remove(remove_flags) => spawn_subcommand(async {
+      tools::registry::remove(flags, remove_flags).await
+    }),
+    DenoSubcommand::Outdated(outdated_flags) => spawn_subcommand(async {
+      tools::registry::outdated(flags, outdated_flags).await
+    }),
     DenoSubcommand::Bench(bench_flags) => spawn_subcommand(async {
       if bench_flags.watch.is_some() {
         tools::bench::run_benchmarks_with_watch(flags, bench_flags).await
@@ -121,11 +127,7 @@ async fn run_subcommand(flags: Flags) -> Result<i32, AnyError> {
       emitter.cache_module_emits(&graph_container.graph())
     }),
     DenoSubcommand::Check(check_flags) => spawn_subcommand(async move {
-      let factory = CliFactory::from_flags(flags)?;
-      let module_load_preparer = factory.module_load_preparer().await?;
-      module_load_preparer
-        .load_and_type_check_files(&check_flags.files)
-        .await
+      tools::check::check(flags, check_flags).await
     }),
     DenoSubcommand::Compile(compile_flags) => spawn_subcommand(async {
       tools::compile::compile(flags, compile_flags).await
@@ -138,13 +140,9 @@ async fn run_subcommand(flags: Flags) -> Result<i32, AnyError> {
         async move { tools::fmt::format(flags, fmt_flags).await },
       )
     }
-    DenoSubcommand::Init(init_flags) => {
-      spawn_subcommand(async {
-        // make compiler happy since init_project is sync
-        tokio::task::yield_now().await;
-        tools::init::init_project(init_flags)
-      })
-    }
+    DenoSubcommand::Init(init_flags) => spawn_subcommand(async {
+      tools::init::init_project(flags, init_flags).await
+    }),
     DenoSubcommand::Info(info_flags) => {
       spawn_subcommand(async { tools::info::info(flags, info_flags).await })
     }
@@ -386,10 +384,17 @@ fn resolve_flags_and_init(
     }
   }
 
+  // V8 only accepts a code cache created with the same flags, so create the
+  // code cache of `deno compile` with only the flags the binary runs with
+  let is_compile_with_code_cache = matches!(
+    &flags.subcommand,
+    DenoSubcommand::Compile(compile_flags) if compile_flags.code_cache
+  );
   let default_v8_flags = match flags.subcommand {
     // Using same default as VSCode:
     // https://github.com/microsoft/vscode/blob/48d4ba271686e8072fc6674137415bc80d936bc7/extensions/typescript-language-features/src/configuration/configuration.ts#L213-L214
     DenoSubcommand::Lsp => vec!["--max-old-space-size=3072".to_string()],
+    _ if is_compile_with_code_cache => vec![],
     _ => {
       if *DENO_FUTURE {
         // deno_ast removes TypeScript `assert` keywords, so this flag only affects JavaScript
@@ -401,7 +406,12 @@ fn resolve_flags_and_init(
     }
   };
 
-  init_v8_flags(&default_v8_flags, &flags.v8_flags, get_v8_flags_from_env());
+  let env_v8_flags = if is_compile_with_code_cache {
+    vec![]
+  } else {
+    get_v8_flags_from_env()
+  };
+  init_v8_flags(&default_v8_flags, &flags.v8_flags, env_v8_flags);
   deno_core::JsRuntime::init_platform(None);
   util::logger::init(flags.log_level);
 
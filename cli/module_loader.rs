@@ -157,6 +157,7 @@ impl ModuleLoadPreparer {
           check::CheckOptions {
             build_fast_check_graph: true,
             lib,
+            log_checked_roots: true,
             log_ignored_options: false,
             reload: self.options.reload_flag(),
             type_check_mode: self.options.type_check_mode(),
@@ -194,7 +195,7 @@ impl ModuleLoadPreparer {
       .await
   }
 
-  fn collect_specifiers(
+  pub fn collect_specifiers(
     &self,
     files: &[String],
   ) -> Result<Vec<ModuleSpecifier>, AnyError> {
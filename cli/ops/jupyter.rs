@@ -6,6 +6,7 @@ use std::sync::Arc;
 
 use crate::tools::jupyter::jupyter_msg::Connection;
 use crate::tools::jupyter::jupyter_msg::JupyterMessage;
+use crate::tools::jupyter::server::CommContainer;
 use crate::tools::jupyter::server::StdioMsg;
 use deno_core::error::AnyError;
 use deno_core::op2;
@@ -17,6 +18,8 @@ use tokio::sync::Mutex;
 deno_core::extension!(deno_jupyter,
   ops = [
     op_jupyter_broadcast,
+    op_jupyter_comm_register,
+    op_jupyter_comm_unregister,
   ],
   options = {
     sender: mpsc::UnboundedSender<StdioMsg>,
@@ -66,6 +69,29 @@ pub async fn op_jupyter_broadcast(
   Ok(())
 }
 
+#[op2]
+pub fn op_jupyter_comm_register(
+  state: &mut OpState,
+  #[string] comm_id: String,
+  #[string] target_name: String,
+) {
+  state
+    .borrow::<Rc<RefCell<CommContainer>>>()
+    .borrow_mut()
+    .register(comm_id, target_name);
+}
+
+#[op2(fast)]
+pub fn op_jupyter_comm_unregister(
+  state: &mut OpState,
+  #[string] comm_id: &str,
+) {
+  state
+    .borrow::<Rc<RefCell<CommContainer>>>()
+    .borrow_mut()
+    .unregister(comm_id);
+}
+
 #[op2(fast)]
 pub fn op_print(
   state: &mut OpState,
@@ -2,7 +2,7 @@
 
 use std::borrow::Cow;
 use std::collections::BTreeMap;
-use std::env::current_exe;
+use std::collections::HashMap;
 use std::ffi::OsString;
 use std::fs;
 use std::future::Future;
@@ -19,8 +19,6 @@ use deno_core::anyhow::bail;
 use deno_core::anyhow::Context;
 use deno_core::error::AnyError;
 use deno_core::futures::io::AllowStdIo;
-use deno_core::futures::AsyncReadExt;
-use deno_core::futures::AsyncSeekExt;
 use deno_core::serde_json;
 use deno_core::url::Url;
 use deno_npm::NpmSystemInfo;
@@ -43,13 +41,18 @@ use crate::file_fetcher::FileFetcher;
 use crate::http_util::HttpClient;
 use crate::npm::CliNpmResolver;
 use crate::npm::InnerCliNpmResolverRef;
+use crate::util::fs::normalize_path;
 use crate::util::progress_bar::ProgressBar;
 use crate::util::progress_bar::ProgressBarStyle;
 
+use super::code_cache::create_code_cache;
+use super::code_cache::deserialize_code_cache;
+use super::code_cache::serialize_code_cache;
 use super::virtual_fs::FileBackedVfs;
 use super::virtual_fs::VfsBuilder;
 use super::virtual_fs::VfsRoot;
 use super::virtual_fs::VirtualDirectory;
+use super::virtual_fs::COMPRESSION_LEVEL;
 
 const MAGIC_TRAILER: &[u8; 8] = b"d3n0l4nd";
 
@@ -146,17 +149,77 @@ pub struct Metadata {
   pub node_modules: Option<NodeModules>,
   pub disable_deprecated_api_warning: bool,
   pub unstable_config: UnstableConfig,
+  /// The root directory of the assets virtual file system. This is the
+  /// directory of the entrypoint, or one of its ancestors if some assets are
+  /// outside of it.
+  pub assets_root: Option<PathBuf>,
+  /// The included asset directories. Only paths within these are served from
+  /// the assets virtual file system and granted read access.
+  pub assets_dirs: Vec<PathBuf>,
+  /// Whether the eszip and the files of the virtual file systems are
+  /// compressed with zstd.
+  pub compressed: bool,
 }
 
-pub fn load_npm_vfs(root_dir_path: PathBuf) -> Result<FileBackedVfs, AnyError> {
-  let file_path = current_exe().unwrap();
-  let mut file = std::fs::File::open(file_path)?;
+pub fn load_npm_vfs(
+  exe_path: &Path,
+  root_dir_path: PathBuf,
+) -> Result<FileBackedVfs, AnyError> {
+  load_vfs(exe_path, root_dir_path, |trailer| {
+    (
+      trailer.npm_vfs_pos,
+      trailer.npm_vfs_len(),
+      trailer.npm_files_pos,
+    )
+  })
+}
+
+pub fn load_assets_vfs(
+  exe_path: &Path,
+  root_dir_path: PathBuf,
+  assets_dirs: Vec<PathBuf>,
+) -> Result<FileBackedVfs, AnyError> {
+  let vfs = load_vfs(exe_path, root_dir_path, |trailer| {
+    (
+      trailer.assets_vfs_pos,
+      trailer.assets_vfs_len(),
+      trailer.assets_files_pos,
+    )
+  })?;
+  Ok(vfs.with_mount_dirs(assets_dirs))
+}
+
+/// Loads the V8 code cache of the executable's modules, keyed by specifier.
+/// This is empty when the executable was compiled without a code cache.
+pub fn load_code_cache(
+  exe_path: &Path,
+) -> Result<HashMap<String, Vec<u8>>, AnyError> {
+  let mut file = std::fs::File::open(exe_path)?;
+  let trailer_pos = file.seek(SeekFrom::End(-(TRAILER_SIZE as i64)))?;
+  let mut trailer = [0; TRAILER_SIZE];
+  file.read_exact(&mut trailer)?;
+  let trailer = Trailer::parse(&trailer)?.unwrap();
+  file.seek(SeekFrom::Start(trailer.code_cache_pos))?;
+  let mut data = vec![0; (trailer_pos - trailer.code_cache_pos) as usize];
+  file.read_exact(&mut data)?;
+  deserialize_code_cache(&data)
+}
+
+/// Loads a virtual file system of the executable, given the position and
+/// length of its directory and the position of its files.
+fn load_vfs(
+  exe_path: &Path,
+  root_dir_path: PathBuf,
+  get_positions: impl FnOnce(&Trailer) -> (u64, u64, u64),
+) -> Result<FileBackedVfs, AnyError> {
+  let mut file = std::fs::File::open(exe_path)?;
   file.seek(SeekFrom::End(-(TRAILER_SIZE as i64)))?;
   let mut trailer = [0; TRAILER_SIZE];
   file.read_exact(&mut trailer)?;
   let trailer = Trailer::parse(&trailer)?.unwrap();
-  file.seek(SeekFrom::Start(trailer.npm_vfs_pos))?;
-  let mut vfs_data = vec![0; trailer.npm_vfs_len() as usize];
+  let (vfs_pos, vfs_len, files_pos) = get_positions(&trailer);
+  file.seek(SeekFrom::Start(vfs_pos))?;
+  let mut vfs_data = vec![0; vfs_len as usize];
   file.read_exact(&mut vfs_data)?;
   let mut dir: VirtualDirectory = serde_json::from_slice(&vfs_data)?;
 
@@ -170,11 +233,12 @@ pub fn load_npm_vfs(root_dir_path: PathBuf) -> Result<FileBackedVfs, AnyError> {
   let fs_root = VfsRoot {
     dir,
     root_path: root_dir_path,
-    start_file_offset: trailer.npm_files_pos,
+    start_file_offset: files_pos,
   };
   Ok(FileBackedVfs::new(file, fs_root))
 }
 
+#[allow(clippy::too_many_arguments)]
 fn write_binary_bytes(
   writer: &mut impl Write,
   original_bin: Vec<u8>,
@@ -182,10 +246,21 @@ fn write_binary_bytes(
   eszip: eszip::EszipV2,
   npm_vfs: Option<&VirtualDirectory>,
   npm_files: &Vec<Vec<u8>>,
+  assets_vfs: Option<&VirtualDirectory>,
+  assets_files: &Vec<Vec<u8>>,
+  code_cache: &[u8],
 ) -> Result<(), AnyError> {
+  let compressed = metadata.compressed;
   let metadata = serde_json::to_string(metadata)?.as_bytes().to_vec();
   let npm_vfs = serde_json::to_string(&npm_vfs)?.as_bytes().to_vec();
+  let assets_vfs = serde_json::to_string(&assets_vfs)?.as_bytes().to_vec();
   let eszip_archive = eszip.into_bytes();
+  let eszip_archive = if compressed {
+    zstd::bulk::compress(&eszip_archive, COMPRESSION_LEVEL)
+      .context("Failed to compress eszip archive")?
+  } else {
+    eszip_archive
+  };
 
   writer.write_all(&original_bin)?;
   writer.write_all(&eszip_archive)?;
@@ -194,6 +269,11 @@ fn write_binary_bytes(
   for file in npm_files {
     writer.write_all(file)?;
   }
+  writer.write_all(&assets_vfs)?;
+  for file in assets_files {
+    writer.write_all(file)?;
+  }
+  writer.write_all(code_cache)?;
 
   // write the trailer, which includes the positions
   // of the data blocks in the file
@@ -202,11 +282,19 @@ fn write_binary_bytes(
     let metadata_pos = eszip_pos + (eszip_archive.len() as u64);
     let npm_vfs_pos = metadata_pos + (metadata.len() as u64);
     let npm_files_pos = npm_vfs_pos + (npm_vfs.len() as u64);
+    let assets_vfs_pos =
+      npm_files_pos + npm_files.iter().map(|f| f.len() as u64).sum::<u64>();
+    let assets_files_pos = assets_vfs_pos + (assets_vfs.len() as u64);
+    let code_cache_pos = assets_files_pos
+      + assets_files.iter().map(|f| f.len() as u64).sum::<u64>();
     Trailer {
       eszip_pos,
       metadata_pos,
       npm_vfs_pos,
       npm_files_pos,
+      assets_vfs_pos,
+      assets_files_pos,
+      code_cache_pos,
     }
     .as_bytes()
   })?;
@@ -256,31 +344,17 @@ pub fn extract_standalone(
     Some(trailer) => trailer,
   };
 
-  file.seek(SeekFrom::Start(trailer.eszip_pos))?;
+  file.seek(SeekFrom::Start(trailer.metadata_pos))?;
 
   let cli_args = cli_args.into_owned();
   // If we have an eszip, read it out
   Ok(Some(async move {
-    let bufreader =
-      deno_core::futures::io::BufReader::new(AllowStdIo::new(file));
-
-    let (eszip, loader) = eszip::EszipV2::parse(bufreader)
-      .await
-      .context("Failed to parse eszip header")?;
-
-    let mut bufreader =
-      loader.await.context("Failed to parse eszip archive")?;
-
-    bufreader
-      .seek(SeekFrom::Start(trailer.metadata_pos))
-      .await?;
-
+    // the metadata is read first because it says whether the eszip is
+    // compressed
     let mut metadata = String::new();
-
-    bufreader
+    (&mut file)
       .take(trailer.metadata_len())
       .read_to_string(&mut metadata)
-      .await
       .context("Failed to read metadata from the current executable")?;
 
     let mut metadata: Metadata = serde_json::from_str(&metadata).unwrap();
@@ -289,10 +363,41 @@ pub fn extract_standalone(
       metadata.argv.push(arg.into_string().unwrap());
     }
 
+    file.seek(SeekFrom::Start(trailer.eszip_pos))?;
+    let eszip = if metadata.compressed {
+      let mut compressed = vec![0; trailer.eszip_len() as usize];
+      file
+        .read_exact(&mut compressed)
+        .context("Failed to read eszip from the current executable")?;
+      let eszip_archive = zstd::decode_all(compressed.as_slice())
+        .context("Failed to decompress eszip archive")?;
+      parse_eszip(deno_core::futures::io::BufReader::new(AllowStdIo::new(
+        std::io::Cursor::new(eszip_archive),
+      )))
+      .await?
+    } else {
+      parse_eszip(deno_core::futures::io::BufReader::new(AllowStdIo::new(
+        file,
+      )))
+      .await?
+    };
+
     Ok((metadata, eszip))
   }))
 }
 
+async fn parse_eszip<R: deno_core::futures::io::AsyncRead + Unpin>(
+  bufreader: deno_core::futures::io::BufReader<R>,
+) -> Result<eszip::EszipV2, AnyError> {
+  let (eszip, loader) = eszip::EszipV2::parse(bufreader)
+    .await
+    .context("Failed to parse eszip header")?;
+
+  loader.await.context("Failed to parse eszip archive")?;
+
+  Ok(eszip)
+}
+
 const TRAILER_SIZE: usize = std::mem::size_of::<Trailer>() + 8; // 8 bytes for the magic trailer string
 
 struct Trailer {
@@ -300,6 +405,9 @@ struct Trailer {
   metadata_pos: u64,
   npm_vfs_pos: u64,
   npm_files_pos: u64,
+  assets_vfs_pos: u64,
+  assets_files_pos: u64,
+  code_cache_pos: u64,
 }
 
 impl Trailer {
@@ -311,19 +419,32 @@ impl Trailer {
 
     let (eszip_archive_pos, rest) = rest.split_at(8);
     let (metadata_pos, rest) = rest.split_at(8);
-    let (npm_vfs_pos, npm_files_pos) = rest.split_at(8);
+    let (npm_vfs_pos, rest) = rest.split_at(8);
+    let (npm_files_pos, rest) = rest.split_at(8);
+    let (assets_vfs_pos, rest) = rest.split_at(8);
+    let (assets_files_pos, code_cache_pos) = rest.split_at(8);
     let eszip_archive_pos = u64_from_bytes(eszip_archive_pos)?;
     let metadata_pos = u64_from_bytes(metadata_pos)?;
     let npm_vfs_pos = u64_from_bytes(npm_vfs_pos)?;
     let npm_files_pos = u64_from_bytes(npm_files_pos)?;
+    let assets_vfs_pos = u64_from_bytes(assets_vfs_pos)?;
+    let assets_files_pos = u64_from_bytes(assets_files_pos)?;
+    let code_cache_pos = u64_from_bytes(code_cache_pos)?;
     Ok(Some(Trailer {
       eszip_pos: eszip_archive_pos,
       metadata_pos,
       npm_vfs_pos,
       npm_files_pos,
+      assets_vfs_pos,
+      assets_files_pos,
+      code_cache_pos,
     }))
   }
 
+  pub fn eszip_len(&self) -> u64 {
+    self.metadata_pos - self.eszip_pos
+  }
+
   pub fn metadata_len(&self) -> u64 {
     self.npm_vfs_pos - self.metadata_pos
   }
@@ -332,6 +453,10 @@ impl Trailer {
     self.npm_files_pos - self.npm_vfs_pos
   }
 
+  pub fn assets_vfs_len(&self) -> u64 {
+    self.assets_files_pos - self.assets_vfs_pos
+  }
+
   pub fn as_bytes(&self) -> Vec<u8> {
     let mut trailer = MAGIC_TRAILER.to_vec();
     trailer.write_all(&self.eszip_pos.to_be_bytes()).unwrap();
@@ -341,6 +466,15 @@ impl Trailer {
       .write_all(&self.npm_files_pos.to_be_bytes())
       .unwrap();
     trailer
+      .write_all(&self.assets_vfs_pos.to_be_bytes())
+      .unwrap();
+    trailer
+      .write_all(&self.assets_files_pos.to_be_bytes())
+      .unwrap();
+    trailer
+      .write_all(&self.code_cache_pos.to_be_bytes())
+      .unwrap();
+    trailer
   }
 }
 
@@ -450,6 +584,7 @@ impl<'a> DenoCompileBinaryWriter<'a> {
     writer: &mut impl Write,
     eszip: eszip::EszipV2,
     module_specifier: &ModuleSpecifier,
+    asset_dirs: &[PathBuf],
     compile_flags: &CompileFlags,
     cli_options: &CliOptions,
   ) -> Result<(), AnyError> {
@@ -473,6 +608,7 @@ impl<'a> DenoCompileBinaryWriter<'a> {
         original_binary,
         eszip,
         module_specifier,
+        asset_dirs,
         cli_options,
         compile_flags,
       )
@@ -557,12 +693,14 @@ impl<'a> DenoCompileBinaryWriter<'a> {
 
   /// This functions creates a standalone deno binary by appending a bundle
   /// and magic trailer to the currently executing binary.
+  #[allow(clippy::too_many_arguments)]
   async fn write_standalone_binary(
     &self,
     writer: &mut impl Write,
     original_bin: Vec<u8>,
     mut eszip: eszip::EszipV2,
     entrypoint: &ModuleSpecifier,
+    asset_dirs: &[PathBuf],
     cli_options: &CliOptions,
     compile_flags: &CompileFlags,
   ) -> Result<(), AnyError> {
@@ -584,7 +722,8 @@ impl<'a> DenoCompileBinaryWriter<'a> {
           let snapshot =
             managed.serialized_valid_snapshot_for_system(&self.npm_system_info);
           if !snapshot.as_serialized().packages.is_empty() {
-            let (root_dir, files) = self.build_vfs()?.into_dir_and_files();
+            let (root_dir, files) =
+              self.build_vfs(compile_flags.compress)?.into_dir_and_files();
             eszip.add_npm_snapshot(snapshot);
             (
               Some(root_dir),
@@ -604,7 +743,8 @@ impl<'a> DenoCompileBinaryWriter<'a> {
           }
         }
         InnerCliNpmResolverRef::Byonm(_) => {
-          let (root_dir, files) = self.build_vfs()?.into_dir_and_files();
+          let (root_dir, files) =
+            self.build_vfs(compile_flags.compress)?.into_dir_and_files();
           (
             Some(root_dir),
             files,
@@ -617,6 +757,16 @@ impl<'a> DenoCompileBinaryWriter<'a> {
         }
       };
 
+    let (assets_vfs, assets_files, assets_root, assets_dirs) =
+      if asset_dirs.is_empty() {
+        (None, Vec::new(), None, Vec::new())
+      } else {
+        let (assets_root, assets_dirs, builder) =
+          build_assets_vfs(entrypoint, asset_dirs, compile_flags.compress)?;
+        let (root_dir, files) = builder.into_dir_and_files();
+        (Some(root_dir), files, Some(assets_root), assets_dirs)
+      };
+
     let metadata = Metadata {
       argv: compile_flags.args.clone(),
       seed: cli_options.seed(),
@@ -641,6 +791,21 @@ impl<'a> DenoCompileBinaryWriter<'a> {
         sloppy_imports: cli_options.unstable_sloppy_imports(),
         features: cli_options.unstable_features(),
       },
+      assets_root,
+      assets_dirs,
+      compressed: compile_flags.compress,
+    };
+
+    let code_cache = if !compile_flags.code_cache {
+      Vec::new()
+    } else if compile_flags.resolve_target() != env!("TARGET") {
+      log::warn!(
+        "{} Skipping the code cache because it can only be created when compiling for the current target.",
+        crate::colors::yellow("Warning"),
+      );
+      Vec::new()
+    } else {
+      serialize_code_cache(&create_code_cache(&eszip).await?)
     };
 
     write_binary_bytes(
@@ -650,10 +815,13 @@ impl<'a> DenoCompileBinaryWriter<'a> {
       eszip,
       npm_vfs.as_ref(),
       &npm_files,
+      assets_vfs.as_ref(),
+      &assets_files,
+      &code_cache,
     )
   }
 
-  fn build_vfs(&self) -> Result<VfsBuilder, AnyError> {
+  fn build_vfs(&self, compress: bool) -> Result<VfsBuilder, AnyError> {
     fn maybe_warn_different_system(system_info: &NpmSystemInfo) {
       if system_info != &NpmSystemInfo::default() {
         log::warn!("{} The node_modules directory may be incompatible with the target system.", crate::colors::yellow("Warning"));
@@ -665,6 +833,7 @@ impl<'a> DenoCompileBinaryWriter<'a> {
         if let Some(node_modules_path) = npm_resolver.root_node_modules_path() {
           maybe_warn_different_system(&self.npm_system_info);
           let mut builder = VfsBuilder::new(node_modules_path.clone())?;
+          builder.set_compress(compress);
           builder.add_dir_recursive(node_modules_path)?;
           Ok(builder)
         } else {
@@ -674,6 +843,7 @@ impl<'a> DenoCompileBinaryWriter<'a> {
           let root_path =
             npm_resolver.registry_folder_in_global_cache(registry_url);
           let mut builder = VfsBuilder::new(root_path)?;
+          builder.set_compress(compress);
           for package in npm_resolver.all_system_packages(&self.npm_system_info)
           {
             let folder =
@@ -691,6 +861,7 @@ impl<'a> DenoCompileBinaryWriter<'a> {
         let node_modules_path = npm_resolver.root_node_modules_path().unwrap();
         let parent_path = node_modules_path.parent().unwrap();
         let mut builder = VfsBuilder::new(parent_path.to_path_buf())?;
+        builder.set_compress(compress);
         let package_json_path = parent_path.join("package.json");
         if package_json_path.exists() {
           builder.add_file_at_path(&package_json_path)?;
@@ -704,6 +875,44 @@ impl<'a> DenoCompileBinaryWriter<'a> {
   }
 }
 
+/// Builds the virtual file system of the included asset directories. It's
+/// stored rooted at the entrypoint's directory, or at the closest ancestor of
+/// it that contains all the asset directories, which is returned along with
+/// the normalized asset directories that the vfs is mounted at.
+fn build_assets_vfs(
+  entrypoint: &ModuleSpecifier,
+  asset_dirs: &[PathBuf],
+  compress: bool,
+) -> Result<(PathBuf, Vec<PathBuf>, VfsBuilder), AnyError> {
+  let Ok(entrypoint_path) = entrypoint.to_file_path() else {
+    bail!(
+      "Including directories of assets requires a local entrypoint, but the entrypoint is '{}'.",
+      entrypoint
+    );
+  };
+  let asset_dirs = asset_dirs
+    .iter()
+    .map(|dir| normalize_path(dir))
+    .collect::<Vec<_>>();
+  let mut root_path = entrypoint_path.parent().unwrap().to_path_buf();
+  for dir in &asset_dirs {
+    while !dir.starts_with(&root_path) {
+      match root_path.parent() {
+        Some(parent) => root_path = parent.to_path_buf(),
+        None => break,
+      }
+    }
+  }
+  let mut builder = VfsBuilder::new(root_path.clone())?;
+  builder.set_compress(compress);
+  for dir in &asset_dirs {
+    builder
+      .add_dir_recursive(dir)
+      .with_context(|| format!("Including assets of '{}'", dir.display()))?;
+  }
+  Ok((root_path, asset_dirs, builder))
+}
+
 /// This function sets the subsystem field in the PE header to 2 (GUI subsystem)
 /// For more information about the PE header: https://learn.microsoft.com/en-us/windows/win32/debug/pe-format
 fn set_windows_binary_to_gui(bin: &mut [u8]) -> Result<(), AnyError> {
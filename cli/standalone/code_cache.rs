@@ -0,0 +1,145 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+use deno_core::anyhow::bail;
+use deno_core::error::AnyError;
+use deno_core::v8;
+use deno_core::JsRuntime;
+use deno_core::RuntimeOptions;
+
+/// Creates a V8 code cache for each JavaScript module in the eszip, keyed
+/// by the module specifier.
+///
+/// The code cache is only accepted by V8 when the version and flags match
+/// the ones used at runtime, so this should only be used when the base
+/// binary is built from the same version of Deno for the current target.
+/// The process is initialized with only the binary's `--v8-flags` in that
+/// case (see `main.rs`).
+pub async fn create_code_cache(
+  eszip: &eszip::EszipV2,
+) -> Result<BTreeMap<String, Vec<u8>>, AnyError> {
+  let mut sources = Vec::new();
+  let mut seen = HashSet::new();
+  for specifier in eszip.specifiers() {
+    let Some(module) = eszip.get_module(&specifier) else {
+      continue;
+    };
+    if !matches!(module.kind, eszip::ModuleKind::JavaScript)
+      || !seen.insert(module.specifier.clone())
+    {
+      continue;
+    }
+    if let Some(source) = module.source().await {
+      sources.push((module.specifier.clone(), source));
+    }
+  }
+
+  let mut runtime = JsRuntime::new(RuntimeOptions::default());
+  let scope = &mut runtime.handle_scope();
+  let mut code_cache = BTreeMap::new();
+  for (specifier, source) in sources {
+    let Ok(source) = std::str::from_utf8(&source) else {
+      continue;
+    };
+    match create_module_code_cache(scope, &specifier, source) {
+      Some(data) => {
+        code_cache.insert(specifier, data);
+      }
+      None => {
+        log::debug!("Failed to create V8 code cache for module: {specifier}");
+      }
+    }
+  }
+  Ok(code_cache)
+}
+
+fn create_module_code_cache(
+  scope: &mut v8::HandleScope,
+  specifier: &str,
+  source: &str,
+) -> Option<Vec<u8>> {
+  let scope = &mut v8::TryCatch::new(scope);
+  let resource_name = v8::String::new(scope, specifier)?;
+  let source_map_url = v8::String::empty(scope);
+  let origin = v8::ScriptOrigin::new(
+    scope,
+    resource_name.into(),
+    0,
+    0,
+    false,
+    0,
+    source_map_url.into(),
+    false,
+    false,
+    true,
+  );
+  let source = v8::String::new(scope, source)?;
+  let source = v8::script_compiler::Source::new(source, Some(&origin));
+  let module = v8::script_compiler::compile_module(scope, source)?;
+  let code_cache = module
+    .get_unbound_module_script(scope)
+    .create_code_cache()?;
+  Some(code_cache.to_vec())
+}
+
+/// Serializes the code cache as a sequence of length prefixed specifiers
+/// and code cache data.
+pub fn serialize_code_cache(code_cache: &BTreeMap<String, Vec<u8>>) -> Vec<u8> {
+  let mut bytes = Vec::new();
+  for (specifier, data) in code_cache {
+    bytes.extend_from_slice(&(specifier.len() as u64).to_be_bytes());
+    bytes.extend_from_slice(specifier.as_bytes());
+    bytes.extend_from_slice(&(data.len() as u64).to_be_bytes());
+    bytes.extend_from_slice(data);
+  }
+  bytes
+}
+
+pub fn deserialize_code_cache(
+  mut bytes: &[u8],
+) -> Result<HashMap<String, Vec<u8>>, AnyError> {
+  fn take<'a>(bytes: &mut &'a [u8]) -> Result<&'a [u8], AnyError> {
+    if bytes.len() < 8 {
+      bail!("Unexpected end of code cache.");
+    }
+    let (len, rest) = bytes.split_at(8);
+    let len = u64::from_be_bytes(len.try_into().unwrap()) as usize;
+    if rest.len() < len {
+      bail!("Unexpected end of code cache.");
+    }
+    let (data, rest) = rest.split_at(len);
+    *bytes = rest;
+    Ok(data)
+  }
+
+  let mut code_cache = HashMap::new();
+  while !bytes.is_empty() {
+    let specifier = String::from_utf8(take(&mut bytes)?.to_vec())?;
+    let data = take(&mut bytes)?.to_vec();
+    code_cache.insert(specifier, data);
+  }
+  Ok(code_cache)
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn serializes_and_deserializes_code_cache() {
+    let mut code_cache = BTreeMap::new();
+    code_cache.insert("file:///main.js".to_string(), vec![1, 2, 3]);
+    code_cache.insert("file:///empty.js".to_string(), vec![]);
+    let bytes = serialize_code_cache(&code_cache);
+    let deserialized = deserialize_code_cache(&bytes).unwrap();
+    assert_eq!(deserialized.len(), 2);
+    assert_eq!(deserialized["file:///main.js"], vec![1, 2, 3]);
+    assert_eq!(deserialized["file:///empty.js"], Vec::<u8>::new());
+
+    assert!(deserialize_code_cache(&bytes[..bytes.len() - 1]).is_err());
+    assert!(deserialize_code_cache(&[]).unwrap().is_empty());
+  }
+}
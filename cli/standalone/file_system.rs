@@ -18,25 +18,37 @@ use deno_runtime::deno_io::fs::FsStat;
 
 use super::virtual_fs::FileBackedVfs;
 
+/// A file system that serves the virtual file systems embedded in the
+/// executable, like the npm packages and the included assets, falling back
+/// to the real file system for paths outside of them.
 #[derive(Debug, Clone)]
-pub struct DenoCompileFileSystem(Arc<FileBackedVfs>);
+pub struct DenoCompileFileSystem(Arc<Vec<Arc<FileBackedVfs>>>);
 
 impl DenoCompileFileSystem {
-  pub fn new(vfs: FileBackedVfs) -> Self {
-    Self(Arc::new(vfs))
+  pub fn new(vfs_list: impl IntoIterator<Item = FileBackedVfs>) -> Self {
+    Self(Arc::new(vfs_list.into_iter().map(Arc::new).collect()))
+  }
+
+  fn vfs_for_path(&self, path: &Path) -> Option<&Arc<FileBackedVfs>> {
+    self.0.iter().find(|vfs| vfs.is_path_within(path))
   }
 
   fn error_if_in_vfs(&self, path: &Path) -> FsResult<()> {
-    if self.0.is_path_within(path) {
+    if self.vfs_for_path(path).is_some() {
       Err(FsError::NotSupported)
     } else {
       Ok(())
     }
   }
 
-  fn copy_to_real_path(&self, oldpath: &Path, newpath: &Path) -> FsResult<()> {
-    let old_file = self.0.file_entry(oldpath)?;
-    let old_file_bytes = self.0.read_file_all(old_file)?;
+  fn copy_to_real_path(
+    &self,
+    vfs: &FileBackedVfs,
+    oldpath: &Path,
+    newpath: &Path,
+  ) -> FsResult<()> {
+    let old_file = vfs.file_entry(oldpath)?;
+    let old_file_bytes = vfs.read_file_all(old_file)?;
     RealFs.write_file_sync(
       newpath,
       OpenOptions {
@@ -79,8 +91,8 @@ impl FileSystem for DenoCompileFileSystem {
     options: OpenOptions,
     access_check: Option<AccessCheckCb>,
   ) -> FsResult<Rc<dyn File>> {
-    if self.0.is_path_within(path) {
-      Ok(self.0.open_file(path)?)
+    if let Some(vfs) = self.vfs_for_path(path) {
+      Ok(vfs.open_file(path)?)
     } else {
       RealFs.open_sync(path, options, access_check)
     }
@@ -91,8 +103,8 @@ impl FileSystem for DenoCompileFileSystem {
     options: OpenOptions,
     access_check: Option<AccessCheckCb<'a>>,
   ) -> FsResult<Rc<dyn File>> {
-    if self.0.is_path_within(&path) {
-      Ok(self.0.open_file(&path)?)
+    if let Some(vfs) = self.vfs_for_path(&path) {
+      Ok(vfs.open_file(&path)?)
     } else {
       RealFs.open_async(path, options, access_check).await
     }
@@ -156,8 +168,8 @@ impl FileSystem for DenoCompileFileSystem {
 
   fn copy_file_sync(&self, oldpath: &Path, newpath: &Path) -> FsResult<()> {
     self.error_if_in_vfs(newpath)?;
-    if self.0.is_path_within(oldpath) {
-      self.copy_to_real_path(oldpath, newpath)
+    if let Some(vfs) = self.vfs_for_path(oldpath) {
+      self.copy_to_real_path(vfs, oldpath, newpath)
     } else {
       RealFs.copy_file_sync(oldpath, newpath)
     }
@@ -168,10 +180,11 @@ impl FileSystem for DenoCompileFileSystem {
     newpath: PathBuf,
   ) -> FsResult<()> {
     self.error_if_in_vfs(&newpath)?;
-    if self.0.is_path_within(&oldpath) {
+    if let Some(vfs) = self.vfs_for_path(&oldpath) {
       let fs = self.clone();
+      let vfs = vfs.clone();
       tokio::task::spawn_blocking(move || {
-        fs.copy_to_real_path(&oldpath, &newpath)
+        fs.copy_to_real_path(&vfs, &oldpath, &newpath)
       })
       .await?
     } else {
@@ -191,60 +204,60 @@ impl FileSystem for DenoCompileFileSystem {
   }
 
   fn stat_sync(&self, path: &Path) -> FsResult<FsStat> {
-    if self.0.is_path_within(path) {
-      Ok(self.0.stat(path)?)
+    if let Some(vfs) = self.vfs_for_path(path) {
+      Ok(vfs.stat(path)?)
     } else {
       RealFs.stat_sync(path)
     }
   }
   async fn stat_async(&self, path: PathBuf) -> FsResult<FsStat> {
-    if self.0.is_path_within(&path) {
-      Ok(self.0.stat(&path)?)
+    if let Some(vfs) = self.vfs_for_path(&path) {
+      Ok(vfs.stat(&path)?)
     } else {
       RealFs.stat_async(path).await
     }
   }
 
   fn lstat_sync(&self, path: &Path) -> FsResult<FsStat> {
-    if self.0.is_path_within(path) {
-      Ok(self.0.lstat(path)?)
+    if let Some(vfs) = self.vfs_for_path(path) {
+      Ok(vfs.lstat(path)?)
     } else {
       RealFs.lstat_sync(path)
     }
   }
   async fn lstat_async(&self, path: PathBuf) -> FsResult<FsStat> {
-    if self.0.is_path_within(&path) {
-      Ok(self.0.lstat(&path)?)
+    if let Some(vfs) = self.vfs_for_path(&path) {
+      Ok(vfs.lstat(&path)?)
     } else {
       RealFs.lstat_async(path).await
     }
   }
 
   fn realpath_sync(&self, path: &Path) -> FsResult<PathBuf> {
-    if self.0.is_path_within(path) {
-      Ok(self.0.canonicalize(path)?)
+    if let Some(vfs) = self.vfs_for_path(path) {
+      Ok(vfs.canonicalize(path)?)
     } else {
       RealFs.realpath_sync(path)
     }
   }
   async fn realpath_async(&self, path: PathBuf) -> FsResult<PathBuf> {
-    if self.0.is_path_within(&path) {
-      Ok(self.0.canonicalize(&path)?)
+    if let Some(vfs) = self.vfs_for_path(&path) {
+      Ok(vfs.canonicalize(&path)?)
     } else {
       RealFs.realpath_async(path).await
     }
   }
 
   fn read_dir_sync(&self, path: &Path) -> FsResult<Vec<FsDirEntry>> {
-    if self.0.is_path_within(path) {
-      Ok(self.0.read_dir(path)?)
+    if let Some(vfs) = self.vfs_for_path(path) {
+      Ok(vfs.read_dir(path)?)
     } else {
       RealFs.read_dir_sync(path)
     }
   }
   async fn read_dir_async(&self, path: PathBuf) -> FsResult<Vec<FsDirEntry>> {
-    if self.0.is_path_within(&path) {
-      Ok(self.0.read_dir(&path)?)
+    if let Some(vfs) = self.vfs_for_path(&path) {
+      Ok(vfs.read_dir(&path)?)
     } else {
       RealFs.read_dir_async(path).await
     }
@@ -302,15 +315,15 @@ impl FileSystem for DenoCompileFileSystem {
   }
 
   fn read_link_sync(&self, path: &Path) -> FsResult<PathBuf> {
-    if self.0.is_path_within(path) {
-      Ok(self.0.read_link(path)?)
+    if let Some(vfs) = self.vfs_for_path(path) {
+      Ok(vfs.read_link(path)?)
     } else {
       RealFs.read_link_sync(path)
     }
   }
   async fn read_link_async(&self, path: PathBuf) -> FsResult<PathBuf> {
-    if self.0.is_path_within(&path) {
-      Ok(self.0.read_link(&path)?)
+    if let Some(vfs) = self.vfs_for_path(&path) {
+      Ok(vfs.read_link(&path)?)
     } else {
       RealFs.read_link_async(path).await
     }
@@ -54,23 +54,30 @@ use deno_runtime::WorkerExecutionMode;
 use deno_runtime::WorkerLogLevel;
 use deno_semver::npm::NpmPackageReqReference;
 use import_map::parse_from_json;
+use std::borrow::Cow;
+use std::collections::HashMap;
 use std::rc::Rc;
 use std::sync::Arc;
 
 pub mod binary;
+mod code_cache;
 mod file_system;
-mod virtual_fs;
+pub mod virtual_fs;
 
 pub use binary::extract_standalone;
 pub use binary::is_standalone_binary;
 pub use binary::DenoCompileBinaryWriter;
 
+use self::binary::load_assets_vfs;
+use self::binary::load_code_cache;
 use self::binary::load_npm_vfs;
 use self::binary::Metadata;
 use self::file_system::DenoCompileFileSystem;
 
 struct SharedModuleLoaderState {
   eszip: eszip::EszipV2,
+  /// V8 code cache of the eszip's modules that was created at compile time.
+  code_cache: HashMap<String, Vec<u8>>,
   mapped_specifier_resolver: MappedSpecifierResolver,
   node_resolver: Arc<CliNodeResolver>,
   npm_module_loader: Arc<NpmModuleLoader>,
@@ -232,6 +239,11 @@ impl ModuleLoader for EmbeddedModuleLoader {
     let original_specifier = original_specifier.clone();
     let found_specifier =
       ModuleSpecifier::parse(&module.specifier).expect("invalid url in eszip");
+    let code_cache = self
+      .shared
+      .code_cache
+      .get(&module.specifier)
+      .map(|data| Cow::Owned(data.clone()));
 
     deno_core::ModuleLoadResponse::Async(
       async move {
@@ -254,7 +266,7 @@ impl ModuleLoader for EmbeddedModuleLoader {
           ModuleSourceCode::String(code.into()),
           &original_specifier,
           &found_specifier,
-          None,
+          code_cache,
         ))
       }
       .boxed_local(),
@@ -351,6 +363,17 @@ pub async fn run(
   let npm_cache_dir = NpmCacheDir::new(root_path.clone());
   let npm_global_cache_dir = npm_cache_dir.get_cache_location();
   let cache_setting = CacheSetting::Only;
+  let maybe_assets_vfs = match &metadata.assets_root {
+    Some(assets_root) => Some(
+      load_assets_vfs(
+        &current_exe_path,
+        assets_root.clone(),
+        metadata.assets_dirs.clone(),
+      )
+      .context("Failed to load assets vfs.")?,
+    ),
+    None => None,
+  };
   let (package_json_deps_provider, fs, npm_resolver, maybe_vfs_root) =
     match metadata.node_modules {
       Some(binary::NodeModules::Managed {
@@ -364,7 +387,7 @@ pub async fn run(
         } else {
           npm_cache_dir.registry_folder(&npm_registry_url)
         };
-        let vfs = load_npm_vfs(vfs_root_dir_path.clone())
+        let vfs = load_npm_vfs(&current_exe_path, vfs_root_dir_path.clone())
           .context("Failed to load npm vfs.")?;
         let maybe_node_modules_path = if node_modules_dir {
           Some(vfs.root().to_path_buf())
@@ -375,8 +398,9 @@ pub async fn run(
           Arc::new(PackageJsonDepsProvider::new(
             package_json_deps.map(|serialized| serialized.into_deps()),
           ));
-        let fs = Arc::new(DenoCompileFileSystem::new(vfs))
-          as Arc<dyn deno_fs::FileSystem>;
+        let fs = Arc::new(DenoCompileFileSystem::new(
+          [vfs].into_iter().chain(maybe_assets_vfs),
+        )) as Arc<dyn deno_fs::FileSystem>;
         let npm_resolver = create_cli_npm_resolver(
           CliNpmResolverCreateOptions::Managed(CliNpmResolverManagedCreateOptions {
             snapshot: CliNpmResolverManagedSnapshotOption::Specified(Some(snapshot)),
@@ -405,15 +429,16 @@ pub async fn run(
       }
       Some(binary::NodeModules::Byonm { package_json_deps }) => {
         let vfs_root_dir_path = root_path;
-        let vfs = load_npm_vfs(vfs_root_dir_path.clone())
+        let vfs = load_npm_vfs(&current_exe_path, vfs_root_dir_path.clone())
           .context("Failed to load npm vfs.")?;
         let node_modules_path = vfs.root().join("node_modules");
         let package_json_deps_provider =
           Arc::new(PackageJsonDepsProvider::new(
             package_json_deps.map(|serialized| serialized.into_deps()),
           ));
-        let fs = Arc::new(DenoCompileFileSystem::new(vfs))
-          as Arc<dyn deno_fs::FileSystem>;
+        let fs = Arc::new(DenoCompileFileSystem::new(
+          [vfs].into_iter().chain(maybe_assets_vfs),
+        )) as Arc<dyn deno_fs::FileSystem>;
         let npm_resolver =
           create_cli_npm_resolver(CliNpmResolverCreateOptions::Byonm(
             CliNpmResolverByonmCreateOptions {
@@ -432,7 +457,13 @@ pub async fn run(
       None => {
         let package_json_deps_provider =
           Arc::new(PackageJsonDepsProvider::new(None));
-        let fs = Arc::new(deno_fs::RealFs) as Arc<dyn deno_fs::FileSystem>;
+        let fs = match maybe_assets_vfs {
+          Some(assets_vfs) => {
+            Arc::new(DenoCompileFileSystem::new([assets_vfs]))
+              as Arc<dyn deno_fs::FileSystem>
+          }
+          None => Arc::new(deno_fs::RealFs) as Arc<dyn deno_fs::FileSystem>,
+        };
         let npm_resolver = create_cli_npm_resolver(
           CliNpmResolverCreateOptions::Managed(CliNpmResolverManagedCreateOptions {
             snapshot: CliNpmResolverManagedSnapshotOption::Specified(None),
@@ -484,6 +515,8 @@ pub async fn run(
   let module_loader_factory = StandaloneModuleLoaderFactory {
     shared: Arc::new(SharedModuleLoaderState {
       eszip,
+      code_cache: load_code_cache(&current_exe_path)
+        .context("Failed to load code cache.")?,
       mapped_specifier_resolver: MappedSpecifierResolver::new(
         maybe_import_map.clone(),
         package_json_deps_provider.clone(),
@@ -502,8 +535,8 @@ pub async fn run(
     let maybe_cwd = std::env::current_dir().ok();
     let mut permissions =
       metadata.permissions.to_options(maybe_cwd.as_deref())?;
-    // if running with an npm vfs, grant read access to it
-    if let Some(vfs_root) = maybe_vfs_root {
+    // if running with an npm vfs or assets, grant read access to them
+    for vfs_root in maybe_vfs_root.into_iter().chain(metadata.assets_dirs) {
       match &mut permissions.allow_read {
         Some(vec) if vec.is_empty() => {
           // do nothing, already granted
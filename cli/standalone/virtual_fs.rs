@@ -23,6 +23,7 @@ use deno_runtime::deno_io;
 use deno_runtime::deno_io::fs::FsError;
 use deno_runtime::deno_io::fs::FsResult;
 use deno_runtime::deno_io::fs::FsStat;
+use indexmap::IndexMap;
 use serde::Deserialize;
 use serde::Serialize;
 use thiserror::Error;
@@ -44,9 +45,14 @@ pub struct VfsBuilder {
   root_dir: VirtualDirectory,
   files: Vec<Vec<u8>>,
   current_offset: u64,
-  file_offsets: HashMap<String, u64>,
+  file_offsets: HashMap<String, (u64, Option<u64>)>,
+  compress: bool,
 }
 
+/// The zstd level used for compressing the payloads of standalone binaries.
+/// Decompression speed barely depends on it, so favour the output size.
+pub const COMPRESSION_LEVEL: i32 = 19;
+
 impl VfsBuilder {
   pub fn new(root_path: PathBuf) -> Result<Self, AnyError> {
     let root_path = canonicalize_path(&root_path)?;
@@ -64,9 +70,15 @@ impl VfsBuilder {
       files: Vec::new(),
       current_offset: 0,
       file_offsets: Default::default(),
+      compress: false,
     })
   }
 
+  /// Stores the contents of files added after this call compressed with zstd.
+  pub fn set_compress(&mut self, compress: bool) {
+    self.compress = compress;
+  }
+
   pub fn set_root_dir_name(&mut self, name: String) {
     self.root_dir.name = name;
   }
@@ -183,17 +195,32 @@ impl VfsBuilder {
   fn add_file(&mut self, path: &Path, data: Vec<u8>) -> Result<(), AnyError> {
     log::debug!("Adding file '{}'", path.display());
     let checksum = util::checksum::gen(&[&data]);
-    let offset = if let Some(offset) = self.file_offsets.get(&checksum) {
+    let len = data.len() as u64;
+    let (offset, compressed_len, new_data) = if let Some((
+      offset,
+      compressed_len,
+    )) =
+      self.file_offsets.get(&checksum)
+    {
       // duplicate file, reuse an old offset
-      *offset
+      (*offset, *compressed_len, None)
     } else {
-      self.file_offsets.insert(checksum, self.current_offset);
-      self.current_offset
+      let (data, compressed_len) = if self.compress {
+        let data = zstd::bulk::compress(&data, COMPRESSION_LEVEL)
+          .with_context(|| format!("Compressing {}", path.display()))?;
+        let compressed_len = data.len() as u64;
+        (data, Some(compressed_len))
+      } else {
+        (data, None)
+      };
+      self
+        .file_offsets
+        .insert(checksum, (self.current_offset, compressed_len));
+      (self.current_offset, compressed_len, Some(data))
     };
 
     let dir = self.add_dir(path.parent().unwrap())?;
     let name = path.file_name().unwrap().to_string_lossy();
-    let data_len = data.len();
     match dir.entries.binary_search_by(|e| e.name().cmp(&name)) {
       Ok(_) => unreachable!(),
       Err(insert_index) => {
@@ -202,16 +229,17 @@ impl VfsBuilder {
           VfsEntry::File(VirtualFile {
             name: name.to_string(),
             offset,
-            len: data.len() as u64,
+            len,
+            compressed_len,
           }),
         );
       }
     }
 
     // new file, update the list of files
-    if self.current_offset == offset {
+    if let Some(data) = new_data {
+      self.current_offset += data.len() as u64;
       self.files.push(data);
-      self.current_offset += data_len as u64;
     }
 
     Ok(())
@@ -381,6 +409,10 @@ pub struct VirtualFile {
   pub name: String,
   pub offset: u64,
   pub len: u64,
+  /// The length of the data stored in the executable when the file is
+  /// compressed with zstd.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub compressed_len: Option<u64>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -723,10 +755,68 @@ impl deno_io::fs::File for FileBackedVfsFile {
   }
 }
 
+/// Least recently used cache of decompressed file contents, bounded by the
+/// total size of the cached contents.
+#[derive(Debug)]
+struct DecompressedFilesCache {
+  entries: IndexMap<u64, Arc<Vec<u8>>>,
+  total_bytes: usize,
+  max_bytes: usize,
+}
+
+impl Default for DecompressedFilesCache {
+  fn default() -> Self {
+    Self::new(64 * 1024 * 1024)
+  }
+}
+
+impl DecompressedFilesCache {
+  fn new(max_bytes: usize) -> Self {
+    Self {
+      entries: Default::default(),
+      total_bytes: 0,
+      max_bytes,
+    }
+  }
+
+  fn get(&mut self, offset: u64) -> Option<Arc<Vec<u8>>> {
+    let index = self.entries.get_index_of(&offset)?;
+    // move the entry to the back so it's evicted last
+    let last_index = self.entries.len() - 1;
+    self.entries.move_index(index, last_index);
+    self
+      .entries
+      .get_index(last_index)
+      .map(|(_, data)| data.clone())
+  }
+
+  fn insert(&mut self, offset: u64, data: Arc<Vec<u8>>) {
+    // don't let a single large file evict everything else
+    if data.len() > self.max_bytes / 4 {
+      return;
+    }
+    if let Some(previous) = self.entries.insert(offset, data.clone()) {
+      self.total_bytes -= previous.len();
+    }
+    self.total_bytes += data.len();
+    while self.total_bytes > self.max_bytes {
+      let Some((_, evicted)) = self.entries.shift_remove_index(0) else {
+        break;
+      };
+      self.total_bytes -= evicted.len();
+    }
+  }
+}
+
 #[derive(Debug)]
 pub struct FileBackedVfs {
   file: Mutex<File>,
   fs_root: VfsRoot,
+  /// When set, only paths within these directories are served from this vfs
+  /// instead of every path under the root.
+  mount_dirs: Option<Vec<PathBuf>>,
+  /// Contents of compressed files that were already read, by offset.
+  decompressed_files: Mutex<DecompressedFilesCache>,
 }
 
 impl FileBackedVfs {
@@ -734,15 +824,29 @@ impl FileBackedVfs {
     Self {
       file: Mutex::new(file),
       fs_root,
+      mount_dirs: None,
+      decompressed_files: Default::default(),
     }
   }
 
+  pub fn with_mount_dirs(mut self, mount_dirs: Vec<PathBuf>) -> Self {
+    self.mount_dirs = Some(mount_dirs);
+    self
+  }
+
   pub fn root(&self) -> &Path {
     &self.fs_root.root_path
   }
 
+  pub fn root_dir(&self) -> &VirtualDirectory {
+    &self.fs_root.dir
+  }
+
   pub fn is_path_within(&self, path: &Path) -> bool {
-    path.starts_with(&self.fs_root.root_path)
+    match &self.mount_dirs {
+      Some(mount_dirs) => mount_dirs.iter().any(|dir| path.starts_with(dir)),
+      None => path.starts_with(&self.fs_root.root_path),
+    }
   }
 
   pub fn open_file(
@@ -813,6 +917,13 @@ impl FileBackedVfs {
     pos: u64,
     buf: &mut [u8],
   ) -> std::io::Result<usize> {
+    if let Some(compressed_len) = file.compressed_len {
+      let data = self.read_decompressed_file(file, compressed_len)?;
+      let start = std::cmp::min(pos as usize, data.len());
+      let end = std::cmp::min(start + buf.len(), data.len());
+      buf[..end - start].copy_from_slice(&data[start..end]);
+      return Ok(end - start);
+    }
     let mut fs_file = self.file.lock();
     fs_file.seek(SeekFrom::Start(
       self.fs_root.start_file_offset + file.offset + pos,
@@ -820,6 +931,31 @@ impl FileBackedVfs {
     fs_file.read(buf)
   }
 
+  fn read_decompressed_file(
+    &self,
+    file: &VirtualFile,
+    compressed_len: u64,
+  ) -> std::io::Result<Arc<Vec<u8>>> {
+    if let Some(data) = self.decompressed_files.lock().get(file.offset) {
+      return Ok(data);
+    }
+    let mut compressed = vec![0; compressed_len as usize];
+    {
+      let mut fs_file = self.file.lock();
+      fs_file.seek(SeekFrom::Start(
+        self.fs_root.start_file_offset + file.offset,
+      ))?;
+      fs_file.read_exact(&mut compressed)?;
+    }
+    let data =
+      Arc::new(zstd::bulk::decompress(&compressed, file.len as usize)?);
+    self
+      .decompressed_files
+      .lock()
+      .insert(file.offset, data.clone());
+    Ok(data)
+  }
+
   pub fn dir_entry(&self, path: &Path) -> std::io::Result<&VirtualDirectory> {
     let (_, entry) = self.fs_root.find_entry(path)?;
     match entry {
@@ -932,6 +1068,49 @@ mod test {
     assert!(virtual_fs.stat(&dest_path.join("e.txt")).unwrap().is_file,);
   }
 
+  #[test]
+  fn builds_and_uses_compressed_virtual_fs() {
+    let temp_dir = TempDir::new();
+    let src_path = temp_dir.path().canonicalize().join("src");
+    src_path.create_dir_all();
+    let src_path = src_path.to_path_buf();
+    let mut builder = VfsBuilder::new(src_path.clone()).unwrap();
+    builder.set_compress(true);
+    let data = "data".repeat(1000);
+    builder
+      .add_file(&src_path.join("a.txt"), data.clone().into())
+      .unwrap();
+    builder
+      .add_file(&src_path.join("b.txt"), data.clone().into())
+      .unwrap();
+    builder
+      .add_file(&src_path.join("c.txt"), "c".into())
+      .unwrap();
+    assert_eq!(builder.files.len(), 2); // because duplicate data
+    assert!(builder.files[0].len() < data.len());
+
+    let (dest_path, virtual_fs) = into_virtual_fs(builder, &temp_dir);
+    assert_eq!(read_file(&virtual_fs, &dest_path.join("a.txt")), data);
+    assert_eq!(read_file(&virtual_fs, &dest_path.join("b.txt")), data);
+    assert_eq!(read_file(&virtual_fs, &dest_path.join("c.txt")), "c");
+    assert_eq!(
+      virtual_fs.stat(&dest_path.join("a.txt")).unwrap().size,
+      data.len() as u64
+    );
+
+    // partial reads
+    let file = virtual_fs.file_entry(&dest_path.join("a.txt")).unwrap();
+    let mut buf = [0; 6];
+    assert_eq!(virtual_fs.read_file(file, 2, &mut buf).unwrap(), 6);
+    assert_eq!(&buf, b"tadata");
+    assert_eq!(
+      virtual_fs
+        .read_file(file, data.len() as u64 - 2, &mut buf)
+        .unwrap(),
+      2
+    );
+  }
+
   #[test]
   fn test_include_dir_recursive() {
     let temp_dir = TempDir::new();
@@ -981,6 +1160,50 @@ mod test {
     );
   }
 
+  #[test]
+  fn decompressed_files_cache_is_bounded() {
+    let mut cache = DecompressedFilesCache::new(40);
+    cache.insert(0, Arc::new(vec![0; 10]));
+    cache.insert(1, Arc::new(vec![1; 10]));
+    cache.insert(2, Arc::new(vec![2; 10]));
+    // too large to be cached
+    cache.insert(3, Arc::new(vec![3; 11]));
+    assert!(cache.get(3).is_none());
+    // mark 0 as recently used so 1 is evicted instead
+    assert_eq!(cache.get(0).unwrap().len(), 10);
+    cache.insert(4, Arc::new(vec![4; 10]));
+    cache.insert(5, Arc::new(vec![5; 10]));
+    assert!(cache.get(1).is_none());
+    assert!(cache.get(0).is_some());
+    assert!(cache.get(2).is_some());
+    assert!(cache.get(4).is_some());
+    assert!(cache.get(5).is_some());
+    assert_eq!(cache.total_bytes, 40);
+  }
+
+  #[test]
+  fn mounted_virtual_fs_only_claims_mount_dirs() {
+    let temp_dir = TempDir::new();
+    let src_path = temp_dir.path().canonicalize().join("src");
+    src_path.create_dir_all();
+    let src_path = src_path.to_path_buf();
+    let mut builder = VfsBuilder::new(src_path.clone()).unwrap();
+    builder
+      .add_file(&src_path.join("assets").join("a.txt"), "a".into())
+      .unwrap();
+
+    let (dest_path, virtual_fs) = into_virtual_fs(builder, &temp_dir);
+    let virtual_fs = virtual_fs.with_mount_dirs(vec![dest_path.join("assets")]);
+    assert!(virtual_fs.is_path_within(&dest_path.join("assets")));
+    assert!(virtual_fs.is_path_within(&dest_path.join("assets/a.txt")));
+    assert!(!virtual_fs.is_path_within(&dest_path));
+    assert!(!virtual_fs.is_path_within(&dest_path.join("other.txt")));
+    assert_eq!(
+      read_file(&virtual_fs, &dest_path.join("assets").join("a.txt")),
+      "a"
+    );
+  }
+
   fn into_virtual_fs(
     builder: VfsBuilder,
     temp_dir: &TempDir,
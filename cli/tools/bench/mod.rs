@@ -6,8 +6,8 @@ use crate::args::Flags;
 use crate::colors;
 use crate::display::write_json_to_stdout;
 use crate::factory::CliFactory;
-use crate::factory::CliFactoryBuilder;
-use crate::graph_util::has_graph_root_local_dependent_changed;
+use crate::factory::WatcherFactory;
+use crate::graph_util::graph_roots_with_changed_dependents;
 use crate::module_loader::ModuleLoadPreparer;
 use crate::ops;
 use crate::tools::test::format_test_error;
@@ -41,7 +41,6 @@ use indexmap::IndexSet;
 use log::Level;
 use serde::Deserialize;
 use serde::Serialize;
-use std::collections::HashSet;
 use std::path::Path;
 use std::sync::Arc;
 use std::time::Duration;
@@ -479,6 +478,7 @@ pub async fn run_benchmarks_with_watch(
   flags: Flags,
   bench_flags: BenchFlags,
 ) -> Result<(), AnyError> {
+  let mut watcher_factory = WatcherFactory::default();
   file_watcher::watch_func(
     flags,
     file_watcher::PrintConfig::new(
@@ -491,9 +491,12 @@ pub async fn run_benchmarks_with_watch(
     ),
     move |flags, watcher_communicator, changed_paths| {
       let bench_flags = bench_flags.clone();
+      let factory = watcher_factory.get(
+        flags,
+        watcher_communicator.clone(),
+        changed_paths.as_deref(),
+      )?;
       Ok(async move {
-        let factory = CliFactoryBuilder::new()
-          .build_from_flags_for_watcher(flags, watcher_communicator.clone())?;
         let cli_options = factory.cli_options();
         let bench_options = cli_options.resolve_bench_options(bench_flags)?;
 
@@ -525,25 +528,8 @@ pub async fn run_benchmarks_with_watch(
           .create_graph(graph_kind, bench_modules)
           .await?;
         module_graph_creator.graph_valid(&graph)?;
-        let bench_modules = &graph.roots;
-
-        let bench_modules_to_reload = if let Some(changed_paths) = changed_paths
-        {
-          let changed_paths = changed_paths.into_iter().collect::<HashSet<_>>();
-          let mut result = Vec::new();
-          for bench_module_specifier in bench_modules {
-            if has_graph_root_local_dependent_changed(
-              &graph,
-              bench_module_specifier,
-              &changed_paths,
-            ) {
-              result.push(bench_module_specifier.clone());
-            }
-          }
-          result
-        } else {
-          bench_modules.clone()
-        };
+        let bench_modules_to_reload =
+          graph_roots_with_changed_dependents(&graph, changed_paths);
 
         let worker_factory =
           Arc::new(factory.create_cli_main_worker_factory().await?);
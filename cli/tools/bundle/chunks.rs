@@ -0,0 +1,224 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+use crate::cache::FastInsecureHasher;
+
+use super::modules::BundleModule;
+use super::modules::DependencyTarget;
+use super::modules::ModuleId;
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct Chunk {
+  pub file_name: String,
+  pub modules: Vec<ModuleId>,
+}
+
+/// The modules of a bundle split into chunks. The first chunk is the entry
+/// chunk, which has the root module and everything it statically imports.
+#[derive(Debug)]
+pub struct Chunks {
+  pub chunks: Vec<Chunk>,
+  /// The chunks to load before a module that's only reachable through a
+  /// dynamic import can be imported.
+  pub loads: HashMap<ModuleId, Vec<usize>>,
+}
+
+impl Chunks {
+  /// Puts all modules in the entry chunk.
+  pub fn single(modules: &[BundleModule], file_name: String) -> Self {
+    Self {
+      chunks: vec![Chunk {
+        file_name,
+        modules: (0..modules.len()).collect(),
+      }],
+      loads: HashMap::new(),
+    }
+  }
+
+  /// Splits the modules that are only reachable through dynamic imports
+  /// into separate chunks. Modules that are reachable from several dynamic
+  /// imports are put in chunks that are shared between them.
+  pub fn split(modules: &[BundleModule], file_name: String) -> Self {
+    let entry_modules = statically_reachable(modules, 0);
+    let mut dynamic_entries = Vec::new();
+    for module in modules {
+      for dependency in module.dependencies.values() {
+        if let DependencyTarget::Module(id) = dependency.target {
+          if dependency.is_dynamic
+            && !entry_modules.contains(&id)
+            && !dynamic_entries.contains(&id)
+          {
+            dynamic_entries.push(id);
+          }
+        }
+      }
+    }
+
+    // group the modules by the dynamic entries they're reachable from
+    let mut entries_by_module = vec![BTreeSet::new(); modules.len()];
+    for (index, entry) in dynamic_entries.iter().enumerate() {
+      for id in statically_reachable(modules, *entry) {
+        if !entry_modules.contains(&id) {
+          entries_by_module[id].insert(index);
+        }
+      }
+    }
+    let mut chunk_entries = Vec::<BTreeSet<usize>>::new();
+    let mut chunks = vec![Chunk {
+      file_name: file_name.clone(),
+      modules: Vec::new(),
+    }];
+    for (id, entries) in entries_by_module.into_iter().enumerate() {
+      if entries.is_empty() {
+        chunks[0].modules.push(id);
+        continue;
+      }
+      let index = match chunk_entries.iter().position(|e| *e == entries) {
+        Some(index) => index + 1,
+        None => {
+          chunk_entries.push(entries);
+          chunks.push(Chunk {
+            file_name: String::new(),
+            modules: Vec::new(),
+          });
+          chunks.len() - 1
+        }
+      };
+      chunks[index].modules.push(id);
+    }
+
+    let (stem, extension) = match file_name.rsplit_once('.') {
+      Some((stem, extension)) => (stem, extension),
+      None => (file_name.as_str(), "js"),
+    };
+    for chunk in chunks.iter_mut().skip(1) {
+      let mut hasher = FastInsecureHasher::new();
+      for id in &chunk.modules {
+        hasher.write_str(modules[*id].specifier.as_str());
+      }
+      chunk.file_name =
+        format!("{}-{:08x}.{}", stem, hasher.finish() as u32, extension);
+    }
+
+    let loads = dynamic_entries
+      .iter()
+      .enumerate()
+      .map(|(index, entry)| {
+        let chunks = chunk_entries
+          .iter()
+          .enumerate()
+          .filter(|(_, entries)| entries.contains(&index))
+          .map(|(chunk_index, _)| chunk_index + 1)
+          .collect();
+        (*entry, chunks)
+      })
+      .collect();
+    Self { chunks, loads }
+  }
+
+  /// The files to load before dynamically importing the module, relative
+  /// to the chunks.
+  pub fn files_to_load(&self, id: ModuleId) -> Vec<String> {
+    self
+      .loads
+      .get(&id)
+      .map(|chunks| {
+        chunks
+          .iter()
+          .map(|index| format!("./{}", self.chunks[*index].file_name))
+          .collect()
+      })
+      .unwrap_or_default()
+  }
+}
+
+fn statically_reachable(
+  modules: &[BundleModule],
+  root: ModuleId,
+) -> HashSet<ModuleId> {
+  let mut seen = HashSet::from([root]);
+  let mut pending = vec![root];
+  while let Some(id) = pending.pop() {
+    for dependency in modules[id].dependencies.values() {
+      if let DependencyTarget::Module(id) = dependency.target {
+        if !dependency.is_dynamic && seen.insert(id) {
+          pending.push(id);
+        }
+      }
+    }
+  }
+  seen
+}
+
+#[cfg(test)]
+mod test {
+  use std::collections::BTreeMap;
+
+  use deno_ast::MediaType;
+  use deno_ast::ModuleSpecifier;
+
+  use super::super::modules::ModuleDependency;
+  use super::super::modules::ModuleKind;
+  use super::*;
+
+  fn module(name: &str, dependencies: &[(ModuleId, bool)]) -> BundleModule {
+    BundleModule {
+      specifier: ModuleSpecifier::parse(&format!("file:///{}.ts", name))
+        .unwrap(),
+      kind: ModuleKind::Esm,
+      media_type: MediaType::TypeScript,
+      source: "".into(),
+      dependencies: dependencies
+        .iter()
+        .map(|(id, is_dynamic)| {
+          (
+            format!("./{}.ts", id),
+            ModuleDependency {
+              target: DependencyTarget::Module(*id),
+              is_dynamic: *is_dynamic,
+            },
+          )
+        })
+        .collect::<BTreeMap<_, _>>(),
+    }
+  }
+
+  #[test]
+  fn test_split() {
+    // main -> a, main ~> b, main ~> c, b -> d, c -> d, c -> a
+    let modules = vec![
+      module("main", &[(1, false), (2, true), (3, true)]),
+      module("a", &[]),
+      module("b", &[(4, false)]),
+      module("c", &[(4, false), (1, false)]),
+      module("d", &[]),
+    ];
+    let chunks = Chunks::split(&modules, "main.js".to_string());
+    assert_eq!(chunks.chunks.len(), 4);
+    assert_eq!(chunks.chunks[0].file_name, "main.js");
+    assert_eq!(chunks.chunks[0].modules, vec![0, 1]);
+    assert_eq!(chunks.chunks[1].modules, vec![2]);
+    assert_eq!(chunks.chunks[2].modules, vec![3]);
+    assert_eq!(chunks.chunks[3].modules, vec![4]);
+    assert!(chunks.chunks[1].file_name.starts_with("main-"));
+    assert!(chunks.chunks[1].file_name.ends_with(".js"));
+    assert_eq!(chunks.loads[&2], vec![1, 3]);
+    assert_eq!(chunks.loads[&3], vec![2, 3]);
+    assert_eq!(chunks.files_to_load(1), Vec::<String>::new());
+    assert_eq!(
+      chunks.files_to_load(2),
+      vec![
+        format!("./{}", chunks.chunks[1].file_name),
+        format!("./{}", chunks.chunks[3].file_name),
+      ]
+    );
+
+    let chunks = Chunks::single(&modules, "main.js".to_string());
+    assert_eq!(chunks.chunks.len(), 1);
+    assert_eq!(chunks.chunks[0].modules, vec![0, 1, 2, 3, 4]);
+    assert!(chunks.loads.is_empty());
+  }
+}
@@ -0,0 +1,161 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+use deno_ast::MediaType;
+use deno_ast::TokenOrComment;
+
+use super::source_map::Mapping;
+
+/// Removes the comments and the whitespace between tokens that isn't needed.
+///
+/// Line breaks are kept where automatic semicolon insertion could depend on
+/// them, which is anywhere except after a punctuator that can't end a
+/// statement. Names aren't mangled.
+pub fn minify(code: &str, mappings: &[Mapping]) -> (String, Vec<Mapping>) {
+  let mut output = String::with_capacity(code.len());
+  let mut token_offsets = Vec::new();
+  let mut last_token: Option<&str> = None;
+  let mut last_end = 0;
+  for item in deno_ast::lex(code, MediaType::JavaScript) {
+    if matches!(item.inner, TokenOrComment::Comment { .. }) {
+      continue;
+    }
+    let text = &code[item.range.clone()];
+    let gap = &code[last_end..item.range.start];
+    // adjacent tokens are kept as is, which also keeps template literals
+    // intact
+    if let (Some(last_token), false) = (last_token, gap.is_empty()) {
+      if has_line_break(gap) && !can_remove_line_break(last_token) {
+        output.push('\n');
+      } else if needs_space(last_token, text) {
+        output.push(' ');
+      }
+    }
+    token_offsets.push((item.range.start, output.len()));
+    output.push_str(text);
+    last_token = Some(text);
+    last_end = item.range.end;
+  }
+
+  let mappings = mappings
+    .iter()
+    .filter_map(|mapping| {
+      let index = token_offsets
+        .binary_search_by_key(&mapping.offset, |(offset, _)| *offset)
+        .ok()?;
+      Some(Mapping {
+        offset: token_offsets[index].1,
+        ..*mapping
+      })
+    })
+    .collect();
+  (output, mappings)
+}
+
+fn has_line_break(text: &str) -> bool {
+  text
+    .chars()
+    .any(|c| matches!(c, '\n' | '\r' | '\u{2028}' | '\u{2029}'))
+}
+
+/// Whether a line break after the token never causes a semicolon to be
+/// inserted.
+fn can_remove_line_break(token: &str) -> bool {
+  let Some(last) = token.chars().last() else {
+    return false;
+  };
+  let is_punctuator =
+    !is_word_char(last) && !matches!(last, '"' | '\'' | '`' | '/');
+  is_punctuator && !matches!(token, ")" | "]" | "}" | "++" | "--")
+}
+
+/// Whether the tokens would be lexed differently without a space between
+/// them.
+fn needs_space(left: &str, right: &str) -> bool {
+  let (Some(last), Some(first)) = (left.chars().last(), right.chars().next())
+  else {
+    return false;
+  };
+  (is_word_char(last) && (is_word_char(first) || first == '\\'))
+    || (last == '+' && first == '+')
+    || (last == '-' && first == '-')
+    || (last == '/' && first == '/')
+    || (is_regex(left) && is_word_char(first))
+    || (last == '<' && right.starts_with("!--"))
+    || (first == '.' && left.chars().all(|c| c.is_ascii_digit()))
+}
+
+fn is_regex(token: &str) -> bool {
+  token.len() > 1 && token.starts_with('/') && token != "/="
+}
+
+fn is_word_char(c: char) -> bool {
+  c.is_alphanumeric() || c == '_' || c == '$' || c == '#' || !c.is_ascii()
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  fn minify_code(code: &str) -> String {
+    minify(code, &[]).0
+  }
+
+  #[test]
+  fn test_minify() {
+    assert_eq!(
+      minify_code("const a = 1; // comment\n/* comment */\nfunction f() {\n  return a;\n}\n"),
+      "const a=1;function f(){return a;}"
+    );
+    assert_eq!(minify_code("let a = b\n(c)"), "let a=b\n(c)");
+    assert_eq!(minify_code("a = b +\n+c - -d"), "a=b+ +c- -d");
+    assert_eq!(minify_code("return\nx"), "return\nx");
+    assert_eq!(minify_code("a++\nb"), "a++\nb");
+    assert_eq!(minify_code("x = `a  ${ b }  c`;"), "x=`a  ${b}  c`;");
+    assert_eq!(minify_code("x = `a${b}c`"), "x=`a${b}c`");
+    assert_eq!(minify_code("1 .toString()"), "1 .toString()");
+    assert_eq!(
+      minify_code("a = /x/ instanceof RegExp"),
+      "a=/x/ instanceof RegExp"
+    );
+  }
+
+  #[test]
+  fn test_minify_mappings() {
+    let (code, mappings) = minify(
+      "a  =\n  b;",
+      &[
+        Mapping {
+          offset: 0,
+          src_line: 1,
+          src_col: 2,
+        },
+        Mapping {
+          offset: 1,
+          src_line: 1,
+          src_col: 3,
+        },
+        Mapping {
+          offset: 7,
+          src_line: 2,
+          src_col: 0,
+        },
+      ],
+    );
+    assert_eq!(code, "a=b;");
+    assert_eq!(
+      mappings,
+      vec![
+        Mapping {
+          offset: 0,
+          src_line: 1,
+          src_col: 2,
+        },
+        Mapping {
+          offset: 2,
+          src_line: 2,
+          src_col: 0,
+        },
+      ]
+    );
+  }
+}
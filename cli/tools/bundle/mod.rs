@@ -0,0 +1,425 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+mod chunks;
+mod minify;
+mod modules;
+mod source_map;
+mod transform;
+
+use std::collections::HashSet;
+use std::path::Path;
+use std::path::PathBuf;
+
+use base64::prelude::BASE64_STANDARD;
+use base64::Engine;
+use deno_core::anyhow::bail;
+use deno_core::error::AnyError;
+use deno_graph::Module;
+use deno_terminal::colors;
+
+use crate::args::BundleFlags;
+use crate::args::BundleSourceMap;
+use crate::args::Flags;
+use crate::args::TsConfigType;
+use crate::cache::FastInsecureHasher;
+use crate::factory::CliFactory;
+use crate::factory::CliFactoryBuilder;
+use crate::util;
+use crate::util::display;
+
+use self::chunks::Chunk;
+use self::chunks::Chunks;
+use self::modules::BundleModule;
+use self::modules::DependencyTarget;
+use self::modules::ModuleCollector;
+use self::source_map::ChunkSourceMapBuilder;
+use self::transform::export_name_text;
+use self::transform::is_hoisted;
+use self::transform::quote;
+use self::transform::ModuleTransformer;
+use self::transform::TransformedModule;
+
+pub use self::minify::minify;
+pub use self::modules::analyze_dependencies;
+
+const RUNTIME: &str = include_str!("runtime.js");
+
+pub async fn bundle(
+  flags: Flags,
+  bundle_flags: BundleFlags,
+) -> Result<(), AnyError> {
+  if let Some(watch_flags) = &bundle_flags.watch {
+    util::file_watcher::watch_func(
+      flags,
+      util::file_watcher::PrintConfig::new(
+        "Bundle",
+        !watch_flags.no_clear_screen,
+      ),
+      move |flags, watcher_communicator, _changed_paths| {
+        let bundle_flags = bundle_flags.clone();
+        Ok(async move {
+          let factory = CliFactoryBuilder::new().build_from_flags_for_watcher(
+            flags,
+            watcher_communicator.clone(),
+          )?;
+          let cli_options = factory.cli_options();
+          let _ = watcher_communicator.watch_paths(cli_options.watch_paths());
+          bundle_action(factory, &bundle_flags).await?;
+
+          Ok(())
+        })
+      },
+    )
+    .await?;
+  } else {
+    let factory = CliFactory::from_flags(flags)?;
+    bundle_action(factory, &bundle_flags).await?;
+  }
+
+  Ok(())
+}
+
+async fn bundle_action(
+  factory: CliFactory,
+  bundle_flags: &BundleFlags,
+) -> Result<(), AnyError> {
+  let cli_options = factory.cli_options();
+  let module_specifier = cli_options.resolve_main_module()?;
+  let out_file = bundle_flags
+    .out_file
+    .as_ref()
+    .map(|out_file| cli_options.initial_cwd().join(out_file));
+  if bundle_flags.source_map == Some(BundleSourceMap::External)
+    && out_file.is_none()
+  {
+    bail!("An external source map requires an output file. Use --source-map=inline instead.");
+  }
+  log::debug!(">>>>> bundle START");
+  let module_graph_creator = factory.module_graph_creator().await?;
+
+  let graph = module_graph_creator
+    .create_graph_and_maybe_check(vec![module_specifier.clone()])
+    .await?;
+
+  let mut paths_to_watch: Vec<PathBuf> = graph
+    .specifiers()
+    .filter_map(|(_, r)| {
+      r.ok().and_then(|module| match module {
+        Module::Js(m) => m.specifier.to_file_path().ok(),
+        Module::Json(m) => m.specifier.to_file_path().ok(),
+        // nothing to watch
+        Module::Node(_) | Module::Npm(_) | Module::External(_) => None,
+      })
+    })
+    .collect();
+
+  if let Ok(Some(import_map_path)) = cli_options
+    .resolve_specified_import_map_specifier()
+    .map(|ms| ms.and_then(|ref s| s.to_file_path().ok()))
+  {
+    paths_to_watch.push(import_map_path);
+  }
+
+  log::info!("{} {}", colors::green("Bundle"), graph.roots[0]);
+
+  let modules = ModuleCollector::new(
+    &graph,
+    factory.cli_node_resolver().await?,
+    factory.fs().as_ref(),
+    bundle_flags.platform,
+  )
+  .collect(&graph.roots[0])?;
+
+  // only split the code into chunks when writing files, since stdout can
+  // only take one
+  let chunks = match &out_file {
+    Some(out_file) => Chunks::split(
+      &modules,
+      out_file.file_name().unwrap().to_string_lossy().to_string(),
+    ),
+    None => Chunks::single(&modules, "bundle.js".to_string()),
+  };
+
+  let ts_config_result =
+    cli_options.resolve_ts_config_for_emit(TsConfigType::Bundle)?;
+  if !cli_options.type_check_mode().is_true() {
+    if let Some(ignored_options) = ts_config_result.maybe_ignored_options {
+      log::warn!("{}", ignored_options);
+    }
+  }
+  let (transpile_options, emit_options) =
+    crate::args::ts_config_to_transpile_and_emit_options(
+      ts_config_result.ts_config,
+    )?;
+  let transformer = ModuleTransformer {
+    transpile_options: &transpile_options,
+    emit_options: &emit_options,
+    source_maps: bundle_flags.source_map.is_some(),
+    minify: bundle_flags.minify,
+  };
+  let transformed = modules
+    .iter()
+    .enumerate()
+    .map(|(id, module)| transformer.transform(id, module, &chunks))
+    .collect::<Result<Vec<_>, _>>()?;
+  let renderer = ChunkRenderer {
+    modules: &modules,
+    transformed: &transformed,
+    bundle_flags,
+    hash: bundle_hash(&modules),
+  };
+  let outputs = chunks
+    .chunks
+    .iter()
+    .enumerate()
+    .map(|(index, chunk)| renderer.render(index, chunk))
+    .collect::<Result<Vec<_>, _>>()?;
+  log::debug!(">>>>> bundle END");
+
+  match out_file {
+    Some(out_file) => {
+      for output in outputs {
+        let file_path = out_file.with_file_name(&output.file_name);
+        write_output(&file_path, output.code.as_bytes())?;
+        if let Some(source_map) = output.source_map {
+          let map_file_path =
+            out_file.with_file_name(format!("{}.map", output.file_name));
+          write_output(&map_file_path, &source_map)?;
+        }
+      }
+    }
+    None => {
+      println!("{}", outputs[0].code);
+    }
+  }
+  Ok(())
+}
+
+fn write_output(path: &Path, bytes: &[u8]) -> Result<(), AnyError> {
+  util::fs::write_file(path, bytes, 0o644)?;
+  log::info!(
+    "{} {:?} ({})",
+    colors::green("Emit"),
+    path,
+    colors::gray(display::human_size(bytes.len() as f64))
+  );
+  Ok(())
+}
+
+/// A hash of the bundled modules that identifies the bundle, so that its
+/// chunks share the module loader.
+fn bundle_hash(modules: &[BundleModule]) -> u64 {
+  let mut hasher = FastInsecureHasher::new();
+  for module in modules {
+    hasher.write_str(module.specifier.as_str());
+    hasher.write_str(&module.source);
+  }
+  hasher.finish()
+}
+
+struct ChunkOutput {
+  file_name: String,
+  code: String,
+  source_map: Option<Vec<u8>>,
+}
+
+struct ChunkRenderer<'a> {
+  modules: &'a [BundleModule],
+  transformed: &'a [TransformedModule],
+  bundle_flags: &'a BundleFlags,
+  hash: u64,
+}
+
+impl<'a> ChunkRenderer<'a> {
+  fn render(
+    &self,
+    index: usize,
+    chunk: &Chunk,
+  ) -> Result<ChunkOutput, AnyError> {
+    let is_entry = index == 0;
+    let mut code = String::new();
+    if is_entry {
+      if let Some(shebang) = &self.transformed[0].shebang {
+        code.push_str(shebang);
+        code.push('\n');
+      }
+    }
+    code.push_str("// deno-fmt-ignore-file\n");
+    code.push_str("// deno-lint-ignore-file\n");
+    code.push_str("// This code was bundled using `deno bundle` and it's not recommended to edit it manually\n\n");
+
+    // the externals that are statically imported by the modules of the
+    // chunk are imported along with it
+    let mut externals = Vec::new();
+    for id in &chunk.modules {
+      for dependency in self.modules[*id].dependencies.values() {
+        if let DependencyTarget::External(specifier) = &dependency.target {
+          if !dependency.is_dynamic && !externals.contains(specifier) {
+            externals.push(specifier.clone());
+          }
+        }
+      }
+    }
+    for (index, specifier) in externals.iter().enumerate() {
+      code.push_str(&format!(
+        "import * as __e{} from {};\n",
+        index,
+        quote(specifier)
+      ));
+    }
+
+    let global =
+      format!("globalThis[Symbol.for(\"deno.bundle.{:x}\")]", self.hash);
+    if is_entry {
+      // skip the leading comments
+      let runtime = RUNTIME
+        .lines()
+        .skip_while(|line| line.is_empty() || line.starts_with("//"))
+        .collect::<Vec<_>>()
+        .join("\n");
+      let runtime = if self.bundle_flags.minify {
+        minify::minify(&runtime, &[]).0
+      } else {
+        runtime
+      };
+      let node_env = if self.bundle_flags.minify {
+        "production"
+      } else {
+        "development"
+      };
+      code.push_str(&format!(
+        "const __bundle = {} ??= ({})({{ nodeEnv: \"{}\" }});\n",
+        global, runtime, node_env
+      ));
+    } else {
+      code.push_str(&format!("const __bundle = {};\n", global));
+    }
+    for (index, specifier) in externals.iter().enumerate() {
+      code.push_str(&format!(
+        "__bundle.external({}, __e{});\n",
+        quote(specifier),
+        index
+      ));
+    }
+
+    let mut source_map_builder = self
+      .bundle_flags
+      .source_map
+      .map(|_| ChunkSourceMapBuilder::new(&chunk.file_name));
+    // the hoisted root module is evaluated once the other modules of the
+    // entry chunk are defined
+    let hoisted = is_entry && is_hoisted(0, &self.modules[0]);
+    let ids = chunk
+      .modules
+      .iter()
+      .filter(|id| !hoisted || **id != 0)
+      .chain(hoisted.then_some(&0));
+    for id in ids {
+      let module = &self.modules[*id];
+      let transformed = &self.transformed[*id];
+      if let Some(builder) = &mut source_map_builder {
+        builder.add_module(
+          module.specifier.as_str(),
+          &module.source,
+          &transformed.code,
+          &transformed.mappings,
+          code.matches('\n').count() as u32,
+        );
+      }
+      code.push_str(&transformed.code);
+    }
+
+    if is_entry {
+      code.push_str(&self.render_entry_exports(hoisted));
+    }
+
+    let mut source_map = None;
+    if let Some(builder) = source_map_builder {
+      let bytes = builder.into_bytes()?;
+      match self.bundle_flags.source_map {
+        Some(BundleSourceMap::Inline) => {
+          code.push_str(&format!(
+            "//# sourceMappingURL=data:application/json;base64,{}\n",
+            BASE64_STANDARD.encode(&bytes)
+          ));
+        }
+        _ => {
+          code.push_str(&format!(
+            "//# sourceMappingURL={}.map\n",
+            chunk.file_name
+          ));
+          source_map = Some(bytes);
+        }
+      }
+    }
+
+    Ok(ChunkOutput {
+      file_name: chunk.file_name.clone(),
+      code,
+      source_map,
+    })
+  }
+
+  /// Re-exports the exports of the root module from the bundle.
+  ///
+  /// The hoisted root module exports its own bindings, so only the names it
+  /// gets from `export * from` are re-exported, as the values they have once
+  /// it's evaluated. Other root modules are evaluated here.
+  fn render_entry_exports(&self, hoisted: bool) -> String {
+    let mut names = Vec::new();
+    self.collect_export_names(0, true, &mut HashSet::new(), &mut names);
+    let namespace = if hoisted {
+      let own_names = &self.transformed[0].export_names;
+      names.retain(|name| !own_names.contains(name));
+      "__exports"
+    } else {
+      "await __bundle.import(0, import.meta.main === true)"
+    };
+    if names.is_empty() {
+      return if hoisted {
+        String::new()
+      } else {
+        format!("{};\n", namespace)
+      };
+    }
+    format!(
+      "const {{ {} }} = {};\nexport {{ {} }};\n",
+      names
+        .iter()
+        .enumerate()
+        .map(|(index, name)| format!("{}: __s{}", quote(name), index))
+        .collect::<Vec<_>>()
+        .join(", "),
+      namespace,
+      names
+        .iter()
+        .enumerate()
+        .map(|(index, name)| {
+          format!("__s{} as {}", index, export_name_text(name))
+        })
+        .collect::<Vec<_>>()
+        .join(", "),
+    )
+  }
+
+  fn collect_export_names(
+    &self,
+    id: usize,
+    include_default: bool,
+    visited: &mut HashSet<usize>,
+    names: &mut Vec<String>,
+  ) {
+    if !visited.insert(id) {
+      return;
+    }
+    let transformed = &self.transformed[id];
+    for name in &transformed.export_names {
+      if (include_default || name != "default") && !names.contains(name) {
+        names.push(name.clone());
+      }
+    }
+    for id in &transformed.star_exports {
+      self.collect_export_names(*id, false, visited, names);
+    }
+  }
+}
@@ -0,0 +1,431 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+use deno_ast::swc::ast::CallExpr;
+use deno_ast::swc::ast::Callee;
+use deno_ast::swc::ast::Expr;
+use deno_ast::swc::ast::Lit;
+use deno_ast::swc::ast::ModuleDecl;
+use deno_ast::swc::ast::ModuleItem;
+use deno_ast::swc::visit::noop_visit_type;
+use deno_ast::swc::visit::Visit;
+use deno_ast::swc::visit::VisitWith;
+use deno_ast::MediaType;
+use deno_ast::ModuleSpecifier;
+use deno_ast::ParsedSource;
+use deno_core::anyhow::bail;
+use deno_core::anyhow::Context;
+use deno_core::error::AnyError;
+use deno_graph::Module;
+use deno_graph::ModuleGraph;
+use deno_graph::Resolution;
+use deno_runtime::deno_fs::FileSystem;
+use deno_runtime::deno_node::NodeResolution;
+use deno_runtime::deno_node::NodeResolutionMode;
+use deno_runtime::permissions::PermissionsContainer;
+use deno_terminal::colors;
+
+use crate::args::BundlePlatform;
+use crate::resolver::CliNodeResolver;
+
+use super::transform::quote;
+
+pub type ModuleId = usize;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ModuleKind {
+  Esm,
+  Cjs,
+  Json,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DependencyTarget {
+  Module(ModuleId),
+  /// A module that's imported at runtime instead of being bundled.
+  External(String),
+}
+
+#[derive(Clone, Debug)]
+pub struct ModuleDependency {
+  pub target: DependencyTarget,
+  /// Whether the dependency is only imported dynamically.
+  pub is_dynamic: bool,
+}
+
+#[derive(Debug)]
+pub struct BundleModule {
+  pub specifier: ModuleSpecifier,
+  pub kind: ModuleKind,
+  pub media_type: MediaType,
+  pub source: Arc<str>,
+  /// The dependencies of the module by the specifier they're imported with.
+  pub dependencies: BTreeMap<String, ModuleDependency>,
+}
+
+/// Collects the modules that get bundled, starting at the root of the
+/// graph. npm packages are resolved with node resolution and bundled for
+/// the browser, and are imported at runtime otherwise. Modules that can't be
+/// bundled for the browser are replaced by stubs that throw when they're
+/// imported.
+pub struct ModuleCollector<'a> {
+  graph: &'a ModuleGraph,
+  node_resolver: &'a CliNodeResolver,
+  fs: &'a dyn FileSystem,
+  platform: BundlePlatform,
+  modules: Vec<BundleModule>,
+  ids: HashMap<ModuleSpecifier, ModuleId>,
+  pending: VecDeque<ModuleId>,
+}
+
+impl<'a> ModuleCollector<'a> {
+  pub fn new(
+    graph: &'a ModuleGraph,
+    node_resolver: &'a CliNodeResolver,
+    fs: &'a dyn FileSystem,
+    platform: BundlePlatform,
+  ) -> Self {
+    Self {
+      graph,
+      node_resolver,
+      fs,
+      platform,
+      modules: Vec::new(),
+      ids: HashMap::new(),
+      pending: VecDeque::new(),
+    }
+  }
+
+  /// Collects the modules, with the root module having the id `0`.
+  pub fn collect(
+    mut self,
+    root: &ModuleSpecifier,
+  ) -> Result<Vec<BundleModule>, AnyError> {
+    match self.resolve_graph_module(root)? {
+      DependencyTarget::Module(_) => {}
+      DependencyTarget::External(specifier) => {
+        bail!("The root module '{}' can't be bundled.", specifier)
+      }
+    }
+    while let Some(id) = self.pending.pop_front() {
+      let dependencies = if self.modules[id].specifier.scheme() == "file"
+        && self
+          .node_resolver
+          .in_npm_package(&self.modules[id].specifier)
+      {
+        self.npm_module_dependencies(id)?
+      } else {
+        self.graph_module_dependencies(id)?
+      };
+      self.modules[id].dependencies = dependencies;
+    }
+    Ok(self.modules)
+  }
+
+  fn graph_module_dependencies(
+    &mut self,
+    id: ModuleId,
+  ) -> Result<BTreeMap<String, ModuleDependency>, AnyError> {
+    let graph = self.graph;
+    let Some(Module::Js(module)) = graph.get(&self.modules[id].specifier)
+    else {
+      return Ok(BTreeMap::new());
+    };
+    let mut dependencies = BTreeMap::new();
+    for (text, dependency) in &module.dependencies {
+      // type only dependencies aren't in the emitted code
+      let Resolution::Ok(resolved) = &dependency.maybe_code else {
+        continue;
+      };
+      let target = self.resolve_graph_module(&resolved.specifier)?;
+      dependencies.insert(
+        text.clone(),
+        ModuleDependency {
+          target,
+          is_dynamic: dependency.is_dynamic,
+        },
+      );
+    }
+    Ok(dependencies)
+  }
+
+  fn npm_module_dependencies(
+    &mut self,
+    id: ModuleId,
+  ) -> Result<BTreeMap<String, ModuleDependency>, AnyError> {
+    let module = &self.modules[id];
+    if module.kind == ModuleKind::Json {
+      return Ok(BTreeMap::new());
+    }
+    let specifier = module.specifier.clone();
+    let parsed_source = deno_ast::parse_program(deno_ast::ParseParams {
+      specifier: specifier.clone(),
+      text_info: deno_ast::SourceTextInfo::new(module.source.clone()),
+      media_type: module.media_type,
+      capture_tokens: false,
+      scope_analysis: false,
+      maybe_syntax: None,
+    })?;
+    let mut dependencies = BTreeMap::new();
+    for (text, is_dynamic) in analyze_dependencies(&parsed_source) {
+      let resolution = self
+        .node_resolver
+        .resolve(
+          &text,
+          &specifier,
+          NodeResolutionMode::Execution,
+          &PermissionsContainer::allow_all(),
+        )
+        .ok()
+        .flatten();
+      let target = match resolution {
+        Some(resolution) => self.resolve_node_resolution(resolution)?,
+        // packages often require modules that are only available in some
+        // environments, so this only fails once the module is required
+        None => {
+          log::warn!(
+            "{} Could not resolve '{}' from '{}'. It will throw when imported.",
+            colors::yellow("Warning"),
+            text,
+            specifier
+          );
+          self.add_stub(
+            ModuleSpecifier::parse(&format!("unresolved:{}", text))?,
+            format!("Could not resolve '{}'.", text),
+          )
+        }
+      };
+      let dependency = dependencies
+        .entry(text)
+        .or_insert(ModuleDependency { target, is_dynamic });
+      dependency.is_dynamic &= is_dynamic;
+    }
+    Ok(dependencies)
+  }
+
+  fn resolve_graph_module(
+    &mut self,
+    specifier: &ModuleSpecifier,
+  ) -> Result<DependencyTarget, AnyError> {
+    let graph = self.graph;
+    let specifier = graph.resolve(specifier);
+    match graph.get(specifier) {
+      Some(Module::Js(module)) => Ok(self.add_module(
+        module.specifier.clone(),
+        ModuleKind::Esm,
+        module.media_type,
+        module.source.clone(),
+      )),
+      Some(Module::Json(module)) => Ok(self.add_module(
+        module.specifier.clone(),
+        ModuleKind::Json,
+        MediaType::Json,
+        module.source.clone(),
+      )),
+      Some(Module::Npm(module)) => match self.platform {
+        BundlePlatform::Deno => {
+          let nv_reference = &module.nv_reference;
+          Ok(DependencyTarget::External(match nv_reference.sub_path() {
+            Some(sub_path) => format!("npm:{}/{}", nv_reference.nv(), sub_path),
+            None => format!("npm:{}", nv_reference.nv()),
+          }))
+        }
+        BundlePlatform::Browser => {
+          let package_folder = self
+            .node_resolver
+            .npm_resolver
+            .as_managed()
+            .unwrap() // byonm won't create a Module::Npm
+            .resolve_pkg_folder_from_deno_module(module.nv_reference.nv())?;
+          let resolution = self
+            .node_resolver
+            .resolve_package_sub_path_from_deno_module(
+              &package_folder,
+              module.nv_reference.sub_path(),
+              &module.specifier,
+              NodeResolutionMode::Execution,
+              &PermissionsContainer::allow_all(),
+            )
+            .with_context(|| {
+              format!("Could not resolve '{}'.", module.nv_reference)
+            })?
+            .with_context(|| {
+              format!("Could not resolve '{}'.", module.nv_reference)
+            })?;
+          self.resolve_node_resolution(resolution)
+        }
+      },
+      Some(Module::Node(module)) => {
+        self.resolve_node_built_in(&module.module_name)
+      }
+      Some(Module::External(module)) => {
+        let specifier =
+          crate::node::resolve_specifier_into_node_modules(&module.specifier);
+        if self.platform == BundlePlatform::Browser
+          && specifier.scheme() == "file"
+          && self.node_resolver.in_npm_package(&specifier)
+        {
+          let resolution =
+            self.node_resolver.url_to_node_resolution(specifier)?;
+          self.resolve_node_resolution(resolution)
+        } else {
+          Ok(DependencyTarget::External(module.specifier.to_string()))
+        }
+      }
+      None => bail!("Module not found in the module graph: {}", specifier),
+    }
+  }
+
+  fn resolve_node_resolution(
+    &mut self,
+    resolution: NodeResolution,
+  ) -> Result<DependencyTarget, AnyError> {
+    let (specifier, kind) = match resolution {
+      NodeResolution::Esm(specifier) => (specifier, ModuleKind::Esm),
+      NodeResolution::CommonJs(specifier) => (specifier, ModuleKind::Cjs),
+      NodeResolution::BuiltIn(module_name) => {
+        return self.resolve_node_built_in(&module_name)
+      }
+    };
+    if let Some(id) = self.ids.get(&specifier) {
+      return Ok(DependencyTarget::Module(*id));
+    }
+    let media_type = MediaType::from_specifier(&specifier);
+    let kind = if media_type == MediaType::Json {
+      ModuleKind::Json
+    } else {
+      kind
+    };
+    let source = self
+      .fs
+      .read_text_file_sync(&specifier.to_file_path().unwrap(), None)
+      .with_context(|| format!("Failed reading '{}'.", specifier))?;
+    Ok(self.add_module(specifier, kind, media_type, source.into()))
+  }
+
+  fn resolve_node_built_in(
+    &mut self,
+    module_name: &str,
+  ) -> Result<DependencyTarget, AnyError> {
+    let specifier = ModuleSpecifier::parse(&format!("node:{}", module_name))?;
+    match self.platform {
+      BundlePlatform::Deno => {
+        Ok(DependencyTarget::External(specifier.to_string()))
+      }
+      BundlePlatform::Browser => {
+        let message = format!(
+          "The Node built-in module '{}' is not available in the browser.",
+          specifier
+        );
+        if !self.ids.contains_key(&specifier) {
+          log::warn!(
+            "{} {} It will throw when imported.",
+            colors::yellow("Warning"),
+            message
+          );
+        }
+        Ok(self.add_stub(specifier, message))
+      }
+    }
+  }
+
+  /// Adds a module that throws the message when it's evaluated, in place of
+  /// a module that can't be bundled.
+  fn add_stub(
+    &mut self,
+    specifier: ModuleSpecifier,
+    message: String,
+  ) -> DependencyTarget {
+    let source = format!("throw new Error({});\n", quote(&message));
+    self.add_module(
+      specifier,
+      ModuleKind::Cjs,
+      MediaType::JavaScript,
+      source.into(),
+    )
+  }
+
+  fn add_module(
+    &mut self,
+    specifier: ModuleSpecifier,
+    kind: ModuleKind,
+    media_type: MediaType,
+    source: Arc<str>,
+  ) -> DependencyTarget {
+    if let Some(id) = self.ids.get(&specifier) {
+      return DependencyTarget::Module(*id);
+    }
+    let id = self.modules.len();
+    self.ids.insert(specifier.clone(), id);
+    self.modules.push(BundleModule {
+      specifier,
+      kind,
+      media_type,
+      source,
+      dependencies: BTreeMap::new(),
+    });
+    self.pending.push_back(id);
+    DependencyTarget::Module(id)
+  }
+}
+
+/// Gets the specifiers that a module in an npm package imports or requires,
+/// along with whether they're only imported dynamically.
+pub fn analyze_dependencies(
+  parsed_source: &ParsedSource,
+) -> Vec<(String, bool)> {
+  let mut collector = DependencyCollector {
+    dependencies: Vec::new(),
+  };
+  if let Some(module) = parsed_source.program_ref().as_module() {
+    for item in &module.body {
+      let ModuleItem::ModuleDecl(module_decl) = item else {
+        continue;
+      };
+      let src = match module_decl {
+        ModuleDecl::Import(import_decl) => Some(&import_decl.src),
+        ModuleDecl::ExportNamed(named_export) => named_export.src.as_ref(),
+        ModuleDecl::ExportAll(export_all) => Some(&export_all.src),
+        _ => None,
+      };
+      if let Some(src) = src {
+        collector.dependencies.push((src.value.to_string(), false));
+      }
+    }
+  }
+  parsed_source.program_ref().visit_with(&mut collector);
+  collector.dependencies
+}
+
+struct DependencyCollector {
+  dependencies: Vec<(String, bool)>,
+}
+
+impl Visit for DependencyCollector {
+  noop_visit_type!();
+
+  fn visit_call_expr(&mut self, call_expr: &CallExpr) {
+    let is_dynamic = match &call_expr.callee {
+      Callee::Import(_) => Some(true),
+      Callee::Expr(expr) => match &**expr {
+        Expr::Ident(ident) if &*ident.sym == "require" => Some(false),
+        _ => None,
+      },
+      Callee::Super(_) => None,
+    };
+    if let Some(is_dynamic) = is_dynamic {
+      if let Some(arg) = call_expr.args.first() {
+        if let Expr::Lit(Lit::Str(str_lit)) = &*arg.expr {
+          self
+            .dependencies
+            .push((str_lit.value.to_string(), is_dynamic));
+        }
+      }
+    }
+    call_expr.visit_children_with(self);
+  }
+}
@@ -0,0 +1,199 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+// The module loader of the bundles created by `deno bundle`. It's created
+// once per bundle in the entry chunk and shared with the other chunks.
+(options) => {
+  const modules = new Map();
+  const externals = new Map();
+  const process = globalThis.process ??
+    { browser: true, env: { NODE_ENV: options.nodeEnv } };
+
+  function define(id, url, kind, deps, factory) {
+    if (!modules.has(id)) {
+      modules.set(id, {
+        id,
+        url,
+        kind,
+        deps,
+        factory,
+        namespace: undefined,
+        promise: undefined,
+        evaluated: false,
+        cjs: undefined,
+      });
+    }
+  }
+
+  function external(specifier, namespace) {
+    externals.set(specifier, namespace);
+  }
+
+  function exportGetters(namespace, getters) {
+    for (const [name, get] of Object.entries(getters)) {
+      Object.defineProperty(namespace, name, { enumerable: true, get });
+    }
+  }
+
+  function exportStar(namespace, from) {
+    for (const name of Object.keys(from)) {
+      if (name !== "default" && !Object.hasOwn(namespace, name)) {
+        Object.defineProperty(namespace, name, {
+          enumerable: true,
+          get: () => from[name],
+        });
+      }
+    }
+  }
+
+  function getModule(id) {
+    const module = modules.get(id);
+    if (module === undefined) {
+      throw new Error(`Module ${id} is not in the bundle.`);
+    }
+    return module;
+  }
+
+  function getExternal(specifier) {
+    const namespace = externals.get(specifier);
+    if (namespace === undefined) {
+      throw new Error(`Cannot find module '${specifier}'.`);
+    }
+    return namespace;
+  }
+
+  function createNamespace() {
+    return Object.create(null, {
+      [Symbol.toStringTag]: { value: "Module" },
+    });
+  }
+
+  function require(id) {
+    if (typeof id === "string") {
+      const namespace = getExternal(id);
+      return namespace.default ?? namespace;
+    }
+    const module = getModule(id);
+    switch (module.kind) {
+      case "cjs":
+        if (module.cjs === undefined) {
+          module.cjs = { exports: {} };
+          module.factory.call(
+            module.cjs.exports,
+            module.cjs,
+            module.cjs.exports,
+            require,
+            process,
+            globalThis,
+          );
+        }
+        return module.cjs.exports;
+      case "json":
+        return module.factory;
+      default:
+        if (!module.evaluated) {
+          throw new Error(
+            `Cannot require the ES module '${module.url}' before it's evaluated.`,
+          );
+        }
+        return module.namespace;
+    }
+  }
+
+  function importModule(id, ancestors, main) {
+    if (typeof id === "string") {
+      return getExternal(id);
+    }
+    const module = getModule(id);
+    if (module.kind !== "esm") {
+      if (module.namespace === undefined) {
+        const exports = require(id);
+        const namespace = createNamespace();
+        if (module.kind === "cjs" && exports !== null) {
+          for (const name of Object.keys(exports)) {
+            if (name !== "default") {
+              Object.defineProperty(namespace, name, {
+                enumerable: true,
+                get: () => exports[name],
+              });
+            }
+          }
+        }
+        namespace.default = exports?.__esModule ? exports.default : exports;
+        module.namespace = namespace;
+      }
+      return module.namespace;
+    }
+    // a cycle, so the namespace is used before the module is evaluated
+    if (ancestors.has(id)) {
+      return module.namespace;
+    }
+    module.promise ??= evaluate(module, ancestors, main);
+    return module.promise;
+  }
+
+  function createMeta(module, main) {
+    return {
+      url: module.url,
+      main,
+      resolve: (specifier) => new URL(specifier, module.url).href,
+    };
+  }
+
+  function importDeps(module, ancestors) {
+    const chain = new Set(ancestors).add(module.id);
+    return async () => {
+      const namespaces = [];
+      for (const dep of module.deps) {
+        namespaces.push(await importModule(dep, chain, false));
+      }
+      return namespaces;
+    };
+  }
+
+  async function evaluate(module, ancestors, main) {
+    const namespace = module.namespace = createNamespace();
+    await module.factory(
+      namespace,
+      createMeta(module, main),
+      importDeps(module, ancestors),
+    );
+    module.evaluated = true;
+    return namespace;
+  }
+
+  // The root module is evaluated in the top scope of the entry chunk, so
+  // that the bundle can export its bindings.
+  function enter(id, url, deps, main) {
+    define(id, url, "esm", deps, undefined);
+    const module = getModule(id);
+    const namespace = module.namespace = createNamespace();
+    let evaluated;
+    module.promise = new Promise((resolve) => {
+      evaluated = () => {
+        module.evaluated = true;
+        resolve(namespace);
+      };
+    });
+    return [
+      namespace,
+      createMeta(module, main),
+      importDeps(module, new Set()),
+      evaluated,
+    ];
+  }
+
+  async function load(id, chunks) {
+    await Promise.all(chunks.map((chunk) => import(chunk)));
+    return await importModule(id, new Set(), false);
+  }
+
+  return {
+    define,
+    enter,
+    external,
+    export: exportGetters,
+    exportStar,
+    import: (id, main) => importModule(id, new Set(), main),
+    load,
+  };
+}
@@ -0,0 +1,252 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+use deno_ast::MediaType;
+use deno_core::error::AnyError;
+use deno_core::sourcemap::SourceMap;
+use deno_core::sourcemap::SourceMapBuilder;
+
+/// Maps a position in generated code back to a position in the original
+/// source of a module.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Mapping {
+  /// The byte offset in the generated code.
+  pub offset: usize,
+  pub src_line: u32,
+  pub src_col: u32,
+}
+
+/// Converts between byte offsets and line and column positions, with the
+/// columns counted in UTF-16 code units as in source maps.
+pub struct LineIndex<'a> {
+  text: &'a str,
+  line_starts: Vec<usize>,
+}
+
+impl<'a> LineIndex<'a> {
+  pub fn new(text: &'a str) -> Self {
+    let mut line_starts = vec![0];
+    line_starts.extend(
+      text
+        .bytes()
+        .enumerate()
+        .filter(|(_, b)| *b == b'\n')
+        .map(|(i, _)| i + 1),
+    );
+    Self { text, line_starts }
+  }
+
+  pub fn line_count(&self) -> usize {
+    self.line_starts.len()
+  }
+
+  pub fn offset(&self, line: u32, col: u32) -> Option<usize> {
+    let line_start = *self.line_starts.get(line as usize)?;
+    let line_end = self
+      .line_starts
+      .get(line as usize + 1)
+      .map(|next_line_start| next_line_start - 1)
+      .unwrap_or(self.text.len());
+    let mut utf16_col = 0;
+    for (i, c) in self.text[line_start..line_end].char_indices() {
+      if utf16_col >= col {
+        return Some(line_start + i);
+      }
+      utf16_col += c.len_utf16() as u32;
+    }
+    (utf16_col >= col).then_some(line_end)
+  }
+
+  pub fn position(&self, offset: usize) -> (u32, u32) {
+    let line = match self.line_starts.binary_search(&offset) {
+      Ok(line) => line,
+      Err(line) => line - 1,
+    };
+    let col = self.text[self.line_starts[line]..offset]
+      .chars()
+      .map(|c| c.len_utf16() as u32)
+      .sum();
+    (line as u32, col)
+  }
+}
+
+/// Gets the mappings of emitted code from the source map of the emit.
+pub fn mappings_from_source_map(
+  code: &str,
+  source_map: &[u8],
+) -> Result<Vec<Mapping>, AnyError> {
+  let source_map = SourceMap::from_slice(source_map)?;
+  let line_index = LineIndex::new(code);
+  Ok(
+    source_map
+      .tokens()
+      .filter(|token| token.get_source().is_some())
+      .filter_map(|token| {
+        Some(Mapping {
+          offset: line_index
+            .offset(token.get_dst_line(), token.get_dst_col())?,
+          src_line: token.get_src_line(),
+          src_col: token.get_src_col(),
+        })
+      })
+      .collect(),
+  )
+}
+
+/// Gets the mappings of code that's bundled as is, which map each token to
+/// itself.
+pub fn identity_mappings(code: &str) -> Vec<Mapping> {
+  let line_index = LineIndex::new(code);
+  deno_ast::lex(code, MediaType::JavaScript)
+    .into_iter()
+    .filter(|item| matches!(item.inner, deno_ast::TokenOrComment::Token(_)))
+    .map(|item| {
+      let (src_line, src_col) = line_index.position(item.range.start);
+      Mapping {
+        offset: item.range.start,
+        src_line,
+        src_col,
+      }
+    })
+    .collect()
+}
+
+/// Adjusts the mappings for text changes that were applied to the code.
+/// Mappings in replaced text are dropped, except at the start of text that
+/// isn't removed.
+pub fn apply_text_changes_to_mappings(
+  mappings: Vec<Mapping>,
+  text_changes: &[deno_ast::TextChange],
+) -> Vec<Mapping> {
+  let mut text_changes = text_changes.iter().collect::<Vec<_>>();
+  text_changes.sort_by_key(|change| change.range.start);
+  let mut mappings = mappings;
+  mappings.sort_by_key(|mapping| mapping.offset);
+  let mut result = Vec::with_capacity(mappings.len());
+  let mut change_index = 0;
+  let mut delta = 0isize;
+  for mut mapping in mappings {
+    while let Some(change) = text_changes.get(change_index) {
+      if change.range.end <= mapping.offset {
+        delta += change.new_text.len() as isize
+          - (change.range.end - change.range.start) as isize;
+        change_index += 1;
+      } else {
+        break;
+      }
+    }
+    let is_replaced = text_changes.get(change_index).is_some_and(|change| {
+      change.range.start < mapping.offset
+        || change.range.start == mapping.offset && change.new_text.is_empty()
+    });
+    if !is_replaced {
+      mapping.offset = (mapping.offset as isize + delta) as usize;
+      result.push(mapping);
+    }
+  }
+  result
+}
+
+/// Builds the source map of a chunk from the mappings of its modules.
+pub struct ChunkSourceMapBuilder {
+  builder: SourceMapBuilder,
+}
+
+impl ChunkSourceMapBuilder {
+  pub fn new(file_name: &str) -> Self {
+    Self {
+      builder: SourceMapBuilder::new(Some(file_name)),
+    }
+  }
+
+  /// Adds the mappings of a module's code that starts at the beginning of
+  /// the given line of the chunk.
+  pub fn add_module(
+    &mut self,
+    specifier: &str,
+    source: &str,
+    code: &str,
+    mappings: &[Mapping],
+    start_line: u32,
+  ) {
+    let src_id = self.builder.add_source(specifier);
+    self.builder.set_source_contents(src_id, Some(source));
+    let line_index = LineIndex::new(code);
+    for mapping in mappings {
+      let (dst_line, dst_col) = line_index.position(mapping.offset);
+      self.builder.add_raw(
+        start_line + dst_line,
+        dst_col,
+        mapping.src_line,
+        mapping.src_col,
+        Some(src_id),
+        None,
+      );
+    }
+  }
+
+  pub fn into_bytes(self) -> Result<Vec<u8>, AnyError> {
+    let mut bytes = Vec::new();
+    self.builder.into_sourcemap().to_writer(&mut bytes)?;
+    Ok(bytes)
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn test_line_index() {
+    let line_index = LineIndex::new("a\nb🦕c\n");
+    assert_eq!(line_index.line_count(), 3);
+    assert_eq!(line_index.position(0), (0, 0));
+    assert_eq!(line_index.position(2), (1, 0));
+    assert_eq!(line_index.position(7), (1, 3));
+    assert_eq!(line_index.offset(1, 3), Some(7));
+    assert_eq!(line_index.offset(1, 4), Some(8));
+    assert_eq!(line_index.offset(1, 5), None);
+    assert_eq!(line_index.offset(3, 0), None);
+  }
+
+  #[test]
+  fn test_apply_text_changes_to_mappings() {
+    let mapping = |offset| Mapping {
+      offset,
+      src_line: 0,
+      src_col: offset as u32,
+    };
+    let mappings = apply_text_changes_to_mappings(
+      vec![mapping(0), mapping(4), mapping(6), mapping(10)],
+      &[
+        deno_ast::TextChange {
+          range: 0..0,
+          new_text: "ab".to_string(),
+        },
+        deno_ast::TextChange {
+          range: 4..8,
+          new_text: "c".to_string(),
+        },
+      ],
+    );
+    assert_eq!(
+      mappings,
+      vec![
+        Mapping {
+          offset: 2,
+          src_line: 0,
+          src_col: 0,
+        },
+        Mapping {
+          offset: 6,
+          src_line: 0,
+          src_col: 4,
+        },
+        Mapping {
+          offset: 9,
+          src_line: 0,
+          src_col: 10,
+        },
+      ]
+    );
+  }
+}
@@ -0,0 +1,911 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+use std::collections::HashMap;
+
+use deno_ast::swc::ast::CallExpr;
+use deno_ast::swc::ast::Callee;
+use deno_ast::swc::ast::Decl;
+use deno_ast::swc::ast::DefaultDecl;
+use deno_ast::swc::ast::ExportSpecifier;
+use deno_ast::swc::ast::Expr;
+use deno_ast::swc::ast::Id;
+use deno_ast::swc::ast::ImportSpecifier;
+use deno_ast::swc::ast::Lit;
+use deno_ast::swc::ast::MetaPropKind;
+use deno_ast::swc::ast::ModuleDecl;
+use deno_ast::swc::ast::ModuleExportName;
+use deno_ast::swc::ast::ModuleItem;
+use deno_ast::swc::ast::ObjectPatProp;
+use deno_ast::swc::ast::Pat;
+use deno_ast::swc::ast::Prop;
+use deno_ast::swc::ast::TaggedTpl;
+use deno_ast::swc::visit::noop_visit_type;
+use deno_ast::swc::visit::Visit;
+use deno_ast::swc::visit::VisitWith;
+use deno_ast::EmitOptions;
+use deno_ast::MediaType;
+use deno_ast::ParsedSource;
+use deno_ast::SourceMapOption;
+use deno_ast::SourceRange;
+use deno_ast::SourceRangedForSpanned;
+use deno_ast::SourceTextInfo;
+use deno_ast::TextChange;
+use deno_ast::TranspileOptions;
+use deno_core::anyhow::Context;
+use deno_core::error::AnyError;
+use deno_core::serde_json;
+
+use super::chunks::Chunks;
+use super::minify::minify;
+use super::modules::BundleModule;
+use super::modules::DependencyTarget;
+use super::modules::ModuleId;
+use super::modules::ModuleKind;
+use super::source_map::apply_text_changes_to_mappings;
+use super::source_map::identity_mappings;
+use super::source_map::mappings_from_source_map;
+use super::source_map::Mapping;
+
+/// A module wrapped in a `__bundle.define(...)` statement.
+pub struct TransformedModule {
+  pub code: String,
+  /// The mappings of the code back to the source of the module.
+  pub mappings: Vec<Mapping>,
+  /// The names the module exports, not including star exports.
+  pub export_names: Vec<String>,
+  /// The bundled modules whose exports the module re-exports with
+  /// `export * from`.
+  pub star_exports: Vec<ModuleId>,
+  pub shebang: Option<String>,
+}
+
+pub struct ModuleTransformer<'a> {
+  pub transpile_options: &'a TranspileOptions,
+  pub emit_options: &'a EmitOptions,
+  pub source_maps: bool,
+  pub minify: bool,
+}
+
+impl<'a> ModuleTransformer<'a> {
+  pub fn transform(
+    &self,
+    id: ModuleId,
+    module: &BundleModule,
+    chunks: &Chunks,
+  ) -> Result<TransformedModule, AnyError> {
+    let mut transformed = match module.kind {
+      ModuleKind::Json => self.transform_json(id, module)?,
+      ModuleKind::Esm | ModuleKind::Cjs => {
+        let (code, mappings) = self.emit(module)?;
+        let parsed_source = parse(module, code)?;
+        let transformed = match module.kind {
+          ModuleKind::Esm => transform_esm(id, module, chunks, &parsed_source)?,
+          _ => transform_cjs(id, module, chunks, &parsed_source)?,
+        };
+        let mappings = if self.source_maps {
+          apply_text_changes_to_mappings(mappings, &transformed.changes)
+            .into_iter()
+            .map(|mapping| Mapping {
+              offset: mapping.offset + transformed.header.len(),
+              ..mapping
+            })
+            .collect()
+        } else {
+          Vec::new()
+        };
+        let code = deno_ast::apply_text_changes(
+          parsed_source.text_info().text_str(),
+          transformed.changes,
+        );
+        TransformedModule {
+          code: format!(
+            "{}{}\n{}",
+            transformed.header, code, transformed.footer
+          ),
+          mappings,
+          export_names: transformed.export_names,
+          star_exports: transformed.star_exports,
+          shebang: transformed.shebang,
+        }
+      }
+    };
+    if self.minify {
+      let (code, mappings) = minify(&transformed.code, &transformed.mappings);
+      transformed.code = code;
+      transformed.code.push('\n');
+      transformed.mappings = mappings;
+    }
+    Ok(transformed)
+  }
+
+  /// Emits the module as JavaScript along with the mappings of the emitted
+  /// code.
+  fn emit(
+    &self,
+    module: &BundleModule,
+  ) -> Result<(String, Vec<Mapping>), AnyError> {
+    if matches!(
+      module.media_type,
+      MediaType::JavaScript
+        | MediaType::Mjs
+        | MediaType::Cjs
+        | MediaType::Unknown
+    ) {
+      let code = module.source.to_string();
+      let mappings = if self.source_maps {
+        identity_mappings(&code)
+      } else {
+        Vec::new()
+      };
+      return Ok((code, mappings));
+    }
+
+    let parsed_source = deno_ast::parse_module(deno_ast::ParseParams {
+      specifier: module.specifier.clone(),
+      text_info: SourceTextInfo::new(module.source.clone()),
+      media_type: module.media_type,
+      capture_tokens: false,
+      scope_analysis: false,
+      maybe_syntax: None,
+    })?;
+    let mut emit_options = self.emit_options.clone();
+    emit_options.source_map = if self.source_maps {
+      SourceMapOption::Separate
+    } else {
+      SourceMapOption::None
+    };
+    let emitted_source = parsed_source
+      .transpile(self.transpile_options, &emit_options)?
+      .into_source();
+    let mappings = match &emitted_source.source_map {
+      Some(source_map) => {
+        mappings_from_source_map(&emitted_source.text, source_map.as_bytes())?
+      }
+      None => Vec::new(),
+    };
+    Ok((emitted_source.text, mappings))
+  }
+
+  fn transform_json(
+    &self,
+    id: ModuleId,
+    module: &BundleModule,
+  ) -> Result<TransformedModule, AnyError> {
+    let value: serde_json::Value = serde_json::from_str(&module.source)
+      .with_context(|| format!("Failed parsing '{}'.", module.specifier))?;
+    let json = if self.minify {
+      serde_json::to_string(&value)?
+    } else {
+      serde_json::to_string_pretty(&value)?
+    };
+    Ok(TransformedModule {
+      code: format!(
+        "__bundle.define({}, {}, \"json\", [], {});\n",
+        id,
+        quote(module.specifier.as_str()),
+        json
+      ),
+      mappings: Vec::new(),
+      export_names: vec!["default".to_string()],
+      star_exports: Vec::new(),
+      shebang: None,
+    })
+  }
+}
+
+fn parse(
+  module: &BundleModule,
+  code: String,
+) -> Result<ParsedSource, AnyError> {
+  let params = deno_ast::ParseParams {
+    specifier: module.specifier.clone(),
+    text_info: SourceTextInfo::from_string(code),
+    media_type: MediaType::JavaScript,
+    capture_tokens: false,
+    scope_analysis: module.kind == ModuleKind::Esm,
+    maybe_syntax: None,
+  };
+  let parsed_source = match module.kind {
+    ModuleKind::Esm => deno_ast::parse_module(params)?,
+    _ => deno_ast::parse_script(params)?,
+  };
+  Ok(parsed_source)
+}
+
+struct ModuleTransform {
+  header: String,
+  footer: String,
+  changes: Vec<TextChange>,
+  export_names: Vec<String>,
+  star_exports: Vec<ModuleId>,
+  shebang: Option<String>,
+}
+
+/// Whether the module is the root module, which is evaluated in the top scope
+/// of the entry chunk instead of being wrapped in a `__bundle.define(...)`
+/// statement.
+pub fn is_hoisted(id: ModuleId, module: &BundleModule) -> bool {
+  id == 0 && module.kind == ModuleKind::Esm
+}
+
+/// Turns the imports of an ES module into reads of the namespaces of its
+/// dependencies, and its exports into getters on its own namespace, so that
+/// the bindings stay live.
+///
+/// The hoisted root module also exports its bindings from the entry chunk.
+/// Re-exports of other bundled modules are exported as the values they have
+/// once the dependencies are evaluated.
+fn transform_esm(
+  id: ModuleId,
+  module: &BundleModule,
+  chunks: &Chunks,
+  parsed_source: &ParsedSource,
+) -> Result<ModuleTransform, AnyError> {
+  let text_info = parsed_source.text_info();
+  let text = text_info.text_str();
+  let file_start = text_info.range().start;
+  let byte_range = |range: SourceRange| range.as_byte_range(file_start);
+  let mut changes = Vec::new();
+  let remove = |changes: &mut Vec<TextChange>, range: SourceRange| {
+    changes.push(TextChange {
+      range: byte_range(range),
+      new_text: String::new(),
+    })
+  };
+
+  let shebang = take_shebang(text, &mut changes);
+
+  // register the dependencies and import bindings first, since imports are
+  // hoisted
+  let mut deps = Vec::<String>::new();
+  let mut dep_index =
+    |specifier: &str| match deps.iter().position(|dep| dep == specifier) {
+      Some(index) => index,
+      None => {
+        deps.push(specifier.to_string());
+        deps.len() - 1
+      }
+    };
+  let mut bindings = HashMap::<Id, String>::new();
+  let body = &parsed_source.module().body;
+  for item in body {
+    let ModuleItem::ModuleDecl(module_decl) = item else {
+      continue;
+    };
+    match module_decl {
+      ModuleDecl::Import(import_decl) => {
+        let index = dep_index(&import_decl.src.value);
+        for specifier in &import_decl.specifiers {
+          let (local, expr) = match specifier {
+            ImportSpecifier::Named(named) => {
+              let name = named
+                .imported
+                .as_ref()
+                .map(module_export_name)
+                .unwrap_or_else(|| named.local.sym.to_string());
+              (&named.local, dep_member(index, &name))
+            }
+            ImportSpecifier::Default(default) => {
+              (&default.local, dep_member(index, "default"))
+            }
+            ImportSpecifier::Namespace(namespace) => {
+              (&namespace.local, format!("__d{}", index))
+            }
+          };
+          bindings.insert(local.to_id(), expr);
+        }
+        remove(&mut changes, import_decl.range());
+      }
+      ModuleDecl::ExportNamed(named_export) => {
+        if let Some(src) = &named_export.src {
+          dep_index(&src.value);
+        }
+      }
+      ModuleDecl::ExportAll(export_all) => {
+        dep_index(&export_all.src.value);
+      }
+      _ => {}
+    }
+  }
+
+  let mut exports = Vec::<(String, String)>::new();
+  let mut star_exports = Vec::new();
+  for item in body {
+    let ModuleItem::ModuleDecl(module_decl) = item else {
+      continue;
+    };
+    match module_decl {
+      ModuleDecl::ExportDecl(export_decl) => {
+        let decl_range = export_decl.decl.range();
+        changes.push(TextChange {
+          range: byte_range(export_decl.range()).start
+            ..byte_range(decl_range).start,
+          new_text: String::new(),
+        });
+        let mut names = Vec::new();
+        decl_names(&export_decl.decl, &mut names);
+        exports.extend(names.into_iter().map(|name| (name.clone(), name)));
+      }
+      ModuleDecl::ExportNamed(named_export) => {
+        let index = named_export.src.as_ref().map(|src| dep_index(&src.value));
+        for specifier in &named_export.specifiers {
+          match specifier {
+            ExportSpecifier::Named(named) => {
+              let exported = module_export_name(
+                named.exported.as_ref().unwrap_or(&named.orig),
+              );
+              let expr = match (index, &named.orig) {
+                (Some(index), orig) => {
+                  dep_member(index, &module_export_name(orig))
+                }
+                (None, ModuleExportName::Ident(ident)) => bindings
+                  .get(&ident.to_id())
+                  .cloned()
+                  .unwrap_or_else(|| ident.sym.to_string()),
+                (None, ModuleExportName::Str(str)) => str.value.to_string(),
+              };
+              exports.push((exported, expr));
+            }
+            ExportSpecifier::Namespace(namespace) => {
+              if let Some(index) = index {
+                exports.push((
+                  module_export_name(&namespace.name),
+                  format!("__d{}", index),
+                ));
+              }
+            }
+            ExportSpecifier::Default(default) => {
+              if let Some(index) = index {
+                exports.push((
+                  default.exported.sym.to_string(),
+                  dep_member(index, "default"),
+                ));
+              }
+            }
+          }
+        }
+        remove(&mut changes, named_export.range());
+      }
+      ModuleDecl::ExportDefaultDecl(export_default_decl) => {
+        let ident = match &export_default_decl.decl {
+          DefaultDecl::Class(class_expr) => class_expr.ident.as_ref(),
+          DefaultDecl::Fn(fn_expr) => fn_expr.ident.as_ref(),
+          DefaultDecl::TsInterfaceDecl(_) => continue,
+        };
+        let range = byte_range(export_default_decl.range());
+        let decl_start = byte_range(export_default_decl.decl.range()).start;
+        match ident {
+          Some(ident) => {
+            changes.push(TextChange {
+              range: range.start..decl_start,
+              new_text: String::new(),
+            });
+            exports.push(("default".to_string(), ident.sym.to_string()));
+          }
+          None => {
+            changes.push(TextChange {
+              range: range.start..decl_start,
+              new_text: "const __default = ".to_string(),
+            });
+            changes.push(TextChange {
+              range: range.end..range.end,
+              new_text: ";".to_string(),
+            });
+            exports.push(("default".to_string(), "__default".to_string()));
+          }
+        }
+      }
+      ModuleDecl::ExportDefaultExpr(export_default_expr) => {
+        let range = byte_range(export_default_expr.range());
+        let expr_range = byte_range(export_default_expr.expr.range());
+        changes.push(TextChange {
+          range: range.start..expr_range.start,
+          new_text: "const __default = ".to_string(),
+        });
+        // the statement has no semicolon when it's inserted automatically
+        if range.end == expr_range.end {
+          changes.push(TextChange {
+            range: range.end..range.end,
+            new_text: ";".to_string(),
+          });
+        }
+        exports.push(("default".to_string(), "__default".to_string()));
+      }
+      ModuleDecl::ExportAll(export_all) => {
+        star_exports.push(dep_index(&export_all.src.value));
+        remove(&mut changes, export_all.range());
+      }
+      _ => {}
+    }
+  }
+
+  let mut rewriter = DependencyRewriter {
+    module,
+    chunks,
+    bindings: &bindings,
+    file_start,
+    changes: &mut changes,
+    is_cjs: false,
+  };
+  parsed_source.module().visit_with(&mut rewriter);
+
+  let dep_targets = deps
+    .iter()
+    .map(|specifier| dependency_target(module, specifier))
+    .collect::<Result<Vec<_>, _>>()?;
+  let dep_targets_text = dep_targets
+    .iter()
+    .map(|target| match target {
+      DependencyTarget::Module(id) => id.to_string(),
+      DependencyTarget::External(specifier) => quote(specifier),
+    })
+    .collect::<Vec<_>>()
+    .join(", ");
+  let hoisted = is_hoisted(id, module);
+  let mut header = if hoisted {
+    format!(
+      "const [__exports, __meta, __deps, __evaluated] = __bundle.enter({}, {}, [{}], import.meta.main === true);\n",
+      id,
+      quote(module.specifier.as_str()),
+      dep_targets_text
+    )
+  } else {
+    format!(
+      "__bundle.define({}, {}, \"esm\", [{}], async function (__exports, __meta, __deps) {{\n",
+      id,
+      quote(module.specifier.as_str()),
+      dep_targets_text
+    )
+  };
+  if !exports.is_empty() {
+    header.push_str(&format!(
+      "__bundle.export(__exports, {{ {} }});\n",
+      exports
+        .iter()
+        .map(|(name, expr)| format!("{}: () => {}", quote(name), expr))
+        .collect::<Vec<_>>()
+        .join(", ")
+    ));
+  }
+  if !deps.is_empty() {
+    header.push_str(&format!(
+      "const [{}] = await __deps();\n",
+      (0..deps.len())
+        .map(|index| format!("__d{}", index))
+        .collect::<Vec<_>>()
+        .join(", ")
+    ));
+  }
+  for index in &star_exports {
+    header
+      .push_str(&format!("__bundle.exportStar(__exports, __d{});\n", index));
+  }
+
+  let footer = if hoisted {
+    let mut specifiers = Vec::with_capacity(exports.len());
+    for (name, expr) in &exports {
+      let local = if is_identifier(expr) {
+        expr.clone()
+      } else {
+        let local = format!("__x{}", specifiers.len());
+        header.push_str(&format!("const {} = {};\n", local, expr));
+        local
+      };
+      if local == *name {
+        specifiers.push(local);
+      } else {
+        specifiers.push(format!("{} as {}", local, export_name_text(name)));
+      }
+    }
+    let mut footer = String::new();
+    if !specifiers.is_empty() {
+      footer.push_str(&format!("export {{ {} }};\n", specifiers.join(", ")));
+    }
+    footer.push_str("__evaluated();\n");
+    footer
+  } else {
+    "});\n".to_string()
+  };
+
+  Ok(ModuleTransform {
+    header,
+    footer,
+    changes,
+    export_names: exports.into_iter().map(|(name, _)| name).collect(),
+    star_exports: star_exports
+      .into_iter()
+      .filter_map(|index| match dep_targets[index] {
+        DependencyTarget::Module(id) => Some(id),
+        DependencyTarget::External(_) => None,
+      })
+      .collect(),
+    shebang,
+  })
+}
+
+/// Wraps a CommonJS module in a function that gets its `module`, `exports`
+/// and `require`, with the required specifiers replaced by module ids.
+fn transform_cjs(
+  id: ModuleId,
+  module: &BundleModule,
+  chunks: &Chunks,
+  parsed_source: &ParsedSource,
+) -> Result<ModuleTransform, AnyError> {
+  let text_info = parsed_source.text_info();
+  let mut changes = Vec::new();
+  let shebang = take_shebang(text_info.text_str(), &mut changes);
+  let mut rewriter = DependencyRewriter {
+    module,
+    chunks,
+    bindings: &HashMap::new(),
+    file_start: text_info.range().start,
+    changes: &mut changes,
+    is_cjs: true,
+  };
+  parsed_source.program_ref().visit_with(&mut rewriter);
+  Ok(ModuleTransform {
+    header: format!(
+      "__bundle.define({}, {}, \"cjs\", [], function (module, exports, require, process, global) {{\n",
+      id,
+      quote(module.specifier.as_str()),
+    ),
+    footer: "});\n".to_string(),
+    changes,
+    export_names: vec!["default".to_string()],
+    star_exports: Vec::new(),
+    shebang,
+  })
+}
+
+fn take_shebang(text: &str, changes: &mut Vec<TextChange>) -> Option<String> {
+  if !text.starts_with("#!") {
+    return None;
+  }
+  let end = text.find('\n').unwrap_or(text.len());
+  changes.push(TextChange {
+    range: 0..end,
+    new_text: String::new(),
+  });
+  Some(text[..end].trim_end().to_string())
+}
+
+fn dependency_target(
+  module: &BundleModule,
+  specifier: &str,
+) -> Result<DependencyTarget, AnyError> {
+  module
+    .dependencies
+    .get(specifier)
+    .map(|dependency| dependency.target.clone())
+    .with_context(|| {
+      format!(
+        "Could not find the dependency '{}' of '{}'.",
+        specifier, module.specifier
+      )
+    })
+}
+
+/// Rewrites the uses of import bindings, `import.meta`, dynamic imports and
+/// requires.
+struct DependencyRewriter<'a> {
+  module: &'a BundleModule,
+  chunks: &'a Chunks,
+  bindings: &'a HashMap<Id, String>,
+  file_start: deno_ast::SourcePos,
+  changes: &'a mut Vec<TextChange>,
+  is_cjs: bool,
+}
+
+impl<'a> DependencyRewriter<'a> {
+  fn replace(&mut self, range: SourceRange, new_text: String) {
+    self.changes.push(TextChange {
+      range: range.as_byte_range(self.file_start),
+      new_text,
+    });
+  }
+
+  /// Rewrites a call to an import binding so that it's called without a
+  /// `this` as with ES modules.
+  fn visit_callee_expr(&mut self, expr: &Expr) {
+    if let Expr::Ident(ident) = expr {
+      if let Some(binding) = self.bindings.get(&ident.to_id()) {
+        let new_text = format!("(0, {})", binding);
+        self.replace(ident.range(), new_text);
+        return;
+      }
+    }
+    expr.visit_with(self);
+  }
+
+  fn string_arg<'b>(&self, call_expr: &'b CallExpr) -> Option<&'b str> {
+    match call_expr.args.first().map(|arg| &*arg.expr) {
+      Some(Expr::Lit(Lit::Str(str_lit))) if call_expr.args.len() == 1 => {
+        Some(&str_lit.value)
+      }
+      _ => None,
+    }
+  }
+}
+
+impl<'a> Visit for DependencyRewriter<'a> {
+  noop_visit_type!();
+
+  fn visit_module_decl(&mut self, module_decl: &ModuleDecl) {
+    match module_decl {
+      // already removed
+      ModuleDecl::Import(_)
+      | ModuleDecl::ExportNamed(_)
+      | ModuleDecl::ExportAll(_) => {}
+      _ => module_decl.visit_children_with(self),
+    }
+  }
+
+  fn visit_expr(&mut self, expr: &Expr) {
+    match expr {
+      Expr::Ident(ident) => {
+        if let Some(binding) = self.bindings.get(&ident.to_id()) {
+          let binding = binding.clone();
+          self.replace(ident.range(), binding);
+        }
+      }
+      Expr::MetaProp(meta_prop)
+        if meta_prop.kind == MetaPropKind::ImportMeta =>
+      {
+        self.replace(meta_prop.range(), "__meta".to_string());
+      }
+      _ => expr.visit_children_with(self),
+    }
+  }
+
+  fn visit_prop(&mut self, prop: &Prop) {
+    if let Prop::Shorthand(ident) = prop {
+      if let Some(binding) = self.bindings.get(&ident.to_id()) {
+        let new_text = format!("{}: {}", ident.sym, binding);
+        self.replace(ident.range(), new_text);
+      }
+      return;
+    }
+    prop.visit_children_with(self);
+  }
+
+  fn visit_tagged_tpl(&mut self, tagged_tpl: &TaggedTpl) {
+    self.visit_callee_expr(&tagged_tpl.tag);
+    tagged_tpl.tpl.visit_with(self);
+  }
+
+  fn visit_call_expr(&mut self, call_expr: &CallExpr) {
+    match &call_expr.callee {
+      Callee::Import(_) => {
+        if let Some(specifier) = self.string_arg(call_expr) {
+          if let Some(dependency) = self.module.dependencies.get(specifier) {
+            let new_text = match &dependency.target {
+              DependencyTarget::Module(id) => format!(
+                "__bundle.load({}, [{}])",
+                id,
+                self
+                  .chunks
+                  .files_to_load(*id)
+                  .iter()
+                  .map(|file| quote(file))
+                  .collect::<Vec<_>>()
+                  .join(", ")
+              ),
+              DependencyTarget::External(specifier) => {
+                format!("import({})", quote(specifier))
+              }
+            };
+            self.replace(call_expr.range(), new_text);
+            return;
+          }
+        }
+      }
+      Callee::Expr(expr) => {
+        let is_require = self.is_cjs
+          && matches!(&**expr, Expr::Ident(ident) if &*ident.sym == "require");
+        if is_require {
+          if let Some(specifier) = self.string_arg(call_expr) {
+            if let Some(dependency) = self.module.dependencies.get(specifier) {
+              let new_text = match &dependency.target {
+                DependencyTarget::Module(id) => id.to_string(),
+                DependencyTarget::External(specifier) => quote(specifier),
+              };
+              self.replace(call_expr.args[0].range(), new_text);
+              return;
+            }
+          }
+        }
+        self.visit_callee_expr(expr);
+        call_expr.args.visit_with(self);
+        return;
+      }
+      Callee::Super(_) => {}
+    }
+    call_expr.visit_children_with(self);
+  }
+}
+
+fn module_export_name(name: &ModuleExportName) -> String {
+  match name {
+    ModuleExportName::Ident(ident) => ident.sym.to_string(),
+    ModuleExportName::Str(str) => str.value.to_string(),
+  }
+}
+
+fn decl_names(decl: &Decl, names: &mut Vec<String>) {
+  match decl {
+    Decl::Class(class_decl) => names.push(class_decl.ident.sym.to_string()),
+    Decl::Fn(fn_decl) => names.push(fn_decl.ident.sym.to_string()),
+    Decl::Var(var_decl) => {
+      for declarator in &var_decl.decls {
+        pat_names(&declarator.name, names);
+      }
+    }
+    _ => {}
+  }
+}
+
+fn pat_names(pat: &Pat, names: &mut Vec<String>) {
+  match pat {
+    Pat::Ident(binding_ident) => names.push(binding_ident.id.sym.to_string()),
+    Pat::Array(array_pat) => {
+      for elem in array_pat.elems.iter().flatten() {
+        pat_names(elem, names);
+      }
+    }
+    Pat::Object(object_pat) => {
+      for prop in &object_pat.props {
+        match prop {
+          ObjectPatProp::KeyValue(key_value) => {
+            pat_names(&key_value.value, names)
+          }
+          ObjectPatProp::Assign(assign) => {
+            names.push(assign.key.sym.to_string())
+          }
+          ObjectPatProp::Rest(rest) => pat_names(&rest.arg, names),
+        }
+      }
+    }
+    Pat::Rest(rest) => pat_names(&rest.arg, names),
+    Pat::Assign(assign) => pat_names(&assign.left, names),
+    Pat::Invalid(_) | Pat::Expr(_) => {}
+  }
+}
+
+/// Gets an expression that reads an export from the namespace of a
+/// dependency.
+fn dep_member(index: usize, name: &str) -> String {
+  if is_identifier(name) {
+    format!("__d{}.{}", index, name)
+  } else {
+    format!("__d{}[{}]", index, quote(name))
+  }
+}
+
+fn is_identifier(name: &str) -> bool {
+  let mut chars = name.chars();
+  chars
+    .next()
+    .is_some_and(|c| c.is_alphabetic() || c == '_' || c == '$')
+    && chars.all(|c| c.is_alphanumeric() || c == '_' || c == '$')
+}
+
+/// Gets the text of a name in an `export { ... }` statement, which is quoted
+/// when it isn't an identifier.
+pub fn export_name_text(name: &str) -> String {
+  if is_identifier(name) {
+    name.to_string()
+  } else {
+    quote(name)
+  }
+}
+
+pub fn quote(text: &str) -> String {
+  serde_json::to_string(text).unwrap()
+}
+
+#[cfg(test)]
+mod test {
+  use std::collections::BTreeMap;
+
+  use deno_ast::ModuleSpecifier;
+
+  use super::super::modules::ModuleDependency;
+  use super::*;
+
+  fn transform(
+    id: ModuleId,
+    source: &str,
+    dependencies: &[(&str, ModuleId)],
+  ) -> String {
+    let module = BundleModule {
+      specifier: ModuleSpecifier::parse("file:///main.js").unwrap(),
+      kind: ModuleKind::Esm,
+      media_type: MediaType::JavaScript,
+      source: source.into(),
+      dependencies: dependencies
+        .iter()
+        .map(|(specifier, id)| {
+          (
+            specifier.to_string(),
+            ModuleDependency {
+              target: DependencyTarget::Module(*id),
+              is_dynamic: false,
+            },
+          )
+        })
+        .collect::<BTreeMap<_, _>>(),
+    };
+    let transformer = ModuleTransformer {
+      transpile_options: &Default::default(),
+      emit_options: &Default::default(),
+      source_maps: false,
+      minify: false,
+    };
+    let chunks = Chunks::single(&[], "main.js".to_string());
+    transformer.transform(id, &module, &chunks).unwrap().code
+  }
+
+  #[test]
+  fn test_transform_esm() {
+    assert_eq!(
+      transform(
+        1,
+        concat!(
+          "import a, { b as c } from \"./a.js\";\n",
+          "export { d } from \"./a.js\";\n",
+          "export const e = c();\n",
+          "export default { a };\n",
+          "console.log(import.meta.url, await import(\"./b.js\"));\n",
+        ),
+        &[("./a.js", 1), ("./b.js", 2)],
+      ),
+      concat!(
+        "__bundle.define(1, \"file:///main.js\", \"esm\", [1], async function (__exports, __meta, __deps) {\n",
+        "__bundle.export(__exports, { \"d\": () => __d0.d, \"e\": () => e, \"default\": () => __default });\n",
+        "const [__d0] = await __deps();\n",
+        "\n",
+        "\n",
+        "const e = (0, __d0.b)();\n",
+        "const __default = { a: __d0.default };\n",
+        "console.log(__meta.url, await __bundle.load(2, []));\n",
+        "\n",
+        "});\n",
+      )
+    );
+  }
+
+  #[test]
+  fn test_transform_esm_root() {
+    assert_eq!(
+      transform(
+        0,
+        concat!(
+          "import { b } from \"./a.js\";\n",
+          "export { b as c };\n",
+          "export * as ns from \"./a.js\";\n",
+          "export let count = 0;\n",
+          "export function increment() {\n",
+          "  count++;\n",
+          "}\n",
+        ),
+        &[("./a.js", 1)],
+      ),
+      concat!(
+        "const [__exports, __meta, __deps, __evaluated] = __bundle.enter(0, \"file:///main.js\", [1], import.meta.main === true);\n",
+        "__bundle.export(__exports, { \"c\": () => __d0.b, \"ns\": () => __d0, \"count\": () => count, \"increment\": () => increment });\n",
+        "const [__d0] = await __deps();\n",
+        "const __x0 = __d0.b;\n",
+        "\n",
+        "\n",
+        "\n",
+        "let count = 0;\n",
+        "function increment() {\n",
+        "  count++;\n",
+        "}\n",
+        "\n",
+        "export { __x0 as c, __d0 as ns, count, increment };\n",
+        "__evaluated();\n",
+      )
+    );
+  }
+}
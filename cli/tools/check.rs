@@ -14,7 +14,9 @@ use deno_terminal::colors;
 use once_cell::sync::Lazy;
 use regex::Regex;
 
+use crate::args::CheckFlags;
 use crate::args::CliOptions;
+use crate::args::Flags;
 use crate::args::TsConfig;
 use crate::args::TsConfigType;
 use crate::args::TsTypeLib;
@@ -22,11 +24,16 @@ use crate::args::TypeCheckMode;
 use crate::cache::Caches;
 use crate::cache::FastInsecureHasher;
 use crate::cache::TypeCheckCache;
+use crate::factory::CliFactory;
+use crate::factory::WatcherFactory;
+use crate::graph_util::graph_lock_or_exit;
+use crate::graph_util::graph_roots_with_changed_dependents;
 use crate::graph_util::BuildFastCheckGraphOptions;
 use crate::graph_util::ModuleGraphBuilder;
 use crate::npm::CliNpmResolver;
 use crate::tsc;
 use crate::tsc::Diagnostics;
+use crate::util::file_watcher;
 use crate::version;
 
 /// Options for performing a check of a module graph. Note that the decision to
@@ -39,6 +46,8 @@ pub struct CheckOptions {
   pub build_fast_check_graph: bool,
   /// Default type library to type check with.
   pub lib: TsTypeLib,
+  /// Whether to log the roots of the graph that are being type checked.
+  pub log_checked_roots: bool,
   /// Whether to log about any ignored compiler options.
   pub log_ignored_options: bool,
   /// If true, valid `.tsbuildinfo` files will be ignored and type checking
@@ -152,9 +161,11 @@ impl TypeChecker {
       }
     }
 
-    for root in &graph.roots {
-      let root_str = root.as_str();
-      log::info!("{} {}", colors::green("Check"), root_str);
+    if options.log_checked_roots {
+      for root in &graph.roots {
+        let root_str = root.as_str();
+        log::info!("{} {}", colors::green("Check"), root_str);
+      }
     }
 
     let check_js = ts_config.get_check_js();
@@ -240,6 +251,66 @@ impl TypeChecker {
   }
 }
 
+pub async fn check(
+  flags: Flags,
+  check_flags: CheckFlags,
+) -> Result<(), AnyError> {
+  let Some(watch_flags) = check_flags.watch.clone() else {
+    let factory = CliFactory::from_flags(flags)?;
+    let module_load_preparer = factory.module_load_preparer().await?;
+    return module_load_preparer
+      .load_and_type_check_files(&check_flags.files)
+      .await;
+  };
+
+  let mut watcher_factory = WatcherFactory::default();
+  file_watcher::watch_func(
+    flags,
+    file_watcher::PrintConfig::new("Check", !watch_flags.no_clear_screen),
+    move |flags, watcher_communicator, changed_paths| {
+      let check_flags = check_flags.clone();
+      let factory = watcher_factory.get(
+        flags,
+        watcher_communicator.clone(),
+        changed_paths.as_deref(),
+      )?;
+      Ok(async move {
+        let cli_options = factory.cli_options();
+        let _ = watcher_communicator.watch_paths(cli_options.watch_paths());
+
+        let module_load_preparer = factory.module_load_preparer().await?;
+        let specifiers =
+          module_load_preparer.collect_specifiers(&check_flags.files)?;
+        if specifiers.is_empty() {
+          log::warn!("{} No matching files found.", colors::yellow("Warning"));
+          return Ok(());
+        }
+
+        // only the modules that depend on a changed file are checked again
+        let module_graph_creator = factory.module_graph_creator().await?;
+        let mut graph = module_graph_creator
+          .create_graph(
+            cli_options.type_check_mode().as_graph_kind(),
+            specifiers,
+          )
+          .await?;
+        module_graph_creator.graph_valid(&graph)?;
+        if let Some(lockfile) = factory.maybe_lockfile() {
+          graph_lock_or_exit(&graph, &mut lockfile.lock());
+        }
+        graph.roots =
+          graph_roots_with_changed_dependents(&graph, changed_paths);
+        if graph.roots.is_empty() {
+          return Ok(());
+        }
+        module_graph_creator.type_check_graph(graph).await?;
+        Ok(())
+      })
+    },
+  )
+  .await
+}
+
 enum CheckHashResult {
   Hash(u64),
   NoFiles,
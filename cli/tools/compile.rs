@@ -27,14 +27,18 @@ pub async fn compile(
   let parsed_source_cache = factory.parsed_source_cache();
   let binary_writer = factory.create_compile_binary_writer().await?;
   let module_specifier = cli_options.resolve_main_module()?;
-  let module_roots = {
-    let mut vec = Vec::with_capacity(compile_flags.include.len() + 1);
-    vec.push(module_specifier.clone());
-    for side_module in &compile_flags.include {
-      vec.push(resolve_url_or_path(side_module, cli_options.initial_cwd())?);
+  let mut module_roots = Vec::with_capacity(compile_flags.include.len() + 1);
+  module_roots.push(module_specifier.clone());
+  let mut asset_dirs = Vec::new();
+  for include in &compile_flags.include {
+    let path = cli_options.initial_cwd().join(include);
+    if path.is_dir() {
+      asset_dirs.push(path);
+    } else {
+      module_roots
+        .push(resolve_url_or_path(include, cli_options.initial_cwd())?);
     }
-    vec
-  };
+  }
 
   // this is not supported, so show a warning about it, but don't error in order
   // to allow someone to still run `deno compile` when this is in a deno.json
@@ -100,6 +104,7 @@ pub async fn compile(
       &mut file,
       eszip,
       &module_specifier,
+      &asset_dirs,
       &compile_flags,
       cli_options,
     )
@@ -245,6 +250,8 @@ mod test {
         target: Some("x86_64-unknown-linux-gnu".to_string()),
         no_terminal: false,
         include: vec![],
+        compress: false,
+        code_cache: false,
       },
       &std::env::current_dir().unwrap(),
     )
@@ -267,6 +274,8 @@ mod test {
         target: Some("x86_64-pc-windows-msvc".to_string()),
         include: vec![],
         no_terminal: false,
+        compress: false,
+        code_cache: false,
       },
       &std::env::current_dir().unwrap(),
     )
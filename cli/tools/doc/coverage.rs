@@ -0,0 +1,301 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+use std::borrow::Borrow;
+use std::fmt::Display;
+
+use deno_core::serde::Serialize;
+use deno_core::ModuleSpecifier;
+use deno_doc::js_doc::JsDoc;
+use deno_doc::js_doc::JsDocTag;
+use deno_doc::DocNode;
+use deno_doc::DocNodeKind;
+use indexmap::IndexMap;
+
+use super::markdown::module_display_name;
+use super::markdown::plain;
+use crate::colors;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct CoverageCount {
+  pub documented: usize,
+  pub total: usize,
+}
+
+impl CoverageCount {
+  fn add(&mut self, documented: bool) {
+    self.total += 1;
+    if documented {
+      self.documented += 1;
+    }
+  }
+
+  fn merge(&mut self, other: CoverageCount) {
+    self.documented += other.documented;
+    self.total += other.total;
+  }
+}
+
+/// The documentation coverage of a module, or of all the modules.
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DocCoverage {
+  pub symbols: CoverageCount,
+  pub parameters: CoverageCount,
+  pub return_values: CoverageCount,
+  pub percent: f64,
+}
+
+impl DocCoverage {
+  fn finish(mut self) -> Self {
+    let documented = self.symbols.documented
+      + self.parameters.documented
+      + self.return_values.documented;
+    let total =
+      self.symbols.total + self.parameters.total + self.return_values.total;
+    self.percent = percent(documented, total);
+    self
+  }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ModuleDocCoverage {
+  pub module: String,
+  #[serde(flatten)]
+  pub coverage: DocCoverage,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DocCoverageReport {
+  pub modules: Vec<ModuleDocCoverage>,
+  pub total: DocCoverage,
+}
+
+impl DocCoverageReport {
+  pub fn new(
+    doc_nodes_by_url: &IndexMap<ModuleSpecifier, Vec<DocNode>>,
+    base: &ModuleSpecifier,
+  ) -> Self {
+    let mut modules = Vec::with_capacity(doc_nodes_by_url.len());
+    let mut total = DocCoverage::default();
+    for (specifier, doc_nodes) in doc_nodes_by_url {
+      let mut coverage = DocCoverage::default();
+      for doc_node in doc_nodes {
+        collect_node(doc_node, &mut coverage);
+      }
+      total.symbols.merge(coverage.symbols);
+      total.parameters.merge(coverage.parameters);
+      total.return_values.merge(coverage.return_values);
+      modules.push(ModuleDocCoverage {
+        module: module_display_name(specifier, base),
+        coverage: coverage.finish(),
+      });
+    }
+    Self {
+      modules,
+      total: total.finish(),
+    }
+  }
+
+  pub fn print(&self) {
+    let name_max = self
+      .modules
+      .iter()
+      .map(|module| module.module.len())
+      .chain(std::iter::once("All files".len()))
+      .max()
+      .unwrap();
+    let header = format!(
+      "{name:name_max$}  | Symbols | Parameters | Returns | Total % |",
+      name = "File"
+    );
+    let separator = "-".repeat(header.len());
+    println!("{}", separator);
+    println!("{}", header);
+    println!("{}", separator);
+    for module in &self.modules {
+      print_coverage_line(&module.module, name_max, &module.coverage);
+    }
+    println!("{}", separator);
+    print_coverage_line("All files", name_max, &self.total);
+    println!("{}", separator);
+  }
+}
+
+fn print_coverage_line(name: &str, name_max: usize, coverage: &DocCoverage) {
+  let ratio =
+    |count: &CoverageCount| format!("{}/{}", count.documented, count.total);
+  let color = |text: String| {
+    if coverage.percent >= 80.0 {
+      colors::green(&text).to_string()
+    } else if coverage.percent >= 50.0 {
+      colors::yellow(&text).to_string()
+    } else {
+      colors::red(&text).to_string()
+    }
+  };
+  println!(
+    " {} | {:>7} | {:>10} | {:>7} | {} |",
+    color(format!("{name:name_max$}")),
+    ratio(&coverage.symbols),
+    ratio(&coverage.parameters),
+    ratio(&coverage.return_values),
+    color(format!("{:>7.1}", coverage.percent)),
+  );
+}
+
+fn percent(documented: usize, total: usize) -> f64 {
+  if total == 0 {
+    100.0
+  } else {
+    documented as f64 * 100.0 / total as f64
+  }
+}
+
+fn collect_node(doc_node: &DocNode, coverage: &mut DocCoverage) {
+  if matches!(doc_node.kind, DocNodeKind::ModuleDoc | DocNodeKind::Import) {
+    return;
+  }
+  coverage.symbols.add(has_doc(&doc_node.js_doc));
+
+  if let Some(function_def) = &doc_node.function_def {
+    collect_function(
+      &doc_node.js_doc,
+      &function_def.params,
+      function_def.return_type.as_ref(),
+      coverage,
+    );
+  }
+  if let Some(class_def) = &doc_node.class_def {
+    for constructor in &class_def.constructors {
+      collect_function(
+        &constructor.js_doc,
+        &constructor.params,
+        None::<&String>,
+        coverage,
+      );
+    }
+    for method in &class_def.methods {
+      collect_function(
+        &method.js_doc,
+        &method.function_def.params,
+        method.function_def.return_type.as_ref(),
+        coverage,
+      );
+    }
+  }
+  if let Some(interface_def) = &doc_node.interface_def {
+    for method in &interface_def.methods {
+      collect_function(
+        &method.js_doc,
+        &method.params,
+        method.return_type.as_ref(),
+        coverage,
+      );
+    }
+  }
+  if let Some(namespace_def) = &doc_node.namespace_def {
+    for element in &namespace_def.elements {
+      let element: &DocNode = element.borrow();
+      collect_node(element, coverage);
+    }
+  }
+}
+
+fn collect_function<P: Display, T: Display>(
+  js_doc: &JsDoc,
+  params: &[P],
+  return_type: Option<&T>,
+  coverage: &mut DocCoverage,
+) {
+  let param_docs = js_doc
+    .tags
+    .iter()
+    .filter_map(|tag| match tag {
+      JsDocTag::Param { name, doc, .. } => {
+        Some((&**name, doc.as_deref().is_some_and(|doc| !doc.is_empty())))
+      }
+      _ => None,
+    })
+    .collect::<Vec<_>>();
+  for (index, param) in params.iter().enumerate() {
+    let param = plain(param);
+    let name = param_name(&param);
+    let documented = match name {
+      Some(name) => param_docs
+        .iter()
+        .any(|(doc_name, documented)| *documented && *doc_name == name),
+      // destructured parameters are documented with any name
+      None => param_docs
+        .get(index)
+        .is_some_and(|(_, documented)| *documented),
+    };
+    coverage.parameters.add(documented);
+  }
+
+  if let Some(return_type) = return_type {
+    if returns_value(&plain(return_type)) {
+      coverage.return_values.add(js_doc.tags.iter().any(|tag| {
+        matches!(
+          tag,
+          JsDocTag::Return { doc: Some(doc), .. } if !doc.is_empty()
+        )
+      }));
+    }
+  }
+}
+
+fn has_doc(js_doc: &JsDoc) -> bool {
+  js_doc
+    .doc
+    .as_deref()
+    .is_some_and(|doc| !doc.trim().is_empty())
+}
+
+/// Gets the name of a parameter from its declaration, or `None` for
+/// destructured parameters.
+fn param_name(param: &str) -> Option<&str> {
+  let param = param.trim_start_matches("...");
+  if param.starts_with('{') || param.starts_with('[') {
+    return None;
+  }
+  let end = param
+    .find(|c: char| matches!(c, ':' | '?' | '=' | ' '))
+    .unwrap_or(param.len());
+  Some(&param[..end])
+}
+
+fn returns_value(return_type: &str) -> bool {
+  !matches!(
+    return_type.trim(),
+    "void" | "Promise<void>" | "undefined" | "never"
+  )
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn test_param_name() {
+    assert_eq!(param_name("a: number"), Some("a"));
+    assert_eq!(param_name("b?: string"), Some("b"));
+    assert_eq!(param_name("...rest: string[]"), Some("rest"));
+    assert_eq!(param_name("c = 1"), Some("c"));
+    assert_eq!(param_name("{ a, b }: Options"), None);
+    assert_eq!(param_name("[a, b]: [number, number]"), None);
+  }
+
+  #[test]
+  fn test_returns_value() {
+    assert!(returns_value("number"));
+    assert!(returns_value("Promise<string>"));
+    assert!(!returns_value("void"));
+    assert!(!returns_value("Promise<void>"));
+  }
+
+  #[test]
+  fn test_percent() {
+    assert_eq!(percent(0, 0), 100.0);
+    assert_eq!(percent(3, 4), 75.0);
+  }
+}
@@ -0,0 +1,297 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+use std::fmt::Write;
+
+use deno_doc::js_doc::JsDocTag;
+use deno_doc::DocNodeKind;
+use indexmap::IndexMap;
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use super::markdown::node_signature;
+use super::markdown::ModuleDocs;
+use super::markdown::SymbolDocs;
+
+static INLINE_RE: Lazy<Regex> = Lazy::new(|| {
+  Regex::new(r"`([^`]+)`|\*\*([^*]+)\*\*|\{@link(?:code|plain)?\s+([^}]+)\}")
+    .unwrap()
+});
+
+/// Generates a man page in section 3 for each exported function, from the
+/// JSDoc of its declarations.
+pub fn generate(
+  modules: &[ModuleDocs],
+  name: Option<&str>,
+) -> IndexMap<String, String> {
+  let mut files = IndexMap::new();
+  for module in modules {
+    for symbol in &module.symbols {
+      if symbol
+        .nodes
+        .iter()
+        .any(|node| node.kind == DocNodeKind::Function)
+        && !files.contains_key(&format!("{}.3", symbol.name))
+      {
+        files.insert(
+          format!("{}.3", symbol.name),
+          render_page(symbol, &module.display_name, name),
+        );
+      }
+    }
+  }
+  files
+}
+
+fn render_page(
+  symbol: &SymbolDocs,
+  module_name: &str,
+  package_name: Option<&str>,
+) -> String {
+  let nodes = symbol
+    .nodes
+    .iter()
+    .filter(|node| node.kind == DocNodeKind::Function)
+    .collect::<Vec<_>>();
+  let js_doc = nodes
+    .iter()
+    .map(|node| &node.js_doc)
+    .find(|js_doc| js_doc.doc.is_some() || !js_doc.tags.is_empty())
+    .unwrap_or(&nodes[0].js_doc);
+  let doc = js_doc.doc.as_deref().unwrap_or_default();
+
+  let mut page = String::new();
+  writeln!(
+    page,
+    ".TH {} 3 \"\" \"{}\"",
+    escape(&symbol.name.to_uppercase()),
+    escape(package_name.unwrap_or(module_name))
+  )
+  .unwrap();
+
+  writeln!(page, ".SH NAME").unwrap();
+  match summary(doc) {
+    Some(summary) => {
+      writeln!(page, "{} \\- {}", escape(&symbol.name), inline(&summary))
+    }
+    None => writeln!(page, "{}", escape(&symbol.name)),
+  }
+  .unwrap();
+
+  writeln!(page, ".SH SYNOPSIS\n.nf").unwrap();
+  writeln!(
+    page,
+    "import {{ {} }} from \"{}\";",
+    symbol.name, module_name
+  )
+  .unwrap();
+  for node in &nodes {
+    writeln!(page, ".B {}", escape(&node_signature(&symbol.name, node)))
+      .unwrap();
+  }
+  writeln!(page, ".fi").unwrap();
+
+  if !doc.is_empty() {
+    writeln!(page, ".SH DESCRIPTION").unwrap();
+    page.push_str(&markdown_to_roff(doc));
+  }
+
+  let params = js_doc
+    .tags
+    .iter()
+    .filter_map(|tag| match tag {
+      JsDocTag::Param { name, doc, .. } => Some((name, doc)),
+      _ => None,
+    })
+    .collect::<Vec<_>>();
+  if !params.is_empty() {
+    writeln!(page, ".SH PARAMETERS").unwrap();
+    for (name, doc) in params {
+      writeln!(page, ".TP\n.I {}", escape(name)).unwrap();
+      if let Some(doc) = doc.as_deref() {
+        page.push_str(&markdown_to_roff(doc));
+      }
+    }
+  }
+
+  let mut examples = Vec::new();
+  let mut see = Vec::new();
+  for tag in &js_doc.tags {
+    match tag {
+      JsDocTag::Return { doc: Some(doc), .. } => {
+        writeln!(page, ".SH RETURN VALUE").unwrap();
+        page.push_str(&markdown_to_roff(doc));
+      }
+      JsDocTag::Deprecated { doc } => {
+        writeln!(page, ".SH DEPRECATED").unwrap();
+        page.push_str(&markdown_to_roff(
+          doc.as_deref().unwrap_or("This function is deprecated."),
+        ));
+      }
+      JsDocTag::Example { doc } => examples.push(doc),
+      JsDocTag::See { doc } => see.push(doc),
+      _ => {}
+    }
+  }
+  if !examples.is_empty() {
+    writeln!(page, ".SH EXAMPLES").unwrap();
+    for doc in examples {
+      page.push_str(&markdown_to_roff(doc));
+    }
+  }
+  if !see.is_empty() {
+    writeln!(page, ".SH SEE ALSO").unwrap();
+    for doc in see {
+      page.push_str(&markdown_to_roff(doc));
+    }
+  }
+
+  writeln!(page, ".SH SOURCE").unwrap();
+  writeln!(
+    page,
+    "{}:{}",
+    escape(&nodes[0].location.filename),
+    nodes[0].location.line
+  )
+  .unwrap();
+  page
+}
+
+/// Gets the first sentence of a doc comment for the NAME section.
+fn summary(doc: &str) -> Option<String> {
+  let paragraph = doc
+    .split("\n\n")
+    .next()?
+    .lines()
+    .map(str::trim)
+    .collect::<Vec<_>>()
+    .join(" ");
+  let sentence = match paragraph.find(". ") {
+    Some(index) => &paragraph[..index],
+    None => paragraph.trim_end_matches('.'),
+  };
+  if sentence.is_empty() {
+    None
+  } else {
+    Some(sentence.to_string())
+  }
+}
+
+/// Escapes text so that roff displays it as is.
+fn escape(text: &str) -> String {
+  text.replace('\\', "\\e").replace('-', "\\-")
+}
+
+fn inline(text: &str) -> String {
+  let mut output = String::new();
+  let mut last_end = 0;
+  for captures in INLINE_RE.captures_iter(text) {
+    let m = captures.get(0).unwrap();
+    output.push_str(&escape(&text[last_end..m.start()]));
+    let bold = captures
+      .get(1)
+      .or_else(|| captures.get(2))
+      .map(|m| m.as_str())
+      .unwrap_or_else(|| {
+        // show the label of the link when there is one
+        let link = captures[3].trim();
+        link
+          .split_once('|')
+          .map(|(_, label)| label)
+          .or_else(|| link.split_once(' ').map(|(_, label)| label))
+          .unwrap_or(link)
+          .trim()
+      });
+    write!(output, "\\fB{}\\fR", escape(bold)).unwrap();
+    last_end = m.end();
+  }
+  output.push_str(&escape(&text[last_end..]));
+  output
+}
+
+/// Converts the Markdown of a doc comment to roff, keeping the paragraphs
+/// and code blocks.
+fn markdown_to_roff(markdown: &str) -> String {
+  let mut output = String::new();
+  let mut in_code_block = false;
+  let mut needs_paragraph = false;
+  for line in markdown.lines() {
+    if line.trim_start().starts_with("```") {
+      if in_code_block {
+        output.push_str(".EE\n");
+      } else {
+        output.push_str(".PP\n.EX\n");
+        needs_paragraph = false;
+      }
+      in_code_block = !in_code_block;
+      continue;
+    }
+    if in_code_block {
+      output.push_str(&protect_line(escape(line)));
+      output.push('\n');
+      continue;
+    }
+    let line = line.trim();
+    if line.is_empty() {
+      needs_paragraph = !output.is_empty();
+      continue;
+    }
+    if needs_paragraph {
+      output.push_str(".PP\n");
+      needs_paragraph = false;
+    }
+    output.push_str(&protect_line(inline(line)));
+    output.push('\n');
+  }
+  if in_code_block {
+    output.push_str(".EE\n");
+  }
+  output
+}
+
+/// Prevents a line from being read as a roff request.
+fn protect_line(line: String) -> String {
+  if line.starts_with('.') || line.starts_with('\'') {
+    format!("\\&{}", line)
+  } else {
+    line
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn test_summary() {
+    assert_eq!(
+      summary("Adds two numbers. Overflows\nto infinity.").unwrap(),
+      "Adds two numbers"
+    );
+    assert_eq!(
+      summary("Adds two\nnumbers.\n\nMore text.").unwrap(),
+      "Adds two numbers"
+    );
+    assert_eq!(summary(""), None);
+  }
+
+  #[test]
+  fn test_markdown_to_roff() {
+    assert_eq!(
+      markdown_to_roff(
+        "Uses `a-b` and **c** with {@link Foo}.\n\n.hidden\n\n```ts\nconst x = a - b;\n```"
+      ),
+      concat!(
+        "Uses \\fBa\\-b\\fR and \\fBc\\fR with \\fBFoo\\fR.\n",
+        ".PP\n",
+        "\\&.hidden\n",
+        ".PP\n.EX\n",
+        "const x = a \\- b;\n",
+        ".EE\n",
+      )
+    );
+    assert_eq!(
+      markdown_to_roff("See {@link https://deno.com | the website}."),
+      "See \\fBthe website\\fR.\n"
+    );
+  }
+}
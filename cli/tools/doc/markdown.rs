@@ -0,0 +1,630 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::fmt::Display;
+use std::fmt::Write;
+
+use deno_ast::swc::ast::VarDeclKind;
+use deno_core::ModuleSpecifier;
+use deno_doc::js_doc::JsDoc;
+use deno_doc::js_doc::JsDocTag;
+use deno_doc::DocNode;
+use deno_doc::DocNodeKind;
+use indexmap::IndexMap;
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+static LINK_RE: Lazy<Regex> = Lazy::new(|| {
+  Regex::new(r"\{@link(code|plain)?\s+([^}\s|]+)(?:\s*\|\s*|\s+)?([^}]*)\}")
+    .unwrap()
+});
+
+pub struct MarkdownOptions<'a> {
+  pub name: Option<&'a str>,
+  pub per_symbol: bool,
+}
+
+/// The documentation of a module along with the files it's written to.
+pub struct ModuleDocs<'a> {
+  /// The path of the module relative to the current directory, or its URL
+  /// for remote modules.
+  pub display_name: String,
+  pub file_stem: String,
+  pub module_doc: Option<&'a JsDoc>,
+  pub symbols: Vec<SymbolDocs<'a>>,
+}
+
+/// The declarations of an exported symbol, which has several for
+/// overloaded functions and merged declarations.
+pub struct SymbolDocs<'a> {
+  pub name: String,
+  pub nodes: Vec<&'a DocNode>,
+}
+
+impl<'a> ModuleDocs<'a> {
+  pub fn collect(
+    doc_nodes_by_url: &'a IndexMap<ModuleSpecifier, Vec<DocNode>>,
+    base: &ModuleSpecifier,
+  ) -> Vec<Self> {
+    // reserved for the table of contents
+    let mut file_stems = HashSet::from(["index".to_string()]);
+    let mut modules = Vec::with_capacity(doc_nodes_by_url.len());
+    for (specifier, doc_nodes) in doc_nodes_by_url {
+      let display_name = module_display_name(specifier, base);
+      let file_stem = unique_file_stem(&display_name, &mut file_stems);
+      let mut module_doc = None;
+      let mut symbols = Vec::<SymbolDocs>::new();
+      for doc_node in doc_nodes {
+        match doc_node.kind {
+          DocNodeKind::ModuleDoc => module_doc = Some(&doc_node.js_doc),
+          DocNodeKind::Import => {}
+          _ => match symbols.iter_mut().find(|s| s.name == doc_node.name) {
+            Some(symbol) => symbol.nodes.push(doc_node),
+            None => symbols.push(SymbolDocs {
+              name: doc_node.name.clone(),
+              nodes: vec![doc_node],
+            }),
+          },
+        }
+      }
+      modules.push(Self {
+        display_name,
+        file_stem,
+        module_doc,
+        symbols,
+      });
+    }
+    modules
+  }
+}
+
+/// Generates Markdown documentation with an `index.md` that links to the
+/// files of the modules, and the files of the symbols when writing a file
+/// per symbol. Links in JSDoc are resolved to the documented symbols.
+pub fn generate(
+  modules: &[ModuleDocs],
+  options: &MarkdownOptions,
+) -> IndexMap<String, String> {
+  let links = LinkResolver::new(modules, options.per_symbol);
+  let mut files = IndexMap::new();
+
+  let mut index = String::new();
+  writeln!(index, "# {}\n", options.name.unwrap_or("Documentation")).unwrap();
+  for module in modules {
+    writeln!(
+      index,
+      "- [{}]({}.md)",
+      module.display_name, module.file_stem
+    )
+    .unwrap();
+  }
+  files.insert("index.md".to_string(), index);
+
+  for (module_index, module) in modules.iter().enumerate() {
+    let mut text = String::new();
+    writeln!(text, "# {}\n", module.display_name).unwrap();
+    if let Some(doc) =
+      module.module_doc.and_then(|js_doc| js_doc.doc.as_deref())
+    {
+      writeln!(text, "{}\n", links.resolve_text(doc, module_index)).unwrap();
+    }
+    if options.per_symbol {
+      for symbol in &module.symbols {
+        writeln!(
+          text,
+          "- [{}]({}.{}.md)",
+          symbol.name, module.file_stem, symbol.name
+        )
+        .unwrap();
+        let mut symbol_text = String::new();
+        writeln!(
+          symbol_text,
+          "_Module: [{}]({}.md)_\n",
+          module.display_name, module.file_stem
+        )
+        .unwrap();
+        write_symbol(&mut symbol_text, symbol, 1, &links, module_index);
+        files.insert(
+          format!("{}.{}.md", module.file_stem, symbol.name),
+          symbol_text,
+        );
+      }
+    } else {
+      for symbol in &module.symbols {
+        write_symbol(&mut text, symbol, 2, &links, module_index);
+      }
+    }
+    files.insert(format!("{}.md", module.file_stem), text);
+  }
+  files
+}
+
+fn write_symbol(
+  text: &mut String,
+  symbol: &SymbolDocs,
+  level: usize,
+  links: &LinkResolver,
+  module_index: usize,
+) {
+  let heading = "#".repeat(level);
+  writeln!(text, "{} {}\n", heading, symbol.name).unwrap();
+  writeln!(text, "```ts").unwrap();
+  for node in &symbol.nodes {
+    writeln!(text, "{}", node_signature(&symbol.name, node)).unwrap();
+  }
+  writeln!(text, "```\n").unwrap();
+
+  // overloads without docs use the docs of the other declarations
+  let js_doc = symbol
+    .nodes
+    .iter()
+    .map(|node| &node.js_doc)
+    .find(|js_doc| js_doc.doc.is_some() || !js_doc.tags.is_empty());
+  if let Some(js_doc) = js_doc {
+    write_js_doc(text, js_doc, links, module_index);
+  }
+
+  for node in &symbol.nodes {
+    write_members(text, &symbol.name, node, level + 1, links, module_index);
+  }
+
+  for node in &symbol.nodes {
+    writeln!(
+      text,
+      "_Defined in `{}:{}`_\n",
+      node.location.filename, node.location.line
+    )
+    .unwrap();
+  }
+}
+
+fn write_members(
+  text: &mut String,
+  parent_name: &str,
+  node: &DocNode,
+  level: usize,
+  links: &LinkResolver,
+  module_index: usize,
+) {
+  let heading = "#".repeat(level.min(6));
+  if let Some(class_def) = &node.class_def {
+    for constructor in &class_def.constructors {
+      writeln!(text, "{} {}.constructor\n", heading, parent_name).unwrap();
+      writeln!(
+        text,
+        "```ts\nconstructor({})\n```\n",
+        join_display(&constructor.params)
+      )
+      .unwrap();
+      write_js_doc(text, &constructor.js_doc, links, module_index);
+    }
+    for property in &class_def.properties {
+      writeln!(text, "{} {}.{}\n", heading, parent_name, property.name)
+        .unwrap();
+      writeln!(
+        text,
+        "```ts\n{}{}{}\n```\n",
+        if property.is_static { "static " } else { "" },
+        property.name,
+        type_annotation(property.ts_type.as_ref())
+      )
+      .unwrap();
+      write_js_doc(text, &property.js_doc, links, module_index);
+    }
+    for method in &class_def.methods {
+      writeln!(text, "{} {}.{}\n", heading, parent_name, method.name).unwrap();
+      writeln!(
+        text,
+        "```ts\n{}{}({}){}\n```\n",
+        if method.is_static { "static " } else { "" },
+        method.name,
+        join_display(&method.function_def.params),
+        type_annotation(method.function_def.return_type.as_ref())
+      )
+      .unwrap();
+      write_js_doc(text, &method.js_doc, links, module_index);
+    }
+  }
+  if let Some(interface_def) = &node.interface_def {
+    for property in &interface_def.properties {
+      writeln!(text, "{} {}.{}\n", heading, parent_name, property.name)
+        .unwrap();
+      writeln!(
+        text,
+        "```ts\n{}{}{}\n```\n",
+        property.name,
+        if property.optional { "?" } else { "" },
+        type_annotation(property.ts_type.as_ref())
+      )
+      .unwrap();
+      write_js_doc(text, &property.js_doc, links, module_index);
+    }
+    for method in &interface_def.methods {
+      writeln!(text, "{} {}.{}\n", heading, parent_name, method.name).unwrap();
+      writeln!(
+        text,
+        "```ts\n{}({}){}\n```\n",
+        method.name,
+        join_display(&method.params),
+        type_annotation(method.return_type.as_ref())
+      )
+      .unwrap();
+      write_js_doc(text, &method.js_doc, links, module_index);
+    }
+  }
+  if let Some(enum_def) = &node.enum_def {
+    for member in &enum_def.members {
+      match member.js_doc.doc.as_deref() {
+        Some(doc) => writeln!(
+          text,
+          "- `{}` — {}",
+          member.name,
+          links.resolve_text(doc, module_index)
+        ),
+        None => writeln!(text, "- `{}`", member.name),
+      }
+      .unwrap();
+    }
+    if !enum_def.members.is_empty() {
+      text.push('\n');
+    }
+  }
+  if let Some(namespace_def) = &node.namespace_def {
+    for element in &namespace_def.elements {
+      let element: &DocNode = element.borrow();
+      let symbol = SymbolDocs {
+        name: format!("{}.{}", parent_name, element.name),
+        nodes: vec![element],
+      };
+      write_symbol(text, &symbol, level.min(6), links, module_index);
+    }
+  }
+}
+
+fn write_js_doc(
+  text: &mut String,
+  js_doc: &JsDoc,
+  links: &LinkResolver,
+  module_index: usize,
+) {
+  if let Some(doc) = js_doc.doc.as_deref() {
+    writeln!(text, "{}\n", links.resolve_text(doc, module_index)).unwrap();
+  }
+  let mut params = Vec::new();
+  for tag in &js_doc.tags {
+    match tag {
+      JsDocTag::Param { name, doc, .. } => {
+        params.push((name.to_string(), doc.as_deref().map(|d| d.to_string())))
+      }
+      JsDocTag::Return { doc: Some(doc), .. } => writeln!(
+        text,
+        "**Returns** — {}\n",
+        links.resolve_text(doc, module_index)
+      )
+      .unwrap(),
+      JsDocTag::Deprecated { doc } => match doc.as_deref() {
+        Some(doc) => writeln!(
+          text,
+          "**Deprecated** — {}\n",
+          links.resolve_text(doc, module_index)
+        ),
+        None => writeln!(text, "**Deprecated**\n"),
+      }
+      .unwrap(),
+      JsDocTag::Example { doc } => writeln!(
+        text,
+        "**Example**\n\n{}\n",
+        links.resolve_text(doc, module_index)
+      )
+      .unwrap(),
+      JsDocTag::See { doc } => writeln!(
+        text,
+        "**See** — {}\n",
+        links.resolve_text(doc, module_index)
+      )
+      .unwrap(),
+      _ => {}
+    }
+  }
+  if !params.is_empty() {
+    writeln!(text, "**Parameters**\n").unwrap();
+    for (name, doc) in params {
+      match doc {
+        Some(doc) => writeln!(
+          text,
+          "- `{}` — {}",
+          name,
+          links.resolve_text(&doc, module_index)
+        ),
+        None => writeln!(text, "- `{}`", name),
+      }
+      .unwrap();
+    }
+    text.push('\n');
+  }
+}
+
+/// Gets the TypeScript declaration of a documented node without its body.
+pub fn node_signature(name: &str, node: &DocNode) -> String {
+  match node.kind {
+    DocNodeKind::Function => match &node.function_def {
+      Some(function_def) => format!(
+        "{}function{} {}({}){}",
+        if function_def.is_async { "async " } else { "" },
+        if function_def.is_generator { "*" } else { "" },
+        name,
+        join_display(&function_def.params),
+        type_annotation(function_def.return_type.as_ref())
+      ),
+      None => format!("function {}()", name),
+    },
+    DocNodeKind::Variable => match &node.variable_def {
+      Some(variable_def) => format!(
+        "{} {}{}",
+        match variable_def.kind {
+          VarDeclKind::Const => "const",
+          VarDeclKind::Let => "let",
+          VarDeclKind::Var => "var",
+        },
+        name,
+        type_annotation(variable_def.ts_type.as_ref())
+      ),
+      None => format!("const {}", name),
+    },
+    DocNodeKind::Class => match node
+      .class_def
+      .as_ref()
+      .and_then(|class_def| class_def.extends.as_ref())
+    {
+      Some(extends) => format!("class {} extends {}", name, extends),
+      None => format!("class {}", name),
+    },
+    DocNodeKind::Interface => format!("interface {}", name),
+    DocNodeKind::Enum => format!("enum {}", name),
+    DocNodeKind::TypeAlias => match &node.type_alias_def {
+      Some(type_alias_def) => {
+        format!("type {} = {}", name, plain(&type_alias_def.ts_type))
+      }
+      None => format!("type {}", name),
+    },
+    DocNodeKind::Namespace => format!("namespace {}", name),
+    DocNodeKind::ModuleDoc | DocNodeKind::Import => name.to_string(),
+  }
+}
+
+pub fn join_display<T: Display>(items: &[T]) -> String {
+  items.iter().map(plain).collect::<Vec<_>>().join(", ")
+}
+
+pub fn type_annotation<T: Display>(ts_type: Option<&T>) -> String {
+  ts_type
+    .map(|ts_type| format!(": {}", plain(ts_type)))
+    .unwrap_or_default()
+}
+
+/// Displays a part of a declaration without the colors used for terminal
+/// output.
+pub fn plain(value: impl Display) -> String {
+  console_static_text::ansi::strip_ansi_codes(&value.to_string()).to_string()
+}
+
+pub fn module_display_name(
+  specifier: &ModuleSpecifier,
+  base: &ModuleSpecifier,
+) -> String {
+  if specifier.scheme() == "file" {
+    if let Some(relative) = base.make_relative(specifier) {
+      if !relative.starts_with("../") {
+        return relative;
+      }
+    }
+  }
+  specifier.to_string()
+}
+
+/// Gets a file name without an extension for a module that can be used in a
+/// flat directory, such as the one of a wiki.
+fn unique_file_stem(
+  display_name: &str,
+  file_stems: &mut HashSet<String>,
+) -> String {
+  let display_name = display_name
+    .split_once("://")
+    .map(|(_, rest)| rest)
+    .unwrap_or(display_name);
+  let without_extension = match display_name.rsplit_once('.') {
+    Some((stem, extension)) if !extension.contains('/') => stem,
+    _ => display_name,
+  };
+  let file_stem = without_extension
+    .chars()
+    .map(|c| {
+      if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') {
+        c
+      } else {
+        '_'
+      }
+    })
+    .collect::<String>();
+  let mut unique_file_stem = file_stem.clone();
+  let mut count = 1;
+  while !file_stems.insert(unique_file_stem.clone()) {
+    count += 1;
+    unique_file_stem = format!("{}_{}", file_stem, count);
+  }
+  unique_file_stem
+}
+
+/// Gets the anchor that GitHub flavored Markdown creates for a heading.
+pub fn heading_anchor(heading: &str) -> String {
+  heading
+    .to_lowercase()
+    .chars()
+    .filter_map(|c| match c {
+      ' ' => Some('-'),
+      c if c.is_alphanumeric() || c == '-' || c == '_' => Some(c),
+      _ => None,
+    })
+    .collect()
+}
+
+/// Resolves `{@link Symbol}` references in JSDoc to the documentation of
+/// the symbol, preferring the symbols of the current module.
+pub struct LinkResolver {
+  targets_by_module: Vec<HashMap<String, String>>,
+}
+
+impl LinkResolver {
+  pub fn new(modules: &[ModuleDocs], per_symbol: bool) -> Self {
+    let targets_by_module = modules
+      .iter()
+      .map(|module| {
+        module
+          .symbols
+          .iter()
+          .map(|symbol| {
+            let target = if per_symbol {
+              format!("{}.{}.md", module.file_stem, symbol.name)
+            } else {
+              format!(
+                "{}.md#{}",
+                module.file_stem,
+                heading_anchor(&symbol.name)
+              )
+            };
+            (symbol.name.clone(), target)
+          })
+          .collect()
+      })
+      .collect();
+    Self { targets_by_module }
+  }
+
+  fn resolve(&self, name: &str, module_index: usize) -> Option<&str> {
+    // members link to the symbol they belong to
+    let symbol_name = name.split_once('.').map(|(n, _)| n).unwrap_or(name);
+    std::iter::once(&self.targets_by_module[module_index])
+      .chain(self.targets_by_module.iter())
+      .find_map(|targets| targets.get(symbol_name))
+      .map(|target| target.as_str())
+  }
+
+  pub fn resolve_text(&self, text: &str, module_index: usize) -> String {
+    LINK_RE
+      .replace_all(text, |captures: &regex::Captures| {
+        let name = &captures[2];
+        let label = captures
+          .get(3)
+          .map(|m| m.as_str().trim())
+          .filter(|label| !label.is_empty())
+          .unwrap_or(name);
+        let label = match captures.get(1).map(|m| m.as_str()) {
+          Some("plain") => label.to_string(),
+          _ => format!("`{}`", label),
+        };
+        if name.contains("://") {
+          return format!("[{}]({})", label, name);
+        }
+        match self.resolve(name, module_index) {
+          Some(target) => format!("[{}]({})", label, target),
+          None => label,
+        }
+      })
+      .to_string()
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn test_module_display_name() {
+    let base = ModuleSpecifier::parse("file:///project/").unwrap();
+    let display_name = |specifier: &str| {
+      module_display_name(&ModuleSpecifier::parse(specifier).unwrap(), &base)
+    };
+    assert_eq!(display_name("file:///project/src/mod.ts"), "src/mod.ts");
+    assert_eq!(display_name("file:///other/mod.ts"), "file:///other/mod.ts");
+    assert_eq!(
+      display_name("https://deno.land/x/a/mod.ts"),
+      "https://deno.land/x/a/mod.ts"
+    );
+  }
+
+  #[test]
+  fn test_unique_file_stem() {
+    let mut file_stems = HashSet::new();
+    assert_eq!(unique_file_stem("src/mod.ts", &mut file_stems), "src_mod");
+    assert_eq!(unique_file_stem("src/mod.js", &mut file_stems), "src_mod_2");
+    assert_eq!(
+      unique_file_stem("https://deno.land/x/a@1.0/mod.ts", &mut file_stems),
+      "deno.land_x_a_1.0_mod"
+    );
+    assert_eq!(unique_file_stem("LICENSE", &mut file_stems), "LICENSE");
+  }
+
+  #[test]
+  fn test_index_module() {
+    let doc_nodes_by_url = IndexMap::from([(
+      ModuleSpecifier::parse("file:///project/index.ts").unwrap(),
+      Vec::new(),
+    )]);
+    let base = ModuleSpecifier::parse("file:///project/").unwrap();
+    let modules = ModuleDocs::collect(&doc_nodes_by_url, &base);
+    assert_eq!(modules[0].file_stem, "index_2");
+    let files = generate(
+      &modules,
+      &MarkdownOptions {
+        name: None,
+        per_symbol: false,
+      },
+    );
+    assert_eq!(
+      files.keys().collect::<Vec<_>>(),
+      vec!["index.md", "index_2.md"]
+    );
+    assert_eq!(
+      files["index.md"],
+      "# Documentation\n\n- [index.ts](index_2.md)\n"
+    );
+  }
+
+  #[test]
+  fn test_heading_anchor() {
+    assert_eq!(heading_anchor("add"), "add");
+    assert_eq!(heading_anchor("MyClass.prop"), "myclassprop");
+    assert_eq!(heading_anchor("Some Title"), "some-title");
+  }
+
+  #[test]
+  fn test_resolve_links() {
+    let links = LinkResolver {
+      targets_by_module: vec![
+        HashMap::from([("add".to_string(), "math.md#add".to_string())]),
+        HashMap::from([
+          ("add".to_string(), "other.md#add".to_string()),
+          ("Point".to_string(), "other.md#point".to_string()),
+        ]),
+      ],
+    };
+    assert_eq!(
+      links.resolve_text("Uses {@link add} and {@link Point.x}.", 0),
+      "Uses [`add`](math.md#add) and [`Point.x`](other.md#point)."
+    );
+    assert_eq!(
+      links.resolve_text("See {@linkplain add the sum}.", 1),
+      "See [the sum](other.md#add)."
+    );
+    assert_eq!(
+      links.resolve_text("See {@link Unknown | unknown}.", 1),
+      "See `unknown`."
+    );
+    assert_eq!(
+      links.resolve_text("See {@link https://deno.com Deno}.", 0),
+      "See [`Deno`](https://deno.com)."
+    );
+  }
+}
@@ -1,7 +1,14 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 
+mod coverage;
+mod man;
+mod markdown;
+
+use crate::args::DocCoverageFlag;
 use crate::args::DocFlags;
 use crate::args::DocHtmlFlag;
+use crate::args::DocManFlag;
+use crate::args::DocMarkdownFlag;
 use crate::args::DocSourceFileFlag;
 use crate::args::Flags;
 use crate::colors;
@@ -28,6 +35,7 @@ use doc::html::ShortPath;
 use doc::DocDiagnostic;
 use indexmap::IndexMap;
 use std::collections::BTreeMap;
+use std::path::Path;
 use std::rc::Rc;
 
 async fn generate_doc_nodes_for_builtin_types(
@@ -144,7 +152,12 @@ pub async fn doc(flags: Flags, doc_flags: DocFlags) -> Result<(), AnyError> {
 
       if doc_flags.lint {
         let diagnostics = doc_parser.take_diagnostics();
-        check_diagnostics(&diagnostics)?;
+        if doc_flags.coverage.is_some() {
+          // the coverage threshold decides whether the command fails
+          print_diagnostics(&diagnostics);
+        } else {
+          check_diagnostics(&diagnostics)?;
+        }
       }
 
       doc_nodes_by_url
@@ -186,6 +199,12 @@ pub async fn doc(flags: Flags, doc_flags: DocFlags) -> Result<(), AnyError> {
     };
 
     generate_docs_directory(doc_nodes_by_url, html_options, deno_ns)
+  } else if let Some(markdown_options) = &doc_flags.markdown {
+    generate_markdown_directory(doc_nodes_by_url, markdown_options)
+  } else if let Some(man_options) = &doc_flags.man {
+    generate_man_directory(doc_nodes_by_url, man_options)
+  } else if let Some(coverage_options) = &doc_flags.coverage {
+    report_doc_coverage(&doc_nodes_by_url, coverage_options, doc_flags.json)
   } else {
     let modules_len = doc_nodes_by_url.len();
     let doc_nodes =
@@ -303,6 +322,99 @@ fn generate_docs_directory(
   Ok(())
 }
 
+fn generate_markdown_directory(
+  doc_nodes_by_url: IndexMap<ModuleSpecifier, Vec<doc::DocNode>>,
+  markdown_options: &DocMarkdownFlag,
+) -> Result<(), AnyError> {
+  let cwd = std::env::current_dir().context("Failed to get CWD")?;
+  let base = ModuleSpecifier::from_directory_path(&cwd).unwrap();
+  let modules = markdown::ModuleDocs::collect(&doc_nodes_by_url, &base);
+  let files = markdown::generate(
+    &modules,
+    &markdown::MarkdownOptions {
+      name: markdown_options.name.as_deref(),
+      per_symbol: markdown_options.per_symbol,
+    },
+  );
+  let no_of_files = files.len();
+  write_docs_files(&cwd.join(&markdown_options.output), files)?;
+
+  log::info!(
+    "{}",
+    colors::green(format!(
+      "Written {} files to {:?}",
+      no_of_files, markdown_options.output
+    ))
+  );
+  Ok(())
+}
+
+fn generate_man_directory(
+  doc_nodes_by_url: IndexMap<ModuleSpecifier, Vec<doc::DocNode>>,
+  man_options: &DocManFlag,
+) -> Result<(), AnyError> {
+  let cwd = std::env::current_dir().context("Failed to get CWD")?;
+  let base = ModuleSpecifier::from_directory_path(&cwd).unwrap();
+  let modules = markdown::ModuleDocs::collect(&doc_nodes_by_url, &base);
+  let files = man::generate(&modules, man_options.name.as_deref());
+  if files.is_empty() {
+    bail!("No exported functions were found to generate man pages for.");
+  }
+  let no_of_files = files.len();
+  write_docs_files(&cwd.join(&man_options.output), files)?;
+
+  log::info!(
+    "{}",
+    colors::green(format!(
+      "Written {} files to {:?}",
+      no_of_files, man_options.output
+    ))
+  );
+  Ok(())
+}
+
+/// Writes generated files to a directory, keeping the other files in it so
+/// that the directory can be a checkout of a wiki.
+fn write_docs_files(
+  path: &Path,
+  files: IndexMap<String, String>,
+) -> Result<(), AnyError> {
+  std::fs::create_dir_all(path)
+    .with_context(|| format!("Failed to create directory {:?}", path))?;
+  for (name, content) in files {
+    let this_path = path.join(name);
+    std::fs::write(&this_path, content)
+      .with_context(|| format!("Failed to write file {:?}", this_path))?;
+  }
+  Ok(())
+}
+
+fn report_doc_coverage(
+  doc_nodes_by_url: &IndexMap<ModuleSpecifier, Vec<doc::DocNode>>,
+  coverage_options: &DocCoverageFlag,
+  json: bool,
+) -> Result<(), AnyError> {
+  let cwd = std::env::current_dir().context("Failed to get CWD")?;
+  let base = ModuleSpecifier::from_directory_path(&cwd).unwrap();
+  let report = coverage::DocCoverageReport::new(doc_nodes_by_url, &base);
+  if json {
+    write_json_to_stdout(&report)?;
+  } else {
+    report.print();
+  }
+
+  if let Some(min_coverage) = coverage_options.min_coverage {
+    if report.total.percent < min_coverage as f64 {
+      bail!(
+        "Documentation coverage of {:.1}% is below the minimum of {}%.",
+        report.total.percent,
+        min_coverage
+      );
+    }
+  }
+  Ok(())
+}
+
 fn print_docs_to_stdout(
   doc_flags: DocFlags,
   mut doc_nodes: Vec<deno_doc::DocNode>,
@@ -332,6 +444,15 @@ fn check_diagnostics(diagnostics: &[DocDiagnostic]) -> Result<(), AnyError> {
     return Ok(());
   }
 
+  print_diagnostics(diagnostics);
+  bail!(
+    "Found {} documentation lint error{}.",
+    colors::bold(diagnostics.len().to_string()),
+    if diagnostics.len() == 1 { "" } else { "s" }
+  );
+}
+
+fn print_diagnostics(diagnostics: &[DocDiagnostic]) {
   // group by location then by line (sorted) then column (sorted)
   let mut diagnostic_groups = IndexMap::new();
   for diagnostic in diagnostics {
@@ -354,9 +475,4 @@ fn check_diagnostics(diagnostics: &[DocDiagnostic]) -> Result<(), AnyError> {
       }
     }
   }
-  bail!(
-    "Found {} documentation lint error{}.",
-    colors::bold(diagnostics.len().to_string()),
-    if diagnostics.len() == 1 { "" } else { "s" }
-  );
 }
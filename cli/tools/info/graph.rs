@@ -0,0 +1,796 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::collections::VecDeque;
+use std::fmt::Write;
+
+use deno_ast::ModuleSpecifier;
+use deno_core::anyhow::bail;
+use deno_core::error::AnyError;
+use deno_graph::Dependency;
+use deno_graph::Module;
+use deno_graph::ModuleGraph;
+use deno_graph::Resolution;
+use deno_npm::NpmPackageId;
+use deno_npm::NpmResolutionPackage;
+use regex::Regex;
+
+use crate::args::jsr_url;
+use crate::args::InfoGraphFormat;
+use crate::display;
+use crate::util::wildcard::wildcard_pattern_to_regex;
+
+use super::NpmInfo;
+
+/// Where a module comes from, which determines its colour in the output.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ModuleOrigin {
+  Local,
+  Remote,
+  Jsr,
+  Npm,
+  Node,
+}
+
+impl ModuleOrigin {
+  const ALL: [ModuleOrigin; 5] = [
+    ModuleOrigin::Local,
+    ModuleOrigin::Remote,
+    ModuleOrigin::Jsr,
+    ModuleOrigin::Npm,
+    ModuleOrigin::Node,
+  ];
+
+  pub fn from_specifier(specifier: &ModuleSpecifier) -> Self {
+    match specifier.scheme() {
+      "file" => ModuleOrigin::Local,
+      "npm" => ModuleOrigin::Npm,
+      "node" => ModuleOrigin::Node,
+      "jsr" => ModuleOrigin::Jsr,
+      _ if specifier.as_str().starts_with(jsr_url().as_str()) => {
+        ModuleOrigin::Jsr
+      }
+      _ => ModuleOrigin::Remote,
+    }
+  }
+
+  pub fn name(&self) -> &'static str {
+    match self {
+      ModuleOrigin::Local => "local",
+      ModuleOrigin::Remote => "remote",
+      ModuleOrigin::Jsr => "jsr",
+      ModuleOrigin::Npm => "npm",
+      ModuleOrigin::Node => "node",
+    }
+  }
+
+  pub fn fill_color(&self) -> &'static str {
+    match self {
+      ModuleOrigin::Local => "#a5d6a7",
+      ModuleOrigin::Remote => "#90caf9",
+      ModuleOrigin::Jsr => "#ffe082",
+      ModuleOrigin::Npm => "#ef9a9a",
+      ModuleOrigin::Node => "#ce93d8",
+    }
+  }
+
+  pub fn stroke_color(&self) -> &'static str {
+    match self {
+      ModuleOrigin::Local => "#2e7d32",
+      ModuleOrigin::Remote => "#1565c0",
+      ModuleOrigin::Jsr => "#ff8f00",
+      ModuleOrigin::Npm => "#c62828",
+      ModuleOrigin::Node => "#6a1b9a",
+    }
+  }
+}
+
+const CYCLE_COLOR: &str = "#d32f2f";
+
+#[derive(Debug)]
+pub struct GraphNode {
+  pub name: String,
+  pub origin: ModuleOrigin,
+  pub size: Option<u64>,
+  /// The id of the npm package when the node is an npm package.
+  pub package_id: Option<NpmPackageId>,
+}
+
+impl GraphNode {
+  fn label(&self) -> String {
+    match self.size {
+      Some(size) => {
+        format!("{}\n{}", self.name, display::human_size(size as f64))
+      }
+      None => self.name.clone(),
+    }
+  }
+
+  /// Scale of the node in the output based on its size, from 1 for modules
+  /// of 1KB or less up to 4 for modules of 1MB or more.
+  fn scale(&self) -> f64 {
+    let size = self.size.unwrap_or(0).max(1) as f64;
+    1.0 + (size / 1000.0).log10().clamp(0.0, 3.0)
+  }
+}
+
+#[derive(Debug)]
+pub struct GraphEdge {
+  pub from: usize,
+  pub to: usize,
+  pub dynamic: bool,
+}
+
+/// Options for filtering the modules that are included in the graph.
+#[derive(Default)]
+pub struct GraphFilterOptions {
+  /// Only include modules that are at most this many imports away from
+  /// the root.
+  pub max_depth: Option<usize>,
+  /// Exclude the modules matching any of these patterns, along with the
+  /// dependencies that are only imported through them.
+  pub exclude: Vec<Regex>,
+  /// Only include the dependencies that are loaded at runtime, skipping
+  /// type dependencies.
+  pub code_only: bool,
+}
+
+impl GraphFilterOptions {
+  pub fn is_excluded(&self, name: &str) -> bool {
+    self.exclude.iter().any(|pattern| pattern.is_match(name))
+  }
+}
+
+/// Converts `--exclude` patterns, where `*` matches any characters, to
+/// regexes.
+pub fn exclude_patterns_to_regexes(
+  patterns: &[String],
+) -> Result<Vec<Regex>, AnyError> {
+  patterns
+    .iter()
+    .map(|pattern| wildcard_pattern_to_regex(pattern))
+    .collect()
+}
+
+#[derive(Clone, Copy)]
+enum NodeKey<'a> {
+  Module(&'a Module),
+  Package(&'a NpmResolutionPackage),
+}
+
+/// A module graph where npm packages are collapsed to a single node, for
+/// displaying it.
+#[derive(Debug, Default)]
+pub struct DependencyGraph {
+  pub nodes: Vec<GraphNode>,
+  pub edges: Vec<GraphEdge>,
+}
+
+impl DependencyGraph {
+  pub fn build(
+    graph: &ModuleGraph,
+    npm_info: &NpmInfo,
+    options: &GraphFilterOptions,
+  ) -> Result<Self, AnyError> {
+    if graph.roots.len() != 1 {
+      bail!("displaying graphs that have multiple roots is not supported.");
+    }
+    let root_specifier = graph.resolve(&graph.roots[0]);
+    let Ok(Some(root)) = graph.try_get(&root_specifier) else {
+      bail!("module could not be found");
+    };
+
+    let mut builder = DependencyGraphBuilder {
+      graph,
+      npm_info,
+      options,
+      dependency_graph: Default::default(),
+      indexes: Default::default(),
+      edge_indexes: Default::default(),
+    };
+    let root_key = builder.resolve_module_key(root);
+    let (root_index, _) = builder.get_or_add_node(root_key);
+    let mut pending = VecDeque::from([(root_key, root_index, 0)]);
+    while let Some((key, index, depth)) = pending.pop_front() {
+      if options
+        .max_depth
+        .is_some_and(|max_depth| depth >= max_depth)
+      {
+        continue;
+      }
+      for (child_key, dynamic) in builder.children(key) {
+        let (child_index, is_new) = builder.get_or_add_node(child_key);
+        if is_new {
+          pending.push_back((child_key, child_index, depth + 1));
+        }
+        builder.add_edge(index, child_index, dynamic);
+      }
+    }
+    Ok(builder.dependency_graph)
+  }
+
+  /// Finds the import cycles in the graph, returning one cycle for each
+  /// group of modules that import each other.
+  pub fn cycles(&self) -> Vec<Vec<usize>> {
+    let mut adjacency = vec![Vec::new(); self.nodes.len()];
+    for edge in &self.edges {
+      adjacency[edge.from].push(edge.to);
+    }
+    let mut cycles = Vec::new();
+    for component in strongly_connected_components(&adjacency) {
+      let start = *component.iter().min().unwrap();
+      if component.len() == 1 && !adjacency[start].contains(&start) {
+        continue;
+      }
+      let members = component.into_iter().collect::<HashSet<_>>();
+      // find the shortest path back to the start within the component
+      let mut previous = HashMap::new();
+      let mut pending = VecDeque::from([start]);
+      'search: while let Some(index) = pending.pop_front() {
+        for &to in &adjacency[index] {
+          if to == start {
+            let mut cycle = vec![index];
+            let mut current = index;
+            while let Some(&prev) = previous.get(&current) {
+              cycle.push(prev);
+              current = prev;
+            }
+            cycle.reverse();
+            cycles.push(cycle);
+            break 'search;
+          }
+          if members.contains(&to) && !previous.contains_key(&to) {
+            previous.insert(to, index);
+            pending.push_back(to);
+          }
+        }
+      }
+    }
+    cycles.sort();
+    cycles
+  }
+
+  pub fn write<TWrite: Write>(
+    &self,
+    format: InfoGraphFormat,
+    writer: &mut TWrite,
+  ) -> Result<(), AnyError> {
+    match format {
+      InfoGraphFormat::Dot => self.write_dot(writer),
+      InfoGraphFormat::Mermaid => self.write_mermaid(writer),
+      InfoGraphFormat::Html => self.write_html(writer),
+    }
+  }
+
+  fn cycle_edges(&self) -> HashSet<(usize, usize)> {
+    let mut edges = HashSet::new();
+    for cycle in self.cycles() {
+      for (i, from) in cycle.iter().enumerate() {
+        edges.insert((*from, cycle[(i + 1) % cycle.len()]));
+      }
+    }
+    edges
+  }
+
+  fn write_dot<TWrite: Write>(
+    &self,
+    writer: &mut TWrite,
+  ) -> Result<(), AnyError> {
+    fn escape(text: &str) -> String {
+      text
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+    }
+
+    let cycle_edges = self.cycle_edges();
+    writeln!(writer, "digraph {{")?;
+    writeln!(
+      writer,
+      "  node [shape=box, style=\"rounded,filled\", fontname=\"Helvetica\"];"
+    )?;
+    for (index, node) in self.nodes.iter().enumerate() {
+      writeln!(
+        writer,
+        "  {} [label=\"{}\", fillcolor=\"{}\", color=\"{}\", fontsize={:.0}];",
+        index,
+        escape(&node.label()),
+        node.origin.fill_color(),
+        node.origin.stroke_color(),
+        node.scale() * 10.0,
+      )?;
+    }
+    for edge in &self.edges {
+      let mut attributes = Vec::new();
+      if edge.dynamic {
+        attributes.push("style=dashed".to_string());
+      }
+      if cycle_edges.contains(&(edge.from, edge.to)) {
+        attributes.push(format!("color=\"{}\"", CYCLE_COLOR));
+      }
+      if attributes.is_empty() {
+        writeln!(writer, "  {} -> {};", edge.from, edge.to)?;
+      } else {
+        writeln!(
+          writer,
+          "  {} -> {} [{}];",
+          edge.from,
+          edge.to,
+          attributes.join(", ")
+        )?;
+      }
+    }
+    writeln!(writer, "}}")?;
+    Ok(())
+  }
+
+  fn write_mermaid<TWrite: Write>(
+    &self,
+    writer: &mut TWrite,
+  ) -> Result<(), AnyError> {
+    fn escape(text: &str) -> String {
+      text.replace('"', "#quot;").replace('\n', "<br/>")
+    }
+
+    let cycle_edges = self.cycle_edges();
+    writeln!(writer, "flowchart LR")?;
+    for origin in ModuleOrigin::ALL {
+      writeln!(
+        writer,
+        "  classDef {} fill:{},stroke:{}",
+        origin.name(),
+        origin.fill_color(),
+        origin.stroke_color()
+      )?;
+    }
+    for (index, node) in self.nodes.iter().enumerate() {
+      writeln!(
+        writer,
+        "  n{}[\"{}\"]:::{}",
+        index,
+        escape(&node.label()),
+        node.origin.name()
+      )?;
+      let scale = node.scale();
+      if scale > 1.0 {
+        writeln!(writer, "  style n{} font-size:{:.0}px", index, scale * 12.0)?;
+      }
+    }
+    for (index, edge) in self.edges.iter().enumerate() {
+      let arrow = if edge.dynamic { "-.->" } else { "-->" };
+      writeln!(writer, "  n{} {} n{}", edge.from, arrow, edge.to)?;
+      if cycle_edges.contains(&(edge.from, edge.to)) {
+        writeln!(writer, "  linkStyle {} stroke:{}", index, CYCLE_COLOR)?;
+      }
+    }
+    Ok(())
+  }
+
+  fn write_html<TWrite: Write>(
+    &self,
+    writer: &mut TWrite,
+  ) -> Result<(), AnyError> {
+    fn escape(text: &str) -> String {
+      text
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+    }
+
+    let title = format!(
+      "Module graph of {}",
+      self
+        .nodes
+        .first()
+        .map(|n| n.name.as_str())
+        .unwrap_or_default()
+    );
+    let mut mermaid = String::new();
+    self.write_mermaid(&mut mermaid)?;
+
+    writeln!(writer, "<!DOCTYPE html>")?;
+    writeln!(writer, "<html>")?;
+    writeln!(writer, "<head>")?;
+    writeln!(writer, "<meta charset=\"utf-8\">")?;
+    writeln!(writer, "<title>{}</title>", escape(&title))?;
+    writeln!(writer, "<style>")?;
+    writeln!(writer, "body {{ font-family: sans-serif; margin: 2em; }}")?;
+    writeln!(
+      writer,
+      ".legend span {{ display: inline-block; padding: 2px 8px; margin-right: 8px; border: 1px solid; border-radius: 4px; }}"
+    )?;
+    writeln!(writer, "</style>")?;
+    writeln!(writer, "</head>")?;
+    writeln!(writer, "<body>")?;
+    writeln!(writer, "<h1>{}</h1>", escape(&title))?;
+    write!(writer, "<p class=\"legend\">")?;
+    for origin in ModuleOrigin::ALL {
+      write!(
+        writer,
+        "<span style=\"background: {}; border-color: {}\">{}</span>",
+        origin.fill_color(),
+        origin.stroke_color(),
+        origin.name()
+      )?;
+    }
+    writeln!(writer, "</p>")?;
+    writeln!(
+      writer,
+      "<p>Modules are sized by their byte size. Dashed arrows are dynamic imports and red arrows are part of an import cycle.</p>"
+    )?;
+    writeln!(writer, "<pre class=\"mermaid\">")?;
+    write!(writer, "{}", escape(&mermaid))?;
+    writeln!(writer, "</pre>")?;
+    let cycles = self.cycles();
+    if !cycles.is_empty() {
+      writeln!(writer, "<h2>Import cycles</h2>")?;
+      writeln!(writer, "<ol>")?;
+      for cycle in &cycles {
+        let names = cycle
+          .iter()
+          .chain(std::iter::once(&cycle[0]))
+          .map(|index| escape(&self.nodes[*index].name))
+          .collect::<Vec<_>>();
+        writeln!(writer, "<li>{}</li>", names.join(" &rarr; "))?;
+      }
+      writeln!(writer, "</ol>")?;
+    }
+    writeln!(writer, "<script type=\"module\">")?;
+    writeln!(
+      writer,
+      "import mermaid from \"https://cdn.jsdelivr.net/npm/mermaid@10/dist/mermaid.esm.min.mjs\";"
+    )?;
+    writeln!(
+      writer,
+      "mermaid.initialize({{ startOnLoad: true, maxTextSize: 10000000, maxEdges: 100000 }});"
+    )?;
+    writeln!(writer, "</script>")?;
+    writeln!(writer, "</body>")?;
+    writeln!(writer, "</html>")?;
+    Ok(())
+  }
+}
+
+struct DependencyGraphBuilder<'a> {
+  graph: &'a ModuleGraph,
+  npm_info: &'a NpmInfo,
+  options: &'a GraphFilterOptions,
+  dependency_graph: DependencyGraph,
+  indexes: HashMap<String, usize>,
+  edge_indexes: HashMap<(usize, usize), usize>,
+}
+
+impl<'a> DependencyGraphBuilder<'a> {
+  fn resolve_module_key(&self, module: &'a Module) -> NodeKey<'a> {
+    match module.npm() {
+      Some(npm) => match self.npm_info.resolve_package(npm.nv_reference.nv()) {
+        Some(package) => NodeKey::Package(package),
+        None => NodeKey::Module(module),
+      },
+      None => NodeKey::Module(module),
+    }
+  }
+
+  fn node_name(&self, key: NodeKey) -> String {
+    match key {
+      NodeKey::Module(module) => module.specifier().to_string(),
+      NodeKey::Package(package) => {
+        format!("npm:/{}", package.id.as_serialized())
+      }
+    }
+  }
+
+  fn get_or_add_node(&mut self, key: NodeKey) -> (usize, bool) {
+    let name = self.node_name(key);
+    if let Some(index) = self.indexes.get(&name) {
+      return (*index, false);
+    }
+    let (origin, size, package_id) = match key {
+      NodeKey::Module(module) => (
+        ModuleOrigin::from_specifier(module.specifier()),
+        match module {
+          Module::Js(module) => Some(module.size() as u64),
+          Module::Json(module) => Some(module.size() as u64),
+          Module::Node(_) | Module::Npm(_) | Module::External(_) => None,
+        },
+        None,
+      ),
+      NodeKey::Package(package) => (
+        ModuleOrigin::Npm,
+        self.npm_info.package_sizes.get(&package.id).copied(),
+        Some(package.id.clone()),
+      ),
+    };
+    let index = self.dependency_graph.nodes.len();
+    self.dependency_graph.nodes.push(GraphNode {
+      name: name.clone(),
+      origin,
+      size,
+      package_id,
+    });
+    self.indexes.insert(name, index);
+    (index, true)
+  }
+
+  fn add_edge(&mut self, from: usize, to: usize, dynamic: bool) {
+    let edges = &mut self.dependency_graph.edges;
+    match self.edge_indexes.get(&(from, to)) {
+      // a static import takes precedence over a dynamic one
+      Some(index) => edges[*index].dynamic &= dynamic,
+      None => {
+        self.edge_indexes.insert((from, to), edges.len());
+        edges.push(GraphEdge { from, to, dynamic });
+      }
+    }
+  }
+
+  /// Gets the keys of the dependencies of a node that aren't excluded and
+  /// whether they're dynamically imported.
+  fn children(&self, key: NodeKey<'a>) -> Vec<(NodeKey<'a>, bool)> {
+    let mut children = Vec::new();
+    match key {
+      NodeKey::Module(module) => {
+        let Some(module) = module.js() else {
+          return children;
+        };
+        if let Some(types_dep) = &module.maybe_types_dependency {
+          if !self.options.code_only {
+            if let Some(child) = self.resolve_child(&types_dep.dependency) {
+              children.push((child, false));
+            }
+          }
+        }
+        for dep in module.dependencies.values() {
+          children.extend(
+            self
+              .resolve_dep_children(dep)
+              .into_iter()
+              .map(|child| (child, dep.is_dynamic)),
+          );
+        }
+      }
+      NodeKey::Package(package) => {
+        let mut deps = package.dependencies.values().collect::<Vec<_>>();
+        deps.sort();
+        for id in deps {
+          if let Some(package) = self.npm_info.packages.get(id) {
+            let key = NodeKey::Package(package);
+            if !self.options.is_excluded(&self.node_name(key)) {
+              children.push((key, false));
+            }
+          }
+        }
+      }
+    }
+    children
+  }
+
+  fn resolve_dep_children(&self, dep: &Dependency) -> Vec<NodeKey<'a>> {
+    let maybe_type = if self.options.code_only {
+      None
+    } else {
+      Some(&dep.maybe_type)
+    };
+    std::iter::once(&dep.maybe_code)
+      .chain(maybe_type)
+      .filter_map(|resolution| self.resolve_child(resolution))
+      .collect()
+  }
+
+  fn resolve_child(&self, resolution: &Resolution) -> Option<NodeKey<'a>> {
+    let Resolution::Ok(resolved) = resolution else {
+      return None;
+    };
+    let specifier = self.graph.resolve(&resolved.specifier);
+    let Ok(Some(module)) = self.graph.try_get(&specifier) else {
+      return None;
+    };
+    if self.options.is_excluded(module.specifier().as_str()) {
+      return None;
+    }
+    let key = self.resolve_module_key(module);
+    if self.options.is_excluded(&self.node_name(key)) {
+      return None;
+    }
+    Some(key)
+  }
+}
+
+/// Tarjan's algorithm for finding the strongly connected components of
+/// a graph.
+fn strongly_connected_components(adjacency: &[Vec<usize>]) -> Vec<Vec<usize>> {
+  struct State<'a> {
+    adjacency: &'a [Vec<usize>],
+    index: usize,
+    indexes: Vec<Option<usize>>,
+    low_links: Vec<usize>,
+    on_stack: Vec<bool>,
+    stack: Vec<usize>,
+    components: Vec<Vec<usize>>,
+  }
+
+  fn visit(state: &mut State, node: usize) {
+    state.indexes[node] = Some(state.index);
+    state.low_links[node] = state.index;
+    state.index += 1;
+    state.stack.push(node);
+    state.on_stack[node] = true;
+
+    for &to in &state.adjacency[node] {
+      match state.indexes[to] {
+        None => {
+          visit(state, to);
+          state.low_links[node] =
+            state.low_links[node].min(state.low_links[to]);
+        }
+        Some(to_index) if state.on_stack[to] => {
+          state.low_links[node] = state.low_links[node].min(to_index);
+        }
+        Some(_) => {}
+      }
+    }
+
+    if Some(state.low_links[node]) == state.indexes[node] {
+      let mut component = Vec::new();
+      while let Some(member) = state.stack.pop() {
+        state.on_stack[member] = false;
+        component.push(member);
+        if member == node {
+          break;
+        }
+      }
+      state.components.push(component);
+    }
+  }
+
+  let mut state = State {
+    adjacency,
+    index: 0,
+    indexes: vec![None; adjacency.len()],
+    low_links: vec![0; adjacency.len()],
+    on_stack: vec![false; adjacency.len()],
+    stack: Vec::new(),
+    components: Vec::new(),
+  };
+  for node in 0..adjacency.len() {
+    if state.indexes[node].is_none() {
+      visit(&mut state, node);
+    }
+  }
+  state.components
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  fn graph(
+    nodes: &[(&str, ModuleOrigin)],
+    edges: &[(usize, usize, bool)],
+  ) -> DependencyGraph {
+    DependencyGraph {
+      nodes: nodes
+        .iter()
+        .map(|(name, origin)| GraphNode {
+          name: name.to_string(),
+          origin: *origin,
+          size: Some(100),
+          package_id: None,
+        })
+        .collect(),
+      edges: edges
+        .iter()
+        .map(|(from, to, dynamic)| GraphEdge {
+          from: *from,
+          to: *to,
+          dynamic: *dynamic,
+        })
+        .collect(),
+    }
+  }
+
+  #[test]
+  fn test_cycles() {
+    let graph = graph(
+      &[
+        ("file:///a.ts", ModuleOrigin::Local),
+        ("file:///b.ts", ModuleOrigin::Local),
+        ("file:///c.ts", ModuleOrigin::Local),
+        ("file:///d.ts", ModuleOrigin::Local),
+        ("file:///e.ts", ModuleOrigin::Local),
+      ],
+      &[
+        (0, 1, false),
+        (1, 2, false),
+        (2, 0, false),
+        (2, 3, false),
+        (3, 3, true),
+        (0, 4, false),
+      ],
+    );
+    assert_eq!(graph.cycles(), vec![vec![0, 1, 2], vec![3]]);
+
+    let graph = graph_without_cycles();
+    assert!(graph.cycles().is_empty());
+  }
+
+  fn graph_without_cycles() -> DependencyGraph {
+    graph(
+      &[
+        ("file:///main.ts", ModuleOrigin::Local),
+        ("https://deno.land/x/mod.ts", ModuleOrigin::Remote),
+        ("npm:/chalk@5.3.0", ModuleOrigin::Npm),
+      ],
+      &[(0, 1, false), (0, 2, true)],
+    )
+  }
+
+  #[test]
+  fn test_write_dot() {
+    let mut output = String::new();
+    graph_without_cycles()
+      .write(InfoGraphFormat::Dot, &mut output)
+      .unwrap();
+    assert_eq!(
+      output,
+      r##"digraph {
+  node [shape=box, style="rounded,filled", fontname="Helvetica"];
+  0 [label="file:///main.ts\n100B", fillcolor="#a5d6a7", color="#2e7d32", fontsize=10];
+  1 [label="https://deno.land/x/mod.ts\n100B", fillcolor="#90caf9", color="#1565c0", fontsize=10];
+  2 [label="npm:/chalk@5.3.0\n100B", fillcolor="#ef9a9a", color="#c62828", fontsize=10];
+  0 -> 1;
+  0 -> 2 [style=dashed];
+}
+"##
+    );
+  }
+
+  #[test]
+  fn test_write_mermaid() {
+    let mut output = String::new();
+    graph(
+      &[
+        ("file:///a.ts", ModuleOrigin::Local),
+        ("file:///b.ts", ModuleOrigin::Local),
+      ],
+      &[(0, 1, false), (1, 0, true)],
+    )
+    .write(InfoGraphFormat::Mermaid, &mut output)
+    .unwrap();
+    assert_eq!(
+      output,
+      r##"flowchart LR
+  classDef local fill:#a5d6a7,stroke:#2e7d32
+  classDef remote fill:#90caf9,stroke:#1565c0
+  classDef jsr fill:#ffe082,stroke:#ff8f00
+  classDef npm fill:#ef9a9a,stroke:#c62828
+  classDef node fill:#ce93d8,stroke:#6a1b9a
+  n0["file:///a.ts<br/>100B"]:::local
+  n1["file:///b.ts<br/>100B"]:::local
+  n0 --> n1
+  linkStyle 0 stroke:#d32f2f
+  n1 -.-> n0
+  linkStyle 1 stroke:#d32f2f
+"##
+    );
+  }
+
+  #[test]
+  fn test_exclude_patterns_to_regexes() {
+    let options = GraphFilterOptions {
+      max_depth: None,
+      code_only: false,
+      exclude: exclude_patterns_to_regexes(&[
+        "npm:*".to_string(),
+        "https://deno.land/x/*/mod.ts".to_string(),
+      ])
+      .unwrap(),
+    };
+    assert!(options.is_excluded("npm:/chalk@5.3.0"));
+    assert!(options.is_excluded("https://deno.land/x/oak/mod.ts"));
+    assert!(!options.is_excluded("https://deno.land/x/oak/deps.ts"));
+    assert!(!options.is_excluded("file:///npm/main.ts"));
+  }
+}
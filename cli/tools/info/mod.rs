@@ -28,6 +28,7 @@ use deno_terminal::colors;
 
 use crate::args::Flags;
 use crate::args::InfoFlags;
+use crate::args::InfoGraphFormat;
 use crate::display;
 use crate::factory::CliFactory;
 use crate::graph_util::graph_lock_or_exit;
@@ -35,9 +36,34 @@ use crate::npm::CliNpmResolver;
 use crate::npm::ManagedCliNpmResolver;
 use crate::util::checksum;
 
+mod graph;
+mod sizes;
+mod standalone;
+mod unused_exports;
+
+use graph::DependencyGraph;
+use graph::GraphFilterOptions;
+
+pub use unused_exports::analyze_module_exports;
+pub use unused_exports::UsedNames;
+
 pub async fn info(flags: Flags, info_flags: InfoFlags) -> Result<(), AnyError> {
   let factory = CliFactory::from_flags(flags)?;
   let cli_options = factory.cli_options();
+  if info_flags.unused_exports {
+    return print_unused_exports(&factory, info_flags).await;
+  }
+  if info_flags.standalone {
+    let initial_cwd = cli_options.initial_cwd();
+    let exe_path = initial_cwd.join(info_flags.file.unwrap());
+    let maybe_extract_dir = info_flags.extract.map(|dir| initial_cwd.join(dir));
+    return standalone::print_standalone_info(
+      &exe_path,
+      info_flags.json,
+      maybe_extract_dir.as_deref(),
+    )
+    .await;
+  }
   if let Some(specifier) = info_flags.file {
     let module_graph_builder = factory.module_graph_builder().await?;
     let module_graph_creator = factory.module_graph_creator().await?;
@@ -77,13 +103,62 @@ pub async fn info(flags: Flags, info_flags: InfoFlags) -> Result<(), AnyError> {
       lockfile.write().context("Failed writing lockfile.")?;
     }
 
-    if info_flags.json {
+    let filter_options = GraphFilterOptions {
+      max_depth: info_flags.depth,
+      exclude: graph::exclude_patterns_to_regexes(&info_flags.exclude)?,
+      code_only: info_flags.sizes,
+    };
+    if info_flags.sizes {
+      let npm_info = NpmInfo::from_npm_resolver(&graph, npm_resolver.as_ref());
+      let dependency_graph =
+        DependencyGraph::build(&graph, &npm_info, &filter_options)?;
+      let report = sizes::build_size_report(
+        &graph,
+        &dependency_graph,
+        factory.emitter()?,
+        npm_resolver.as_ref(),
+        factory.cli_node_resolver().await?,
+        factory.fs().as_ref(),
+      )?;
+      if info_flags.json {
+        display::write_json_to_stdout(&report)?;
+      } else {
+        let mut output = String::new();
+        match info_flags.format {
+          Some(InfoGraphFormat::Html) => {
+            sizes::write_treemap_html(&report, &mut output)?
+          }
+          Some(_) => bail!("The size report only supports --format=html."),
+          None => sizes::write_size_report(&report, &mut output)?,
+        }
+        display::write_to_stdout_ignore_sigpipe(output.as_bytes())?;
+      }
+    } else if let Some(format) = info_flags.format {
+      let npm_info = NpmInfo::from_npm_resolver(&graph, npm_resolver.as_ref());
+      let dependency_graph =
+        DependencyGraph::build(&graph, &npm_info, &filter_options)?;
+      let mut output = String::new();
+      dependency_graph.write(format, &mut output)?;
+      display::write_to_stdout_ignore_sigpipe(output.as_bytes())?;
+    } else if info_flags.cycles {
+      let npm_info = NpmInfo::from_npm_resolver(&graph, npm_resolver.as_ref());
+      let dependency_graph =
+        DependencyGraph::build(&graph, &npm_info, &filter_options)?;
+      let mut output = String::new();
+      write_cycles(&dependency_graph, &mut output)?;
+      display::write_to_stdout_ignore_sigpipe(output.as_bytes())?;
+    } else if info_flags.json {
       let mut json_graph = json!(graph);
       add_npm_packages_to_json(&mut json_graph, npm_resolver.as_ref());
       display::write_json_to_stdout(&json_graph)?;
     } else {
       let mut output = String::new();
-      GraphDisplayContext::write(&graph, npm_resolver.as_ref(), &mut output)?;
+      GraphDisplayContext::write(
+        &graph,
+        npm_resolver.as_ref(),
+        &filter_options,
+        &mut output,
+      )?;
       display::write_to_stdout_ignore_sigpipe(output.as_bytes())?;
     }
   } else {
@@ -97,6 +172,99 @@ pub async fn info(flags: Flags, info_flags: InfoFlags) -> Result<(), AnyError> {
   Ok(())
 }
 
+fn write_cycles<TWrite: Write>(
+  dependency_graph: &DependencyGraph,
+  writer: &mut TWrite,
+) -> Result<(), AnyError> {
+  let cycles = dependency_graph.cycles();
+  if cycles.is_empty() {
+    writeln!(writer, "No import cycles found.")?;
+    return Ok(());
+  }
+  for cycle in &cycles {
+    writeln!(writer, "{}", dependency_graph.nodes[cycle[0]].name)?;
+    for index in cycle.iter().skip(1).chain(std::iter::once(&cycle[0])) {
+      writeln!(
+        writer,
+        "  {} {}",
+        colors::gray("->"),
+        dependency_graph.nodes[*index].name
+      )?;
+    }
+    writeln!(writer)?;
+  }
+  writeln!(
+    writer,
+    "Found {} import cycle{}.",
+    cycles.len(),
+    if cycles.len() == 1 { "" } else { "s" },
+  )?;
+  Ok(())
+}
+
+async fn print_unused_exports(
+  factory: &CliFactory,
+  info_flags: InfoFlags,
+) -> Result<(), AnyError> {
+  let cli_options = factory.cli_options();
+  let entry_points = match &info_flags.file {
+    Some(file) => vec![resolve_url_or_path(file, cli_options.initial_cwd())?],
+    None => match cli_options.maybe_config_file() {
+      Some(config_file) => config_file.resolve_export_value_urls()?,
+      None => Vec::new(),
+    },
+  };
+  if entry_points.is_empty() {
+    bail!(
+      "Finding unused exports requires a file or a configuration file with an \"exports\" field."
+    );
+  }
+
+  let module_graph_creator = factory.module_graph_creator().await?;
+  let graph = module_graph_creator
+    .create_graph(GraphKind::All, entry_points.clone())
+    .await?;
+  module_graph_creator.graph_valid(&graph)?;
+  let unused_exports = unused_exports::find_unused_exports(
+    &graph,
+    factory.parsed_source_cache(),
+    &entry_points,
+  )?;
+
+  if info_flags.json {
+    display::write_json_to_stdout(&json!(unused_exports
+      .iter()
+      .map(|e| json!({
+        "specifier": e.specifier,
+        "name": e.name,
+        "line": e.line,
+        "column": e.column,
+      }))
+      .collect::<Vec<_>>()))?;
+  } else {
+    let mut output = String::new();
+    for unused_export in &unused_exports {
+      writeln!(
+        output,
+        "{} {}",
+        colors::bold(&unused_export.name),
+        colors::gray(format!(
+          "at {}:{}:{}",
+          unused_export.specifier, unused_export.line, unused_export.column
+        )),
+      )?;
+    }
+    writeln!(
+      output,
+      "Found {} unused export{}.",
+      unused_exports.len(),
+      if unused_exports.len() == 1 { "" } else { "s" },
+    )?;
+    display::write_to_stdout_ignore_sigpipe(output.as_bytes())?;
+  }
+  Ok(())
+}
+
 fn print_cache_info(
   factory: &CliFactory,
   json: bool,
@@ -349,6 +517,19 @@ struct NpmInfo {
 }
 
 impl NpmInfo {
+  pub fn from_npm_resolver(
+    graph: &ModuleGraph,
+    npm_resolver: &dyn CliNpmResolver,
+  ) -> Self {
+    match npm_resolver.as_managed() {
+      Some(npm_resolver) => {
+        let npm_snapshot = npm_resolver.snapshot();
+        NpmInfo::build(graph, npm_resolver, &npm_snapshot)
+      }
+      None => NpmInfo::default(),
+    }
+  }
+
   pub fn build<'a>(
     graph: &'a ModuleGraph,
     npm_resolver: &'a ManagedCliNpmResolver,
@@ -405,30 +586,36 @@ impl NpmInfo {
 struct GraphDisplayContext<'a> {
   graph: &'a ModuleGraph,
   npm_info: NpmInfo,
+  filter_options: &'a GraphFilterOptions,
   seen: HashSet<String>,
+  /// The depth of the module that's currently being built.
+  depth: usize,
 }
 
 impl<'a> GraphDisplayContext<'a> {
   pub fn write<TWrite: Write>(
     graph: &'a ModuleGraph,
     npm_resolver: &'a dyn CliNpmResolver,
+    filter_options: &'a GraphFilterOptions,
     writer: &mut TWrite,
   ) -> Result<(), AnyError> {
-    let npm_info = match npm_resolver.as_managed() {
-      Some(npm_resolver) => {
-        let npm_snapshot = npm_resolver.snapshot();
-        NpmInfo::build(graph, npm_resolver, &npm_snapshot)
-      }
-      None => NpmInfo::default(),
-    };
     Self {
       graph,
-      npm_info,
+      npm_info: NpmInfo::from_npm_resolver(graph, npm_resolver),
+      filter_options,
       seen: Default::default(),
+      depth: 0,
     }
     .into_writer(writer)
   }
 
+  fn is_at_max_depth(&self) -> bool {
+    self
+      .filter_options
+      .max_depth
+      .is_some_and(|max_depth| self.depth >= max_depth)
+  }
+
   fn into_writer<TWrite: Write>(
     mut self,
     writer: &mut TWrite,
@@ -556,10 +743,18 @@ impl<'a> GraphDisplayContext<'a> {
       },
       None => Specifier(module.specifier().clone()),
     };
-    let was_seen = !self.seen.insert(match &package_or_specifier {
+    let seen_key = match &package_or_specifier {
       Package(package) => package.id.as_serialized(),
       Specifier(specifier) => specifier.to_string(),
-    });
+    };
+    // modules at the max depth aren't expanded, so they're only marked as
+    // seen once they're found at a lower depth
+    let at_max_depth = self.is_at_max_depth();
+    let was_seen = if at_max_depth {
+      self.seen.contains(&seen_key)
+    } else {
+      !self.seen.insert(seen_key)
+    };
     let header_text = if was_seen {
       let specifier_str = if type_dep {
         colors::italic_gray(module.specifier()).to_string()
@@ -588,7 +783,8 @@ impl<'a> GraphDisplayContext<'a> {
 
     let mut tree_node = TreeNode::from_text(header_text);
 
-    if !was_seen {
+    if !was_seen && !at_max_depth {
+      self.depth += 1;
       match &package_or_specifier {
         Package(package) => {
           tree_node.children.extend(self.build_npm_deps(package));
@@ -608,6 +804,7 @@ impl<'a> GraphDisplayContext<'a> {
           }
         }
       }
+      self.depth -= 1;
     }
     tree_node
   }
@@ -620,21 +817,23 @@ impl<'a> GraphDisplayContext<'a> {
     deps.sort();
     let mut children = Vec::with_capacity(deps.len());
     for dep_id in deps.into_iter() {
+      let name = format!("npm:/{}", dep_id.as_serialized());
+      if self.filter_options.is_excluded(&name) {
+        continue;
+      }
       let maybe_size = self.npm_info.package_sizes.get(dep_id).cloned();
       let size_str = maybe_size_to_text(maybe_size);
-      let mut child = TreeNode::from_text(format!(
-        "npm:/{} {}",
-        dep_id.as_serialized(),
-        size_str
-      ));
+      let mut child = TreeNode::from_text(format!("{} {}", name, size_str));
       if let Some(package) = self.npm_info.packages.get(dep_id) {
-        if !package.dependencies.is_empty() {
+        if !package.dependencies.is_empty() && !self.is_at_max_depth() {
           let was_seen = !self.seen.insert(package.id.as_serialized());
           if was_seen {
             child.text = format!("{} {}", child.text, colors::gray("*"));
           } else {
             let package = package.clone();
+            self.depth += 1;
             child.children.extend(self.build_npm_deps(&package));
+            self.depth -= 1;
           }
         }
       }
@@ -643,6 +842,22 @@ impl<'a> GraphDisplayContext<'a> {
     children
   }
 
+  /// Gets if the module is excluded by its specifier or, for npm modules,
+  /// by its resolved package.
+  fn is_excluded(&self, module: &Module) -> bool {
+    if self.filter_options.is_excluded(module.specifier().as_str()) {
+      return true;
+    }
+    module
+      .npm()
+      .and_then(|npm| self.npm_info.resolve_package(npm.nv_reference.nv()))
+      .is_some_and(|package| {
+        self
+          .filter_options
+          .is_excluded(&format!("npm:/{}", package.id.as_serialized()))
+      })
+  }
+
   fn build_error_info(
     &mut self,
     err: &ModuleError,
@@ -705,7 +920,12 @@ impl<'a> GraphDisplayContext<'a> {
         let specifier = &resolved.specifier;
         let resolved_specifier = self.graph.resolve(specifier);
         Some(match self.graph.try_get(&resolved_specifier) {
-          Ok(Some(module)) => self.build_module_info(module, type_dep),
+          Ok(Some(module)) => {
+            if self.is_excluded(module) {
+              return None;
+            }
+            self.build_module_info(module, type_dep)
+          }
           Err(err) => self.build_error_info(err, &resolved_specifier),
           Ok(None) => TreeNode::from_text(format!(
             "{} {}",
@@ -0,0 +1,683 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::fmt::Write as _;
+use std::io::Write as _;
+
+use deno_ast::MediaType;
+use deno_ast::ModuleSpecifier;
+use deno_core::error::AnyError;
+use deno_core::serde_json;
+use deno_core::serde_json::json;
+use deno_graph::Module;
+use deno_graph::ModuleGraph;
+use deno_npm::NpmPackageId;
+use deno_runtime::deno_fs::FileSystem;
+use deno_runtime::deno_node::NodeResolution;
+use deno_runtime::deno_node::NodeResolutionMode;
+use deno_runtime::permissions::PermissionsContainer;
+use deno_terminal::colors;
+use serde::Serialize;
+
+use crate::args::jsr_url;
+use crate::display;
+use crate::emit::Emitter;
+use crate::npm::CliNpmResolver;
+use crate::npm::ManagedCliNpmResolver;
+use crate::resolver::CliNodeResolver;
+use crate::tools::bundle::analyze_dependencies;
+use crate::tools::bundle::minify;
+
+use super::graph::DependencyGraph;
+use super::graph::ModuleOrigin;
+
+/// The size of emitted code along with estimates of its minified and gzipped
+/// sizes.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize)]
+pub struct Sizes {
+  pub emitted: u64,
+  pub minified: u64,
+  pub gzip: u64,
+}
+
+impl Sizes {
+  pub fn measure(code: &str) -> Self {
+    // the same minification as `deno bundle --minify`
+    let (minified, _) = minify(code, &[]);
+    Self {
+      emitted: code.len() as u64,
+      minified: minified.len() as u64,
+      gzip: gzip_size(minified.as_bytes()),
+    }
+  }
+
+  fn add(&mut self, other: Sizes) {
+    self.emitted += other.emitted;
+    self.minified += other.minified;
+    self.gzip += other.gzip;
+  }
+}
+
+fn gzip_size(bytes: &[u8]) -> u64 {
+  let mut encoder =
+    flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+  encoder.write_all(bytes).unwrap();
+  encoder.finish().unwrap().len() as u64
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModuleSizeInfo {
+  pub specifier: String,
+  /// The jsr or npm package the module belongs to.
+  pub package: Option<String>,
+  pub size: Sizes,
+  /// The size of the module and all the modules it imports.
+  pub transitive_size: Sizes,
+  #[serde(skip)]
+  origin: ModuleOrigin,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PackageSizeInfo {
+  pub name: String,
+  pub size: Sizes,
+}
+
+/// A package that's resolved at multiple versions.
+#[derive(Debug, Serialize)]
+pub struct DuplicatePackage {
+  pub name: String,
+  pub versions: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SizeReport {
+  pub root: String,
+  pub total: Sizes,
+  /// The modules sorted by their transitive size, largest first.
+  pub modules: Vec<ModuleSizeInfo>,
+  /// The packages sorted by their size, largest first.
+  pub packages: Vec<PackageSizeInfo>,
+  pub duplicates: Vec<DuplicatePackage>,
+}
+
+/// Builds a size report from a graph of the code dependencies of a module.
+///
+/// Modules are measured by their emitted code. npm packages are measured by
+/// their modules that are reachable from the entrypoints the graph imports.
+pub fn build_size_report(
+  graph: &ModuleGraph,
+  dependency_graph: &DependencyGraph,
+  emitter: &Emitter,
+  npm_resolver: &dyn CliNpmResolver,
+  node_resolver: &CliNodeResolver,
+  fs: &dyn FileSystem,
+) -> Result<SizeReport, AnyError> {
+  let npm_package_sizes = match npm_resolver.as_managed() {
+    Some(npm_resolver) => {
+      measure_npm_packages(graph, npm_resolver, node_resolver, fs)
+    }
+    None => HashMap::new(),
+  };
+  let mut node_sizes = Vec::with_capacity(dependency_graph.nodes.len());
+  let mut packages = Vec::with_capacity(dependency_graph.nodes.len());
+  for node in &dependency_graph.nodes {
+    let (size, package) = match &node.package_id {
+      Some(package_id) => {
+        let size = npm_package_sizes
+          .get(package_id)
+          .copied()
+          .unwrap_or_default();
+        let package = PackageName {
+          name: format!("npm:{}", package_id.nv.name),
+          version: package_id.nv.version.to_string(),
+        };
+        (size, Some(package))
+      }
+      None => {
+        let specifier = ModuleSpecifier::parse(&node.name)?;
+        let size = match graph.get(&specifier) {
+          Some(module) => match emitted_code(emitter, module)? {
+            Some(code) => Sizes::measure(&code),
+            None => Sizes::default(),
+          },
+          None => Sizes::default(),
+        };
+        (size, jsr_package_name(&specifier))
+      }
+    };
+    node_sizes.push(size);
+    packages.push(package);
+  }
+
+  let mut adjacency = vec![Vec::new(); dependency_graph.nodes.len()];
+  for edge in &dependency_graph.edges {
+    adjacency[edge.from].push(edge.to);
+  }
+
+  let mut total = Sizes::default();
+  let mut package_sizes = BTreeMap::<String, Sizes>::new();
+  let mut package_versions = BTreeMap::<String, BTreeSet<String>>::new();
+  let mut modules = Vec::with_capacity(dependency_graph.nodes.len());
+  for (index, node) in dependency_graph.nodes.iter().enumerate() {
+    let size = node_sizes[index];
+    total.add(size);
+    let package = packages[index].as_ref().map(|package| {
+      let display_name = package.display_name();
+      package_sizes
+        .entry(display_name.clone())
+        .or_default()
+        .add(size);
+      package_versions
+        .entry(package.name.clone())
+        .or_default()
+        .insert(package.version.clone());
+      display_name
+    });
+
+    // sum the sizes of all the modules that are reachable from this one
+    let mut transitive_size = Sizes::default();
+    let mut visited = vec![false; dependency_graph.nodes.len()];
+    let mut pending = vec![index];
+    visited[index] = true;
+    while let Some(current) = pending.pop() {
+      transitive_size.add(node_sizes[current]);
+      for &to in &adjacency[current] {
+        if !visited[to] {
+          visited[to] = true;
+          pending.push(to);
+        }
+      }
+    }
+
+    modules.push(ModuleSizeInfo {
+      specifier: node.name.clone(),
+      package,
+      size,
+      transitive_size,
+      origin: node.origin,
+    });
+  }
+  modules.sort_by(|a, b| {
+    b.transitive_size
+      .emitted
+      .cmp(&a.transitive_size.emitted)
+      .then_with(|| a.specifier.cmp(&b.specifier))
+  });
+
+  let mut packages = package_sizes
+    .into_iter()
+    .map(|(name, size)| PackageSizeInfo { name, size })
+    .collect::<Vec<_>>();
+  packages.sort_by(|a, b| b.size.emitted.cmp(&a.size.emitted));
+
+  let duplicates = package_versions
+    .into_iter()
+    .filter(|(_, versions)| versions.len() > 1)
+    .map(|(name, versions)| DuplicatePackage {
+      name,
+      versions: versions.into_iter().collect(),
+    })
+    .collect();
+
+  Ok(SizeReport {
+    root: dependency_graph
+      .nodes
+      .first()
+      .map(|node| node.name.clone())
+      .unwrap_or_default(),
+    total,
+    modules,
+    packages,
+    duplicates,
+  })
+}
+
+struct PackageName {
+  name: String,
+  version: String,
+}
+
+impl PackageName {
+  fn display_name(&self) -> String {
+    format!("{}@{}", self.name, self.version)
+  }
+}
+
+/// Gets the name of the jsr package of a module from its specifier, which
+/// is in the form `https://jsr.io/@scope/name/version/path`.
+fn jsr_package_name(specifier: &ModuleSpecifier) -> Option<PackageName> {
+  let path = specifier.as_str().strip_prefix(jsr_url().as_str())?;
+  let mut parts = path.split('/');
+  let scope = parts.next().filter(|scope| scope.starts_with('@'))?;
+  let name = parts.next()?;
+  let version = parts.next()?;
+  Some(PackageName {
+    name: format!("jsr:{}/{}", scope, name),
+    version: version.to_string(),
+  })
+}
+
+/// Gets the code of the module that's loaded at runtime, which is `None`
+/// for modules that only contain types.
+fn emitted_code(
+  emitter: &Emitter,
+  module: &Module,
+) -> Result<Option<String>, AnyError> {
+  match module {
+    Module::Js(module) => match module.media_type {
+      MediaType::TypeScript
+      | MediaType::Mts
+      | MediaType::Cts
+      | MediaType::Jsx
+      | MediaType::Tsx => {
+        Ok(Some(emitter.emit_parsed_source_without_source_map(
+          &module.specifier,
+          module.media_type,
+          &module.source,
+        )?))
+      }
+      MediaType::Dts | MediaType::Dmts | MediaType::Dcts => Ok(None),
+      _ => Ok(Some(module.source.to_string())),
+    },
+    Module::Json(module) => Ok(Some(module.source.to_string())),
+    Module::Node(_) | Module::Npm(_) | Module::External(_) => Ok(None),
+  }
+}
+
+/// Measures the modules of npm packages that are reachable from the npm
+/// specifiers in the graph, by package. Modules that fail to resolve, read or
+/// parse are skipped.
+fn measure_npm_packages(
+  graph: &ModuleGraph,
+  npm_resolver: &ManagedCliNpmResolver,
+  node_resolver: &CliNodeResolver,
+  fs: &dyn FileSystem,
+) -> HashMap<NpmPackageId, Sizes> {
+  fn resolution_specifier(
+    resolution: NodeResolution,
+  ) -> Option<ModuleSpecifier> {
+    match resolution {
+      NodeResolution::Esm(specifier) | NodeResolution::CommonJs(specifier) => {
+        Some(specifier)
+      }
+      NodeResolution::BuiltIn(_) => None,
+    }
+  }
+
+  let permissions = PermissionsContainer::allow_all();
+  let mut pending = Vec::new();
+  for module in graph.modules() {
+    let Module::Npm(module) = module else {
+      continue;
+    };
+    let nv_reference = &module.nv_reference;
+    let resolution = npm_resolver
+      .resolve_pkg_folder_from_deno_module(nv_reference.nv())
+      .ok()
+      .and_then(|package_folder| {
+        node_resolver
+          .resolve_package_sub_path_from_deno_module(
+            &package_folder,
+            nv_reference.sub_path(),
+            &module.specifier,
+            NodeResolutionMode::Execution,
+            &permissions,
+          )
+          .ok()
+          .flatten()
+      });
+    pending.extend(resolution.and_then(resolution_specifier));
+  }
+
+  let mut sizes = HashMap::<NpmPackageId, Sizes>::new();
+  let mut visited = HashSet::new();
+  while let Some(specifier) = pending.pop() {
+    if !visited.insert(specifier.clone()) {
+      continue;
+    }
+    let Ok(path) = specifier.to_file_path() else {
+      continue;
+    };
+    let Ok(source) = fs.read_text_file_sync(&path, None) else {
+      continue;
+    };
+    if let Ok(Some(package_id)) =
+      npm_resolver.resolve_pkg_id_from_specifier(&specifier)
+    {
+      sizes
+        .entry(package_id)
+        .or_default()
+        .add(Sizes::measure(&source));
+    }
+    let media_type = MediaType::from_specifier(&specifier);
+    if media_type == MediaType::Json {
+      continue;
+    }
+    let Ok(parsed_source) = deno_ast::parse_program(deno_ast::ParseParams {
+      specifier: specifier.clone(),
+      text_info: deno_ast::SourceTextInfo::new(source.into()),
+      media_type,
+      capture_tokens: false,
+      scope_analysis: false,
+      maybe_syntax: None,
+    }) else {
+      continue;
+    };
+    for (text, _) in analyze_dependencies(&parsed_source) {
+      let resolution = node_resolver
+        .resolve(
+          &text,
+          &specifier,
+          NodeResolutionMode::Execution,
+          &permissions,
+        )
+        .ok()
+        .flatten();
+      pending.extend(resolution.and_then(resolution_specifier));
+    }
+  }
+  sizes
+}
+
+fn sizes_row(sizes: &Sizes) -> String {
+  format!(
+    "{:>10} {:>10} {:>10}",
+    display::human_size(sizes.emitted as f64),
+    display::human_size(sizes.minified as f64),
+    display::human_size(sizes.gzip as f64),
+  )
+}
+
+pub fn write_size_report<TWrite: std::fmt::Write>(
+  report: &SizeReport,
+  writer: &mut TWrite,
+) -> Result<(), AnyError> {
+  let header = format!("{:>10} {:>10} {:>10}", "emitted", "minified", "gzip");
+  writeln!(writer, "{} {}", colors::bold("size report:"), report.root)?;
+  writeln!(
+    writer,
+    "{} {} emitted, {} minified, {} gzip",
+    colors::bold("total:"),
+    display::human_size(report.total.emitted as f64),
+    display::human_size(report.total.minified as f64),
+    display::human_size(report.total.gzip as f64),
+  )?;
+  writeln!(
+    writer,
+    "{}",
+    colors::gray("The minified and gzip sizes are estimates.")
+  )?;
+
+  writeln!(writer)?;
+  writeln!(writer, "{}", colors::bold("modules (transitive size):"))?;
+  writeln!(writer, "{}", colors::gray(&header))?;
+  for module in &report.modules {
+    writeln!(
+      writer,
+      "{}  {}",
+      sizes_row(&module.transitive_size),
+      module.specifier
+    )?;
+  }
+
+  if !report.packages.is_empty() {
+    writeln!(writer)?;
+    writeln!(writer, "{}", colors::bold("packages:"))?;
+    writeln!(writer, "{}", colors::gray(&header))?;
+    for package in &report.packages {
+      writeln!(writer, "{}  {}", sizes_row(&package.size), package.name)?;
+    }
+  }
+
+  if !report.duplicates.is_empty() {
+    writeln!(writer)?;
+    writeln!(writer, "{}", colors::bold("duplicate packages:"))?;
+    for duplicate in &report.duplicates {
+      writeln!(
+        writer,
+        "{} {}",
+        colors::yellow(&duplicate.name),
+        duplicate.versions.join(", ")
+      )?;
+    }
+  }
+  Ok(())
+}
+
+/// Writes an HTML page showing the modules as a treemap, grouped by their
+/// package, or by their origin for modules outside of packages.
+pub fn write_treemap_html<TWrite: std::fmt::Write>(
+  report: &SizeReport,
+  writer: &mut TWrite,
+) -> Result<(), AnyError> {
+  let mut groups =
+    BTreeMap::<String, (ModuleOrigin, Vec<serde_json::Value>)>::new();
+  for module in &report.modules {
+    if module.size == Sizes::default() {
+      continue;
+    }
+    let group_name = match &module.package {
+      Some(package) => package.clone(),
+      None => match module.origin {
+        ModuleOrigin::Local => "local".to_string(),
+        ModuleOrigin::Remote => ModuleSpecifier::parse(&module.specifier)
+          .map(|specifier| specifier.origin().ascii_serialization())
+          .unwrap_or_else(|_| "remote".to_string()),
+        origin => origin.name().to_string(),
+      },
+    };
+    groups
+      .entry(group_name)
+      .or_insert_with(|| (module.origin, Vec::new()))
+      .1
+      .push(json!({
+        "name": module.specifier,
+        "size": module.size,
+        "color": module.origin.fill_color(),
+      }));
+  }
+  let data = json!({
+    "name": report.root,
+    "children": groups
+      .into_iter()
+      .map(|(name, (origin, children))| json!({
+        "name": name,
+        "color": origin.stroke_color(),
+        "children": children,
+      }))
+      .collect::<Vec<_>>(),
+  });
+  // prevent the data from closing the script element
+  let data = serde_json::to_string(&data)?.replace("</", "<\\/");
+  write!(
+    writer,
+    "{}",
+    TREEMAP_HTML
+      .replace("{{root}}", &html_escape(&report.root))
+      .replace("{{data}}", &data)
+  )?;
+  Ok(())
+}
+
+fn html_escape(text: &str) -> String {
+  text
+    .replace('&', "&amp;")
+    .replace('<', "&lt;")
+    .replace('>', "&gt;")
+    .replace('"', "&quot;")
+}
+
+const TREEMAP_HTML: &str = r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>Size report of {{root}}</title>
+<style>
+body { font-family: sans-serif; margin: 1em; }
+#treemap { position: relative; width: 100%; height: 80vh; }
+#treemap div { position: absolute; box-sizing: border-box; overflow: hidden; border: 1px solid #fff; font-size: 11px; padding: 1px 3px; white-space: nowrap; }
+#treemap .group { color: #fff; font-weight: bold; }
+</style>
+</head>
+<body>
+<h1>Size report of {{root}}</h1>
+<p>
+<label>Size: <select id="metric">
+<option value="emitted">emitted</option>
+<option value="minified">minified (estimate)</option>
+<option value="gzip">gzip (estimate)</option>
+</select></label>
+<span id="total"></span>
+</p>
+<div id="treemap"></div>
+<script id="data" type="application/json">{{data}}</script>
+<script>
+const data = JSON.parse(document.getElementById("data").textContent);
+const container = document.getElementById("treemap");
+const select = document.getElementById("metric");
+
+function formatSize(size) {
+  const units = ["B", "KB", "MB", "GB"];
+  let unit = 0;
+  while (size >= 1024 && unit < units.length - 1) {
+    size /= 1024;
+    unit++;
+  }
+  return `${Math.round(size * 100) / 100}${units[unit]}`;
+}
+
+function total(node, metric) {
+  if (!node.children) {
+    return node.size[metric];
+  }
+  return node.children.reduce((sum, child) => sum + total(child, metric), 0);
+}
+
+function worst(row, length) {
+  const sum = row.reduce((sum, item) => sum + item.area, 0);
+  const max = Math.max(...row.map((item) => item.area));
+  const min = Math.min(...row.map((item) => item.area));
+  return Math.max(
+    (length * length * max) / (sum * sum),
+    (sum * sum) / (length * length * min),
+  );
+}
+
+// lays out the items in rows that keep the rectangles close to squares
+function squarify(items, x, y, width, height) {
+  const rects = [];
+  let rest = items;
+  while (rest.length > 0) {
+    const length = Math.min(width, height);
+    let count = 1;
+    while (
+      count < rest.length &&
+      worst(rest.slice(0, count + 1), length) <=
+        worst(rest.slice(0, count), length)
+    ) {
+      count++;
+    }
+    const row = rest.slice(0, count);
+    rest = rest.slice(count);
+    const thickness = row.reduce((sum, item) => sum + item.area, 0) / length;
+    let offset = 0;
+    for (const item of row) {
+      const size = item.area / thickness;
+      if (width >= height) {
+        rects.push({ item, x, y: y + offset, width: thickness, height: size });
+      } else {
+        rects.push({ item, x: x + offset, y, width: size, height: thickness });
+      }
+      offset += size;
+    }
+    if (width >= height) {
+      x += thickness;
+      width -= thickness;
+    } else {
+      y += thickness;
+      height -= thickness;
+    }
+  }
+  return rects;
+}
+
+function layout(node, x, y, width, height, metric) {
+  if (width <= 0 || height <= 0) {
+    return;
+  }
+  const children = node.children
+    .map((child) => ({ node: child, value: total(child, metric) }))
+    .filter((child) => child.value > 0)
+    .sort((a, b) => b.value - a.value);
+  const sum = children.reduce((sum, child) => sum + child.value, 0);
+  if (sum === 0) {
+    return;
+  }
+  const scale = (width * height) / sum;
+  const items = children.map((child) => ({ ...child, area: child.value * scale }));
+  for (const rect of squarify(items, x, y, width, height)) {
+    const child = rect.item.node;
+    const element = document.createElement("div");
+    element.className = child.children ? "group" : "module";
+    element.style.left = `${rect.x}px`;
+    element.style.top = `${rect.y}px`;
+    element.style.width = `${rect.width}px`;
+    element.style.height = `${rect.height}px`;
+    element.style.background = child.color;
+    element.title = `${child.name}\n${formatSize(rect.item.value)}`;
+    element.textContent = child.children
+      ? child.name
+      : child.name.split("/").pop();
+    container.append(element);
+    if (child.children) {
+      layout(child, rect.x + 2, rect.y + 16, rect.width - 4, rect.height - 18, metric);
+    }
+  }
+}
+
+function render() {
+  const metric = select.value;
+  container.replaceChildren();
+  document.getElementById("total").textContent = `Total: ${formatSize(total(data, metric))}`;
+  layout(data, 0, 0, container.clientWidth, container.clientHeight, metric);
+}
+
+select.addEventListener("change", render);
+window.addEventListener("resize", render);
+render();
+</script>
+</body>
+</html>
+"#;
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn test_measure() {
+    let code = "export const value = 1;\n".repeat(100);
+    let sizes = Sizes::measure(&code);
+    assert_eq!(sizes.emitted, code.len() as u64);
+    assert_eq!(sizes.minified, "export const value=1;".len() as u64 * 100);
+    assert!(sizes.gzip < sizes.minified);
+  }
+
+  #[test]
+  fn test_jsr_package_name() {
+    let specifier =
+      ModuleSpecifier::parse(&format!("{}@std/path/1.0.0/mod.ts", jsr_url()))
+        .unwrap();
+    let package = jsr_package_name(&specifier).unwrap();
+    assert_eq!(package.display_name(), "jsr:@std/path@1.0.0");
+
+    let specifier =
+      ModuleSpecifier::parse("https://deno.land/std/path/mod.ts").unwrap();
+    assert!(jsr_package_name(&specifier).is_none());
+  }
+}
@@ -0,0 +1,614 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+use std::borrow::Cow;
+use std::collections::HashSet;
+use std::fmt::Write;
+use std::path::Component;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use deno_core::anyhow::bail;
+use deno_core::anyhow::Context;
+use deno_core::error::AnyError;
+use deno_core::serde_json;
+use deno_core::serde_json::json;
+use deno_core::url::Url;
+use deno_terminal::colors;
+
+use crate::args::PermissionFlags;
+use crate::args::UnstableConfig;
+use crate::display;
+use crate::standalone::binary::extract_standalone;
+use crate::standalone::binary::load_assets_vfs;
+use crate::standalone::binary::load_code_cache;
+use crate::standalone::binary::load_npm_vfs;
+use crate::standalone::binary::Metadata;
+use crate::standalone::is_standalone_binary;
+use crate::standalone::virtual_fs::FileBackedVfs;
+use crate::standalone::virtual_fs::VfsEntry;
+use crate::standalone::virtual_fs::VirtualDirectory;
+
+struct EmbeddedModule {
+  specifier: String,
+  kind: &'static str,
+  source: Arc<[u8]>,
+}
+
+/// Everything that `deno compile` embedded in an executable.
+struct StandaloneContents {
+  metadata: Metadata,
+  modules: Vec<EmbeddedModule>,
+  npm_packages: Vec<String>,
+  npm_vfs: Option<FileBackedVfs>,
+  assets_vfs: Option<FileBackedVfs>,
+  code_cache_modules: usize,
+}
+
+pub async fn print_standalone_info(
+  exe_path: &Path,
+  json: bool,
+  maybe_extract_dir: Option<&Path>,
+) -> Result<(), AnyError> {
+  let contents = read_standalone_contents(exe_path).await?;
+  if let Some(extract_dir) = maybe_extract_dir {
+    extract_contents(&contents, extract_dir)?;
+  }
+  if json {
+    display::write_json_to_stdout(&contents_to_json(&contents)?)
+  } else {
+    let mut output = String::new();
+    write_contents(&contents, &mut output)?;
+    display::write_to_stdout_ignore_sigpipe(output.as_bytes())
+  }
+}
+
+async fn read_standalone_contents(
+  exe_path: &Path,
+) -> Result<StandaloneContents, AnyError> {
+  if !is_standalone_binary(exe_path) {
+    bail!(
+      "'{}' is not an executable produced by 'deno compile'.",
+      exe_path.display()
+    );
+  }
+  let Some(future) = extract_standalone(
+    exe_path,
+    Cow::Owned(vec![exe_path.as_os_str().to_os_string()]),
+  )?
+  else {
+    bail!(
+      "'{}' is not an executable produced by 'deno compile'.",
+      exe_path.display()
+    );
+  };
+  let (metadata, mut eszip) = future
+    .await
+    .with_context(|| format!("Failed reading '{}'.", exe_path.display()))?;
+
+  let mut modules = Vec::new();
+  let mut seen = HashSet::new();
+  for specifier in eszip.specifiers() {
+    let Some(module) = eszip.get_module(&specifier) else {
+      continue;
+    };
+    if !seen.insert(module.specifier.clone()) {
+      continue;
+    }
+    let kind = match module.kind {
+      eszip::ModuleKind::JavaScript => "JavaScript",
+      eszip::ModuleKind::Json => "JSON",
+      eszip::ModuleKind::Jsonc => "JSONC",
+      eszip::ModuleKind::OpaqueData => "Data",
+    };
+    let source = module
+      .source()
+      .await
+      .unwrap_or_else(|| Arc::from(Vec::new()));
+    modules.push(EmbeddedModule {
+      specifier: module.specifier.clone(),
+      kind,
+      source,
+    });
+  }
+  modules.sort_by(|a, b| a.specifier.cmp(&b.specifier));
+
+  let mut npm_packages = eszip
+    .take_npm_snapshot()
+    .map(|snapshot| {
+      snapshot
+        .as_serialized()
+        .packages
+        .iter()
+        .map(|package| package.id.as_serialized())
+        .collect::<Vec<_>>()
+    })
+    .unwrap_or_default();
+  npm_packages.sort();
+
+  let npm_vfs = match &metadata.node_modules {
+    Some(_) => Some(
+      load_npm_vfs(exe_path, PathBuf::from("node_modules"))
+        .context("Failed to load npm vfs.")?,
+    ),
+    None => None,
+  };
+  let assets_vfs = match &metadata.assets_root {
+    Some(assets_root) => Some(
+      load_assets_vfs(
+        exe_path,
+        assets_root.clone(),
+        metadata.assets_dirs.clone(),
+      )
+      .context("Failed to load assets vfs.")?,
+    ),
+    None => None,
+  };
+  let code_cache_modules = load_code_cache(exe_path)
+    .context("Failed to load code cache.")?
+    .len();
+
+  Ok(StandaloneContents {
+    metadata,
+    modules,
+    npm_packages,
+    npm_vfs,
+    assets_vfs,
+    code_cache_modules,
+  })
+}
+
+fn contents_to_json(
+  contents: &StandaloneContents,
+) -> Result<serde_json::Value, AnyError> {
+  let metadata = &contents.metadata;
+  Ok(json!({
+    "entrypoint": metadata.entrypoint,
+    "argv": metadata.argv,
+    "location": metadata.location,
+    "seed": metadata.seed,
+    "compressed": metadata.compressed,
+    "codeCacheModules": contents.code_cache_modules,
+    "permissions": permission_flags_to_args(&metadata.permissions)?,
+    "v8Flags": metadata.v8_flags,
+    "unstable": unstable_config_to_args(&metadata.unstable_config),
+    "importMap": metadata.maybe_import_map.as_ref().map(|(base_url, json)| {
+      json!({
+        "baseUrl": base_url,
+        "json": json,
+      })
+    }),
+    "modules": contents.modules.iter().map(|module| {
+      json!({
+        "specifier": module.specifier,
+        "kind": module.kind,
+        "size": module.source.len(),
+      })
+    }).collect::<Vec<_>>(),
+    "npmPackages": contents.npm_packages,
+    "npmFiles": contents
+      .npm_vfs
+      .as_ref()
+      .map(|vfs| vfs_dir_to_json(vfs.root_dir())),
+    "assetsRoot": metadata.assets_root,
+    "assetsDirs": metadata.assets_dirs,
+    "assets": contents
+      .assets_vfs
+      .as_ref()
+      .map(|vfs| vfs_dir_to_json(vfs.root_dir())),
+  }))
+}
+
+fn vfs_dir_to_json(dir: &VirtualDirectory) -> Vec<serde_json::Value> {
+  dir
+    .entries
+    .iter()
+    .map(|entry| match entry {
+      VfsEntry::Dir(dir) => json!({
+        "name": dir.name,
+        "kind": "dir",
+        "entries": vfs_dir_to_json(dir),
+      }),
+      VfsEntry::File(file) => json!({
+        "name": file.name,
+        "kind": "file",
+        "size": file.len,
+      }),
+      VfsEntry::Symlink(symlink) => json!({
+        "name": symlink.name,
+        "kind": "symlink",
+        "target": symlink.dest_parts.join("/"),
+      }),
+    })
+    .collect()
+}
+
+fn write_contents(
+  contents: &StandaloneContents,
+  output: &mut String,
+) -> Result<(), AnyError> {
+  let metadata = &contents.metadata;
+  writeln!(
+    output,
+    "{} {}",
+    colors::bold("entrypoint:"),
+    metadata.entrypoint
+  )?;
+  if !metadata.argv.is_empty() {
+    writeln!(
+      output,
+      "{} {}",
+      colors::bold("args:"),
+      metadata.argv.join(" ")
+    )?;
+  }
+  if let Some(location) = &metadata.location {
+    writeln!(output, "{} {}", colors::bold("location:"), location)?;
+  }
+  if let Some(seed) = metadata.seed {
+    writeln!(output, "{} {}", colors::bold("seed:"), seed)?;
+  }
+  writeln!(
+    output,
+    "{} {}",
+    colors::bold("compressed:"),
+    if metadata.compressed { "yes" } else { "no" }
+  )?;
+  writeln!(
+    output,
+    "{} {}",
+    colors::bold("code cache:"),
+    match contents.code_cache_modules {
+      0 => "none".to_string(),
+      1 => "1 module".to_string(),
+      count => format!("{count} modules"),
+    }
+  )?;
+  write_args(
+    output,
+    "permissions:",
+    &permission_flags_to_args(&metadata.permissions)?,
+  )?;
+  write_args(output, "v8 flags:", &metadata.v8_flags)?;
+  write_args(
+    output,
+    "unstable:",
+    &unstable_config_to_args(&metadata.unstable_config),
+  )?;
+  if let Some((base_url, json)) = &metadata.maybe_import_map {
+    writeln!(output, "{} {}", colors::bold("import map:"), base_url)?;
+    for line in json.lines() {
+      writeln!(output, "  {}", line)?;
+    }
+  }
+
+  writeln!(output)?;
+  writeln!(
+    output,
+    "{} {}",
+    colors::bold("modules:"),
+    colors::gray(format!("({})", contents.modules.len()))
+  )?;
+  for module in &contents.modules {
+    writeln!(
+      output,
+      "  {} {}",
+      module.specifier,
+      colors::gray(format!(
+        "({}, {})",
+        module.kind,
+        display::human_size(module.source.len() as f64)
+      ))
+    )?;
+  }
+
+  if !contents.npm_packages.is_empty() {
+    writeln!(output)?;
+    writeln!(
+      output,
+      "{} {}",
+      colors::bold("npm packages:"),
+      colors::gray(format!("({})", contents.npm_packages.len()))
+    )?;
+    for package in &contents.npm_packages {
+      writeln!(output, "  npm:{}", package)?;
+    }
+  }
+
+  if let Some(vfs) = &contents.npm_vfs {
+    writeln!(output)?;
+    writeln!(output, "{}", colors::bold("npm files:"))?;
+    write_vfs_dir(output, vfs.root_dir(), 1)?;
+  }
+
+  if let (Some(vfs), Some(assets_root)) =
+    (&contents.assets_vfs, &metadata.assets_root)
+  {
+    writeln!(output)?;
+    writeln!(
+      output,
+      "{} {}",
+      colors::bold("assets:"),
+      colors::gray(format!("({})", assets_root.display()))
+    )?;
+    write_vfs_dir(output, vfs.root_dir(), 1)?;
+  }
+  Ok(())
+}
+
+fn write_args(
+  output: &mut String,
+  label: &str,
+  args: &[String],
+) -> Result<(), AnyError> {
+  if args.is_empty() {
+    writeln!(output, "{} {}", colors::bold(label), colors::gray("none"))?;
+  } else {
+    writeln!(output, "{} {}", colors::bold(label), args.join(" "))?;
+  }
+  Ok(())
+}
+
+fn write_vfs_dir(
+  output: &mut String,
+  dir: &VirtualDirectory,
+  depth: usize,
+) -> Result<(), AnyError> {
+  let indent = "  ".repeat(depth);
+  for entry in &dir.entries {
+    match entry {
+      VfsEntry::Dir(dir) => {
+        writeln!(output, "{}{}/", indent, dir.name)?;
+        write_vfs_dir(output, dir, depth + 1)?;
+      }
+      VfsEntry::File(file) => {
+        writeln!(
+          output,
+          "{}{} {}",
+          indent,
+          file.name,
+          colors::gray(format!("({})", display::human_size(file.len as f64)))
+        )?;
+      }
+      VfsEntry::Symlink(symlink) => {
+        writeln!(
+          output,
+          "{}{} -> {}",
+          indent,
+          symlink.name,
+          symlink.dest_parts.join("/")
+        )?;
+      }
+    }
+  }
+  Ok(())
+}
+
+/// Formats the permissions as the flags that would grant them to
+/// `deno run`.
+fn permission_flags_to_args(
+  permissions: &PermissionFlags,
+) -> Result<Vec<String>, AnyError> {
+  let serde_json::Value::Object(permissions) =
+    serde_json::to_value(permissions)?
+  else {
+    unreachable!();
+  };
+  let mut args = Vec::new();
+  for (name, value) in permissions {
+    let flag = format!("--{}", name.replace('_', "-"));
+    match value {
+      serde_json::Value::Bool(true) => args.push(flag),
+      serde_json::Value::Array(values) if values.is_empty() => args.push(flag),
+      serde_json::Value::Array(values) => {
+        let values = values
+          .iter()
+          .filter_map(|value| value.as_str())
+          .collect::<Vec<_>>();
+        args.push(format!("{}={}", flag, values.join(",")));
+      }
+      _ => {}
+    }
+  }
+  Ok(args)
+}
+
+fn unstable_config_to_args(config: &UnstableConfig) -> Vec<String> {
+  let mut args = Vec::new();
+  if config.legacy_flag_enabled {
+    args.push("--unstable".to_string());
+  }
+  if config.bare_node_builtins {
+    args.push("--unstable-bare-node-builtins".to_string());
+  }
+  if config.byonm {
+    args.push("--unstable-byonm".to_string());
+  }
+  if config.sloppy_imports {
+    args.push("--unstable-sloppy-imports".to_string());
+  }
+  for feature in &config.features {
+    args.push(format!("--unstable-{}", feature));
+  }
+  args
+}
+
+/// Writes the embedded modules, npm files and assets to the given directory,
+/// along with the metadata the executable runs with.
+fn extract_contents(
+  contents: &StandaloneContents,
+  dir: &Path,
+) -> Result<(), AnyError> {
+  std::fs::create_dir_all(dir)
+    .with_context(|| format!("Failed creating '{}'.", dir.display()))?;
+  std::fs::write(
+    dir.join("metadata.json"),
+    serde_json::to_string_pretty(&contents.metadata)?,
+  )?;
+  if let Some((_, json)) = &contents.metadata.maybe_import_map {
+    std::fs::write(dir.join("import_map.json"), json)?;
+  }
+  let modules_dir = dir.join("modules");
+  for module in &contents.modules {
+    let path = modules_dir.join(specifier_to_relative_path(&module.specifier)?);
+    std::fs::create_dir_all(path.parent().unwrap())?;
+    std::fs::write(&path, &module.source)
+      .with_context(|| format!("Failed writing '{}'.", path.display()))?;
+  }
+  if let Some(vfs) = &contents.npm_vfs {
+    let root = dir.join("node_modules");
+    extract_vfs_dir(vfs, vfs.root_dir(), &root, &root)?;
+  }
+  if let Some(vfs) = &contents.assets_vfs {
+    let root = dir.join("assets");
+    extract_vfs_dir(vfs, vfs.root_dir(), &root, &root)?;
+  }
+  log::info!(
+    "{} the contents of the executable to {}",
+    colors::green("Extracted"),
+    dir.display()
+  );
+  Ok(())
+}
+
+fn extract_vfs_dir(
+  vfs: &FileBackedVfs,
+  dir: &VirtualDirectory,
+  root: &Path,
+  path: &Path,
+) -> Result<(), AnyError> {
+  std::fs::create_dir_all(path)?;
+  for entry in &dir.entries {
+    match entry {
+      VfsEntry::Dir(dir) => {
+        let dir_path = vfs_entry_path(root, path, &dir.name)?;
+        extract_vfs_dir(vfs, dir, root, &dir_path)?;
+      }
+      VfsEntry::File(file) => {
+        let file_path = vfs_entry_path(root, path, &file.name)?;
+        std::fs::write(&file_path, vfs.read_file_all(file)?).with_context(
+          || format!("Failed writing '{}'.", file_path.display()),
+        )?;
+      }
+      // symlinks are only listed, as they can't be created everywhere
+      VfsEntry::Symlink(_) => {}
+    }
+  }
+  Ok(())
+}
+
+/// Joins the name of a virtual file system entry to the path of its parent
+/// directory. The names come from the executable, so anything other than a
+/// single normal path component is rejected to not write outside of `root`.
+fn vfs_entry_path(
+  root: &Path,
+  parent: &Path,
+  name: &str,
+) -> Result<PathBuf, AnyError> {
+  let mut components = Path::new(name).components();
+  let is_single_normal_component =
+    matches!(components.next(), Some(Component::Normal(_)))
+      && components.next().is_none();
+  if !is_single_normal_component {
+    bail!("Invalid file name in the virtual file system: '{}'", name);
+  }
+  let path = parent.join(name);
+  if !path.starts_with(root) {
+    bail!(
+      "Virtual file system entry '{}' is outside of '{}'.",
+      path.display(),
+      root.display()
+    );
+  }
+  Ok(path)
+}
+
+/// Maps a module specifier to a relative path made of its scheme, host and
+/// path segments, e.g. `https://deno.land/x/mod.ts` to
+/// `https/deno.land/x/mod.ts`.
+fn specifier_to_relative_path(specifier: &str) -> Result<PathBuf, AnyError> {
+  let url = Url::parse(specifier)?;
+  let mut path = PathBuf::from(url.scheme());
+  if let Some(host) = url.host_str() {
+    match url.port() {
+      Some(port) => path.push(format!("{host}_{port}")),
+      None => path.push(host),
+    }
+  }
+  let segments = url
+    .path()
+    .split('/')
+    .filter(|segment| {
+      !segment.is_empty() && *segment != "." && *segment != ".."
+    })
+    .map(|segment| segment.replace(':', "_"))
+    .collect::<Vec<_>>();
+  if segments.is_empty() || url.path().ends_with('/') {
+    path.extend(segments);
+    path.push("index");
+  } else {
+    path.extend(segments);
+  }
+  Ok(path)
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn test_specifier_to_relative_path() {
+    let cases = [
+      ("file:///home/user/main.ts", "file/home/user/main.ts"),
+      ("file:///C:/project/main.ts", "file/C_/project/main.ts"),
+      ("https://deno.land/x/mod.ts", "https/deno.land/x/mod.ts"),
+      ("http://localhost:4545/a/b.js", "http/localhost_4545/a/b.js"),
+      ("https://example.com/", "https/example.com/index"),
+      (
+        "https://example.com/../../etc/passwd",
+        "https/example.com/etc/passwd",
+      ),
+    ];
+    for (specifier, expected) in cases {
+      assert_eq!(
+        specifier_to_relative_path(specifier).unwrap(),
+        expected.split('/').collect::<PathBuf>(),
+        "{specifier}"
+      );
+    }
+  }
+
+  #[test]
+  fn test_vfs_entry_path() {
+    let root = PathBuf::from("out").join("assets");
+    let parent = root.join("dir");
+    assert_eq!(
+      vfs_entry_path(&root, &parent, "file.txt").unwrap(),
+      parent.join("file.txt")
+    );
+    for name in ["", ".", "..", "../file.txt", "a/b", "/etc/passwd"] {
+      assert!(vfs_entry_path(&root, &parent, name).is_err(), "{name}");
+    }
+    // the parent is expected to be within the root
+    assert!(vfs_entry_path(&root, Path::new("other"), "file.txt").is_err());
+  }
+
+  #[test]
+  fn test_permission_flags_to_args() {
+    let args = permission_flags_to_args(&PermissionFlags {
+      allow_net: Some(vec!["deno.land".to_string(), "example.com".to_string()]),
+      allow_read: Some(vec![]),
+      no_prompt: true,
+      ..Default::default()
+    })
+    .unwrap();
+    assert_eq!(
+      args,
+      vec![
+        "--allow-net=deno.land,example.com",
+        "--allow-read",
+        "--no-prompt"
+      ]
+    );
+  }
+}
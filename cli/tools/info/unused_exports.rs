@@ -0,0 +1,467 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+use deno_ast::swc::ast::CallExpr;
+use deno_ast::swc::ast::Callee;
+use deno_ast::swc::ast::Decl;
+use deno_ast::swc::ast::DefaultDecl;
+use deno_ast::swc::ast::ExportSpecifier;
+use deno_ast::swc::ast::Expr;
+use deno_ast::swc::ast::ImportSpecifier;
+use deno_ast::swc::ast::Lit;
+use deno_ast::swc::ast::ModuleDecl;
+use deno_ast::swc::ast::ModuleExportName;
+use deno_ast::swc::ast::ModuleItem;
+use deno_ast::swc::ast::ObjectPatProp;
+use deno_ast::swc::ast::Pat;
+use deno_ast::swc::ast::TsModuleName;
+use deno_ast::swc::visit::noop_visit_type;
+use deno_ast::swc::visit::Visit;
+use deno_ast::swc::visit::VisitWith;
+use deno_ast::ModuleSpecifier;
+use deno_ast::ParsedSource;
+use deno_ast::SourceRange;
+use deno_ast::SourceRangedForSpanned;
+use deno_core::error::AnyError;
+use deno_graph::JsModule;
+use deno_graph::Module;
+use deno_graph::ModuleGraph;
+
+use crate::cache::ParsedSourceCache;
+
+/// A symbol exported by a module.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExportedSymbol {
+  pub name: String,
+  /// The range of the exported name, or of the whole declaration for
+  /// anonymous default exports.
+  pub range: SourceRange,
+}
+
+/// The exports a module uses from one of its imports.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UsedNames {
+  /// The module namespace is used as a whole, so all exports are used.
+  All,
+  Some(HashSet<String>),
+}
+
+impl Default for UsedNames {
+  fn default() -> Self {
+    Self::Some(HashSet::new())
+  }
+}
+
+impl UsedNames {
+  pub fn contains(&self, name: &str) -> bool {
+    match self {
+      Self::All => true,
+      Self::Some(names) => names.contains(name),
+    }
+  }
+
+  /// Adds the names of `other`, returning if any weren't used yet.
+  pub fn extend(&mut self, other: &UsedNames) -> bool {
+    match (&mut *self, other) {
+      (Self::All, _) => false,
+      (_, Self::All) => {
+        *self = Self::All;
+        true
+      }
+      (Self::Some(names), Self::Some(other_names)) => {
+        let len = names.len();
+        names.extend(other_names.iter().cloned());
+        names.len() != len
+      }
+    }
+  }
+
+  fn insert(&mut self, name: String) {
+    if let Self::Some(names) = self {
+      names.insert(name);
+    }
+  }
+}
+
+/// The exports of a module and what it uses of the modules it imports.
+#[derive(Debug, Default)]
+pub struct ModuleExports {
+  pub exports: Vec<ExportedSymbol>,
+  /// The specifiers of `export * from "..."` declarations.
+  pub star_reexports: Vec<String>,
+  /// The exports used of each imported specifier.
+  pub imports: HashMap<String, UsedNames>,
+}
+
+pub fn analyze_module_exports(parsed_source: &ParsedSource) -> ModuleExports {
+  let mut module_exports = ModuleExports::default();
+  let module = parsed_source.module();
+  for item in &module.body {
+    let ModuleItem::ModuleDecl(module_decl) = item else {
+      continue;
+    };
+    match module_decl {
+      ModuleDecl::Import(import_decl) => {
+        let used = module_exports
+          .imports
+          .entry(import_decl.src.value.to_string())
+          .or_default();
+        for specifier in &import_decl.specifiers {
+          match specifier {
+            ImportSpecifier::Named(named) => used.insert(
+              named
+                .imported
+                .as_ref()
+                .map(module_export_name)
+                .unwrap_or_else(|| named.local.sym.to_string()),
+            ),
+            ImportSpecifier::Default(_) => used.insert("default".to_string()),
+            ImportSpecifier::Namespace(_) => *used = UsedNames::All,
+          }
+        }
+      }
+      ModuleDecl::ExportDecl(export_decl) => {
+        for (name, range) in decl_names(&export_decl.decl) {
+          module_exports.exports.push(ExportedSymbol { name, range });
+        }
+      }
+      ModuleDecl::ExportNamed(named_export) => {
+        for specifier in &named_export.specifiers {
+          let (exported, orig) = match specifier {
+            ExportSpecifier::Named(named) => (
+              named.exported.as_ref().unwrap_or(&named.orig),
+              Some(module_export_name(&named.orig)),
+            ),
+            ExportSpecifier::Namespace(namespace) => (&namespace.name, None),
+            ExportSpecifier::Default(default) => {
+              module_exports.exports.push(ExportedSymbol {
+                name: default.exported.sym.to_string(),
+                range: default.exported.range(),
+              });
+              if let Some(src) = &named_export.src {
+                module_exports
+                  .imports
+                  .entry(src.value.to_string())
+                  .or_default()
+                  .insert("default".to_string());
+              }
+              continue;
+            }
+          };
+          module_exports.exports.push(ExportedSymbol {
+            name: module_export_name(exported),
+            range: exported.range(),
+          });
+          if let Some(src) = &named_export.src {
+            let used = module_exports
+              .imports
+              .entry(src.value.to_string())
+              .or_default();
+            match orig {
+              Some(orig) => used.insert(orig),
+              None => *used = UsedNames::All,
+            }
+          }
+        }
+      }
+      ModuleDecl::ExportDefaultDecl(export_default_decl) => {
+        let ident = match &export_default_decl.decl {
+          DefaultDecl::Class(class_expr) => class_expr.ident.as_ref(),
+          DefaultDecl::Fn(fn_expr) => fn_expr.ident.as_ref(),
+          DefaultDecl::TsInterfaceDecl(_) => None,
+        };
+        module_exports.exports.push(ExportedSymbol {
+          name: "default".to_string(),
+          range: ident
+            .map(|i| i.range())
+            .unwrap_or_else(|| export_default_decl.range()),
+        });
+      }
+      ModuleDecl::ExportDefaultExpr(export_default_expr) => {
+        module_exports.exports.push(ExportedSymbol {
+          name: "default".to_string(),
+          range: export_default_expr.range(),
+        });
+      }
+      ModuleDecl::ExportAll(export_all) => {
+        let specifier = export_all.src.value.to_string();
+        module_exports.imports.entry(specifier.clone()).or_default();
+        module_exports.star_reexports.push(specifier);
+      }
+      _ => {}
+    }
+  }
+  module.visit_with(&mut DynamicImportCollector {
+    imports: &mut module_exports.imports,
+  });
+  module_exports
+}
+
+fn module_export_name(name: &ModuleExportName) -> String {
+  match name {
+    ModuleExportName::Ident(ident) => ident.sym.to_string(),
+    ModuleExportName::Str(str) => str.value.to_string(),
+  }
+}
+
+fn decl_names(decl: &Decl) -> Vec<(String, SourceRange)> {
+  let mut names = Vec::new();
+  match decl {
+    Decl::Class(class_decl) => {
+      names.push((class_decl.ident.sym.to_string(), class_decl.ident.range()))
+    }
+    Decl::Fn(fn_decl) => {
+      names.push((fn_decl.ident.sym.to_string(), fn_decl.ident.range()))
+    }
+    Decl::Var(var_decl) => {
+      for declarator in &var_decl.decls {
+        pat_names(&declarator.name, &mut names);
+      }
+    }
+    Decl::TsInterface(interface_decl) => {
+      names.push((interface_decl.id.sym.to_string(), interface_decl.id.range()))
+    }
+    Decl::TsTypeAlias(type_alias_decl) => names.push((
+      type_alias_decl.id.sym.to_string(),
+      type_alias_decl.id.range(),
+    )),
+    Decl::TsEnum(enum_decl) => {
+      names.push((enum_decl.id.sym.to_string(), enum_decl.id.range()))
+    }
+    Decl::TsModule(module_decl) => {
+      if let TsModuleName::Ident(ident) = &module_decl.id {
+        names.push((ident.sym.to_string(), ident.range()));
+      }
+    }
+    _ => {}
+  }
+  names
+}
+
+fn pat_names(pat: &Pat, names: &mut Vec<(String, SourceRange)>) {
+  match pat {
+    Pat::Ident(binding_ident) => {
+      names.push((binding_ident.id.sym.to_string(), binding_ident.id.range()))
+    }
+    Pat::Array(array_pat) => {
+      for elem in array_pat.elems.iter().flatten() {
+        pat_names(elem, names);
+      }
+    }
+    Pat::Object(object_pat) => {
+      for prop in &object_pat.props {
+        match prop {
+          ObjectPatProp::KeyValue(key_value) => {
+            pat_names(&key_value.value, names)
+          }
+          ObjectPatProp::Assign(assign) => {
+            names.push((assign.key.sym.to_string(), assign.key.range()))
+          }
+          ObjectPatProp::Rest(rest) => pat_names(&rest.arg, names),
+        }
+      }
+    }
+    Pat::Rest(rest_pat) => pat_names(&rest_pat.arg, names),
+    Pat::Assign(assign_pat) => pat_names(&assign_pat.left, names),
+    _ => {}
+  }
+}
+
+/// Dynamically imported modules are treated as being used as a whole.
+struct DynamicImportCollector<'a> {
+  imports: &'a mut HashMap<String, UsedNames>,
+}
+
+impl Visit for DynamicImportCollector<'_> {
+  noop_visit_type!();
+
+  fn visit_call_expr(&mut self, call_expr: &CallExpr) {
+    if matches!(call_expr.callee, Callee::Import(_)) {
+      if let Some(arg) = call_expr.args.first() {
+        if let Expr::Lit(Lit::Str(str_lit)) = &*arg.expr {
+          self
+            .imports
+            .insert(str_lit.value.to_string(), UsedNames::All);
+        }
+      }
+    }
+    call_expr.visit_children_with(self);
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnusedExport {
+  pub specifier: ModuleSpecifier,
+  pub name: String,
+  /// One-based line number of the export.
+  pub line: usize,
+  /// One-based column number of the export.
+  pub column: usize,
+}
+
+/// Finds the exports of the local modules in the graph that no other module
+/// of the graph uses. All the exports of the entry points are considered
+/// used, including the ones they re-export with `export *`.
+pub fn find_unused_exports(
+  graph: &ModuleGraph,
+  parsed_source_cache: &ParsedSourceCache,
+  entry_points: &[ModuleSpecifier],
+) -> Result<Vec<UnusedExport>, AnyError> {
+  let mut modules = Vec::new();
+  for module in graph.modules() {
+    let Module::Js(module) = module else {
+      continue;
+    };
+    let parsed_source =
+      parsed_source_cache.get_parsed_source_from_js_module(module)?;
+    let module_exports = analyze_module_exports(&parsed_source);
+    modules.push((module, parsed_source, module_exports));
+  }
+  let resolve = |module: &JsModule, specifier: &str| {
+    let dependency = module.dependencies.get(specifier)?;
+    let resolved = dependency.get_code().or_else(|| dependency.get_type())?;
+    Some(graph.resolve(resolved).clone())
+  };
+
+  let mut used_by_specifier: HashMap<ModuleSpecifier, UsedNames> = entry_points
+    .iter()
+    .map(|s| (graph.resolve(s).clone(), UsedNames::All))
+    .collect();
+  for (module, _, module_exports) in &modules {
+    for (specifier, used) in &module_exports.imports {
+      if let Some(resolved) = resolve(module, specifier) {
+        used_by_specifier.entry(resolved).or_default().extend(used);
+      }
+    }
+  }
+  // the names used of a module which it doesn't export itself are forwarded
+  // to the modules it re-exports with `export *`, until nothing changes
+  loop {
+    let mut changed = false;
+    for (module, _, module_exports) in &modules {
+      if module_exports.star_reexports.is_empty() {
+        continue;
+      }
+      let forwarded = match used_by_specifier.get(&module.specifier) {
+        None => continue,
+        Some(UsedNames::All) => UsedNames::All,
+        Some(UsedNames::Some(names)) => UsedNames::Some(
+          names
+            .iter()
+            .filter(|name| {
+              !module_exports.exports.iter().any(|e| &e.name == *name)
+            })
+            .cloned()
+            .collect(),
+        ),
+      };
+      for specifier in &module_exports.star_reexports {
+        if let Some(resolved) = resolve(module, specifier) {
+          changed |= used_by_specifier
+            .entry(resolved)
+            .or_default()
+            .extend(&forwarded);
+        }
+      }
+    }
+    if !changed {
+      break;
+    }
+  }
+
+  let mut unused_exports = Vec::new();
+  for (module, parsed_source, module_exports) in &modules {
+    if module.specifier.scheme() != "file" {
+      continue;
+    }
+    let used = used_by_specifier.get(&module.specifier);
+    for symbol in &module_exports.exports {
+      if used.map(|u| u.contains(&symbol.name)).unwrap_or(false) {
+        continue;
+      }
+      let line_and_column = parsed_source
+        .text_info()
+        .line_and_column_index(symbol.range.start);
+      unused_exports.push(UnusedExport {
+        specifier: module.specifier.clone(),
+        name: symbol.name.clone(),
+        line: line_and_column.line_index + 1,
+        column: line_and_column.column_index + 1,
+      });
+    }
+  }
+  unused_exports.sort_by(|a, b| {
+    (&a.specifier, a.line, a.column).cmp(&(&b.specifier, b.line, b.column))
+  });
+  Ok(unused_exports)
+}
+
+#[cfg(test)]
+mod test {
+  use deno_ast::MediaType;
+  use deno_ast::ParseParams;
+  use deno_ast::SourceTextInfo;
+
+  use super::*;
+
+  fn parse_module(text: &str) -> ParsedSource {
+    deno_ast::parse_module(ParseParams {
+      specifier: ModuleSpecifier::parse("file:///mod.ts").unwrap(),
+      capture_tokens: false,
+      maybe_syntax: None,
+      media_type: MediaType::TypeScript,
+      scope_analysis: false,
+      text_info: SourceTextInfo::from_string(text.to_string()),
+    })
+    .unwrap()
+  }
+
+  #[test]
+  fn analyzes_exports_and_imports() {
+    let module_exports = analyze_module_exports(&parse_module(
+      r#"import { a, b as c } from "./a.ts";
+import d from "./d.ts";
+import * as ns from "./ns.ts";
+export { e as f } from "./e.ts";
+export * from "./star.ts";
+export const { g, h: [i] } = {};
+export function j() {}
+export interface K {}
+export default class {}
+const l = await import("./dynamic.ts");
+"#,
+    ));
+    assert_eq!(
+      module_exports
+        .exports
+        .iter()
+        .map(|e| e.name.as_str())
+        .collect::<Vec<_>>(),
+      vec!["f", "g", "i", "j", "K", "default"]
+    );
+    assert_eq!(module_exports.star_reexports, vec!["./star.ts"]);
+    let names = |names: &[&str]| {
+      UsedNames::Some(names.iter().map(|n| n.to_string()).collect())
+    };
+    assert_eq!(module_exports.imports["./a.ts"], names(&["a", "b"]));
+    assert_eq!(module_exports.imports["./d.ts"], names(&["default"]));
+    assert_eq!(module_exports.imports["./ns.ts"], UsedNames::All);
+    assert_eq!(module_exports.imports["./e.ts"], names(&["e"]));
+    assert_eq!(module_exports.imports["./star.ts"], names(&[]));
+    assert_eq!(module_exports.imports["./dynamic.ts"], UsedNames::All);
+  }
+
+  #[test]
+  fn used_names_extend() {
+    let mut used = UsedNames::default();
+    assert!(!used.extend(&UsedNames::default()));
+    assert!(used.extend(&UsedNames::Some(HashSet::from(["a".to_string()]))));
+    assert!(used.contains("a"));
+    assert!(!used.contains("b"));
+    assert!(used.extend(&UsedNames::All));
+    assert!(used.contains("b"));
+    assert!(!used.extend(&UsedNames::Some(HashSet::from(["c".to_string()]))));
+  }
+}
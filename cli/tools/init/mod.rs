@@ -1,17 +1,378 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 
+use crate::args::jsr_url;
+use crate::args::CacheSetting;
+use crate::args::Flags;
 use crate::args::InitFlags;
 use crate::colors;
+use crate::factory::CliFactory;
+use crate::file_fetcher::FileFetcher;
+use crate::jsr::JsrFetchResolver;
+use deno_core::anyhow::bail;
 use deno_core::anyhow::Context;
 use deno_core::error::AnyError;
+use deno_core::serde_json;
+use deno_runtime::permissions::PermissionsContainer;
+use deno_semver::jsr::JsrPackageReqReference;
 use log::info;
+use std::io::IsTerminal;
 use std::io::Write;
+use std::path::Component;
 use std::path::Path;
+use std::path::PathBuf;
+
+/// A template that is built into the binary.
+struct BuiltInTemplate {
+  name: &'static str,
+  files: &'static [(&'static str, &'static str)],
+  /// The commands shown after initializing the project, with a comment
+  /// describing each one.
+  commands: &'static [(&'static str, &'static str)],
+  /// Whether the package name is published to JSR, so needs a scope.
+  jsr_package: bool,
+}
+
+const BUILT_IN_TEMPLATES: &[BuiltInTemplate] = &[
+  BuiltInTemplate {
+    name: "library",
+    files: &[
+      ("deno.json", include_str!("./templates/library/deno.json")),
+      ("mod.ts", include_str!("./templates/library/mod.ts")),
+      (
+        "mod_test.ts",
+        include_str!("./templates/library/mod_test.ts"),
+      ),
+      ("README.md", include_str!("./templates/library/README.md")),
+    ],
+    commands: &[
+      ("Run the tests", "deno test"),
+      ("Run the tests and watch for file changes", "deno task dev"),
+      ("Publish the package to JSR", "deno publish"),
+    ],
+    jsr_package: true,
+  },
+  BuiltInTemplate {
+    name: "serve",
+    files: &[
+      ("deno.json", include_str!("./templates/serve/deno.json")),
+      ("main.ts", include_str!("./templates/serve/main.ts")),
+      (
+        "main_test.ts",
+        include_str!("./templates/serve/main_test.ts"),
+      ),
+    ],
+    commands: &[
+      ("Run the server", "deno serve main.ts"),
+      ("Run the server and watch for file changes", "deno task dev"),
+      ("Run the tests", "deno test"),
+    ],
+    jsr_package: false,
+  },
+  BuiltInTemplate {
+    name: "cli",
+    files: &[
+      ("deno.json", include_str!("./templates/cli/deno.json")),
+      ("main.ts", include_str!("./templates/cli/main.ts")),
+      ("main_test.ts", include_str!("./templates/cli/main_test.ts")),
+    ],
+    commands: &[
+      ("Run the program", "deno run main.ts --name=Deno"),
+      ("Compile the program to an executable", "deno task compile"),
+      ("Run the tests", "deno test"),
+    ],
+    jsr_package: false,
+  },
+  BuiltInTemplate {
+    name: "npm",
+    files: &[
+      ("package.json", include_str!("./templates/npm/package.json")),
+      ("mod.ts", include_str!("./templates/npm/mod.ts")),
+      ("mod_test.ts", include_str!("./templates/npm/mod_test.ts")),
+    ],
+    commands: &[("Run the tests", "deno test")],
+    jsr_package: false,
+  },
+];
+
+enum TemplateSource {
+  BuiltIn(&'static BuiltInTemplate),
+  Jsr(JsrPackageReqReference),
+  Git(String),
+  Local(PathBuf),
+}
+
+fn resolve_template_source(
+  template: &str,
+  cwd: &Path,
+) -> Result<TemplateSource, AnyError> {
+  if let Some(built_in) = BUILT_IN_TEMPLATES.iter().find(|t| t.name == template)
+  {
+    return Ok(TemplateSource::BuiltIn(built_in));
+  }
+  if template.starts_with("jsr:") {
+    let req_ref = JsrPackageReqReference::from_str(template)
+      .with_context(|| format!("Invalid JSR template: {template}"))?;
+    return Ok(TemplateSource::Jsr(req_ref));
+  }
+  if let Some(url) = template.strip_prefix("git+") {
+    // would be parsed as an option by git
+    if url.starts_with('-') {
+      bail!("Invalid git repository URL: {url}");
+    }
+    return Ok(TemplateSource::Git(url.to_string()));
+  }
+  if template.starts_with("https://")
+    || template.starts_with("http://")
+    || template.starts_with("git@")
+  {
+    return Ok(TemplateSource::Git(template.to_string()));
+  }
+  let path = cwd.join(template);
+  if path.is_dir() {
+    return Ok(TemplateSource::Local(path));
+  }
+  bail!(
+    "Unknown template '{}'. Use one of {}, a jsr: package, a git repository URL or a path to a directory.",
+    template,
+    BUILT_IN_TEMPLATES
+      .iter()
+      .map(|t| format!("'{}'", t.name))
+      .collect::<Vec<_>>()
+      .join(", ")
+  )
+}
+
+/// The scope written in place of a JSR scope when it isn't prompted for.
+const PLACEHOLDER_JSR_SCOPE: &str = "your-scope";
+
+/// The values substituted for `{{name}}` and `{{author}}` in template files.
+struct TemplateVariables {
+  name: String,
+  author: String,
+}
+
+impl TemplateVariables {
+  /// Substitutes the variables in the text of the file at the given path,
+  /// escaping them in JSON files.
+  fn substitute(&self, path: &str, text: &str) -> String {
+    let is_json = path.ends_with(".json") || path.ends_with(".jsonc");
+    let escape = |value: &str| {
+      if is_json {
+        let quoted = serde_json::Value::String(value.to_string()).to_string();
+        quoted[1..quoted.len() - 1].to_string()
+      } else {
+        value.to_string()
+      }
+    };
+    text
+      .replace("{{name}}", &escape(&self.name))
+      .replace("{{author}}", &escape(&self.author))
+  }
+}
+
+fn resolve_template_variables(
+  dir: &Path,
+  prompt: bool,
+  jsr_package: bool,
+) -> Result<TemplateVariables, AnyError> {
+  let mut default_name = dir
+    .file_name()
+    .map(|name| name.to_string_lossy().to_string())
+    .unwrap_or_else(|| "my-project".to_string());
+  if jsr_package {
+    let scope = if prompt {
+      prompt_value("JSR scope", PLACEHOLDER_JSR_SCOPE)?
+    } else {
+      PLACEHOLDER_JSR_SCOPE.to_string()
+    };
+    default_name = format!(
+      "@{}/{}",
+      scope.trim_start_matches('@'),
+      to_jsr_package_name(&default_name)
+    );
+  }
+  let default_author = git_user_name().unwrap_or_default();
+  if !prompt {
+    return Ok(TemplateVariables {
+      name: default_name,
+      author: default_author,
+    });
+  }
+  Ok(TemplateVariables {
+    name: prompt_value("Package name", &default_name)?,
+    author: prompt_value("Author", &default_author)?,
+  })
+}
+
+/// Converts a directory name to a valid JSR package name, which only has
+/// lowercase letters, digits and hyphens.
+fn to_jsr_package_name(name: &str) -> String {
+  let name = name
+    .to_lowercase()
+    .chars()
+    .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+    .collect::<String>();
+  let name = name.trim_matches('-');
+  if name.is_empty() {
+    "my-library".to_string()
+  } else {
+    name.to_string()
+  }
+}
+
+fn git_user_name() -> Option<String> {
+  let output = std::process::Command::new("git")
+    .args(["config", "user.name"])
+    .output()
+    .ok()?;
+  if !output.status.success() {
+    return None;
+  }
+  let name = String::from_utf8(output.stdout).ok()?.trim().to_string();
+  Some(name).filter(|name| !name.is_empty())
+}
+
+fn prompt_value(label: &str, default: &str) -> Result<String, AnyError> {
+  if default.is_empty() {
+    eprint!("{}: ", label);
+  } else {
+    eprint!("{} {}: ", label, colors::gray(format!("({default})")));
+  }
+  std::io::stderr().flush()?;
+  let mut value = String::new();
+  std::io::stdin().read_line(&mut value)?;
+  let value = value.trim();
+  Ok(if value.is_empty() {
+    default.to_string()
+  } else {
+    value.to_string()
+  })
+}
+
+/// Gets the relative paths and contents of the files of a template.
+async fn load_template_files(
+  flags: Flags,
+  source: &TemplateSource,
+) -> Result<Vec<(String, Vec<u8>)>, AnyError> {
+  let files = match source {
+    TemplateSource::BuiltIn(template) => Ok(
+      template
+        .files
+        .iter()
+        .map(|(path, content)| (path.to_string(), content.as_bytes().to_vec()))
+        .collect(),
+    ),
+    TemplateSource::Jsr(req_ref) => {
+      load_jsr_template_files(flags, req_ref).await
+    }
+    TemplateSource::Git(url) => {
+      let temp_dir = tempfile::TempDir::new()?;
+      let status = std::process::Command::new("git")
+        .args(["clone", "--depth", "1", "--quiet", "--", url.as_str()])
+        .arg(temp_dir.path())
+        .status()
+        .context("Failed to run git, is it installed?")?;
+      if !status.success() {
+        bail!("Failed to clone template repository: {url}");
+      }
+      load_local_template_files(temp_dir.path())
+    }
+    TemplateSource::Local(dir) => load_local_template_files(dir),
+  }?;
+  for (path, _) in &files {
+    ensure_relative_template_path(path)?;
+  }
+  Ok(files)
+}
+
+/// Errors for template file paths that would be written outside of the
+/// directory of the project.
+fn ensure_relative_template_path(path: &str) -> Result<(), AnyError> {
+  let is_relative = Path::new(path)
+    .components()
+    .all(|c| matches!(c, Component::Normal(_) | Component::CurDir));
+  if !is_relative {
+    bail!("Invalid file path in template: '{}'", path);
+  }
+  Ok(())
+}
+
+fn load_local_template_files(
+  dir: &Path,
+) -> Result<Vec<(String, Vec<u8>)>, AnyError> {
+  let mut files = Vec::new();
+  let entries = walkdir::WalkDir::new(dir)
+    .sort_by_file_name()
+    .into_iter()
+    .filter_entry(|entry| entry.file_name() != ".git");
+  for entry in entries {
+    let entry = entry?;
+    if !entry.file_type().is_file() {
+      continue;
+    }
+    let relative_path = entry.path().strip_prefix(dir)?;
+    let relative_path = relative_path
+      .components()
+      .map(|c| c.as_os_str().to_string_lossy())
+      .collect::<Vec<_>>()
+      .join("/");
+    let content = std::fs::read(entry.path()).with_context(|| {
+      format!("Failed to read template file {}", entry.path().display())
+    })?;
+    files.push((relative_path, content));
+  }
+  Ok(files)
+}
+
+async fn load_jsr_template_files(
+  flags: Flags,
+  req_ref: &JsrPackageReqReference,
+) -> Result<Vec<(String, Vec<u8>)>, AnyError> {
+  let factory = CliFactory::from_flags(flags)?;
+  let mut file_fetcher = FileFetcher::new(
+    factory.global_http_cache()?.clone(),
+    CacheSetting::ReloadAll,
+    true,
+    factory.http_client().clone(),
+    Default::default(),
+    None,
+  );
+  file_fetcher.set_download_log_level(log::Level::Trace);
+  let jsr_resolver = JsrFetchResolver::new(file_fetcher.clone());
+  let req = req_ref.req();
+  let Some(nv) = jsr_resolver.req_to_nv(req).await else {
+    bail!("Could not find JSR template package: {req}");
+  };
+  let package_url = jsr_url().join(&format!("{}/{}/", nv.name, nv.version))?;
+  let meta_url =
+    jsr_url().join(&format!("{}/{}_meta.json", nv.name, nv.version))?;
+  let meta = file_fetcher
+    .fetch(&meta_url, &PermissionsContainer::allow_all())
+    .await?;
+  let meta: serde_json::Value = serde_json::from_slice(&meta.source)?;
+  let paths = meta["manifest"]
+    .as_object()
+    .map(|manifest| manifest.keys().cloned().collect::<Vec<_>>())
+    .unwrap_or_default();
+  let mut files = Vec::with_capacity(paths.len());
+  for path in paths {
+    let relative_path = path.trim_start_matches('/').to_string();
+    // checked before fetching, as the path could also point out of the
+    // package on the registry
+    ensure_relative_template_path(&relative_path)?;
+    let url = package_url.join(&relative_path)?;
+    let file = file_fetcher
+      .fetch(&url, &PermissionsContainer::allow_all())
+      .await?;
+    files.push((relative_path, file.source.to_vec()));
+  }
+  Ok(files)
+}
 
 fn create_file(
   dir: &Path,
   filename: &str,
-  content: &str,
+  content: &[u8],
 ) -> Result<(), AnyError> {
   let path = dir.join(filename);
   if path.exists() {
@@ -21,19 +382,29 @@ fn create_file(
     );
     Ok(())
   } else {
+    if let Some(parent) = path.parent() {
+      std::fs::create_dir_all(parent)?;
+    }
     let mut file = std::fs::OpenOptions::new()
       .write(true)
       .create_new(true)
       .open(path)
       .with_context(|| format!("Failed to create {filename} file"))?;
-    file.write_all(content.as_bytes())?;
+    file.write_all(content)?;
     Ok(())
   }
 }
 
-pub fn init_project(init_flags: InitFlags) -> Result<(), AnyError> {
+pub async fn init_project(
+  flags: Flags,
+  init_flags: InitFlags,
+) -> Result<(), AnyError> {
   let cwd =
     std::env::current_dir().context("Can't read current working directory.")?;
+  let template_source = match &init_flags.template {
+    Some(template) => Some(resolve_template_source(template, &cwd)?),
+    None => None,
+  };
   let dir = if let Some(dir) = &init_flags.dir {
     let dir = cwd.join(dir);
     std::fs::create_dir_all(&dir)?;
@@ -42,17 +413,82 @@ pub fn init_project(init_flags: InitFlags) -> Result<(), AnyError> {
     cwd
   };
 
-  let main_ts = include_str!("./templates/main.ts");
-  create_file(&dir, "main.ts", main_ts)?;
+  let Some(template_source) = template_source else {
+    let main_ts = include_str!("./templates/main.ts");
+    create_file(&dir, "main.ts", main_ts.as_bytes())?;
+
+    create_file(
+      &dir,
+      "main_test.ts",
+      include_str!("./templates/main_test.ts").as_bytes(),
+    )?;
+    create_file(
+      &dir,
+      "deno.json",
+      include_str!("./templates/deno.json").as_bytes(),
+    )?;
 
-  create_file(
-    &dir,
-    "main_test.ts",
-    include_str!("./templates/main_test.ts"),
-  )?;
-  create_file(&dir, "deno.json", include_str!("./templates/deno.json"))?;
+    info!("✅ {}", colors::green("Project initialized"));
+    info!("");
+    info!("{}", colors::gray("Run these commands to get started"));
+    info!("");
+    if let Some(dir) = init_flags.dir {
+      info!("  cd {}", dir);
+      info!("");
+    }
+    info!("  {}", colors::gray("# Run the program"));
+    info!("  deno run main.ts");
+    info!("");
+    info!(
+      "  {}",
+      colors::gray("# Run the program and watch for file changes")
+    );
+    info!("  deno task dev");
+    info!("");
+    info!("  {}", colors::gray("# Run the tests"));
+    info!("  deno test");
+    return Ok(());
+  };
+
+  let prompt = !init_flags.yes && std::io::stdin().is_terminal();
+  let jsr_package = matches!(
+    template_source,
+    TemplateSource::BuiltIn(BuiltInTemplate {
+      jsr_package: true,
+      ..
+    })
+  );
+  let variables = resolve_template_variables(&dir, prompt, jsr_package)?;
+  let files = load_template_files(flags, &template_source).await?;
+  for (path, content) in files {
+    // only substitute variables in text files
+    let content = match String::from_utf8(content) {
+      Ok(text) => variables.substitute(&path, &text).into_bytes(),
+      Err(err) => err.into_bytes(),
+    };
+    create_file(&dir, &path, &content)?;
+  }
 
   info!("✅ {}", colors::green("Project initialized"));
+  if jsr_package
+    && variables
+      .name
+      .starts_with(&format!("@{PLACEHOLDER_JSR_SCOPE}/"))
+  {
+    info!(
+      "ℹ️ {}",
+      colors::gray(format!(
+        "Replace the placeholder scope '@{PLACEHOLDER_JSR_SCOPE}' of the package name in deno.json with a JSR scope you own before publishing"
+      ))
+    );
+  }
+  let commands: &[(&str, &str)] = match template_source {
+    TemplateSource::BuiltIn(template) => template.commands,
+    _ => &[],
+  };
+  if init_flags.dir.is_none() && commands.is_empty() {
+    return Ok(());
+  }
   info!("");
   info!("{}", colors::gray("Run these commands to get started"));
   info!("");
@@ -60,16 +496,88 @@ pub fn init_project(init_flags: InitFlags) -> Result<(), AnyError> {
     info!("  cd {}", dir);
     info!("");
   }
-  info!("  {}", colors::gray("# Run the program"));
-  info!("  deno run main.ts");
-  info!("");
-  info!(
-    "  {}",
-    colors::gray("# Run the program and watch for file changes")
-  );
-  info!("  deno task dev");
-  info!("");
-  info!("  {}", colors::gray("# Run the tests"));
-  info!("  deno test");
+  for (i, (description, command)) in commands.iter().enumerate() {
+    if i > 0 {
+      info!("");
+    }
+    info!("  {}", colors::gray(format!("# {description}")));
+    info!("  {}", variables.substitute("", command));
+  }
   Ok(())
 }
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn test_resolve_template_source() {
+    let cwd = std::env::current_dir().unwrap();
+    assert!(matches!(
+      resolve_template_source("serve", &cwd).unwrap(),
+      TemplateSource::BuiltIn(BuiltInTemplate { name: "serve", .. })
+    ));
+    assert!(matches!(
+      resolve_template_source("jsr:@scope/template@1", &cwd).unwrap(),
+      TemplateSource::Jsr(_)
+    ));
+    assert!(matches!(
+      resolve_template_source("https://github.com/user/template.git", &cwd)
+        .unwrap(),
+      TemplateSource::Git(url) if url == "https://github.com/user/template.git"
+    ));
+    assert!(matches!(
+      resolve_template_source("git+file:///templates/app", &cwd).unwrap(),
+      TemplateSource::Git(url) if url == "file:///templates/app"
+    ));
+    assert!(matches!(
+      resolve_template_source(".", &cwd).unwrap(),
+      TemplateSource::Local(_)
+    ));
+    assert!(resolve_template_source("unknown", &cwd).is_err());
+    assert!(resolve_template_source("git+--upload-pack=touch", &cwd).is_err());
+  }
+
+  #[test]
+  fn test_ensure_relative_template_path() {
+    assert!(ensure_relative_template_path("deno.json").is_ok());
+    assert!(ensure_relative_template_path("src/main.ts").is_ok());
+    assert!(ensure_relative_template_path("../main.ts").is_err());
+    assert!(ensure_relative_template_path("src/../../main.ts").is_err());
+    assert!(ensure_relative_template_path("/etc/passwd").is_err());
+    if cfg!(windows) {
+      assert!(ensure_relative_template_path("C:\\main.ts").is_err());
+    }
+  }
+
+  #[test]
+  fn test_template_variables_substitute() {
+    let variables = TemplateVariables {
+      name: "my-app".to_string(),
+      author: "Jane \"JD\" Doe".to_string(),
+    };
+    assert_eq!(
+      variables.substitute(
+        "deno.json",
+        "{\"name\": \"{{name}}\", \"author\": \"{{author}}\"}"
+      ),
+      "{\"name\": \"my-app\", \"author\": \"Jane \\\"JD\\\" Doe\"}"
+    );
+    assert_eq!(
+      variables.substitute("README.md", "# {{name}} by {{author}}"),
+      "# my-app by Jane \"JD\" Doe"
+    );
+  }
+
+  #[test]
+  fn test_resolve_template_variables_jsr_package() {
+    let variables =
+      resolve_template_variables(Path::new("/projects/My_Lib"), false, true)
+        .unwrap();
+    assert_eq!(variables.name, "@your-scope/my-lib");
+    let variables =
+      resolve_template_variables(Path::new("/projects/my_app"), false, false)
+        .unwrap();
+    assert_eq!(variables.name, "my_app");
+  }
+}
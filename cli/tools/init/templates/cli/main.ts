@@ -0,0 +1,19 @@
+import { parseArgs } from "jsr:@std/cli/parse-args";
+
+export function greet(name: string): string {
+  return `Hello, ${name}!`;
+}
+
+// Learn more at https://deno.land/manual/examples/module_metadata#concepts
+if (import.meta.main) {
+  const args = parseArgs(Deno.args, {
+    boolean: ["help"],
+    string: ["name"],
+    default: { name: "world" },
+  });
+  if (args.help) {
+    console.log("Usage: {{name}} [--name <name>]");
+    Deno.exit(0);
+  }
+  console.log(greet(args.name));
+}
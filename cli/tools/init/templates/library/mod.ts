@@ -0,0 +1,23 @@
+/**
+ * A library by {{author}}.
+ *
+ * @example
+ * ```ts
+ * import { add } from "{{name}}";
+ *
+ * add(2, 3); // 5
+ * ```
+ *
+ * @module
+ */
+
+/**
+ * Adds two numbers.
+ *
+ * @param a The first number.
+ * @param b The second number.
+ * @returns The sum of the numbers.
+ */
+export function add(a: number, b: number): number {
+  return a + b;
+}
@@ -0,0 +1,6 @@
+import assert from "node:assert";
+import { add } from "./mod.ts";
+
+Deno.test(function addTest() {
+  assert.strictEqual(add(2, 3), 5);
+});
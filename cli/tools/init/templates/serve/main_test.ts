@@ -0,0 +1,13 @@
+import { assertEquals } from "jsr:@std/assert";
+import { handler } from "./main.ts";
+
+Deno.test(async function homePageTest() {
+  const res = handler(new Request("http://localhost/"));
+  assertEquals(res.status, 200);
+  assertEquals(await res.text(), "Hello from {{name}}!");
+});
+
+Deno.test(function notFoundTest() {
+  const res = handler(new Request("http://localhost/missing"));
+  assertEquals(res.status, 404);
+});
@@ -5,21 +5,32 @@ use crate::args::CaData;
 use crate::args::Flags;
 use crate::args::InstallFlags;
 use crate::args::InstallFlagsGlobal;
+use crate::args::InstallFlagsLocal;
 use crate::args::InstallKind;
 use crate::args::TypeCheckMode;
 use crate::args::UninstallFlags;
 use crate::args::UninstallKind;
+use crate::colors;
 use crate::factory::CliFactory;
+use crate::graph_util::graph_lock_or_exit;
 use crate::http_util::HttpClient;
+use crate::jsr::JsrFetchResolver;
 use crate::util::fs::canonicalize_path_maybe_not_exists;
 
 use deno_config::ConfigFlag;
+use deno_core::anyhow::bail;
 use deno_core::anyhow::Context;
 use deno_core::error::generic_error;
 use deno_core::error::AnyError;
 use deno_core::resolve_url_or_path;
 use deno_core::url::Url;
+use deno_core::ModuleSpecifier;
+use deno_graph::GraphKind;
+use deno_lockfile::Lockfile;
+use deno_semver::jsr::JsrPackageReqReference;
 use deno_semver::npm::NpmPackageReqReference;
+use deno_semver::package::PackageNv;
+use deno_semver::package::PackageReq;
 use log::Level;
 use once_cell::sync::Lazy;
 use regex::Regex;
@@ -257,15 +268,17 @@ pub async fn install_command(
   flags: Flags,
   install_flags: InstallFlags,
 ) -> Result<(), AnyError> {
-  if !install_flags.global {
-    log::warn!("⚠️ `deno install` behavior will change in Deno 2. To preserve the current behavior use the `-g` or `--global` flag.");
-  }
-
   let install_flags_global = match install_flags.kind {
     InstallKind::Global(flags) => flags,
-    InstallKind::Local => unreachable!(),
+    InstallKind::Local(local_flags) => {
+      return install_local(flags, local_flags).await;
+    }
   };
 
+  if !install_flags.global {
+    log::warn!("⚠️ `deno install` behavior will change in Deno 2. To preserve the current behavior use the `-g` or `--global` flag.");
+  }
+
   // ensure the module is cached
   CliFactory::from_flags(flags.clone())?
     .module_load_preparer()
@@ -277,6 +290,201 @@ pub async fn install_command(
   create_install_shim(flags, install_flags_global).await
 }
 
+/// Installs every dependency of the project, without needing any of its
+/// source files: the jsr, npm and remote specifiers of the import map, the
+/// package.json dependencies and the packages and remote modules that are
+/// in the lockfile.
+async fn install_local(
+  flags: Flags,
+  install_flags_local: InstallFlagsLocal,
+) -> Result<(), AnyError> {
+  let factory = CliFactory::from_flags(flags)?;
+  let cli_options = factory.cli_options();
+  if cli_options.maybe_config_file().is_none()
+    && cli_options.maybe_package_json().is_none()
+  {
+    bail!("No deno.json or package.json found in the current directory or its ancestors. Pass a script to install it as an executable with `deno install --global <script>`.");
+  }
+  let maybe_lockfile = factory.maybe_lockfile().clone();
+  if install_flags_local.frozen && maybe_lockfile.is_none() {
+    bail!("Using --frozen requires a lockfile.");
+  }
+
+  let mut roots = Vec::new();
+  let mut npm_reqs = Vec::new();
+  if let Some(import_map) = factory.maybe_import_map().await? {
+    let specifier_maps = std::iter::once(import_map.imports())
+      .chain(import_map.scopes().map(|scope| scope.imports));
+    for specifier_map in specifier_maps {
+      for entry in specifier_map.entries() {
+        if let Some(value) = entry.value {
+          add_install_root(value, &mut roots, &mut npm_reqs);
+        }
+      }
+    }
+  }
+  if let Some(lockfile) = &maybe_lockfile {
+    let lockfile = lockfile.lock();
+    for specifier in lockfile.content.packages.specifiers.keys() {
+      if let Ok(req_ref) = NpmPackageReqReference::from_str(specifier) {
+        npm_reqs.push(req_ref.req().clone());
+      }
+    }
+    for specifier in lockfile.content.remote.keys() {
+      if let Ok(specifier) = ModuleSpecifier::parse(specifier) {
+        roots.push(specifier);
+      }
+    }
+    // the exports of locked jsr packages are added as roots, so that modules
+    // imported with a prefix mapping or directly from the source code are
+    // installed as well
+    let jsr_nvs = lockfile
+      .content
+      .packages
+      .jsr
+      .keys()
+      .filter_map(|nv| PackageNv::from_str(nv).ok())
+      .collect::<Vec<_>>();
+    drop(lockfile);
+    let jsr_resolver =
+      JsrFetchResolver::new(factory.file_fetcher()?.as_ref().clone());
+    for nv in jsr_nvs {
+      let Some(info) = jsr_resolver.package_version_info(&nv).await else {
+        bail!("Failed to fetch the version info of jsr:{}.", nv);
+      };
+      for (export, _) in info.exports() {
+        let specifier = match export.strip_prefix("./") {
+          Some(sub_path) => format!("jsr:{}/{}", nv, sub_path),
+          None => format!("jsr:{}", nv),
+        };
+        roots.push(ModuleSpecifier::parse(&specifier)?);
+      }
+    }
+  }
+  roots.sort();
+  roots.dedup();
+
+  if install_flags_local.frozen {
+    // fail before anything is downloaded or node_modules is set up
+    let mut npm_reqs = npm_reqs.clone();
+    if let Some(reqs) = factory.package_json_deps_provider().reqs() {
+      npm_reqs.extend(reqs.into_iter().cloned());
+    }
+    ensure_dependencies_locked(
+      &maybe_lockfile.as_ref().unwrap().lock(),
+      &roots,
+      &npm_reqs,
+    )?;
+  }
+
+  let npm_resolver = factory.npm_resolver().await?;
+  if let Some(npm_resolver) = npm_resolver.as_managed() {
+    npm_resolver.ensure_top_level_package_json_install().await?;
+    npm_resolver.add_package_reqs(&npm_reqs).await?;
+  }
+
+  if !roots.is_empty() {
+    let module_graph_creator = factory.module_graph_creator().await?;
+    let graph = module_graph_creator
+      .create_graph(GraphKind::All, roots)
+      .await?;
+    module_graph_creator.graph_valid(&graph)?;
+    if let Some(lockfile) = &maybe_lockfile {
+      graph_lock_or_exit(&graph, &mut lockfile.lock());
+    }
+  }
+
+  if let Some(npm_resolver) = npm_resolver.as_managed() {
+    // sets up the node_modules directory when it's enabled
+    npm_resolver.resolve_pending().await?;
+    if let Some(lockfile) = &maybe_lockfile {
+      npm_resolver.lock(&mut lockfile.lock())?;
+    }
+  }
+
+  if let Some(lockfile) = &maybe_lockfile {
+    let lockfile = lockfile.lock();
+    if install_flags_local.frozen {
+      if lockfile.has_content_changed {
+        bail!(
+          "The lockfile is out of date. Run `deno install` without --frozen to update it.\n    at {}",
+          lockfile.filename.display()
+        );
+      }
+    } else {
+      lockfile.write().context("Failed writing lockfile.")?;
+    }
+  }
+
+  log::info!("{} Installed the dependencies.", colors::green("✅"));
+  Ok(())
+}
+
+/// Makes sure that the root modules and npm packages of the project are in
+/// the lockfile. Their own dependencies are locked along with them.
+fn ensure_dependencies_locked(
+  lockfile: &Lockfile,
+  roots: &[ModuleSpecifier],
+  npm_reqs: &[PackageReq],
+) -> Result<(), AnyError> {
+  let packages = &lockfile.content.packages;
+  let is_locked = |specifier: &ModuleSpecifier| match specifier.scheme() {
+    "jsr" => match JsrPackageReqReference::from_specifier(specifier) {
+      Ok(req_ref) => {
+        let req = req_ref.req();
+        packages.specifiers.contains_key(&format!("jsr:{}", req))
+          // the exports of locked packages are added with their exact version
+          || packages.jsr.contains_key(&req.to_string())
+      }
+      Err(_) => false,
+    },
+    _ => {
+      lockfile.content.remote.contains_key(specifier.as_str())
+        || lockfile.content.redirects.contains_key(specifier.as_str())
+    }
+  };
+  let maybe_unlocked = roots
+    .iter()
+    .find(|specifier| !is_locked(specifier))
+    .map(|specifier| specifier.to_string())
+    .or_else(|| {
+      npm_reqs
+        .iter()
+        .map(|req| format!("npm:{}", req))
+        .find(|specifier| !packages.specifiers.contains_key(specifier))
+    });
+  if let Some(specifier) = maybe_unlocked {
+    bail!(
+      "The lockfile is out of date, \"{}\" is missing from it. Run `deno install` without --frozen to update it.\n    at {}",
+      specifier,
+      lockfile.filename.display()
+    );
+  }
+  Ok(())
+}
+
+fn add_install_root(
+  specifier: &ModuleSpecifier,
+  roots: &mut Vec<ModuleSpecifier>,
+  npm_reqs: &mut Vec<PackageReq>,
+) {
+  match specifier.scheme() {
+    "npm" => {
+      let text = specifier.as_str();
+      let text = text.strip_suffix('/').unwrap_or(text);
+      if let Ok(req_ref) = NpmPackageReqReference::from_str(text) {
+        npm_reqs.push(req_ref.req().clone());
+      }
+    }
+    // prefix mappings can't be loaded on their own, so their modules are
+    // only installed when the package is in the lockfile
+    "jsr" | "http" | "https" if !specifier.as_str().ends_with('/') => {
+      roots.push(specifier.clone());
+    }
+    _ => {}
+  }
+}
+
 async fn create_install_shim(
   flags: Flags,
   install_flags_global: InstallFlagsGlobal,
@@ -447,6 +655,10 @@ async fn resolve_shim_data(
     executable_args.push(format!("--inspect-brk={inspect_brk}"));
   }
 
+  if let Some(inspect_dap) = flags.inspect_dap {
+    executable_args.push(format!("--inspect-dap={inspect_dap}"));
+  }
+
   if let Some(import_map_path) = &flags.import_map_path {
     let import_map_url = resolve_url_or_path(import_map_path, &cwd)?;
     executable_args.push("--import-map".to_string());
@@ -914,6 +1126,35 @@ mod tests {
     );
   }
 
+  #[tokio::test]
+  async fn install_inspect_dap() {
+    let shim_data = resolve_shim_data(
+      &Flags {
+        inspect_dap: Some("127.0.0.1:4711".parse().unwrap()),
+        ..Flags::default()
+      },
+      &InstallFlagsGlobal {
+        module_url: "http://localhost:4545/echo_server.ts".to_string(),
+        args: vec![],
+        name: Some("echo_test".to_string()),
+        root: Some(env::temp_dir().to_string_lossy().to_string()),
+        force: false,
+      },
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+      shim_data.args,
+      vec![
+        "run",
+        "--inspect-dap=127.0.0.1:4711",
+        "--no-config",
+        "http://localhost:4545/echo_server.ts",
+      ]
+    );
+  }
+
   #[tokio::test]
   async fn install_prompt() {
     let shim_data = resolve_shim_data(
@@ -225,6 +225,22 @@ impl JupyterMessage {
     self.content["comm_id"].as_str().unwrap_or("")
   }
 
+  pub fn target_name(&self) -> Option<&str> {
+    self.content["target_name"].as_str()
+  }
+
+  pub fn content(&self) -> &serde_json::Value {
+    &self.content
+  }
+
+  pub fn metadata(&self) -> &serde_json::Value {
+    &self.metadata
+  }
+
+  pub fn buffers(&self) -> &[Bytes] {
+    &self.buffers
+  }
+
   // Creates a new child message of this message. ZMQ identities are not transferred.
   pub fn new_message(&self, msg_type: &str) -> JupyterMessage {
     let mut header = self.header.clone();
@@ -151,7 +151,7 @@ pub async fn kernel(
   Ok(())
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct ConnectionSpec {
   ip: String,
   transport: String,
@@ -14,8 +14,10 @@ use deno_core::error::AnyError;
 use deno_core::futures;
 use deno_core::serde_json;
 use deno_core::serde_json::json;
+use deno_core::v8;
 use deno_core::CancelFuture;
 use deno_core::CancelHandle;
+use deno_runtime::tokio_util::create_basic_runtime;
 use tokio::sync::mpsc;
 use tokio::sync::Mutex;
 
@@ -28,9 +30,173 @@ pub enum StdioMsg {
   Stderr(String),
 }
 
+/// The comms that are currently open between the kernel and the frontend.
+#[derive(Default)]
+pub struct CommContainer {
+  /// Maps comm ids to their target names.
+  comms: HashMap<String, String>,
+}
+
+impl CommContainer {
+  pub fn register(&mut self, comm_id: String, target_name: String) {
+    self.comms.insert(comm_id, target_name);
+  }
+
+  pub fn unregister(&mut self, comm_id: &str) {
+    self.comms.remove(comm_id);
+  }
+
+  fn comm_info(&self, target_name: Option<&str>) -> serde_json::Value {
+    let comms = self
+      .comms
+      .iter()
+      .filter(|(_, name)| target_name.map_or(true, |t| t == name.as_str()))
+      .map(|(id, name)| (id.clone(), json!({ "target_name": name })))
+      .collect::<serde_json::Map<_, _>>();
+    serde_json::Value::Object(comms)
+  }
+}
+
+/// Interrupts the cell that is currently executing. The control channel is
+/// handled on its own thread so that it can do this while a long-running cell
+/// blocks the thread of the JS runtime.
+#[derive(Clone)]
+struct ExecutionInterrupter {
+  isolate_handle: v8::IsolateHandle,
+  /// Locked while terminating, so that an interrupt never outlives the
+  /// execution it was meant for.
+  state: Arc<deno_core::parking_lot::Mutex<InterruptState>>,
+}
+
+#[derive(Default)]
+struct InterruptState {
+  executing: bool,
+  interrupted: bool,
+}
+
+impl ExecutionInterrupter {
+  fn interrupt(&self) {
+    let mut state = self.state.lock();
+    if state.executing && !state.interrupted {
+      state.interrupted = true;
+      self.isolate_handle.terminate_execution();
+    }
+  }
+
+  fn start_execution(&self) {
+    let mut state = self.state.lock();
+    state.executing = true;
+    state.interrupted = false;
+  }
+
+  /// Returns whether the execution was interrupted.
+  fn finish_execution(&self) -> bool {
+    let mut state = self.state.lock();
+    state.executing = false;
+    let interrupted = std::mem::take(&mut state.interrupted);
+    if interrupted {
+      // allow the kernel to keep executing cells
+      self.isolate_handle.cancel_terminate_execution();
+    }
+    interrupted
+  }
+}
+
+/// The code executed during this kernel session, used to answer history
+/// requests.
+#[derive(Default)]
+struct ExecutionHistory {
+  entries: Vec<(usize, String)>,
+}
+
+impl ExecutionHistory {
+  /// The session number reported in history replies. There is only ever the
+  /// current session.
+  const SESSION: usize = 1;
+
+  fn add(&mut self, execution_count: usize, code: &str) {
+    self.entries.push((execution_count, code.to_string()));
+  }
+
+  fn search(&self, content: &serde_json::Value) -> Vec<serde_json::Value> {
+    let entries = match content["hist_access_type"].as_str().unwrap_or("") {
+      "tail" => {
+        let n = content["n"].as_u64().unwrap_or(10) as usize;
+        self.entries[self.entries.len().saturating_sub(n)..]
+          .iter()
+          .collect::<Vec<_>>()
+      }
+      "range" => {
+        let start = content["start"].as_u64().unwrap_or(0) as usize;
+        let stop = content["stop"].as_u64().map(|stop| stop as usize);
+        self
+          .entries
+          .iter()
+          .filter(|(line, _)| {
+            *line >= start && stop.map_or(true, |stop| *line < stop)
+          })
+          .collect()
+      }
+      "search" => {
+        let pattern = content["pattern"].as_str().unwrap_or("*");
+        let regex = glob_to_regex(pattern);
+        let mut entries = self
+          .entries
+          .iter()
+          .filter(|(_, code)| {
+            regex.as_ref().map_or(false, |r| r.is_match(code))
+          })
+          .collect::<Vec<_>>();
+        if content["unique"].as_bool().unwrap_or(false) {
+          let mut seen = std::collections::HashSet::new();
+          entries.reverse();
+          entries.retain(|(_, code)| seen.insert(code.as_str()));
+          entries.reverse();
+        }
+        if let Some(n) = content["n"].as_u64() {
+          entries =
+            entries[entries.len().saturating_sub(n as usize)..].to_vec();
+        }
+        entries
+      }
+      _ => Vec::new(),
+    };
+    let output = content["output"].as_bool().unwrap_or(false);
+    entries
+      .into_iter()
+      .map(|(line, code)| {
+        if output {
+          // outputs aren't recorded
+          json!([Self::SESSION, line, [code, null]])
+        } else {
+          json!([Self::SESSION, line, code])
+        }
+      })
+      .collect()
+  }
+}
+
+/// Converts a history search pattern, where `*` matches any characters and
+/// `?` matches a single character, to a regex.
+fn glob_to_regex(pattern: &str) -> Option<regex::Regex> {
+  let mut regex = String::from("^");
+  for c in pattern.chars() {
+    match c {
+      '*' => regex.push_str("(?s:.*)"),
+      '?' => regex.push_str("(?s:.)"),
+      c => regex.push_str(&regex::escape(&c.to_string())),
+    }
+  }
+  regex.push('$');
+  regex::Regex::new(&regex).ok()
+}
+
 pub struct JupyterServer {
   execution_count: usize,
+  execution_history: ExecutionHistory,
+  interrupter: ExecutionInterrupter,
   last_execution_request: Rc<RefCell<Option<JupyterMessage>>>,
+  comm_container: Rc<RefCell<CommContainer>>,
   // This is Arc<Mutex<>>, so we don't hold RefCell borrows across await
   // points.
   iopub_socket: Arc<Mutex<Connection<zeromq::PubSocket>>>,
@@ -47,14 +213,13 @@ impl JupyterServer {
       bind_socket::<zeromq::RepSocket>(&spec, spec.hb_port).await?;
     let shell_socket =
       bind_socket::<zeromq::RouterSocket>(&spec, spec.shell_port).await?;
-    let control_socket =
-      bind_socket::<zeromq::RouterSocket>(&spec, spec.control_port).await?;
     let _stdin_socket =
       bind_socket::<zeromq::RouterSocket>(&spec, spec.stdin_port).await?;
     let iopub_socket =
       bind_socket::<zeromq::PubSocket>(&spec, spec.iopub_port).await?;
     let iopub_socket = Arc::new(Mutex::new(iopub_socket));
     let last_execution_request = Rc::new(RefCell::new(None));
+    let comm_container = Rc::new(RefCell::new(CommContainer::default()));
 
     // Store `iopub_socket` in the op state so it's accessible to the runtime API.
     {
@@ -62,14 +227,27 @@ impl JupyterServer {
       let mut op_state = op_state_rc.borrow_mut();
       op_state.put(iopub_socket.clone());
       op_state.put(last_execution_request.clone());
+      op_state.put(comm_container.clone());
     }
 
     let cancel_handle = CancelHandle::new_rc();
 
+    let interrupter = ExecutionInterrupter {
+      isolate_handle: repl_session
+        .worker
+        .js_runtime
+        .v8_isolate()
+        .thread_safe_handle(),
+      state: Default::default(),
+    };
+
     let mut server = Self {
       execution_count: 0,
+      execution_history: ExecutionHistory::default(),
+      interrupter: interrupter.clone(),
       iopub_socket: iopub_socket.clone(),
       last_execution_request: last_execution_request.clone(),
+      comm_container,
       repl_session,
     };
 
@@ -79,13 +257,29 @@ impl JupyterServer {
       }
     });
 
+    let (shutdown_tx, mut shutdown_rx) = mpsc::unbounded_channel();
+    std::thread::spawn({
+      let spec = spec.clone();
+      move || {
+        let runtime = create_basic_runtime();
+        runtime.block_on(async move {
+          let result = async {
+            let control_socket =
+              bind_socket::<zeromq::RouterSocket>(&spec, spec.control_port)
+                .await?;
+            Self::handle_control(control_socket, interrupter, shutdown_tx).await
+          };
+          if let Err(err) = result.await {
+            eprintln!("Control error: {}", err);
+          }
+        });
+      }
+    });
     let handle2 = deno_core::unsync::spawn({
       let cancel_handle = cancel_handle.clone();
       async move {
-        if let Err(err) =
-          Self::handle_control(control_socket, cancel_handle).await
-        {
-          eprintln!("Control error: {}", err);
+        if shutdown_rx.recv().await.is_some() {
+          cancel_handle.cancel();
         }
       }
     });
@@ -152,7 +346,8 @@ impl JupyterServer {
 
   async fn handle_control(
     mut connection: Connection<zeromq::RouterSocket>,
-    cancel_handle: Rc<CancelHandle>,
+    interrupter: ExecutionInterrupter,
+    shutdown_tx: mpsc::UnboundedSender<()>,
   ) -> Result<(), AnyError> {
     loop {
       let msg = connection.read().await?;
@@ -163,10 +358,21 @@ impl JupyterServer {
             .await?;
         }
         "shutdown_request" => {
-          cancel_handle.cancel();
+          connection
+            .send(&msg.new_reply().with_content(json!({
+              "status": "ok",
+              "restart": msg.content()["restart"].as_bool().unwrap_or(false),
+            })))
+            .await?;
+          // stop a running cell so the runtime's thread can shut down
+          interrupter.interrupt();
+          let _ = shutdown_tx.send(());
         }
         "interrupt_request" => {
-          eprintln!("Interrupt request currently not supported");
+          interrupter.interrupt();
+          connection
+            .send(&msg.new_reply().with_content(json!({"status": "ok"})))
+            .await?;
         }
         _ => {
           eprintln!(
@@ -218,11 +424,26 @@ impl JupyterServer {
           .await?;
       }
       "comm_open" => {
-        self.send_iopub(&msg.comm_close_message()).await?;
+        // close comms for targets the user code didn't register
+        if !self.handle_comm_message(&msg).await? {
+          self.send_iopub(&msg.comm_close_message()).await?;
+        }
+      }
+      "comm_msg" | "comm_close" => {
+        self.handle_comm_message(&msg).await?;
+      }
+      "comm_info_request" => {
+        let comms = self.comm_container.borrow().comm_info(msg.target_name());
+        connection
+          .send(&msg.new_reply().with_content(json!({
+            "status": "ok",
+            "comms": comms,
+          })))
+          .await?;
       }
       "complete_request" => {
         let user_code = msg.code();
-        let cursor_pos = msg.cursor_pos();
+        let cursor_pos = code_point_to_byte_offset(user_code, msg.cursor_pos());
 
         let lsp_completions = self
           .repl_session
@@ -250,8 +471,8 @@ impl JupyterServer {
             .send(&msg.new_reply().with_content(json!({
               "status": "ok",
               "matches": matches,
-              "cursor_start": cursor_start,
-              "cursor_end": cursor_end,
+              "cursor_start": byte_offset_to_code_point(user_code, cursor_start),
+              "cursor_end": byte_offset_to_code_point(user_code, cursor_end),
               "metadata": {},
             })))
             .await?;
@@ -296,15 +517,51 @@ impl JupyterServer {
             .send(&msg.new_reply().with_content(json!({
               "status": "ok",
               "matches": completions,
-              "cursor_start": cursor_start,
-              "cursor_end": cursor_pos,
+              "cursor_start": byte_offset_to_code_point(user_code, cursor_start),
+              "cursor_end": byte_offset_to_code_point(user_code, cursor_pos),
               "metadata": {},
             })))
             .await?;
         }
       }
-      "comm_msg" | "comm_info_request" | "history_request" => {
-        // We don't handle these messages
+      "history_request" => {
+        let history = self.execution_history.search(msg.content());
+        connection
+          .send(&msg.new_reply().with_content(json!({
+            "status": "ok",
+            "history": history,
+          })))
+          .await?;
+      }
+      "inspect_request" => {
+        let documentation = self
+          .repl_session
+          .language_server
+          .hover(
+            msg.code(),
+            code_point_to_byte_offset(msg.code(), msg.cursor_pos()),
+          )
+          .await;
+        let content = match documentation {
+          Some(documentation) => json!({
+            "status": "ok",
+            "found": true,
+            "data": {
+              "text/plain": documentation,
+              "text/markdown": documentation,
+            },
+            "metadata": {},
+          }),
+          None => json!({
+            "status": "ok",
+            "found": false,
+            "data": {},
+            "metadata": {},
+          }),
+        };
+        connection
+          .send(&msg.new_reply().with_content(content))
+          .await?;
       }
       _ => {
         eprintln!("Unrecognized shell message type: {}", msg.message_type());
@@ -328,6 +585,7 @@ impl JupyterServer {
   ) -> Result<(), AnyError> {
     if !msg.silent() && msg.store_history() {
       self.execution_count += 1;
+      self.execution_history.add(self.execution_count, msg.code());
     }
     *self.last_execution_request.borrow_mut() = Some(msg.clone());
 
@@ -338,11 +596,32 @@ impl JupyterServer {
       })))
       .await?;
 
+    self.interrupter.start_execution();
     let result = self
       .repl_session
       .evaluate_line_with_object_wrapping(msg.code())
       .await;
 
+    if self.interrupter.finish_execution() {
+      self
+        .send_iopub(&msg.new_message("error").with_content(json!({
+          "ename": "Interrupted",
+          "evalue": "Execution was interrupted",
+          "traceback": [],
+        })))
+        .await?;
+      connection
+        .send(&msg.new_reply().with_content(json!({
+          "status": "error",
+          "execution_count": self.execution_count,
+          "ename": "Interrupted",
+          "evalue": "Execution was interrupted",
+          "traceback": [],
+        })))
+        .await?;
+      return Ok(());
+    }
+
     let evaluate_response = match result {
       Ok(eval_response) => eval_response,
       Err(err) => {
@@ -474,6 +753,64 @@ impl JupyterServer {
     Ok(())
   }
 
+  /// Dispatches a comm message from the frontend to the comm targets and
+  /// listeners registered through `Deno.jupyter`. Returns whether the
+  /// message was handled.
+  async fn handle_comm_message(
+    &mut self,
+    msg: &JupyterMessage,
+  ) -> Result<bool, AnyError> {
+    let buffers = msg
+      .buffers()
+      .iter()
+      .map(|buffer| buffer.to_vec())
+      .collect::<Vec<_>>();
+    let arguments = [
+      json!(msg.message_type()),
+      msg.content().clone(),
+      msg.metadata().clone(),
+      json!(buffers),
+    ]
+    .into_iter()
+    .map(|value| cdp::CallArgument {
+      value: Some(value),
+      unserializable_value: None,
+      object_id: None,
+    })
+    .collect::<Vec<_>>();
+
+    let response = self
+      .repl_session
+      .post_message_with_event_loop(
+        "Runtime.callFunctionOn",
+        Some(json!({
+          "functionDeclaration": r#"async function (msgType, content, metadata, buffers) {
+            return await Deno[Deno.internal].jupyter.handleCommMessage(msgType, content, metadata, buffers);
+      }"#,
+          "arguments": arguments,
+          "executionContextId": self.repl_session.context_id,
+          "awaitPromise": true,
+          "returnByValue": true,
+        })),
+      )
+      .await?;
+
+    let response: cdp::CallFunctionOnResponse =
+      serde_json::from_value(response)?;
+    if let Some(exception_details) = &response.exception_details {
+      eprintln!("Exception encountered: {}", exception_details.text);
+      return Ok(false);
+    }
+
+    Ok(
+      response
+        .result
+        .value
+        .and_then(|value| value.as_bool())
+        .unwrap_or(false),
+    )
+  }
+
   async fn send_iopub(
     &mut self,
     message: &JupyterMessage,
@@ -553,6 +890,20 @@ async fn publish_result(
   Ok(None)
 }
 
+/// Converts a cursor position in Unicode code points, which is what Jupyter
+/// frontends send, to a byte offset in the code.
+fn code_point_to_byte_offset(code: &str, cursor_pos: usize) -> usize {
+  code
+    .char_indices()
+    .nth(cursor_pos)
+    .map(|(index, _)| index)
+    .unwrap_or(code.len())
+}
+
+fn byte_offset_to_code_point(code: &str, offset: usize) -> usize {
+  code[..offset.min(code.len())].chars().count()
+}
+
 // TODO(bartlomieju): dedup with repl::editor
 fn get_expr_from_line_at_pos(line: &str, cursor_pos: usize) -> &str {
   let start = line[..cursor_pos].rfind(is_word_boundary).unwrap_or(0);
@@ -702,3 +1053,20 @@ async fn evaluate_expression(
     Some(evaluate_response)
   }
 }
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn test_code_point_offsets() {
+    let code = "const café = 1; café";
+    // the cursor after the last "é" in code points
+    let cursor_pos = code.chars().count();
+    assert_eq!(code_point_to_byte_offset(code, cursor_pos), code.len());
+    assert_eq!(code_point_to_byte_offset(code, 10), "const café".len());
+    assert_eq!(code_point_to_byte_offset(code, 100), code.len());
+    assert_eq!(byte_offset_to_code_point(code, code.len()), cursor_pos);
+    assert_eq!(byte_offset_to_code_point(code, "const café".len()), 10);
+  }
+}
@@ -43,8 +43,10 @@ use crate::args::LintRulesConfig;
 use crate::cache::IncrementalCache;
 use crate::colors;
 use crate::factory::CliFactory;
+use crate::factory::WatcherFactory;
 use crate::tools::fmt::run_parallelized;
 use crate::util::file_watcher;
+use crate::util::file_watcher::has_config_changed;
 use crate::util::fs::canonicalize_path;
 use crate::util::fs::specifier_from_file_path;
 use crate::util::fs::FileCollector;
@@ -70,13 +72,18 @@ pub async fn lint(flags: Flags, lint_flags: LintFlags) -> Result<(), AnyError> {
         "Lint watch on standard input is not supported.",
       ));
     }
+    let mut watcher_factory = WatcherFactory::default();
     file_watcher::watch_func(
       flags,
       file_watcher::PrintConfig::new("Lint", !watch_flags.no_clear_screen),
       move |flags, watcher_communicator, changed_paths| {
         let lint_flags = lint_flags.clone();
+        let factory = watcher_factory.get(
+          flags,
+          watcher_communicator.clone(),
+          changed_paths.as_deref(),
+        )?;
         Ok(async move {
-          let factory = CliFactory::from_flags(flags)?;
           let cli_options = factory.cli_options();
           let lint_options = cli_options.resolve_lint_options(lint_flags)?;
           let files =
@@ -89,23 +96,27 @@ pub async fn lint(flags: Flags, lint_flags: LintFlags) -> Result<(), AnyError> {
                 }
               })?;
           _ = watcher_communicator.watch_paths(files.clone());
-
-          let lint_paths = if let Some(paths) = changed_paths {
-            // lint all files on any changed (https://github.com/denoland/deno/issues/12446)
-            files
-              .iter()
-              .any(|path| {
-                canonicalize_path(path)
-                  .map(|p| paths.contains(&p))
-                  .unwrap_or(false)
-              })
-              .then_some(files)
-              .unwrap_or_else(|| [].to_vec())
-          } else {
-            files
+          _ = watcher_communicator.watch_paths(cli_options.watch_paths());
+
+          // only the changed files are linted again, unless the
+          // configuration changed
+          let lint_paths = match changed_paths {
+            Some(changed_paths)
+              if !has_config_changed(cli_options, &changed_paths) =>
+            {
+              files
+                .into_iter()
+                .filter(|path| {
+                  canonicalize_path(path)
+                    .map(|path| changed_paths.contains(&path))
+                    .unwrap_or(false)
+                })
+                .collect()
+            }
+            _ => files,
           };
 
-          lint_files(factory, lint_options, lint_paths).await?;
+          lint_files(&factory, lint_options, lint_paths).await?;
           Ok(())
         })
       },
@@ -143,7 +154,7 @@ pub async fn lint(flags: Flags, lint_flags: LintFlags) -> Result<(), AnyError> {
           }
         })?;
       debug!("Found {} files", target_files.len());
-      lint_files(factory, lint_options, target_files).await?
+      lint_files(&factory, lint_options, target_files).await?
     };
     if !success {
       std::process::exit(1);
@@ -154,7 +165,7 @@ pub async fn lint(flags: Flags, lint_flags: LintFlags) -> Result<(), AnyError> {
 }
 
 async fn lint_files(
-  factory: CliFactory,
+  factory: &CliFactory,
   lint_options: LintOptions,
   paths: Vec<PathBuf>,
 ) -> Result<bool, AnyError> {
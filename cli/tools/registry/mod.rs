@@ -64,6 +64,9 @@ mod unfurl;
 use auth::get_auth_method;
 use auth::AuthMethod;
 pub use pm::add;
+pub use pm::outdated;
+pub use pm::remove;
+pub use publish_order::build_publish_order_graph_from_pkgs_deps;
 use publish_order::PublishOrderGraph;
 use unfurl::SpecifierUnfurler;
 
@@ -935,6 +938,7 @@ async fn build_and_check_graph_for_publish(
           CheckOptions {
             build_fast_check_graph: false, // already built
             lib: cli_options.ts_type_lib_window(),
+            log_checked_roots: true,
             log_ignored_options: false,
             reload: cli_options.reload_flag(),
             type_check_mode: cli_options.type_check_mode(),
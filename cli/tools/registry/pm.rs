@@ -1,6 +1,8 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 
 use std::collections::HashMap;
+use std::collections::HashSet;
+use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
 
@@ -14,12 +16,17 @@ use deno_core::futures::StreamExt;
 use deno_core::serde_json;
 use deno_semver::jsr::JsrPackageReqReference;
 use deno_semver::npm::NpmPackageReqReference;
+use deno_semver::package::PackageNv;
+use deno_semver::package::PackageReq;
+use deno_semver::Version;
 use jsonc_parser::ast::ObjectProp;
 use jsonc_parser::ast::Value;
 
 use crate::args::AddFlags;
 use crate::args::CacheSetting;
 use crate::args::Flags;
+use crate::args::OutdatedFlags;
+use crate::args::RemoveFlags;
 use crate::factory::CliFactory;
 use crate::file_fetcher::FileFetcher;
 use crate::jsr::JsrFetchResolver;
@@ -301,3 +308,743 @@ fn update_config_file_content(
   .map(|formatted_text| formatted_text.unwrap_or_else(|| new_text.clone()))
   .unwrap_or(new_text)
 }
+
+pub async fn remove(
+  flags: Flags,
+  remove_flags: RemoveFlags,
+) -> Result<(), AnyError> {
+  let cli_factory = CliFactory::from_flags(flags.clone())?;
+  let cli_options = cli_factory.cli_options();
+  if cli_options.maybe_config_file().is_none()
+    && cli_options.maybe_package_json().is_none()
+  {
+    bail!("No deno.json or package.json found in the current directory or its ancestors.");
+  }
+
+  let packages = remove_flags
+    .packages
+    .iter()
+    .map(|package| parse_package_name(package))
+    .collect::<Vec<_>>();
+  let mut removed_packages = HashSet::new();
+
+  if let Some(config_file) = cli_options.maybe_config_file() {
+    if config_file.specifier.scheme() != "file" {
+      bail!("Can't remove dependencies from a remote configuration file");
+    }
+    let config_file_path = config_file.specifier.to_file_path().unwrap();
+    let fmt_config_options = config_file
+      .to_fmt_config()
+      .ok()
+      .flatten()
+      .map(|config| config.options)
+      .unwrap_or_default();
+    update_dependencies_file(
+      &config_file_path,
+      &["imports"],
+      &fmt_config_options,
+      |key, value| {
+        let dependency = parse_import_value(value);
+        let index = packages.iter().position(|(kind, name)| {
+          key == *name
+            || dependency.as_ref().is_some_and(|(dep_kind, req)| {
+              req.name == *name && kind.map_or(true, |kind| kind == *dep_kind)
+            })
+        })?;
+        removed_packages.insert(index);
+        log::info!("Remove {}", crate::colors::green(key));
+        Some(EntryChange::Remove)
+      },
+    )
+    .await?;
+  }
+
+  if let Some(package_json) = cli_options.maybe_package_json() {
+    update_dependencies_file(
+      &package_json.path,
+      &["dependencies", "devDependencies"],
+      &Default::default(),
+      |key, value| {
+        let req = parse_package_json_value(key, value);
+        let index = packages.iter().position(|(kind, name)| {
+          *kind != Some(PackageKind::Jsr)
+            && (key == *name
+              || req.as_ref().is_some_and(|req| req.name == *name))
+        })?;
+        removed_packages.insert(index);
+        log::info!("Remove {}", crate::colors::green(key));
+        Some(EntryChange::Remove)
+      },
+    )
+    .await?;
+  }
+
+  for (index, package) in remove_flags.packages.iter().enumerate() {
+    if !removed_packages.contains(&index) {
+      log::warn!(
+        "{} {} was not found in the dependencies.",
+        crate::colors::yellow("Warning"),
+        package
+      );
+    }
+  }
+  if removed_packages.is_empty() {
+    return Ok(());
+  }
+
+  // loading the lockfile with the updated configuration removes the
+  // packages that are no longer depended on
+  let cli_factory = CliFactory::from_flags(flags)?;
+  if let Some(lockfile) = cli_factory.maybe_lockfile() {
+    lockfile
+      .lock()
+      .write()
+      .context("Failed writing lockfile.")?;
+  }
+
+  Ok(())
+}
+
+pub async fn outdated(
+  flags: Flags,
+  outdated_flags: OutdatedFlags,
+) -> Result<(), AnyError> {
+  let cli_factory = CliFactory::from_flags(flags.clone())?;
+  let cli_options = cli_factory.cli_options();
+  if cli_options.maybe_config_file().is_none()
+    && cli_options.maybe_package_json().is_none()
+  {
+    bail!("No deno.json or package.json found in the current directory or its ancestors.");
+  }
+
+  let mut dependencies = Vec::new();
+  if let Some(config_file) = cli_options.maybe_config_file() {
+    if let Some(imports) = config_file
+      .json
+      .imports
+      .as_ref()
+      .and_then(|i| i.as_object())
+    {
+      for value in imports.values() {
+        if let Some(dependency) = value.as_str().and_then(parse_import_value) {
+          dependencies.push(dependency);
+        }
+      }
+    }
+  }
+  if let Some(package_json) = cli_options.maybe_package_json() {
+    let entries = package_json
+      .dependencies
+      .iter()
+      .chain(package_json.dev_dependencies.iter())
+      .flatten();
+    for (key, value) in entries {
+      if let Some(req) = parse_package_json_value(key, value) {
+        dependencies.push((PackageKind::Npm, req));
+      }
+    }
+  }
+  let mut seen = HashSet::new();
+  dependencies.retain(|dependency| seen.insert(dependency.clone()));
+
+  let locked_versions = match cli_factory.maybe_lockfile() {
+    Some(lockfile) => {
+      let lockfile = lockfile.lock();
+      lockfile
+        .content
+        .packages
+        .specifiers
+        .iter()
+        .filter_map(|(specifier, resolved)| {
+          Some((specifier.clone(), parse_locked_version(resolved)?))
+        })
+        .collect::<HashMap<_, _>>()
+    }
+    None => HashMap::new(),
+  };
+
+  let mut deps_file_fetcher = FileFetcher::new(
+    cli_factory.global_http_cache()?.clone(),
+    CacheSetting::ReloadAll,
+    true,
+    cli_factory.http_client().clone(),
+    Default::default(),
+    None,
+  );
+  deps_file_fetcher.set_download_log_level(log::Level::Trace);
+  let jsr_resolver = Arc::new(JsrFetchResolver::new(deps_file_fetcher.clone()));
+  let npm_resolver = Arc::new(NpmFetchResolver::new(deps_file_fetcher));
+
+  let package_futures = dependencies
+    .into_iter()
+    .map(|(kind, req)| {
+      let jsr_resolver = jsr_resolver.clone();
+      let npm_resolver = npm_resolver.clone();
+      let current = locked_versions
+        .get(&format!("{}{}", kind.prefix(), req))
+        .cloned();
+      async move {
+        let (wanted, latest) = match kind {
+          PackageKind::Jsr => {
+            let latest_req = PackageReq::from_str(&format!("{}@*", req.name))?;
+            (
+              jsr_resolver.req_to_nv(&req).await,
+              jsr_resolver.req_to_nv(&latest_req).await,
+            )
+          }
+          PackageKind::Npm => {
+            let latest =
+              match npm_resolver.package_info(&req.name).await {
+                Some(info) => {
+                  info.dist_tags.get("latest").cloned().map(|version| {
+                    PackageNv {
+                      name: req.name.clone(),
+                      version,
+                    }
+                  })
+                }
+                None => None,
+              };
+            (npm_resolver.req_to_nv(&req).await, latest)
+          }
+        };
+        Ok::<_, AnyError>(OutdatedPackage {
+          kind,
+          req,
+          current,
+          wanted: wanted.map(|nv| nv.version),
+          latest: latest.map(|nv| nv.version),
+        })
+      }
+      .boxed_local()
+    })
+    .collect::<Vec<_>>();
+
+  let stream_of_futures = deno_core::futures::stream::iter(package_futures);
+  let mut buffered = stream_of_futures.buffer_unordered(10);
+  let mut outdated_packages = Vec::new();
+  while let Some(package_result) = buffered.next().await {
+    let package = package_result?;
+    if package.latest.is_none() {
+      log::warn!(
+        "{} {}{} was not found in the registry.",
+        crate::colors::yellow("Warning"),
+        package.kind.prefix(),
+        package.req.name
+      );
+      continue;
+    }
+    if package.is_outdated() {
+      outdated_packages.push(package);
+    }
+  }
+  outdated_packages.sort_by(|a, b| {
+    (
+      a.kind.prefix(),
+      &a.req.name,
+      a.req.version_req.version_text(),
+    )
+      .cmp(&(
+        b.kind.prefix(),
+        &b.req.name,
+        b.req.version_req.version_text(),
+      ))
+  });
+
+  if outdated_packages.is_empty() {
+    log::info!("All dependencies are up to date.");
+    return Ok(());
+  }
+  print_outdated_table(&outdated_packages);
+
+  if !outdated_flags.update {
+    return Ok(());
+  }
+
+  // maps the outdated dependencies to their new version requirements
+  let updates = outdated_packages
+    .iter()
+    .filter_map(|package| {
+      let version = if outdated_flags.latest {
+        package.latest.as_ref()?
+      } else {
+        package.wanted.as_ref()?
+      };
+      let version_text = package.req.version_req.version_text();
+      let new_version_text = update_version_text(version_text, version)?;
+      Some(((package.kind, package.req.clone()), new_version_text))
+    })
+    .collect::<HashMap<_, _>>();
+  let mut updated_count = 0;
+
+  if let Some(config_file) = cli_options.maybe_config_file() {
+    if config_file.specifier.scheme() != "file" {
+      bail!("Can't update dependencies of a remote configuration file");
+    }
+    let config_file_path = config_file.specifier.to_file_path().unwrap();
+    update_dependencies_file(
+      &config_file_path,
+      &["imports"],
+      &Default::default(),
+      |key, value| {
+        let (kind, req) = parse_import_value(value)?;
+        let new_version_text = updates.get(&(kind, req.clone()))?;
+        let new_value = replace_version_text(value, &req, new_version_text)?;
+        log::info!(
+          "Update {} to {}",
+          crate::colors::green(key),
+          new_version_text
+        );
+        updated_count += 1;
+        Some(EntryChange::Update(new_value))
+      },
+    )
+    .await?;
+  }
+
+  if let Some(package_json) = cli_options.maybe_package_json() {
+    update_dependencies_file(
+      &package_json.path,
+      &["dependencies", "devDependencies"],
+      &Default::default(),
+      |key, value| {
+        let req = parse_package_json_value(key, value)?;
+        let new_version_text = updates.get(&(PackageKind::Npm, req.clone()))?;
+        let new_value = replace_version_text(value, &req, new_version_text)?;
+        log::info!(
+          "Update {} to {}",
+          crate::colors::green(key),
+          new_version_text
+        );
+        updated_count += 1;
+        Some(EntryChange::Update(new_value))
+      },
+    )
+    .await?;
+  }
+
+  if updated_count > 0 {
+    // loading the lockfile with the updated configuration removes the
+    // outdated version requirements
+    let cli_factory = CliFactory::from_flags(flags)?;
+    if let Some(lockfile) = cli_factory.maybe_lockfile() {
+      lockfile
+        .lock()
+        .write()
+        .context("Failed writing lockfile.")?;
+    }
+  }
+
+  Ok(())
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum PackageKind {
+  Jsr,
+  Npm,
+}
+
+impl PackageKind {
+  fn prefix(&self) -> &'static str {
+    match self {
+      PackageKind::Jsr => "jsr:",
+      PackageKind::Npm => "npm:",
+    }
+  }
+}
+
+struct OutdatedPackage {
+  kind: PackageKind,
+  req: PackageReq,
+  /// The version in the lockfile.
+  current: Option<Version>,
+  /// The newest version matching the version requirement.
+  wanted: Option<Version>,
+  latest: Option<Version>,
+}
+
+impl OutdatedPackage {
+  fn is_outdated(&self) -> bool {
+    let current = self.current.as_ref().or(self.wanted.as_ref());
+    current != self.latest.as_ref()
+      || matches!(
+        (&self.current, &self.wanted),
+        (Some(current), Some(wanted)) if current < wanted
+      )
+  }
+}
+
+fn print_outdated_table(packages: &[OutdatedPackage]) {
+  let header = ["Package", "Current", "Wanted", "Latest"];
+  let rows = packages
+    .iter()
+    .map(|package| {
+      let display_version = |version: &Option<Version>| {
+        version
+          .as_ref()
+          .map(|v| v.to_string())
+          .unwrap_or_else(|| "-".to_string())
+      };
+      [
+        format!("{}{}", package.kind.prefix(), package.req.name),
+        display_version(&package.current),
+        display_version(&package.wanted),
+        display_version(&package.latest),
+      ]
+    })
+    .collect::<Vec<_>>();
+  let mut widths = header.map(|column| column.len());
+  for row in &rows {
+    for (width, cell) in widths.iter_mut().zip(row) {
+      *width = (*width).max(cell.chars().count());
+    }
+  }
+  let format_row = |row: &[String]| {
+    row
+      .iter()
+      .zip(widths)
+      .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+      .collect::<Vec<_>>()
+      .join("  ")
+      .trim_end()
+      .to_string()
+  };
+  log::info!(
+    "{}",
+    crate::colors::bold(format_row(&header.map(|c| c.to_string())))
+  );
+  for row in &rows {
+    log::info!("{}", format_row(row));
+  }
+}
+
+/// Gets the new version requirement text for a dependency, keeping the
+/// kind of range of the current one. Returns `None` when it doesn't change.
+fn update_version_text(
+  version_text: &str,
+  version: &Version,
+) -> Option<String> {
+  let new_version_text = if version_text.starts_with('~') {
+    format!("~{}", version)
+  } else if Version::parse_standard(version_text).is_ok() {
+    version.to_string()
+  } else {
+    format!("^{}", version)
+  };
+  (new_version_text != version_text).then_some(new_version_text)
+}
+
+/// Replaces the version requirement of a dependency in the value of an
+/// import map entry or a package.json dependency.
+fn replace_version_text(
+  value: &str,
+  req: &PackageReq,
+  new_version_text: &str,
+) -> Option<String> {
+  let version_text = req.version_req.version_text();
+  if value == version_text {
+    return Some(new_version_text.to_string());
+  }
+  let name_and_version = format!("{}@{}", req.name, version_text);
+  value.contains(&name_and_version).then(|| {
+    value.replacen(
+      &name_and_version,
+      &format!("{}@{}", req.name, new_version_text),
+      1,
+    )
+  })
+}
+
+/// Parses a package name given on the command line, like `@std/path`,
+/// `jsr:@std/path` or `npm:chalk`.
+fn parse_package_name(package: &str) -> (Option<PackageKind>, &str) {
+  if let Some(name) = package.strip_prefix("jsr:") {
+    (Some(PackageKind::Jsr), name)
+  } else if let Some(name) = package.strip_prefix("npm:") {
+    (Some(PackageKind::Npm), name)
+  } else {
+    (None, package)
+  }
+}
+
+fn parse_import_value(value: &str) -> Option<(PackageKind, PackageReq)> {
+  // prefix mappings are written like `jsr:/@std/path@^1/`
+  let value = match value.split_once(":/") {
+    Some((scheme @ ("jsr" | "npm"), rest)) => format!("{}:{}", scheme, rest),
+    _ => value.to_string(),
+  };
+  if let Ok(req_ref) = JsrPackageReqReference::from_str(&value) {
+    Some((PackageKind::Jsr, req_ref.into_inner().req))
+  } else if let Ok(req_ref) = NpmPackageReqReference::from_str(&value) {
+    Some((PackageKind::Npm, req_ref.into_inner().req))
+  } else {
+    None
+  }
+}
+
+fn parse_package_json_value(key: &str, value: &str) -> Option<PackageReq> {
+  if value.starts_with("npm:") {
+    NpmPackageReqReference::from_str(value)
+      .ok()
+      .map(|req_ref| req_ref.into_inner().req)
+  } else {
+    PackageReq::from_str(&format!("{}@{}", key, value)).ok()
+  }
+}
+
+/// Gets the version of a resolved lockfile specifier, like
+/// `jsr:@std/path@1.0.0` or `npm:chalk@5.3.0`.
+fn parse_locked_version(resolved: &str) -> Option<Version> {
+  let nv = resolved
+    .strip_prefix("jsr:")
+    .or_else(|| resolved.strip_prefix("npm:"))?;
+  // skip the `@` of scoped packages
+  let (_, version) = nv.get(1..)?.split_once('@')?;
+  // npm package ids may have a suffix with their peer dependencies
+  let version = version.split('_').next().unwrap();
+  Version::parse_standard(version).ok()
+}
+
+enum EntryChange {
+  Remove,
+  Update(String),
+}
+
+/// Changes the string entries of the given top level objects of a JSON(C)
+/// file, like the "imports" of a deno.json or the "dependencies" of a
+/// package.json.
+async fn update_dependencies_file(
+  file_path: &Path,
+  object_names: &[&str],
+  fmt_options: &FmtOptionsConfig,
+  change_entry: impl FnMut(&str, &str) -> Option<EntryChange>,
+) -> Result<(), AnyError> {
+  let contents = tokio::fs::read_to_string(file_path)
+    .await
+    .with_context(|| format!("Failed reading {}", file_path.display()))?;
+  let new_text =
+    update_object_entries(&contents, object_names, fmt_options, change_entry)?;
+  if let Some(new_text) = new_text {
+    tokio::fs::write(file_path, new_text)
+      .await
+      .with_context(|| format!("Failed updating {}", file_path.display()))?;
+  }
+  Ok(())
+}
+
+fn update_object_entries(
+  contents: &str,
+  object_names: &[&str],
+  fmt_options: &FmtOptionsConfig,
+  mut change_entry: impl FnMut(&str, &str) -> Option<EntryChange>,
+) -> Result<Option<String>, AnyError> {
+  let contents = if contents.trim().is_empty() {
+    "{}\n"
+  } else {
+    contents
+  };
+  let ast = jsonc_parser::parse_to_ast(
+    contents,
+    &Default::default(),
+    &Default::default(),
+  )?;
+  let obj = match ast.value {
+    Some(Value::Object(obj)) => obj,
+    _ => bail!("Failed updating config file due to no object."),
+  };
+
+  let mut text_changes = vec![];
+  let mut should_format = false;
+  for object_name in object_names {
+    let Some(ObjectProp {
+      value: Value::Object(entries),
+      ..
+    }) = obj.get(object_name)
+    else {
+      continue;
+    };
+    let mut entry_texts = Vec::with_capacity(entries.properties.len());
+    let mut value_changes = Vec::new();
+    let mut has_removed = false;
+    for prop in &entries.properties {
+      let original_text = &contents[prop.range.start..prop.range.end];
+      let Value::StringLit(lit) = &prop.value else {
+        entry_texts.push(original_text.to_string());
+        continue;
+      };
+      match change_entry(prop.name.as_str(), &lit.value) {
+        None => entry_texts.push(original_text.to_string()),
+        Some(EntryChange::Remove) => has_removed = true,
+        Some(EntryChange::Update(value)) => {
+          let value = serde_json::to_string(&value)?;
+          entry_texts.push(format!(
+            "{}: {}",
+            serde_json::to_string(prop.name.as_str())?,
+            value
+          ));
+          value_changes.push(TextChange {
+            range: lit.range.start..lit.range.end,
+            new_text: value,
+          });
+        }
+      }
+    }
+    if has_removed {
+      // regenerate the entries and format after to make it look nice
+      text_changes.push(TextChange {
+        range: (entries.range.start + 1)..(entries.range.end - 1),
+        // NOTE: the newlines force the formatter to keep the object multiline
+        new_text: if entry_texts.is_empty() {
+          String::new()
+        } else {
+          format!("\n{}\n", entry_texts.join(",\n"))
+        },
+      });
+      should_format = true;
+    } else {
+      text_changes.extend(value_changes);
+    }
+  }
+
+  if text_changes.is_empty() {
+    return Ok(None);
+  }
+  let new_text = deno_ast::apply_text_changes(contents, text_changes);
+  if !should_format {
+    return Ok(Some(new_text));
+  }
+  Ok(Some(
+    crate::tools::fmt::format_json(
+      &PathBuf::from("deno.json"),
+      &new_text,
+      fmt_options,
+    )
+    .ok()
+    .map(|formatted_text| formatted_text.unwrap_or_else(|| new_text.clone()))
+    .unwrap_or(new_text),
+  ))
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn test_update_object_entries() {
+    let contents = r#"{
+  // comment
+  "imports": {
+    "@std/path": "jsr:@std/path@^0.220.0",
+    "chalk": "npm:chalk@5"
+  },
+  "tasks": {}
+}
+"#;
+    let new_text = update_object_entries(
+      contents,
+      &["imports"],
+      &Default::default(),
+      |key, _| (key == "chalk").then_some(EntryChange::Remove),
+    )
+    .unwrap()
+    .unwrap();
+    assert_eq!(
+      new_text,
+      r#"{
+  // comment
+  "imports": {
+    "@std/path": "jsr:@std/path@^0.220.0"
+  },
+  "tasks": {}
+}
+"#
+    );
+
+    let new_text = update_object_entries(
+      contents,
+      &["imports"],
+      &Default::default(),
+      |key, value| {
+        (key == "@std/path")
+          .then(|| EntryChange::Update(value.replace("0.220.0", "1.0.0")))
+      },
+    )
+    .unwrap()
+    .unwrap();
+    assert_eq!(new_text, contents.replace("0.220.0", "1.0.0"));
+
+    let new_text = update_object_entries(
+      contents,
+      &["dependencies"],
+      &Default::default(),
+      |_, _| Some(EntryChange::Remove),
+    )
+    .unwrap();
+    assert_eq!(new_text, None);
+  }
+
+  #[test]
+  fn test_update_version_text() {
+    let version = Version::parse_standard("1.2.3").unwrap();
+    assert_eq!(
+      update_version_text("^1.0.0", &version),
+      Some("^1.2.3".to_string())
+    );
+    assert_eq!(
+      update_version_text("~1.2.0", &version),
+      Some("~1.2.3".to_string())
+    );
+    assert_eq!(
+      update_version_text("1.0.0", &version),
+      Some("1.2.3".to_string())
+    );
+    assert_eq!(
+      update_version_text("1", &version),
+      Some("^1.2.3".to_string())
+    );
+    assert_eq!(update_version_text("^1.2.3", &version), None);
+  }
+
+  #[test]
+  fn test_replace_version_text() {
+    let req = PackageReq::from_str("@std/path@^0.220.0").unwrap();
+    assert_eq!(
+      replace_version_text("jsr:@std/path@^0.220.0/posix", &req, "^1.0.0"),
+      Some("jsr:@std/path@^1.0.0/posix".to_string())
+    );
+    let req = PackageReq::from_str("chalk@^5.0.0").unwrap();
+    assert_eq!(
+      replace_version_text("^5.0.0", &req, "^5.3.0"),
+      Some("^5.3.0".to_string())
+    );
+  }
+
+  #[test]
+  fn test_parse_locked_version() {
+    assert_eq!(
+      parse_locked_version("jsr:@std/path@1.0.0"),
+      Some(Version::parse_standard("1.0.0").unwrap())
+    );
+    assert_eq!(
+      parse_locked_version("npm:preact-render-to-string@6.4.0_preact@10.20.0"),
+      Some(Version::parse_standard("6.4.0").unwrap())
+    );
+    assert_eq!(parse_locked_version("https://deno.land/x/mod.ts"), None);
+  }
+
+  #[test]
+  fn test_parse_import_value() {
+    assert_eq!(
+      parse_import_value("jsr:/@std/path@^1/"),
+      Some((
+        PackageKind::Jsr,
+        PackageReq::from_str("@std/path@^1").unwrap()
+      ))
+    );
+    assert_eq!(
+      parse_import_value("npm:chalk@5"),
+      Some((PackageKind::Npm, PackageReq::from_str("chalk@5").unwrap()))
+    );
+    assert_eq!(parse_import_value("./mod.ts"), None);
+  }
+}
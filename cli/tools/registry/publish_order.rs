@@ -178,7 +178,7 @@ fn build_pkg_deps(
   Ok(members)
 }
 
-fn build_publish_order_graph_from_pkgs_deps(
+pub fn build_publish_order_graph_from_pkgs_deps(
   packages: HashMap<String, HashSet<String>>,
 ) -> PublishOrderGraph {
   let mut in_degree = HashMap::new();
@@ -0,0 +1,367 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use deno_ast::swc::ast;
+use deno_ast::MediaType;
+use deno_ast::ModuleSpecifier;
+use deno_core::error::AnyError;
+use deno_graph::GraphKind;
+
+use crate::args::CliOptions;
+use crate::args::TsTypeLib;
+use crate::args::TypeCheckMode;
+use crate::file_fetcher::File;
+use crate::file_fetcher::FileFetcher;
+use crate::graph_util::ModuleGraphCreator;
+use crate::tools::check::CheckOptions;
+use crate::tools::check::TypeChecker;
+use crate::tsc::Diagnostics;
+
+/// Declarations for the globals that the REPL injects at runtime.
+const PRELUDE: &str = r#"declare let _: any;
+declare let _error: any;
+declare function clear(): void;
+"#;
+
+/// Codes of the diagnostics about declaring a binding twice, which is allowed
+/// at the top level of the REPL.
+const REDECLARATION_DIAGNOSTIC_CODES: &[u64] = &[
+  2300, // Duplicate identifier
+  2393, // Duplicate function implementation
+  2451, // Cannot redeclare block-scoped variable
+];
+
+/// An evaluated input that later inputs are checked in the context of.
+struct EvaluatedInput {
+  source: String,
+  /// The names of the top level bindings the input declares.
+  declared_names: HashSet<String>,
+  /// Whether the input only has declarations, so it can be dropped once all
+  /// of its bindings are redeclared.
+  only_declarations: bool,
+}
+
+/// Type checks REPL inputs in the context of the previously evaluated inputs.
+///
+/// Only the inputs that declare bindings are kept as context, and inputs that
+/// were entirely redeclared are dropped, so that the checked source grows
+/// with the bindings of the session rather than every input. The REPL's
+/// language server is given the same context, so that completions and hover
+/// use the types the inputs are checked with.
+pub struct ReplTypeChecker {
+  file_fetcher: Arc<FileFetcher>,
+  module_graph_creator: Arc<ModuleGraphCreator>,
+  type_checker: Arc<TypeChecker>,
+  lib: TsTypeLib,
+  type_check_mode: TypeCheckMode,
+  /// The in-memory module that the inputs are checked as.
+  specifier: ModuleSpecifier,
+  /// The inputs that were evaluated successfully.
+  evaluated_inputs: Vec<EvaluatedInput>,
+  /// The last input line and the form of it that type checked, which is
+  /// committed once it's evaluated successfully.
+  pending_input: Option<(String, String)>,
+  enabled: bool,
+}
+
+impl ReplTypeChecker {
+  pub fn new(
+    cli_options: &CliOptions,
+    file_fetcher: Arc<FileFetcher>,
+    module_graph_creator: Arc<ModuleGraphCreator>,
+    type_checker: Arc<TypeChecker>,
+    specifier: ModuleSpecifier,
+  ) -> Self {
+    let type_check_mode = cli_options.type_check_mode();
+    Self {
+      file_fetcher,
+      module_graph_creator,
+      type_checker,
+      lib: cli_options.ts_type_lib_window(),
+      // when enabled with `.check`, default to checking local modules
+      type_check_mode: if type_check_mode.is_true() {
+        type_check_mode
+      } else {
+        TypeCheckMode::Local
+      },
+      specifier,
+      evaluated_inputs: Vec::new(),
+      pending_input: None,
+      enabled: type_check_mode.is_true(),
+    }
+  }
+
+  pub fn is_enabled(&self) -> bool {
+    self.enabled
+  }
+
+  pub fn set_enabled(&mut self, enabled: bool) {
+    self.enabled = enabled;
+    self.pending_input = None;
+  }
+
+  /// Type checks the input, returning the diagnostics found in it.
+  ///
+  /// Expressions that look like an object literal are checked as one first,
+  /// to match how they're evaluated.
+  pub async fn check(&mut self, line: &str) -> Result<Diagnostics, AnyError> {
+    self.pending_input = None;
+    let wrapped_line = if line.trim_start().starts_with('{')
+      && !line.trim_end().ends_with(';')
+    {
+      format!("({})", &line)
+    } else {
+      line.to_string()
+    };
+
+    let diagnostics = self.check_input(&wrapped_line).await?;
+    if diagnostics.is_empty() {
+      self.pending_input = Some((line.to_string(), wrapped_line));
+      return Ok(diagnostics);
+    }
+    if wrapped_line != line && self.check_input(line).await?.is_empty() {
+      self.pending_input = Some((line.to_string(), line.to_string()));
+      return Ok(Diagnostics::default());
+    }
+    Ok(diagnostics)
+  }
+
+  /// Adds the input to the context that later inputs are checked in. This
+  /// should be called once the input was evaluated successfully.
+  pub fn commit(&mut self, line: &str) {
+    let source = match self.pending_input.take() {
+      Some((pending_line, input)) if pending_line == line => input,
+      _ => line.to_string(),
+    };
+    let Some((declared_names, only_declarations)) = analyze_input(&source)
+    else {
+      // keep inputs that fail to parse as they are
+      self.evaluated_inputs.push(EvaluatedInput {
+        source,
+        declared_names: HashSet::new(),
+        only_declarations: false,
+      });
+      return;
+    };
+    if declared_names.is_empty() && only_declarations {
+      // expressions don't affect how later inputs are checked
+      return;
+    }
+    self.evaluated_inputs.retain(|input| {
+      !input.only_declarations
+        || input.declared_names.is_empty()
+        || !input.declared_names.is_subset(&declared_names)
+    });
+    self.evaluated_inputs.push(EvaluatedInput {
+      source,
+      declared_names,
+      only_declarations,
+    });
+  }
+
+  /// Forgets the last checked input. This should be called when it failed to
+  /// evaluate.
+  pub fn discard_pending(&mut self) {
+    self.pending_input = None;
+  }
+
+  /// Gets the source that inputs are checked in the context of.
+  pub fn context_source(&self) -> String {
+    let mut source = PRELUDE.to_string();
+    for evaluated_input in &self.evaluated_inputs {
+      source.push_str(&evaluated_input.source);
+      source.push('\n');
+    }
+    source
+  }
+
+  async fn check_input(&self, input: &str) -> Result<Diagnostics, AnyError> {
+    let context_source = self.context_source();
+    // the `export {}` makes top level await valid
+    let source = format!("{}{}\nexport {{}};\n", context_source, input);
+    let input_line_offset = context_source.matches('\n').count() as u64;
+    self.file_fetcher.insert_memory_files(File {
+      specifier: self.specifier.clone(),
+      maybe_headers: None,
+      source: source.into_bytes().into(),
+    });
+
+    let graph = self
+      .module_graph_creator
+      .create_graph(GraphKind::All, vec![self.specifier.clone()])
+      .await?;
+    self.module_graph_creator.graph_valid(&graph)?;
+    let (_, diagnostics) = self
+      .type_checker
+      .check_diagnostics(
+        graph,
+        CheckOptions {
+          build_fast_check_graph: true,
+          lib: self.lib,
+          log_checked_roots: false,
+          log_ignored_options: false,
+          reload: false,
+          type_check_mode: self.type_check_mode,
+        },
+      )
+      .await?;
+
+    // only report the diagnostics of the input, with its positions
+    // relative to the start of the input
+    let file_name = self.specifier.as_str();
+    Ok(
+      diagnostics
+        .filter(|d| {
+          if d.file_name.as_deref() != Some(file_name) {
+            return true;
+          }
+          let in_input = d
+            .start
+            .as_ref()
+            .map(|start| start.line >= input_line_offset)
+            .unwrap_or(true);
+          in_input && !REDECLARATION_DIAGNOSTIC_CODES.contains(&d.code)
+        })
+        .map(|mut d| {
+          if d.file_name.as_deref() == Some(file_name) {
+            for position in [&mut d.start, &mut d.end].into_iter().flatten() {
+              position.line = position.line.saturating_sub(input_line_offset);
+            }
+          }
+          d
+        }),
+    )
+  }
+}
+
+/// Gets the names of the top level bindings the input declares and whether
+/// it only has declarations or expressions, or `None` when it doesn't parse.
+/// Inputs that only have expressions report no names.
+fn analyze_input(source: &str) -> Option<(HashSet<String>, bool)> {
+  let parsed = deno_ast::parse_module(deno_ast::ParseParams {
+    specifier: ModuleSpecifier::parse("file:///repl.ts").unwrap(),
+    text_info: deno_ast::SourceTextInfo::from_string(source.to_string()),
+    media_type: MediaType::TypeScript,
+    capture_tokens: false,
+    maybe_syntax: None,
+    scope_analysis: false,
+  })
+  .ok()?;
+  let mut names = HashSet::new();
+  let mut only_declarations = true;
+  for item in &parsed.module().body {
+    match item {
+      ast::ModuleItem::Stmt(ast::Stmt::Expr(_)) => {}
+      ast::ModuleItem::Stmt(ast::Stmt::Decl(decl)) => match decl {
+        ast::Decl::Class(decl) => {
+          names.insert(decl.ident.sym.to_string());
+        }
+        ast::Decl::Fn(decl) => {
+          names.insert(decl.ident.sym.to_string());
+        }
+        ast::Decl::Var(decl) => {
+          for declarator in &decl.decls {
+            collect_pat_names(&declarator.name, &mut names);
+          }
+        }
+        ast::Decl::TsInterface(decl) => {
+          names.insert(decl.id.sym.to_string());
+        }
+        ast::Decl::TsTypeAlias(decl) => {
+          names.insert(decl.id.sym.to_string());
+        }
+        ast::Decl::TsEnum(decl) => {
+          names.insert(decl.id.sym.to_string());
+        }
+        ast::Decl::TsModule(_) | ast::Decl::Using(_) => {
+          only_declarations = false;
+        }
+      },
+      ast::ModuleItem::ModuleDecl(ast::ModuleDecl::Import(import)) => {
+        for specifier in &import.specifiers {
+          let local = match specifier {
+            ast::ImportSpecifier::Named(specifier) => &specifier.local,
+            ast::ImportSpecifier::Default(specifier) => &specifier.local,
+            ast::ImportSpecifier::Namespace(specifier) => &specifier.local,
+          };
+          names.insert(local.sym.to_string());
+        }
+      }
+      _ => {
+        only_declarations = false;
+      }
+    }
+  }
+  Some((names, only_declarations))
+}
+
+fn collect_pat_names(pat: &ast::Pat, names: &mut HashSet<String>) {
+  match pat {
+    ast::Pat::Ident(ident) => {
+      names.insert(ident.id.sym.to_string());
+    }
+    ast::Pat::Array(array) => {
+      for elem in array.elems.iter().flatten() {
+        collect_pat_names(elem, names);
+      }
+    }
+    ast::Pat::Object(object) => {
+      for prop in &object.props {
+        match prop {
+          ast::ObjectPatProp::KeyValue(prop) => {
+            collect_pat_names(&prop.value, names)
+          }
+          ast::ObjectPatProp::Assign(prop) => {
+            names.insert(prop.key.sym.to_string());
+          }
+          ast::ObjectPatProp::Rest(rest) => collect_pat_names(&rest.arg, names),
+        }
+      }
+    }
+    ast::Pat::Rest(rest) => collect_pat_names(&rest.arg, names),
+    ast::Pat::Assign(assign) => collect_pat_names(&assign.left, names),
+    ast::Pat::Invalid(_) | ast::Pat::Expr(_) => {}
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  fn names(source: &str) -> (Vec<String>, bool) {
+    let (names, only_declarations) = analyze_input(source).unwrap();
+    let mut names = names.into_iter().collect::<Vec<_>>();
+    names.sort();
+    (names, only_declarations)
+  }
+
+  #[test]
+  fn test_analyze_input() {
+    assert_eq!(names("a + 1"), (vec![], true));
+    assert_eq!(
+      names("const { a, b: [c], ...d } = obj; function e() {}"),
+      (
+        vec![
+          "a".to_string(),
+          "c".to_string(),
+          "d".to_string(),
+          "e".to_string()
+        ],
+        true
+      )
+    );
+    assert_eq!(
+      names("import f, { g as h } from './mod.ts'; type T = string;"),
+      (
+        vec!["T".to_string(), "f".to_string(), "h".to_string()],
+        true
+      )
+    );
+    assert_eq!(
+      names("let a = 1; if (a) { a++; }"),
+      (vec!["a".to_string()], false)
+    );
+  }
+}
@@ -19,6 +19,7 @@ use deno_runtime::WorkerExecutionMode;
 use rustyline::error::ReadlineError;
 
 mod channel;
+mod check;
 mod editor;
 mod session;
 
@@ -26,6 +27,7 @@ use channel::rustyline_channel;
 use channel::RustylineSyncMessage;
 use channel::RustylineSyncMessageHandler;
 use channel::RustylineSyncResponse;
+use check::ReplTypeChecker;
 use editor::EditorHelper;
 use editor::ReplEditor;
 pub use session::EvaluationOutput;
@@ -38,6 +40,8 @@ struct Repl {
   session: ReplSession,
   editor: ReplEditor,
   message_handler: RustylineSyncMessageHandler,
+  /// The inputs that were evaluated successfully, for `.save`.
+  session_inputs: Vec<String>,
 }
 
 impl Repl {
@@ -47,13 +51,19 @@ impl Repl {
         &mut self.session,
         &mut self.message_handler,
         self.editor.clone(),
+        "> ",
       )
       .await;
       match line {
         Ok(line) => {
           self.editor.set_should_exit_on_interrupt(false);
           self.editor.update_history(line.clone());
-          let output = self.session.evaluate_line_and_get_output(&line).await;
+          let source = match self.handle_command(&line).await? {
+            CommandResult::NotCommand => line,
+            CommandResult::Evaluate(source) => source,
+            CommandResult::Handled => continue,
+          };
+          let output = self.session.evaluate_line_and_get_output(&source).await;
 
           // We check for close and break here instead of making it a loop condition to get
           // consistent behavior in when the user evaluates a call to close().
@@ -61,6 +71,9 @@ impl Repl {
             break;
           }
 
+          if let EvaluationOutput::Value(_) = &output {
+            self.session_inputs.push(source);
+          }
           println!("{output}");
         }
         Err(ReadlineError::Interrupted) => {
@@ -83,14 +96,116 @@ impl Repl {
 
     Ok(())
   }
+
+  /// Handles the REPL commands, which start with a `.`.
+  async fn handle_command(
+    &mut self,
+    line: &str,
+  ) -> Result<CommandResult, AnyError> {
+    let line = line.trim();
+    let (command, arg) = match line.split_once(char::is_whitespace) {
+      Some((command, arg)) => (command, arg.trim()),
+      None => (line, ""),
+    };
+    match command {
+      ".help" => {
+        println!(".check   Toggle type checking of the inputs");
+        println!(".editor  Enter editor mode to write multiple lines");
+        println!(".help    Print this help message");
+        println!(".load    Load and evaluate a file in the session");
+        println!(".save    Save the evaluated inputs of the session to a file");
+        Ok(CommandResult::Handled)
+      }
+      ".check" => {
+        if let Some(type_checker) = self.session.type_checker_mut() {
+          let enabled = !type_checker.is_enabled();
+          type_checker.set_enabled(enabled);
+          if enabled {
+            println!("Type checking enabled");
+          } else {
+            println!("Type checking disabled");
+          }
+        }
+        Ok(CommandResult::Handled)
+      }
+      ".save" => {
+        if arg.is_empty() {
+          println!("{} .save <file>", colors::red("usage:"));
+          return Ok(CommandResult::Handled);
+        }
+        let mut text = self.session_inputs.join("\n");
+        text.push('\n');
+        match std::fs::write(arg, text) {
+          Ok(()) => println!("Session saved to: {arg}"),
+          Err(err) => {
+            println!("{} failed to save {arg}: {err}", colors::red("error:"))
+          }
+        }
+        Ok(CommandResult::Handled)
+      }
+      ".load" => {
+        if arg.is_empty() {
+          println!("{} .load <file>", colors::red("usage:"));
+          return Ok(CommandResult::Handled);
+        }
+        match std::fs::read_to_string(arg) {
+          Ok(source) => Ok(CommandResult::Evaluate(source)),
+          Err(err) => {
+            println!("{} failed to load {arg}: {err}", colors::red("error:"));
+            Ok(CommandResult::Handled)
+          }
+        }
+      }
+      ".editor" => {
+        println!(
+          "// Entering editor mode (ctrl+d to finish, ctrl+c to cancel)"
+        );
+        let mut lines = Vec::new();
+        loop {
+          let line = read_line_and_poll(
+            &mut self.session,
+            &mut self.message_handler,
+            self.editor.clone(),
+            "",
+          )
+          .await;
+          match line {
+            Ok(line) => lines.push(line),
+            Err(ReadlineError::Eof) => break,
+            Err(ReadlineError::Interrupted) => {
+              return Ok(CommandResult::Handled);
+            }
+            Err(err) => return Err(err.into()),
+          }
+        }
+        let source = lines.join("\n");
+        if source.trim().is_empty() {
+          return Ok(CommandResult::Handled);
+        }
+        self.editor.update_history(source.clone());
+        Ok(CommandResult::Evaluate(source))
+      }
+      _ => Ok(CommandResult::NotCommand),
+    }
+  }
+}
+
+enum CommandResult {
+  /// The line isn't a command and should be evaluated.
+  NotCommand,
+  /// The command produced source code to evaluate.
+  Evaluate(String),
+  /// The command was handled.
+  Handled,
 }
 
 async fn read_line_and_poll(
   repl_session: &mut ReplSession,
   message_handler: &mut RustylineSyncMessageHandler,
   editor: ReplEditor,
+  prompt: &'static str,
 ) -> Result<String, ReadlineError> {
-  let mut line_fut = spawn_blocking(move || editor.readline());
+  let mut line_fut = spawn_blocking(move || editor.readline(prompt));
   let mut poll_worker = true;
   let notifications_rc = repl_session.notifications.clone();
   let mut notifications = notifications_rc.lock().await;
@@ -162,11 +277,17 @@ pub async fn run(flags: Flags, repl_flags: ReplFlags) -> Result<i32, AnyError> {
   let npm_resolver = factory.npm_resolver().await?.clone();
   let resolver = factory.resolver().await?.clone();
   let file_fetcher = factory.file_fetcher()?;
+  let module_graph_creator = factory.module_graph_creator().await?.clone();
+  let type_checker = factory.type_checker().await?.clone();
   let worker_factory = factory.create_cli_main_worker_factory().await?;
-  let history_file_path = factory
-    .deno_dir()
-    .ok()
-    .and_then(|dir| dir.repl_history_file_path());
+  let history_file_path = factory.deno_dir().ok().and_then(|dir| {
+    dir.repl_history_file_path(
+      cli_options
+        .maybe_config_file()
+        .as_ref()
+        .map(|config_file| &config_file.specifier),
+    )
+  });
   let (worker, test_event_receiver) = create_single_test_event_channel();
   let test_event_sender = worker.sender;
   let mut worker = worker_factory
@@ -180,7 +301,7 @@ pub async fn run(flags: Flags, repl_flags: ReplFlags) -> Result<i32, AnyError> {
     .await?;
   worker.setup_repl().await?;
   let worker = worker.into_main_worker();
-  let session = ReplSession::initialize(
+  let mut session = ReplSession::initialize(
     cli_options,
     npm_resolver,
     resolver,
@@ -189,6 +310,14 @@ pub async fn run(flags: Flags, repl_flags: ReplFlags) -> Result<i32, AnyError> {
     test_event_receiver,
   )
   .await?;
+  let type_checker = ReplTypeChecker::new(
+    cli_options,
+    file_fetcher.clone(),
+    module_graph_creator,
+    type_checker,
+    session.referrer().clone(),
+  );
+  session.set_type_checker(type_checker);
   let rustyline_channel = rustyline_channel();
 
   let helper = EditorHelper {
@@ -202,6 +331,7 @@ pub async fn run(flags: Flags, repl_flags: ReplFlags) -> Result<i32, AnyError> {
     session,
     editor,
     message_handler: rustyline_channel.1,
+    session_inputs: Vec::new(),
   };
 
   if let Some(eval_files) = repl_flags.eval_files {
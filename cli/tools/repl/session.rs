@@ -53,6 +53,8 @@ use regex::Match;
 use regex::Regex;
 use tokio::sync::Mutex;
 
+use super::check::ReplTypeChecker;
+
 fn comment_source_to_position_range(
   comment_start: SourcePos,
   m: &Match,
@@ -188,6 +190,7 @@ pub struct ReplSession {
   test_event_receiver: Option<TestEventReceiver>,
   jsx: ReplJsxState,
   experimental_decorators: bool,
+  type_checker: Option<ReplTypeChecker>,
 }
 
 impl ReplSession {
@@ -283,6 +286,7 @@ impl ReplSession {
         import_source: None,
       },
       experimental_decorators,
+      type_checker: None,
     };
 
     // inject prelude
@@ -298,6 +302,19 @@ impl ReplSession {
     self.test_reporter_factory = f;
   }
 
+  pub fn set_type_checker(&mut self, type_checker: ReplTypeChecker) {
+    self.type_checker = Some(type_checker);
+  }
+
+  pub fn type_checker_mut(&mut self) -> Option<&mut ReplTypeChecker> {
+    self.type_checker.as_mut()
+  }
+
+  /// The specifier that inputs are evaluated relative to.
+  pub fn referrer(&self) -> &ModuleSpecifier {
+    &self.referrer
+  }
+
   pub async fn closing(&mut self) -> Result<bool, AnyError> {
     let closed = self
       .evaluate_expression("(this.closed)")
@@ -355,7 +372,26 @@ impl ReplSession {
       session: &mut ReplSession,
       line: &str,
     ) -> Result<EvaluationOutput, AnyError> {
-      match session.evaluate_line_with_object_wrapping(line).await {
+      if let Some(type_checker) = &mut session.type_checker {
+        if type_checker.is_enabled() {
+          let diagnostics = type_checker.check(line).await?;
+          if !diagnostics.is_empty() {
+            return Ok(EvaluationOutput::Error(diagnostics.to_string()));
+          }
+        }
+      }
+
+      let result = session.evaluate_line_with_object_wrapping(line).await;
+      if let Some(type_checker) = &mut session.type_checker {
+        let evaluated = matches!(
+          &result,
+          Ok(response) if response.value.exception_details.is_none()
+        );
+        if !evaluated {
+          type_checker.discard_pending();
+        }
+      }
+      match result {
         Ok(evaluate_response) => {
           let cdp::EvaluateResponse {
             result,
@@ -381,10 +417,25 @@ impl ReplSession {
               exception_details.text, description
             ))
           } else {
-            session
-              .language_server
-              .commit_text(&evaluate_response.ts_code)
-              .await;
+            match &mut session.type_checker {
+              Some(type_checker) if type_checker.is_enabled() => {
+                type_checker.commit(line);
+                // complete and hover in the context inputs are checked in
+                session
+                  .language_server
+                  .set_committed_text(type_checker.context_source())
+                  .await;
+              }
+              maybe_type_checker => {
+                if let Some(type_checker) = maybe_type_checker {
+                  type_checker.commit(line);
+                }
+                session
+                  .language_server
+                  .commit_text(&evaluate_response.ts_code)
+                  .await;
+              }
+            }
 
             session.set_last_eval_result(&result).await?;
             let value = session.get_eval_value(&result).await?;
@@ -1,32 +1,53 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 
-use crate::args::CliOptions;
+use crate::args::deno_json::TaskConfig;
 use crate::args::Flags;
 use crate::args::TaskFlags;
+use crate::cache::TaskCache;
 use crate::colors;
 use crate::factory::CliFactory;
 use crate::npm::CliNpmResolver;
 use crate::npm::InnerCliNpmResolverRef;
 use crate::npm::ManagedCliNpmResolver;
+use crate::tools::registry::build_publish_order_graph_from_pkgs_deps;
+use crate::util::checksum;
 use crate::util::fs::canonicalize_path;
+use crate::util::fs::FileCollector;
+use crate::util::wildcard::wildcard_pattern_to_regex;
+use deno_config::glob::FilePatterns;
+use deno_config::glob::PathOrPatternSet;
+use deno_config::WorkspaceMemberConfig;
 use deno_core::anyhow::bail;
 use deno_core::anyhow::Context;
 use deno_core::error::AnyError;
 use deno_core::futures;
 use deno_core::futures::future::LocalBoxFuture;
+use deno_core::futures::stream::FuturesUnordered;
+use deno_core::futures::StreamExt;
+use deno_core::unsync::spawn_blocking;
+use deno_core::ModuleSpecifier;
 use deno_runtime::deno_node::NodeResolver;
+use deno_runtime::deno_node::PackageJson;
+use deno_semver::jsr::JsrPackageReqReference;
 use deno_semver::package::PackageNv;
 use deno_task_shell::ExecuteResult;
 use deno_task_shell::ShellCommand;
 use deno_task_shell::ShellCommandContext;
+use deno_task_shell::ShellPipeReader;
+use deno_task_shell::ShellPipeWriter;
+use deno_task_shell::ShellState;
 use indexmap::IndexMap;
+use indexmap::IndexSet;
 use lazy_regex::Lazy;
 use regex::Regex;
 use std::collections::HashMap;
+use std::collections::HashSet;
+use std::io::Write;
 use std::path::Path;
 use std::path::PathBuf;
 use std::rc::Rc;
 use tokio::task::LocalSet;
+use tokio_util::sync::CancellationToken;
 
 pub async fn execute_script(
   flags: Flags,
@@ -34,6 +55,9 @@ pub async fn execute_script(
 ) -> Result<i32, AnyError> {
   let factory = CliFactory::from_flags(flags)?;
   let cli_options = factory.cli_options();
+  if task_flags.recursive || task_flags.filter.is_some() {
+    return execute_workspace_script(&factory, &task_flags).await;
+  }
   let tasks_config = cli_options.resolve_tasks_config()?;
   let maybe_package_json = cli_options.maybe_package_json();
   let package_json_scripts = maybe_package_json
@@ -48,39 +72,35 @@ pub async fn execute_script(
       return Ok(1);
     }
   };
+  let (task_names, args) = resolve_task_names_and_args(
+    task_name,
+    cli_options.argv(),
+    task_flags.parallel || task_flags.sequential,
+  );
+  for task_name in &task_names {
+    if !tasks_config.contains_key(task_name)
+      && !package_json_scripts.contains_key(task_name)
+    {
+      eprintln!("Task not found: {task_name}");
+      print_available_tasks(&tasks_config, &package_json_scripts);
+      return Ok(1);
+    }
+  }
+
+  let task_dependencies = tasks_config
+    .iter()
+    .map(|(name, config)| (name.clone(), config.dependencies.clone()))
+    .collect::<HashMap<_, _>>();
+  let all_task_names =
+    resolve_tasks_with_dependencies(&task_names, &task_dependencies)?;
+
   let npm_resolver = factory.npm_resolver().await?;
   let node_resolver = factory.node_resolver().await?;
 
-  if let Some(
-    deno_config::Task::Definition(script)
-    | deno_config::Task::Commented {
-      definition: script, ..
-    },
-  ) = tasks_config.get(task_name)
-  {
-    let config_file_url = cli_options.maybe_config_file_specifier().unwrap();
-    let config_file_path = if config_file_url.scheme() == "file" {
-      config_file_url.to_file_path().unwrap()
-    } else {
-      bail!("Only local configuration files are supported")
-    };
-    let cwd = match task_flags.cwd {
-      Some(path) => canonicalize_path(&PathBuf::from(path))?,
-      None => config_file_path.parent().unwrap().to_owned(),
-    };
-
-    let npm_commands =
-      resolve_npm_commands(npm_resolver.as_ref(), node_resolver)?;
-    run_task(
-      task_name,
-      script,
-      &cwd,
-      cli_options,
-      npm_commands,
-      npm_resolver.as_ref(),
-    )
-    .await
-  } else if package_json_scripts.contains_key(task_name) {
+  let runs_package_json_script = all_task_names
+    .iter()
+    .any(|name| !tasks_config.contains_key(name));
+  if runs_package_json_script {
     let package_json_deps_provider = factory.package_json_deps_provider();
 
     if let Some(package_deps) = package_json_deps_provider.deps() {
@@ -104,77 +124,694 @@ pub async fn execute_script(
         npm_resolver.resolve_pending().await?;
       }
     }
+  }
+
+  let runs_config_file_task = all_task_names
+    .iter()
+    .any(|name| tasks_config.contains_key(name));
+  let config_file_dir = if runs_config_file_task {
+    let config_file_url = cli_options.maybe_config_file_specifier().unwrap();
+    if config_file_url.scheme() == "file" {
+      let config_file_path = config_file_url.to_file_path().unwrap();
+      Some(config_file_path.parent().unwrap().to_owned())
+    } else {
+      bail!("Only local configuration files are supported")
+    }
+  } else {
+    None
+  };
+  let task_flags_cwd = match task_flags.cwd {
+    Some(path) => Some(canonicalize_path(&PathBuf::from(path))?),
+    None => None,
+  };
+
+  let task_runner = TaskRunner {
+    tasks_config: &tasks_config,
+    package_json_scripts: &package_json_scripts,
+    task_names: &task_names,
+    args: &args,
+    config_file_dir: task_flags_cwd.as_deref().or(config_file_dir.as_deref()),
+    package_json_dir: task_flags_cwd
+      .as_deref()
+      .or_else(|| maybe_package_json.as_ref().and_then(|p| p.path.parent())),
+    // prefix the output when more than one task runs, so that the output of
+    // tasks that run at the same time can be told apart
+    prefix_output: all_task_names.len() > 1,
+    task_cache: TaskCache::new(factory.deno_dir()?.task_cache_folder_path()),
+    npm_commands: resolve_npm_commands(npm_resolver.as_ref(), node_resolver)?,
+    npm_resolver: npm_resolver.as_ref(),
+    cancellation_token: Default::default(),
+  };
+  let local = LocalSet::new();
+  local
+    .run_until(task_runner.run(&task_dependencies, task_flags.parallel))
+    .await
+}
+
+/// Runs a task in every workspace member that defines it, or in the members
+/// matching the filter. The members run one after another, so that their
+/// output is grouped, and after the members they depend on.
+async fn execute_workspace_script(
+  factory: &CliFactory,
+  task_flags: &TaskFlags,
+) -> Result<i32, AnyError> {
+  let cli_options = factory.cli_options();
+  let members = cli_options.resolve_workspace_tasks_config()?;
+  let Some(task_name) = &task_flags.task else {
+    for (member, tasks_config) in &members {
+      eprintln!("{}", colors::bold(&member.package_name));
+      print_available_tasks(tasks_config, &Default::default());
+    }
+    return Ok(1);
+  };
+  let (task_names, args) = resolve_task_names_and_args(
+    task_name,
+    cli_options.argv(),
+    task_flags.parallel || task_flags.sequential,
+  );
 
-    let cwd = match task_flags.cwd {
-      Some(path) => canonicalize_path(&PathBuf::from(path))?,
-      None => maybe_package_json
-        .as_ref()
-        .unwrap()
-        .path
-        .parent()
-        .unwrap()
-        .to_owned(),
+  let member_dependencies = resolve_workspace_member_dependencies(
+    &members
+      .iter()
+      .map(|(member, _)| *member)
+      .collect::<Vec<_>>(),
+  )?;
+  let sorted_member_names = sort_workspace_members(member_dependencies)?;
+  let filter = match &task_flags.filter {
+    Some(pattern) => Some(wildcard_pattern_to_regex(pattern)?),
+    None => None,
+  };
+  let selected_members = sorted_member_names
+    .iter()
+    .filter_map(|name| members.iter().find(|(m, _)| m.package_name == *name))
+    .filter(|(member, tasks_config)| {
+      task_names
+        .iter()
+        .any(|name| tasks_config.contains_key(name))
+        && filter.as_ref().map_or(true, |filter| {
+          filter.is_match(&member.package_name)
+            || filter.is_match(&member.member_name)
+        })
+    })
+    .collect::<Vec<_>>();
+  if selected_members.is_empty() {
+    eprintln!("Task not found in any workspace member: {task_name}");
+    return Ok(1);
+  }
+
+  let npm_resolver = factory.npm_resolver().await?;
+  let node_resolver = factory.node_resolver().await?;
+  let npm_commands =
+    resolve_npm_commands(npm_resolver.as_ref(), node_resolver)?;
+  let local = LocalSet::new();
+  for (member, tasks_config) in selected_members {
+    log::info!(
+      "{} {} {}",
+      colors::green("Package"),
+      colors::cyan(&member.package_name),
+      colors::gray(format!("({})", member.member_name)),
+    );
+    let task_names = task_names
+      .iter()
+      .filter(|name| tasks_config.contains_key(*name))
+      .cloned()
+      .collect::<Vec<_>>();
+    let task_dependencies = tasks_config
+      .iter()
+      .map(|(name, config)| (name.clone(), config.dependencies.clone()))
+      .collect::<HashMap<_, _>>();
+    let all_task_names =
+      resolve_tasks_with_dependencies(&task_names, &task_dependencies)?;
+    let task_runner = TaskRunner {
+      tasks_config,
+      package_json_scripts: &Default::default(),
+      task_names: &task_names,
+      args: &args,
+      config_file_dir: Some(member.dir_path.as_path()),
+      package_json_dir: None,
+      prefix_output: all_task_names.len() > 1,
+      task_cache: TaskCache::new(factory.deno_dir()?.task_cache_folder_path()),
+      npm_commands: npm_commands.clone(),
+      npm_resolver: npm_resolver.as_ref(),
+      cancellation_token: Default::default(),
     };
+    let exit_code = local
+      .run_until(task_runner.run(&task_dependencies, task_flags.parallel))
+      .await?;
+    if exit_code > 0 {
+      return Ok(exit_code);
+    }
+  }
+  Ok(0)
+}
 
-    // At this point we already checked if the task name exists in package.json.
-    // We can therefore check for "pre" and "post" scripts too, since we're only
-    // dealing with package.json here and not deno.json
-    let task_names = vec![
-      format!("pre{}", task_name),
-      task_name.clone(),
-      format!("post{}", task_name),
-    ];
-    let npm_commands =
-      resolve_npm_commands(npm_resolver.as_ref(), node_resolver)?;
-    for task_name in task_names {
-      if let Some(script) = package_json_scripts.get(&task_name) {
-        let exit_code = run_task(
-          &task_name,
-          script,
-          &cwd,
-          cli_options,
-          npm_commands.clone(),
-          npm_resolver.as_ref(),
-        )
-        .await?;
-        if exit_code > 0 {
-          return Ok(exit_code);
-        }
+/// Finds the workspace members each member depends on, either by importing
+/// them in its configuration file as a jsr package or with a path in the
+/// member's directory, or as a dependency in its package.json.
+fn resolve_workspace_member_dependencies(
+  members: &[&WorkspaceMemberConfig],
+) -> Result<HashMap<String, HashSet<String>>, AnyError> {
+  let member_dir_urls = members
+    .iter()
+    .filter_map(|member| {
+      let dir_url =
+        ModuleSpecifier::from_directory_path(&member.dir_path).ok()?;
+      Some((dir_url, member.package_name.as_str()))
+    })
+    .collect::<Vec<_>>();
+  let find_member_by_name = |name: &str| {
+    members
+      .iter()
+      .find(|m| m.package_name == name)
+      .map(|m| m.package_name.as_str())
+  };
+  let find_member_by_url = |url: &ModuleSpecifier| {
+    // the most nested directory in case members are nested
+    member_dir_urls
+      .iter()
+      .filter(|(dir_url, _)| url.as_str().starts_with(dir_url.as_str()))
+      .max_by_key(|(dir_url, _)| dir_url.as_str().len())
+      .map(|(_, name)| *name)
+  };
+  let mut result = HashMap::with_capacity(members.len());
+  for member in members {
+    let mut dependencies = HashSet::new();
+    let import_values = member
+      .config_file
+      .json
+      .imports
+      .as_ref()
+      .and_then(|imports| imports.as_object())
+      .into_iter()
+      .flat_map(|imports| imports.values())
+      .filter_map(|value| value.as_str());
+    for value in import_values {
+      let dependency =
+        if let Ok(req_ref) = JsrPackageReqReference::from_str(value) {
+          find_member_by_name(&req_ref.req().name)
+        } else if let Ok(url) = member.config_file.specifier.join(value) {
+          find_member_by_url(&url)
+        } else {
+          None
+        };
+      dependencies.extend(dependency.map(ToString::to_string));
+    }
+
+    let package_json_path = member.dir_path.join("package.json");
+    if let Some(package_json) = load_package_json(&package_json_path)? {
+      let package_json_deps = package_json
+        .dependencies
+        .iter()
+        .chain(package_json.dev_dependencies.iter())
+        .flatten();
+      for (name, value) in package_json_deps {
+        let dependency = match value.strip_prefix("file:") {
+          Some(path) => {
+            ModuleSpecifier::from_directory_path(member.dir_path.join(path))
+              .ok()
+              .and_then(|url| find_member_by_url(&url))
+          }
+          None => find_member_by_name(name),
+        };
+        dependencies.extend(dependency.map(ToString::to_string));
       }
     }
 
-    Ok(0)
-  } else {
-    eprintln!("Task not found: {task_name}");
-    print_available_tasks(&tasks_config, &package_json_scripts);
-    Ok(1)
+    dependencies.remove(&member.package_name);
+    result.insert(member.package_name.clone(), dependencies);
   }
+  Ok(result)
 }
 
-async fn run_task(
+fn load_package_json(path: &Path) -> Result<Option<PackageJson>, AnyError> {
+  match std::fs::read_to_string(path) {
+    Ok(source) => Ok(Some(PackageJson::load_from_string(
+      path.to_path_buf(),
+      source,
+    )?)),
+    Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+    Err(err) => Err(err).with_context(|| {
+      format!("Error loading package.json at {}.", path.display())
+    }),
+  }
+}
+
+/// Sorts the workspace members so that every member comes after the members
+/// it depends on, erroring for dependency cycles.
+fn sort_workspace_members(
+  member_dependencies: HashMap<String, HashSet<String>>,
+) -> Result<Vec<String>, AnyError> {
+  let mut graph = build_publish_order_graph_from_pkgs_deps(member_dependencies);
+  let mut sorted_member_names = Vec::new();
+  loop {
+    let member_names = graph.next();
+    if member_names.is_empty() {
+      break;
+    }
+    for member_name in &member_names {
+      graph.finish_package(member_name);
+    }
+    sorted_member_names.extend(member_names);
+  }
+  graph.ensure_no_pending()?;
+  Ok(sorted_member_names)
+}
+
+/// Gets the tasks to run and the arguments to forward to them. When running
+/// multiple tasks, the arguments before a `--` are the other tasks to run and
+/// only the arguments after it are forwarded. Otherwise all the arguments are
+/// forwarded to the single task, which keeps `deno task a b c` passing `b c`
+/// to `a` like it always did.
+fn resolve_task_names_and_args(
   task_name: &str,
-  script: &str,
-  cwd: &Path,
-  cli_options: &CliOptions,
+  argv: &[String],
+  multiple_tasks: bool,
+) -> (Vec<String>, Vec<String>) {
+  if !multiple_tasks {
+    return (vec![task_name.to_string()], argv.to_vec());
+  }
+  let separator_index = argv.iter().position(|arg| arg == "--");
+  let mut task_names = vec![task_name.to_string()];
+  task_names.extend(
+    argv[..separator_index.unwrap_or(argv.len())]
+      .iter()
+      .cloned(),
+  );
+  let args = match separator_index {
+    Some(index) => argv[index + 1..].to_vec(),
+    None => Vec::new(),
+  };
+  (task_names, args)
+}
+
+/// Gets the given tasks and all the tasks they depend on, erroring for
+/// unknown dependencies and dependency cycles.
+fn resolve_tasks_with_dependencies(
+  task_names: &[String],
+  task_dependencies: &HashMap<String, Vec<String>>,
+) -> Result<Vec<String>, AnyError> {
+  fn visit(
+    task_name: &str,
+    task_dependencies: &HashMap<String, Vec<String>>,
+    path: &mut Vec<String>,
+    visited: &mut IndexSet<String>,
+  ) -> Result<(), AnyError> {
+    if let Some(index) = path.iter().position(|name| name == task_name) {
+      let mut cycle = path[index..].to_vec();
+      cycle.push(task_name.to_string());
+      bail!("Tasks have a dependency cycle: {}", cycle.join(" -> "));
+    }
+    if visited.contains(task_name) {
+      return Ok(());
+    }
+    path.push(task_name.to_string());
+    for dependency in task_dependencies.get(task_name).into_iter().flatten() {
+      if !task_dependencies.contains_key(dependency) {
+        bail!(
+          "Task \"{}\" depends on \"{}\", which is not a task of the configuration file.",
+          task_name,
+          dependency
+        );
+      }
+      visit(dependency, task_dependencies, path, visited)?;
+    }
+    path.pop();
+    visited.insert(task_name.to_string());
+    Ok(())
+  }
+
+  let mut visited = IndexSet::new();
+  for task_name in task_names {
+    visit(task_name, task_dependencies, &mut Vec::new(), &mut visited)?;
+  }
+  Ok(visited.into_iter().collect())
+}
+
+struct TaskRunner<'a> {
+  tasks_config: &'a IndexMap<String, TaskConfig>,
+  package_json_scripts: &'a IndexMap<String, String>,
+  /// The tasks given on the command line.
+  task_names: &'a [String],
+  /// The arguments forwarded to the tasks given on the command line.
+  args: &'a [String],
+  config_file_dir: Option<&'a Path>,
+  package_json_dir: Option<&'a Path>,
+  prefix_output: bool,
+  task_cache: TaskCache,
   npm_commands: HashMap<String, Rc<dyn ShellCommand>>,
-  npm_resolver: &dyn CliNpmResolver,
-) -> Result<i32, AnyError> {
-  let script = get_script_with_args(script, cli_options);
-  output_task(task_name, &script);
-  let seq_list = deno_task_shell::parser::parse(&script)
-    .with_context(|| format!("Error parsing script '{}'.", task_name))?;
-  let env_vars = match npm_resolver.root_node_modules_path() {
-    Some(dir_path) => collect_env_vars_with_node_modules_dir(dir_path),
-    None => collect_env_vars(),
+  npm_resolver: &'a dyn CliNpmResolver,
+  /// Cancelled when a task fails, to stop the tasks running alongside it.
+  cancellation_token: CancellationToken,
+}
+
+impl<'a> TaskRunner<'a> {
+  /// Runs the tasks given on the command line after the tasks they depend
+  /// on, with independent tasks running at the same time. The given tasks
+  /// run one after another, unless `parallel` is set. The first task that
+  /// fails cancels the others.
+  async fn run(
+    &self,
+    task_dependencies: &HashMap<String, Vec<String>>,
+    parallel: bool,
+  ) -> Result<i32, AnyError> {
+    let batches = if parallel {
+      vec![self.task_names.to_vec()]
+    } else {
+      self
+        .task_names
+        .iter()
+        .map(|name| vec![name.clone()])
+        .collect()
+    };
+    let mut completed = HashSet::new();
+    for batch in batches {
+      let batch_task_names =
+        resolve_tasks_with_dependencies(&batch, task_dependencies)?
+          .into_iter()
+          .filter(|name| !completed.contains(name))
+          .collect::<Vec<_>>();
+      let mut started = HashSet::new();
+      let mut running = FuturesUnordered::new();
+      loop {
+        for task_name in &batch_task_names {
+          let is_ready = !started.contains(task_name)
+            && task_dependencies
+              .get(task_name)
+              .into_iter()
+              .flatten()
+              .all(|dependency| completed.contains(dependency));
+          if is_ready {
+            started.insert(task_name.clone());
+            running.push(async move {
+              (task_name, self.run_task_with_hooks(task_name).await)
+            });
+          }
+        }
+        let Some((task_name, result)) = running.next().await else {
+          break;
+        };
+        if !matches!(result, Ok(0)) {
+          // stop the tasks that are still running and wait for them to exit
+          self.cancellation_token.cancel();
+          while running.next().await.is_some() {}
+          return result;
+        }
+        completed.insert(task_name.clone());
+      }
+    }
+    Ok(0)
+  }
+
+  /// Runs a task of the configuration file, or a package.json script along
+  /// with its "pre" and "post" scripts.
+  async fn run_task_with_hooks(
+    &self,
+    task_name: &str,
+  ) -> Result<i32, AnyError> {
+    let args = if self.task_names.iter().any(|name| name == task_name) {
+      self.args
+    } else {
+      &[]
+    };
+    if let Some(task_config) = self.tasks_config.get(task_name) {
+      let script = match &task_config.task {
+        deno_config::Task::Definition(script)
+        | deno_config::Task::Commented {
+          definition: script, ..
+        } => script,
+      };
+      // tasks that only group their dependencies have no command
+      if script.is_empty() {
+        return Ok(0);
+      }
+      let cwd = self.config_file_dir.unwrap();
+      if task_config.inputs.is_empty() {
+        self.run_task(task_name, script, cwd, args).await
+      } else {
+        self
+          .run_cached_task(task_name, task_config, script, cwd, args)
+          .await
+      }
+    } else {
+      // We already checked that the task name exists in package.json.
+      // We can therefore check for "pre" and "post" scripts too, since we're
+      // only dealing with package.json here and not deno.json
+      let task_names = vec![
+        format!("pre{}", task_name),
+        task_name.to_string(),
+        format!("post{}", task_name),
+      ];
+      for task_name in task_names {
+        if let Some(script) = self.package_json_scripts.get(&task_name) {
+          let exit_code = self
+            .run_task(&task_name, script, self.package_json_dir.unwrap(), args)
+            .await?;
+          if exit_code > 0 {
+            return Ok(exit_code);
+          }
+        }
+      }
+      Ok(0)
+    }
+  }
+
+  /// Runs a task that declares its inputs, unless a previous run with the
+  /// same inputs is in the cache, in which case its outputs are restored.
+  async fn run_cached_task(
+    &self,
+    task_name: &str,
+    task_config: &TaskConfig,
+    script: &str,
+    cwd: &Path,
+    args: &[String],
+  ) -> Result<i32, AnyError> {
+    let input_files = collect_glob_files(cwd, &task_config.inputs)?;
+    let hash =
+      get_task_hash(&get_script_with_args(script, args), &input_files, cwd)?;
+    match self.task_cache.restore(&hash, cwd) {
+      Ok(true) => {
+        log::info!(
+          "{} {} {}",
+          colors::green("Task"),
+          colors::cyan(&task_name),
+          colors::gray("(cached)"),
+        );
+        return Ok(0);
+      }
+      Ok(false) => {}
+      Err(err) => {
+        log::warn!(
+          "{} Failed restoring the outputs of task '{}' from the cache: {:#}",
+          colors::yellow("Warning"),
+          task_name,
+          err
+        );
+      }
+    }
+
+    let exit_code = self.run_task(task_name, script, cwd, args).await?;
+    if exit_code == 0 {
+      let result = collect_glob_files(cwd, &task_config.outputs).and_then(
+        |output_files| self.task_cache.save(&hash, cwd, &output_files),
+      );
+      if let Err(err) = result {
+        log::warn!(
+          "{} Failed caching the outputs of task '{}': {:#}",
+          colors::yellow("Warning"),
+          task_name,
+          err
+        );
+      }
+    }
+    Ok(exit_code)
+  }
+
+  async fn run_task(
+    &self,
+    task_name: &str,
+    script: &str,
+    cwd: &Path,
+    args: &[String],
+  ) -> Result<i32, AnyError> {
+    if self.cancellation_token.is_cancelled() {
+      return Ok(1);
+    }
+    let script = get_script_with_args(script, args);
+    output_task(task_name, &script);
+    let seq_list = deno_task_shell::parser::parse(&script)
+      .with_context(|| format!("Error parsing script '{}'.", task_name))?;
+    let env_vars = match self.npm_resolver.root_node_modules_path() {
+      Some(dir_path) => collect_env_vars_with_node_modules_dir(dir_path),
+      None => collect_env_vars(),
+    };
+    let state = ShellState::new(env_vars, cwd, self.npm_commands.clone());
+    let shell_token = state.token().clone();
+
+    let (stdout_writer, stderr_writer, output_handles) = if self.prefix_output {
+      let prefix = format!("[{}] ", task_name);
+      let (stdout_reader, stdout_writer) = deno_task_shell::pipe();
+      let (stderr_reader, stderr_writer) = deno_task_shell::pipe();
+      let stdout_handle = spawn_blocking({
+        let mut writer = PrefixedWriter::new(
+          colors::cyan(&prefix).to_string(),
+          std::io::stdout(),
+        );
+        move || {
+          stdout_reader.pipe_to(&mut writer)?;
+          writer.finish()?;
+          Ok::<_, AnyError>(())
+        }
+      });
+      let stderr_handle = spawn_blocking({
+        let mut writer = PrefixedWriter::new(
+          colors::cyan(&prefix).to_string(),
+          std::io::stderr(),
+        );
+        move || {
+          stderr_reader.pipe_to(&mut writer)?;
+          writer.finish()?;
+          Ok::<_, AnyError>(())
+        }
+      });
+      (
+        stdout_writer,
+        stderr_writer,
+        Some((stdout_handle, stderr_handle)),
+      )
+    } else {
+      (ShellPipeWriter::stdout(), ShellPipeWriter::stderr(), None)
+    };
+
+    let mut execution = std::pin::pin!(deno_task_shell::execute_with_pipes(
+      seq_list,
+      state,
+      ShellPipeReader::stdin(),
+      stdout_writer,
+      stderr_writer,
+    ));
+    let exit_code = tokio::select! {
+      exit_code = &mut execution => exit_code,
+      _ = self.cancellation_token.cancelled() => {
+        // kills the commands of the task
+        shell_token.cancel();
+        execution.await
+      }
+    };
+    if let Some((stdout_handle, stderr_handle)) = output_handles {
+      stdout_handle.await??;
+      stderr_handle.await??;
+    }
+    Ok(exit_code)
+  }
+}
+
+fn collect_glob_files(
+  cwd: &Path,
+  globs: &[String],
+) -> Result<Vec<PathBuf>, AnyError> {
+  if globs.is_empty() {
+    return Ok(Vec::new());
+  }
+  let file_patterns = FilePatterns {
+    base: cwd.to_path_buf(),
+    include: Some(PathOrPatternSet::from_include_relative_path_or_patterns(
+      cwd, globs,
+    )?),
+    exclude: PathOrPatternSet::new(Vec::new()),
   };
-  let local = LocalSet::new();
-  let future = deno_task_shell::execute(seq_list, env_vars, cwd, npm_commands);
-  Ok(local.run_until(future).await)
+  let mut files = FileCollector::new(|_| true)
+    .ignore_git_folder()
+    .collect_file_patterns(file_patterns)?;
+  files.sort();
+  Ok(files)
+}
+
+/// Gets the hash that identifies a run of a task from its command, the
+/// environment variables the command references and its input files.
+fn get_task_hash(
+  script: &str,
+  input_files: &[PathBuf],
+  cwd: &Path,
+) -> Result<String, AnyError> {
+  static ENV_VAR_RE: Lazy<Regex> =
+    lazy_regex::lazy_regex!(r"\$\{?([A-Za-z_][A-Za-z0-9_]*)");
+
+  let mut parts = vec![script.to_string()];
+  let mut env_var_names = ENV_VAR_RE
+    .captures_iter(script)
+    .map(|captures| captures.get(1).unwrap().as_str())
+    .collect::<Vec<_>>();
+  env_var_names.sort();
+  env_var_names.dedup();
+  for name in env_var_names {
+    let value = std::env::var(name).unwrap_or_default();
+    parts.push(format!("{}={}", name, value));
+  }
+  for file_path in input_files {
+    let relative_path = file_path.strip_prefix(cwd).unwrap_or(file_path);
+    let bytes = std::fs::read(file_path).with_context(|| {
+      format!("Failed reading task input '{}'", file_path.display())
+    })?;
+    parts.push(relative_path.to_string_lossy().to_string());
+    parts.push(checksum::gen(&[bytes]));
+  }
+  Ok(checksum::gen(&[parts.join("\0")]))
+}
+
+/// Writes the output of a task line by line with a prefix, so that the lines
+/// of tasks running at the same time don't get mixed up.
+struct PrefixedWriter<W: Write> {
+  prefix: String,
+  line: Vec<u8>,
+  inner: W,
+}
+
+impl<W: Write> PrefixedWriter<W> {
+  fn new(prefix: String, inner: W) -> Self {
+    Self {
+      prefix,
+      line: Vec::new(),
+      inner,
+    }
+  }
+
+  fn write_line(&mut self, line: &[u8]) -> std::io::Result<()> {
+    let mut text = Vec::with_capacity(self.prefix.len() + line.len());
+    text.extend(self.prefix.as_bytes());
+    text.extend(line);
+    // a single write, so that lines from different threads don't interleave
+    self.inner.write_all(&text)
+  }
+
+  /// Writes the last line when it didn't end with a newline.
+  fn finish(mut self) -> std::io::Result<()> {
+    if !self.line.is_empty() {
+      let mut line = std::mem::take(&mut self.line);
+      line.push(b'\n');
+      self.write_line(&line)?;
+    }
+    self.inner.flush()
+  }
+}
+
+impl<W: Write> Write for PrefixedWriter<W> {
+  fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+    self.line.extend(buf);
+    while let Some(index) = self.line.iter().position(|b| *b == b'\n') {
+      let rest = self.line.split_off(index + 1);
+      let line = std::mem::replace(&mut self.line, rest);
+      self.write_line(&line)?;
+    }
+    Ok(buf.len())
+  }
+
+  fn flush(&mut self) -> std::io::Result<()> {
+    self.inner.flush()
+  }
 }
 
-fn get_script_with_args(script: &str, options: &CliOptions) -> String {
-  let additional_args = options
-    .argv()
+fn get_script_with_args(script: &str, args: &[String]) -> String {
+  let additional_args = args
     .iter()
     // surround all the additional arguments in double quotes
     // and sanitize any command substitution
@@ -240,20 +877,33 @@ fn collect_env_vars() -> HashMap<String, String> {
 
 fn print_available_tasks(
   // order can be important, so these use an index map
-  tasks_config: &IndexMap<String, deno_config::Task>,
+  tasks_config: &IndexMap<String, TaskConfig>,
   package_json_scripts: &IndexMap<String, String>,
 ) {
   eprintln!("{}", colors::green("Available tasks:"));
 
   let mut had_task = false;
-  for (is_deno, (key, task)) in tasks_config
+  for (is_deno, (key, task_config)) in tasks_config
     .iter()
     .map(|(k, t)| (true, (k, t.clone())))
     .chain(
       package_json_scripts
         .iter()
         .filter(|(key, _)| !tasks_config.contains_key(*key))
-        .map(|(k, v)| (false, (k, deno_config::Task::Definition(v.clone())))),
+        .map(|(k, v)| {
+          (
+            false,
+            (
+              k,
+              TaskConfig {
+                task: deno_config::Task::Definition(v.clone()),
+                dependencies: Vec::new(),
+                inputs: Vec::new(),
+                outputs: Vec::new(),
+              },
+            ),
+          )
+        }),
     )
   {
     eprintln!(
@@ -265,17 +915,27 @@ fn print_available_tasks(
         format!(" {}", colors::italic_gray("(package.json)"))
       }
     );
-    let definition = match &task {
+    let task = &task_config.task;
+    let definition = match task {
       deno_config::Task::Definition(definition) => definition,
       deno_config::Task::Commented { definition, .. } => definition,
     };
-    if let deno_config::Task::Commented { comments, .. } = &task {
+    if let deno_config::Task::Commented { comments, .. } = task {
       let slash_slash = colors::italic_gray("//");
       for comment in comments {
         eprintln!("    {slash_slash} {}", colors::italic_gray(comment));
       }
     }
-    eprintln!("    {definition}");
+    if !definition.is_empty() {
+      eprintln!("    {definition}");
+    }
+    if !task_config.dependencies.is_empty() {
+      eprintln!(
+        "    {} {}",
+        colors::italic_gray("depends on:"),
+        task_config.dependencies.join(", ")
+      );
+    }
     had_task = true;
   }
   if !had_task {
@@ -521,6 +1181,125 @@ mod test {
     );
   }
 
+  #[test]
+  fn test_resolve_task_names_and_args() {
+    assert_eq!(
+      resolve_task_names_and_args("a", &[], false),
+      (vec!["a".to_string()], vec![])
+    );
+    // other task names are only arguments unless running multiple tasks
+    assert_eq!(
+      resolve_task_names_and_args("a", &["b".into(), "1".into()], false),
+      (
+        vec!["a".to_string()],
+        vec!["b".to_string(), "1".to_string()]
+      )
+    );
+    assert_eq!(
+      resolve_task_names_and_args("a", &["--".into(), "b".into()], false),
+      (
+        vec!["a".to_string()],
+        vec!["--".to_string(), "b".to_string()]
+      )
+    );
+    assert_eq!(
+      resolve_task_names_and_args("a", &["b".into(), "c".into()], true),
+      (
+        vec!["a".to_string(), "b".to_string(), "c".to_string()],
+        vec![]
+      )
+    );
+    assert_eq!(
+      resolve_task_names_and_args(
+        "a",
+        &["b".into(), "--".into(), "1".into()],
+        true
+      ),
+      (
+        vec!["a".to_string(), "b".to_string()],
+        vec!["1".to_string()]
+      )
+    );
+  }
+
+  #[test]
+  fn test_resolve_tasks_with_dependencies() {
+    let task_dependencies = HashMap::from([
+      (
+        "build".to_string(),
+        vec!["codegen".to_string(), "lint".to_string()],
+      ),
+      ("codegen".to_string(), vec!["fetch".to_string()]),
+      ("lint".to_string(), vec![]),
+      ("fetch".to_string(), vec![]),
+      ("cycle_a".to_string(), vec!["cycle_b".to_string()]),
+      ("cycle_b".to_string(), vec!["cycle_a".to_string()]),
+      ("unknown".to_string(), vec!["missing".to_string()]),
+    ]);
+    assert_eq!(
+      resolve_tasks_with_dependencies(
+        &["build".to_string()],
+        &task_dependencies
+      )
+      .unwrap(),
+      vec!["fetch", "codegen", "lint", "build"]
+    );
+    assert_eq!(
+      resolve_tasks_with_dependencies(
+        &["cycle_a".to_string()],
+        &task_dependencies
+      )
+      .unwrap_err()
+      .to_string(),
+      "Tasks have a dependency cycle: cycle_a -> cycle_b -> cycle_a"
+    );
+    assert_eq!(
+      resolve_tasks_with_dependencies(
+        &["unknown".to_string()],
+        &task_dependencies
+      )
+      .unwrap_err()
+      .to_string(),
+      "Task \"unknown\" depends on \"missing\", which is not a task of the configuration file."
+    );
+  }
+
+  #[test]
+  fn test_get_task_hash() {
+    let temp_dir = test_util::TempDir::new();
+    let cwd = temp_dir.path().to_path_buf();
+    let input_path = cwd.join("input.txt");
+    std::fs::write(&input_path, "1").unwrap();
+    let input_files = vec![input_path.clone()];
+
+    let hash = get_task_hash("echo 1", &input_files, &cwd).unwrap();
+    assert_eq!(hash, get_task_hash("echo 1", &input_files, &cwd).unwrap());
+    assert_ne!(hash, get_task_hash("echo 2", &input_files, &cwd).unwrap());
+    assert_ne!(hash, get_task_hash("echo 1", &[], &cwd).unwrap());
+    std::fs::write(&input_path, "2").unwrap();
+    assert_ne!(hash, get_task_hash("echo 1", &input_files, &cwd).unwrap());
+
+    let script = "echo $DENO_TEST_TASK_HASH_VAR";
+    std::env::set_var("DENO_TEST_TASK_HASH_VAR", "1");
+    let hash = get_task_hash(script, &input_files, &cwd).unwrap();
+    std::env::set_var("DENO_TEST_TASK_HASH_VAR", "2");
+    assert_ne!(hash, get_task_hash(script, &input_files, &cwd).unwrap());
+    std::env::remove_var("DENO_TEST_TASK_HASH_VAR");
+  }
+
+  #[test]
+  fn test_prefixed_writer() {
+    let mut output = Vec::new();
+    let mut writer = PrefixedWriter::new("[a] ".to_string(), &mut output);
+    writer.write_all(b"hello\nwor").unwrap();
+    writer.write_all(b"ld\n\nend").unwrap();
+    writer.finish().unwrap();
+    assert_eq!(
+      String::from_utf8(output).unwrap(),
+      "[a] hello\n[a] world\n[a] \n[a] end\n"
+    );
+  }
+
   #[test]
   fn test_resolve_execution_path_from_npx_shim() {
     // example shim on unix
@@ -551,4 +1330,28 @@ fi"#;
       path.parent().unwrap().join("../example/bin/example")
     );
   }
+
+  #[test]
+  fn test_sort_workspace_members() {
+    let dependencies = HashMap::from([
+      ("app".to_string(), HashSet::from(["lib".to_string()])),
+      ("lib".to_string(), HashSet::from(["utils".to_string()])),
+      ("utils".to_string(), HashSet::new()),
+    ]);
+    assert_eq!(
+      sort_workspace_members(dependencies).unwrap(),
+      vec!["utils".to_string(), "lib".to_string(), "app".to_string()]
+    );
+
+    let dependencies = HashMap::from([
+      ("app".to_string(), HashSet::from(["lib".to_string()])),
+      ("lib".to_string(), HashSet::from(["app".to_string()])),
+    ]);
+    assert_eq!(
+      sort_workspace_members(dependencies)
+        .unwrap_err()
+        .to_string(),
+      "Circular package dependency detected: app -> lib -> app"
+    );
+  }
 }
@@ -0,0 +1,80 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+// The helpers of the tests that `deno test --doc` creates to run the code
+// blocks of documentation.
+(() => {
+  const inspectOptions = {
+    depth: Infinity,
+    iterableLimit: Infinity,
+    sorted: true,
+    strAbbreviateSize: Infinity,
+  };
+
+  function inspect(value) {
+    return Deno.inspect(value, inspectOptions);
+  }
+
+  // Asserts the result of an expression followed by a `// => expected`
+  // comment, which is either an expression or the text of the result.
+  function assertResult(actual, expected, location) {
+    let expectedText = expected.text;
+    if (expected.value !== undefined) {
+      try {
+        expectedText = inspect(expected.value());
+      } catch (error) {
+        if (!(error instanceof ReferenceError)) {
+          throw error;
+        }
+      }
+    }
+    const actualText = inspect(actual);
+    if (actualText !== expectedText && String(actual) !== expectedText) {
+      throw new Error(
+        `The result at ${location} doesn't match the expected result.\n\n` +
+          `    Expected: ${expectedText}\n` +
+          `    Actual:   ${actualText}`,
+      );
+    }
+  }
+
+  async function run(fn, options) {
+    const captureOutput = typeof options.output === "string";
+    const originals = {};
+    let output = "";
+    if (captureOutput) {
+      for (const method of ["debug", "error", "info", "log", "warn"]) {
+        originals[method] = console[method];
+        console[method] = (...args) => {
+          output += args
+            .map((arg) => typeof arg === "string" ? arg : Deno.inspect(arg))
+            .join(" ") + "\n";
+        };
+      }
+    }
+
+    let threw = false;
+    try {
+      await fn();
+    } catch (error) {
+      if (!options.throws) {
+        throw error;
+      }
+      threw = true;
+    } finally {
+      Object.assign(console, originals);
+    }
+
+    if (options.throws && !threw) {
+      throw new Error("The example was expected to throw, but it completed.");
+    }
+    if (captureOutput && output.trimEnd() !== options.output.trimEnd()) {
+      throw new Error(
+        "The output of the example doesn't match the expected output.\n\n" +
+          `Expected:\n${options.output.trimEnd()}\n\n` +
+          `Actual:\n${output.trimEnd()}`,
+      );
+    }
+  }
+
+  return { assertResult, run };
+})()
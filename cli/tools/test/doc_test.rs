@@ -0,0 +1,294 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+use deno_ast::swc::ast::ModuleDecl;
+use deno_ast::swc::ast::ModuleItem;
+use deno_ast::swc::ast::Stmt;
+use deno_ast::MediaType;
+use deno_ast::ParsedSource;
+use deno_ast::SourceRange;
+use deno_ast::SourceRangedForSpanned;
+use deno_ast::SourceTextInfo;
+use deno_ast::TextChange;
+use deno_core::serde_json;
+use deno_core::ModuleSpecifier;
+
+const HELPERS: &str = include_str!("doc_test.js");
+
+/// How the code block of an example is tested, from the attributes that
+/// follow its language, such as in "```ts no_run" or "```ts no-run".
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct DocTestOptions {
+  /// Only type check the example.
+  pub no_run: bool,
+  /// The example passes when it throws.
+  pub throws: bool,
+  /// The expected console output, from an `output` block that directly
+  /// follows the example.
+  pub output: Option<String>,
+}
+
+impl DocTestOptions {
+  pub fn from_attributes(attributes: &[&str]) -> Self {
+    Self {
+      no_run: attributes.contains(&"no_run") || attributes.contains(&"no-run"),
+      throws: attributes.contains(&"throws"),
+      output: None,
+    }
+  }
+}
+
+/// Creates a test module that runs an example. The imports of the example
+/// are kept at the top level of the module and the rest of it runs in a
+/// test. The expressions that are followed by a `// => expected` comment
+/// assert their result.
+///
+/// Returns `None` when the example doesn't parse, which type checking
+/// reports instead.
+pub fn create_doc_test_module(
+  specifier: &ModuleSpecifier,
+  test_name: &str,
+  source: &str,
+  media_type: MediaType,
+  first_line: usize,
+  options: &DocTestOptions,
+) -> Option<String> {
+  let parsed_source = parse(specifier, source.to_string(), media_type)?;
+  let text_info = parsed_source.text_info();
+  let text = text_info.text_str();
+  let file_start = text_info.range().start;
+  let byte_range = |range: SourceRange| range.as_byte_range(file_start);
+
+  let mut top_level = String::new();
+  let mut changes = Vec::new();
+  for item in &parsed_source.module().body {
+    match item {
+      // these are only allowed at the top level of the module
+      ModuleItem::ModuleDecl(
+        ModuleDecl::Import(_)
+        | ModuleDecl::ExportAll(_)
+        | ModuleDecl::ExportDefaultDecl(_)
+        | ModuleDecl::TsImportEquals(_),
+      ) => {
+        let range = byte_range(item.range());
+        top_level.push_str(&text[range.clone()]);
+        top_level.push('\n');
+        changes.push(TextChange {
+          range,
+          new_text: String::new(),
+        });
+      }
+      ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(export_named))
+        if export_named.src.is_some() =>
+      {
+        let range = byte_range(item.range());
+        top_level.push_str(&text[range.clone()]);
+        top_level.push('\n');
+        changes.push(TextChange {
+          range,
+          new_text: String::new(),
+        });
+      }
+      ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export_decl)) => {
+        changes.push(TextChange {
+          range: byte_range(export_decl.range()).start
+            ..byte_range(export_decl.decl.range()).start,
+          new_text: String::new(),
+        });
+      }
+      ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(export_expr)) => {
+        changes.push(TextChange {
+          range: byte_range(export_expr.range()).start
+            ..byte_range(export_expr.expr.range()).start,
+          new_text: String::new(),
+        });
+      }
+      ModuleItem::ModuleDecl(_) => {
+        changes.push(TextChange {
+          range: byte_range(item.range()),
+          new_text: String::new(),
+        });
+      }
+      ModuleItem::Stmt(Stmt::Expr(expr_stmt)) => {
+        let range = byte_range(expr_stmt.range());
+        let Some(expected) = find_expected_result(text, range.end) else {
+          continue;
+        };
+        let location = format!(
+          "line {}",
+          first_line + text_info.line_index(expr_stmt.start())
+        );
+        changes.push(TextChange {
+          range,
+          new_text: format!(
+            "__docTest.assertResult(({}), {}, {});",
+            &text[byte_range(expr_stmt.expr.range())],
+            expected_result_object(specifier, &expected, media_type),
+            serde_json::to_string(&location).unwrap(),
+          ),
+        });
+      }
+      ModuleItem::Stmt(_) => {}
+    }
+  }
+  changes.sort_by_key(|change| change.range.start);
+  let body = deno_ast::apply_text_changes(text, changes);
+
+  let run_options = format!(
+    "{{ throws: {}, output: {} }}",
+    options.throws,
+    serde_json::to_string(&options.output).unwrap()
+  );
+  Some(format!(
+    "{}const __docTest = {};\nDeno.test({}, () => __docTest.run(async () => {{\n{}\n}}, {}));\n",
+    top_level,
+    helpers(),
+    serde_json::to_string(test_name).unwrap(),
+    body,
+    run_options,
+  ))
+}
+
+fn parse(
+  specifier: &ModuleSpecifier,
+  source: String,
+  media_type: MediaType,
+) -> Option<ParsedSource> {
+  deno_ast::parse_module(deno_ast::ParseParams {
+    specifier: specifier.clone(),
+    text_info: SourceTextInfo::from_string(source),
+    media_type,
+    capture_tokens: false,
+    maybe_syntax: None,
+    scope_analysis: false,
+  })
+  .ok()
+}
+
+fn helpers() -> String {
+  // skip the leading comments
+  HELPERS
+    .lines()
+    .skip_while(|line| line.is_empty() || line.starts_with("//"))
+    .collect::<Vec<_>>()
+    .join("\n")
+}
+
+/// Finds a `// => expected` comment at the end of the line of a statement
+/// or on the line after it.
+fn find_expected_result(text: &str, statement_end: usize) -> Option<String> {
+  let expected_re = lazy_regex::regex!(r"^\s*;?\s*//\s*=>\s*(.*?)\s*$");
+  let mut lines = text[statement_end..].split('\n');
+  let rest_of_line = lines.next()?;
+  let comment_line = if rest_of_line.trim().is_empty() {
+    lines.next()?
+  } else {
+    rest_of_line
+  };
+  let captures = expected_re.captures(comment_line.trim_end_matches('\r'))?;
+  let expected = captures.get(1)?.as_str();
+  if expected.is_empty() {
+    None
+  } else {
+    Some(expected.to_string())
+  }
+}
+
+/// The expected result is compared as a value when it's an expression, and
+/// falls back to the text when evaluating it fails with a `ReferenceError`,
+/// such as for `// => Hello world`.
+fn expected_result_object(
+  specifier: &ModuleSpecifier,
+  expected: &str,
+  media_type: MediaType,
+) -> String {
+  let text = serde_json::to_string(expected).unwrap();
+  let is_expression = deno_ast::parse_script(deno_ast::ParseParams {
+    specifier: specifier.clone(),
+    text_info: SourceTextInfo::from_string(format!("({});", expected)),
+    media_type,
+    capture_tokens: false,
+    maybe_syntax: None,
+    scope_analysis: false,
+  })
+  .is_ok();
+  if is_expression {
+    format!("{{ value: () => ({}), text: {} }}", expected, text)
+  } else {
+    format!("{{ text: {} }}", text)
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn test_find_expected_result() {
+    let text = "add(1, 2); // => 3\nadd(1, 2)\n// => 3\nadd(1, 2);\nfoo();";
+    assert_eq!(find_expected_result(text, 10).unwrap(), "3");
+    assert_eq!(find_expected_result(text, 28).unwrap(), "3");
+    assert_eq!(find_expected_result(text, 47), None);
+    assert_eq!(find_expected_result("a; // a comment", 2), None);
+  }
+
+  #[test]
+  fn test_create_doc_test_module() {
+    let specifier = ModuleSpecifier::parse("file:///mod.ts$3-9.ts").unwrap();
+    let source = concat!(
+      "import { add } from \"./mod.ts\";\n",
+      "export const a = add(1, 2);\n",
+      "a; // => 3\n",
+      "console.log(a);\n",
+    );
+    let module = create_doc_test_module(
+      &specifier,
+      "mod.ts (lines 3-9)",
+      source,
+      MediaType::TypeScript,
+      4,
+      &DocTestOptions {
+        output: Some("3\n".to_string()),
+        ..Default::default()
+      },
+    )
+    .unwrap();
+    assert!(module.starts_with(concat!(
+      "import { add } from \"./mod.ts\";\n",
+      "const __docTest = (() => {\n",
+    )));
+    assert!(module.ends_with(concat!(
+      "Deno.test(\"mod.ts (lines 3-9)\", () => __docTest.run(async () => {\n",
+      "\n",
+      "const a = add(1, 2);\n",
+      "__docTest.assertResult((a), { value: () => (3), text: \"3\" }, \"line 6\"); // => 3\n",
+      "console.log(a);\n",
+      "\n",
+      "}, { throws: false, output: \"3\\n\" }));\n",
+    )));
+
+    assert_eq!(
+      create_doc_test_module(
+        &specifier,
+        "mod.ts (lines 3-9)",
+        "const = ;",
+        MediaType::TypeScript,
+        4,
+        &DocTestOptions::default(),
+      ),
+      None
+    );
+  }
+
+  #[test]
+  fn test_expected_result_object() {
+    let specifier = ModuleSpecifier::parse("file:///mod.ts$3-9.ts").unwrap();
+    assert_eq!(
+      expected_result_object(&specifier, "[1, 2]", MediaType::TypeScript),
+      "{ value: () => ([1, 2]), text: \"[1, 2]\" }"
+    );
+    assert_eq!(
+      expected_result_object(&specifier, "Hello world", MediaType::TypeScript),
+      "{ text: \"Hello world\" }"
+    );
+  }
+}
@@ -7,10 +7,10 @@ use crate::args::TestReporterConfig;
 use crate::colors;
 use crate::display;
 use crate::factory::CliFactory;
-use crate::factory::CliFactoryBuilder;
+use crate::factory::WatcherFactory;
 use crate::file_fetcher::File;
 use crate::file_fetcher::FileFetcher;
-use crate::graph_util::has_graph_root_local_dependent_changed;
+use crate::graph_util::graph_roots_with_changed_dependents;
 use crate::module_loader::ModuleLoadPreparer;
 use crate::ops;
 use crate::util::file_watcher;
@@ -89,6 +89,7 @@ use std::time::Instant;
 use tokio::signal;
 
 mod channel;
+mod doc_test;
 pub mod fmt;
 pub mod reporters;
 
@@ -511,6 +512,8 @@ struct TestSpecifiersOptions {
   specifier: TestSpecifierOptions,
   reporter: TestReporterConfig,
   junit_path: Option<String>,
+  /// The test modules that run the code blocks of documentation.
+  doc_tests: HashSet<ModuleSpecifier>,
 }
 
 #[derive(Debug, Default, Clone)]
@@ -518,6 +521,9 @@ pub struct TestSpecifierOptions {
   pub shuffle: Option<u64>,
   pub filter: TestFilter,
   pub trace_leaks: bool,
+  /// Only run the test of the module, and not the ones registered by the
+  /// modules that the code block of documentation imports.
+  pub doc_test: bool,
 }
 
 impl TestSummary {
@@ -794,14 +800,19 @@ async fn run_tests_for_worker_inner(
   options: &TestSpecifierOptions,
   fail_fast_tracker: &FailFastTracker,
 ) -> Result<(), AnyError> {
-  let unfiltered = tests.len();
+  // the tests of a code block in documentation don't include the ones of
+  // the modules it imports
+  let is_own_test = |d: &TestDescription| {
+    !options.doc_test || d.location.file_name == specifier.as_str()
+  };
+  let unfiltered = tests.tests.values().filter(|d| is_own_test(d)).count();
   let state_rc = worker.js_runtime.op_state();
 
   // Build the test plan in a single pass
   let mut tests_to_run = Vec::with_capacity(tests.len());
   let mut used_only = false;
   for ((_, d), f) in tests.tests.iter().zip(test_functions) {
-    if !options.filter.includes(&d.name) {
+    if !is_own_test(d) || !options.filter.includes(&d.name) {
       continue;
     }
 
@@ -1123,6 +1134,22 @@ async fn wait_for_activity_to_stabilize(
   })
 }
 
+/// The files extracted from the code blocks of documentation.
+#[derive(Default)]
+struct InlineFiles {
+  /// The code blocks, which are type checked.
+  check_files: Vec<File>,
+  /// The test modules that run the code blocks.
+  test_files: Vec<File>,
+}
+
+impl InlineFiles {
+  fn extend(&mut self, other: InlineFiles) {
+    self.check_files.extend(other.check_files);
+    self.test_files.extend(other.test_files);
+  }
+}
+
 fn extract_files_from_regex_blocks(
   specifier: &ModuleSpecifier,
   source: &str,
@@ -1130,78 +1157,116 @@ fn extract_files_from_regex_blocks(
   file_line_index: usize,
   blocks_regex: &Regex,
   lines_regex: &Regex,
-) -> Result<Vec<File>, AnyError> {
-  let files = blocks_regex
-    .captures_iter(source)
-    .filter_map(|block| {
-      block.get(1)?;
-
-      let maybe_attributes: Option<Vec<_>> = block
-        .get(1)
-        .map(|attributes| attributes.as_str().split(' ').collect());
-
-      let file_media_type = if let Some(attributes) = maybe_attributes {
-        if attributes.contains(&"ignore") {
-          return None;
-        }
-
-        match attributes.first() {
-          Some(&"js") => MediaType::JavaScript,
-          Some(&"javascript") => MediaType::JavaScript,
-          Some(&"mjs") => MediaType::Mjs,
-          Some(&"cjs") => MediaType::Cjs,
-          Some(&"jsx") => MediaType::Jsx,
-          Some(&"ts") => MediaType::TypeScript,
-          Some(&"typescript") => MediaType::TypeScript,
-          Some(&"mts") => MediaType::Mts,
-          Some(&"cts") => MediaType::Cts,
-          Some(&"tsx") => MediaType::Tsx,
-          _ => MediaType::Unknown,
-        }
-      } else {
-        media_type
-      };
+  run_examples: bool,
+) -> Result<InlineFiles, AnyError> {
+  let block_text = |block: &regex::Captures| {
+    let body = block.get(2).unwrap();
+    let mut text = String::new();
+    for line in lines_regex.captures_iter(body.as_str()) {
+      let line = line.get(1).unwrap();
+      writeln!(text, "{}", line.as_str()).unwrap();
+    }
+    text
+  };
+  let blocks = blocks_regex.captures_iter(source).collect::<Vec<_>>();
+  let mut files = InlineFiles::default();
+  for (index, block) in blocks.iter().enumerate() {
+    let Some(attributes) = block.get(1) else {
+      continue;
+    };
+    let attributes: Vec<_> = attributes.as_str().split(' ').collect();
+    if attributes.contains(&"ignore") {
+      continue;
+    }
 
-      if file_media_type == MediaType::Unknown {
-        return None;
-      }
+    let file_media_type = match attributes.first() {
+      Some(&"js") => MediaType::JavaScript,
+      Some(&"javascript") => MediaType::JavaScript,
+      Some(&"mjs") => MediaType::Mjs,
+      Some(&"cjs") => MediaType::Cjs,
+      Some(&"jsx") => MediaType::Jsx,
+      Some(&"ts") => MediaType::TypeScript,
+      Some(&"typescript") => MediaType::TypeScript,
+      Some(&"mts") => MediaType::Mts,
+      Some(&"cts") => MediaType::Cts,
+      Some(&"tsx") => MediaType::Tsx,
+      _ => MediaType::Unknown,
+    };
 
-      let line_offset = source[0..block.get(0).unwrap().start()]
-        .chars()
-        .filter(|c| *c == '\n')
-        .count();
+    if file_media_type == MediaType::Unknown {
+      continue;
+    }
 
-      let line_count = block.get(0).unwrap().as_str().split('\n').count();
+    let line_offset = source[0..block.get(0).unwrap().start()]
+      .chars()
+      .filter(|c| *c == '\n')
+      .count();
 
-      let body = block.get(2).unwrap();
-      let text = body.as_str();
+    let line_count = block.get(0).unwrap().as_str().split('\n').count();
 
-      // TODO(caspervonb) generate an inline source map
-      let mut file_source = String::new();
-      for line in lines_regex.captures_iter(text) {
-        let text = line.get(1).unwrap();
-        writeln!(file_source, "{}", text.as_str()).unwrap();
-      }
+    // TODO(caspervonb) generate an inline source map
+    let file_source = block_text(block);
 
-      let file_specifier = ModuleSpecifier::parse(&format!(
-        "{}${}-{}",
+    let start_line = file_line_index + line_offset + 1;
+    let end_line = file_line_index + line_offset + line_count + 1;
+    let file_specifier = ModuleSpecifier::parse(&format!(
+      "{}${}-{}",
+      specifier, start_line, end_line
+    ))
+    .unwrap();
+    let file_specifier =
+      mapped_specifier_for_tsc(&file_specifier, file_media_type)
+        .map(|s| ModuleSpecifier::parse(&s).unwrap())
+        .unwrap_or(file_specifier);
+
+    let mut options = doc_test::DocTestOptions::from_attributes(&attributes);
+    if run_examples && !options.no_run {
+      // an `output` block right after the example has its expected output
+      options.output = blocks.get(index + 1).and_then(|next| {
+        let is_output = next
+          .get(1)
+          .is_some_and(|attributes| attributes.as_str().trim() == "output");
+        let between =
+          &source[block.get(0).unwrap().end()..next.get(0).unwrap().start()];
+        (is_output && between.chars().all(|c| c.is_whitespace() || c == '*'))
+          .then(|| block_text(next))
+      });
+      let test_specifier = ModuleSpecifier::parse(&format!(
+        "{}${}-{}.test{}",
         specifier,
-        file_line_index + line_offset + 1,
-        file_line_index + line_offset + line_count + 1,
+        start_line,
+        end_line,
+        file_media_type.as_ts_extension()
       ))
       .unwrap();
-      let file_specifier =
-        mapped_specifier_for_tsc(&file_specifier, file_media_type)
-          .map(|s| ModuleSpecifier::parse(&s).unwrap())
-          .unwrap_or(file_specifier);
-
-      Some(File {
-        specifier: file_specifier,
-        maybe_headers: None,
-        source: file_source.into_bytes().into(),
-      })
-    })
-    .collect();
+      let test_name = format!(
+        "{} (lines {}-{})",
+        specifier.path().rsplit('/').next().unwrap_or_default(),
+        start_line,
+        end_line
+      );
+      if let Some(test_source) = doc_test::create_doc_test_module(
+        &test_specifier,
+        &test_name,
+        &file_source,
+        file_media_type,
+        start_line + 1,
+        &options,
+      ) {
+        files.test_files.push(File {
+          specifier: test_specifier,
+          maybe_headers: None,
+          source: test_source.into_bytes().into(),
+        });
+      }
+    }
+
+    files.check_files.push(File {
+      specifier: file_specifier,
+      maybe_headers: None,
+      source: file_source.into_bytes().into(),
+    });
+  }
 
   Ok(files)
 }
@@ -1210,7 +1275,8 @@ fn extract_files_from_source_comments(
   specifier: &ModuleSpecifier,
   source: Arc<str>,
   media_type: MediaType,
-) -> Result<Vec<File>, AnyError> {
+  run_examples: bool,
+) -> Result<InlineFiles, AnyError> {
   let parsed_source = deno_ast::parse_module(deno_ast::ParseParams {
     specifier: specifier.clone(),
     text_info: deno_ast::SourceTextInfo::new(source),
@@ -1223,27 +1289,24 @@ fn extract_files_from_source_comments(
   let blocks_regex = lazy_regex::regex!(r"```([^\r\n]*)\r?\n([\S\s]*?)```");
   let lines_regex = lazy_regex::regex!(r"(?:\* ?)(?:\# ?)?(.*)");
 
-  let files = comments
-    .iter()
-    .filter(|comment| {
-      if comment.kind != CommentKind::Block || !comment.text.starts_with('*') {
-        return false;
-      }
+  let mut files = InlineFiles::default();
+  for comment in comments.iter().filter(|comment| {
+    if comment.kind != CommentKind::Block || !comment.text.starts_with('*') {
+      return false;
+    }
 
-      true
-    })
-    .flat_map(|comment| {
-      extract_files_from_regex_blocks(
-        specifier,
-        &comment.text,
-        media_type,
-        parsed_source.text_info().line_index(comment.start()),
-        blocks_regex,
-        lines_regex,
-      )
-    })
-    .flatten()
-    .collect();
+    true
+  }) {
+    files.extend(extract_files_from_regex_blocks(
+      specifier,
+      &comment.text,
+      media_type,
+      parsed_source.text_info().line_index(comment.start()),
+      blocks_regex,
+      lines_regex,
+      run_examples,
+    )?);
+  }
 
   Ok(files)
 }
@@ -1252,7 +1315,8 @@ fn extract_files_from_fenced_blocks(
   specifier: &ModuleSpecifier,
   source: &str,
   media_type: MediaType,
-) -> Result<Vec<File>, AnyError> {
+  run_examples: bool,
+) -> Result<InlineFiles, AnyError> {
   // The pattern matches code blocks as well as anything in HTML comment syntax,
   // but it stores the latter without any capturing groups. This way, a simple
   // check can be done to see if a block is inside a comment (and skip typechecking)
@@ -1268,14 +1332,16 @@ fn extract_files_from_fenced_blocks(
     /* file line index */ 0,
     blocks_regex,
     lines_regex,
+    run_examples,
   )
 }
 
 async fn fetch_inline_files(
   file_fetcher: &FileFetcher,
   specifiers: Vec<ModuleSpecifier>,
-) -> Result<Vec<File>, AnyError> {
-  let mut files = Vec::new();
+  run_examples: bool,
+) -> Result<InlineFiles, AnyError> {
+  let mut files = InlineFiles::default();
   for specifier in specifiers {
     let fetch_permissions = PermissionsContainer::allow_all();
     let file = file_fetcher
@@ -1288,12 +1354,20 @@ async fn fetch_inline_files(
         &file.specifier,
         &file.source,
         file.media_type,
+        run_examples,
       )
     } else {
+      // the examples of declaration files are only type checked, since
+      // they describe code that lives elsewhere, such as in the runtime
+      let is_declaration = matches!(
+        file.media_type,
+        MediaType::Dts | MediaType::Dmts | MediaType::Dcts
+      );
       extract_files_from_source_comments(
         &file.specifier,
         file.source,
         file.media_type,
+        run_examples && !is_declaration,
       )
     };
 
@@ -1303,13 +1377,16 @@ async fn fetch_inline_files(
   Ok(files)
 }
 
-/// Type check a collection of module and document specifiers.
+/// Type check a collection of module and document specifiers. When
+/// `run_examples` is set, this also prepares the tests that run the code
+/// blocks of the documents and returns their specifiers.
 pub async fn check_specifiers(
   cli_options: &CliOptions,
   file_fetcher: &FileFetcher,
   module_load_preparer: &ModuleLoadPreparer,
   specifiers: Vec<(ModuleSpecifier, TestMode)>,
-) -> Result<(), AnyError> {
+  run_examples: bool,
+) -> Result<Vec<ModuleSpecifier>, AnyError> {
   let lib = cli_options.ts_type_lib_window();
   let inline_files = fetch_inline_files(
     file_fetcher,
@@ -1323,6 +1400,7 @@ pub async fn check_specifiers(
         }
       })
       .collect(),
+    run_examples,
   )
   .await?;
 
@@ -1337,11 +1415,15 @@ pub async fn check_specifiers(
     })
     .collect::<Vec<_>>();
 
-  if !inline_files.is_empty() {
-    module_specifiers
-      .extend(inline_files.iter().map(|file| file.specifier.clone()));
+  if !inline_files.check_files.is_empty() {
+    module_specifiers.extend(
+      inline_files
+        .check_files
+        .iter()
+        .map(|file| file.specifier.clone()),
+    );
 
-    for file in inline_files {
+    for file in inline_files.check_files {
       file_fetcher.insert_memory_files(file);
     }
   }
@@ -1355,7 +1437,29 @@ pub async fn check_specifiers(
     )
     .await?;
 
-  Ok(())
+  // The code blocks were type checked as they're written, so the test
+  // modules are added to the graph afterwards, which doesn't check them
+  // again.
+  let doc_test_specifiers = inline_files
+    .test_files
+    .iter()
+    .map(|file| file.specifier.clone())
+    .collect::<Vec<_>>();
+  if !doc_test_specifiers.is_empty() {
+    for file in inline_files.test_files {
+      file_fetcher.insert_memory_files(file);
+    }
+    module_load_preparer
+      .prepare_module_load(
+        doc_test_specifiers.clone(),
+        false,
+        lib,
+        PermissionsContainer::allow_all(),
+      )
+      .await?;
+  }
+
+  Ok(doc_test_specifiers)
 }
 
 static HAS_TEST_RUN_SIGINT_HANDLER: AtomicBool = AtomicBool::new(false);
@@ -1394,7 +1498,8 @@ async fn test_specifiers(
     let permissions = permissions.clone();
     let worker_sender = test_event_sender_factory.worker();
     let fail_fast_tracker = fail_fast_tracker.clone();
-    let specifier_options = options.specifier.clone();
+    let mut specifier_options = options.specifier.clone();
+    specifier_options.doc_test = options.doc_tests.contains(&specifier);
     spawn_blocking(move || {
       create_and_run_current_thread(test_specifier(
         worker_factory,
@@ -1719,11 +1824,12 @@ pub async fn run_tests(
     return Err(generic_error("No test modules found"));
   }
 
-  check_specifiers(
+  let doc_test_specifiers = check_specifiers(
     cli_options,
     file_fetcher,
     module_load_preparer,
     specifiers_with_mode.clone(),
+    test_options.run_doc,
   )
   .await?;
 
@@ -1743,6 +1849,7 @@ pub async fn run_tests(
         TestMode::Documentation => None,
         _ => Some(s),
       })
+      .chain(doc_test_specifiers.iter().cloned())
       .collect(),
     TestSpecifiersOptions {
       cwd: Url::from_directory_path(cli_options.initial_cwd()).map_err(
@@ -1763,7 +1870,9 @@ pub async fn run_tests(
         filter: TestFilter::from_flag(&test_options.filter),
         shuffle: test_options.shuffle,
         trace_leaks: test_options.trace_leaks,
+        doc_test: false,
       },
+      doc_tests: doc_test_specifiers.into_iter().collect(),
     },
   )
   .await?;
@@ -1788,6 +1897,7 @@ pub async fn run_tests_with_watch(
     }
   });
 
+  let mut watcher_factory = WatcherFactory::default();
   file_watcher::watch_func(
     flags,
     file_watcher::PrintConfig::new(
@@ -1800,9 +1910,12 @@ pub async fn run_tests_with_watch(
     ),
     move |flags, watcher_communicator, changed_paths| {
       let test_flags = test_flags.clone();
+      let factory = watcher_factory.get(
+        flags,
+        watcher_communicator.clone(),
+        changed_paths.as_deref(),
+      )?;
       Ok(async move {
-        let factory = CliFactoryBuilder::new()
-          .build_from_flags_for_watcher(flags, watcher_communicator.clone())?;
         let cli_options = factory.cli_options();
         let test_options = cli_options.resolve_test_options(test_flags)?;
 
@@ -1839,25 +1952,8 @@ pub async fn run_tests_with_watch(
           .create_graph(graph_kind, test_modules)
           .await?;
         module_graph_creator.graph_valid(&graph)?;
-        let test_modules = &graph.roots;
-
-        let test_modules_to_reload = if let Some(changed_paths) = changed_paths
-        {
-          let mut result = Vec::new();
-          let changed_paths = changed_paths.into_iter().collect::<HashSet<_>>();
-          for test_module_specifier in test_modules {
-            if has_graph_root_local_dependent_changed(
-              &graph,
-              test_module_specifier,
-              &changed_paths,
-            ) {
-              result.push(test_module_specifier.clone());
-            }
-          }
-          result
-        } else {
-          test_modules.clone()
-        };
+        let test_modules_to_reload =
+          graph_roots_with_changed_dependents(&graph, changed_paths);
 
         let worker_factory =
           Arc::new(factory.create_cli_main_worker_factory().await?);
@@ -1873,11 +1969,12 @@ pub async fn run_tests_with_watch(
         .filter(|(specifier, _)| test_modules_to_reload.contains(specifier))
         .collect::<Vec<(ModuleSpecifier, TestMode)>>();
 
-        check_specifiers(
+        let doc_test_specifiers = check_specifiers(
           &cli_options,
           file_fetcher,
           module_load_preparer,
           specifiers_with_mode.clone(),
+          test_options.run_doc,
         )
         .await?;
 
@@ -1894,6 +1991,7 @@ pub async fn run_tests_with_watch(
               TestMode::Documentation => None,
               _ => Some(s),
             })
+            .chain(doc_test_specifiers.iter().cloned())
             .collect(),
           TestSpecifiersOptions {
             cwd: Url::from_directory_path(cli_options.initial_cwd()).map_err(
@@ -1914,7 +2012,9 @@ pub async fn run_tests_with_watch(
               filter: TestFilter::from_flag(&test_options.filter),
               shuffle: test_options.shuffle,
               trace_leaks: test_options.trace_leaks,
+              doc_test: false,
             },
+            doc_tests: doc_test_specifiers.into_iter().collect(),
           },
         )
         .await?;
@@ -290,6 +290,16 @@ impl Diagnostics {
     Self(diagnostics)
   }
 
+  /// Return a set of diagnostics where each value is replaced with the
+  /// value returned by the function.
+  pub fn map<F>(self, f: F) -> Self
+  where
+    F: FnMut(Diagnostic) -> Diagnostic,
+  {
+    let diagnostics = self.0.into_iter().map(f).collect();
+    Self(diagnostics)
+  }
+
   pub fn is_empty(&self) -> bool {
     self.0.is_empty()
   }
@@ -2273,6 +2273,124 @@ declare namespace Deno {
         buffers?: Uint8Array[];
       },
     ): Promise<void>;
+
+    /**
+     * Metadata and binary buffers sent along with a comm message.
+     *
+     * @category Jupyter
+     * @tags unstable
+     */
+    export interface CommMessageOptions {
+      metadata?: Record<string, unknown>;
+      buffers?: Uint8Array[];
+    }
+
+    /**
+     * One end of a Jupyter comm, a channel for custom messages between the
+     * kernel and the frontend. Comms back interactive widgets such as
+     * ipywidgets sliders and buttons.
+     *
+     * @category Jupyter
+     * @tags unstable
+     */
+    export interface Comm {
+      /** The id shared by both ends of the comm. */
+      readonly id: string;
+      /** The target name the comm was opened with. */
+      readonly targetName: string;
+      /** Whether the comm was closed by either end. */
+      readonly closed: boolean;
+
+      /** Send a message with the given data to the frontend. */
+      send(
+        data?: Record<string, unknown>,
+        options?: CommMessageOptions,
+      ): Promise<void>;
+
+      /** Close the comm and notify the frontend. */
+      close(
+        data?: Record<string, unknown>,
+        options?: CommMessageOptions,
+      ): Promise<void>;
+
+      /** Add a listener for messages sent by the frontend. */
+      onMessage(
+        listener: (
+          data: Record<string, unknown>,
+          options: Required<CommMessageOptions>,
+        ) => void | Promise<void>,
+      ): void;
+
+      /** Add a listener called when the frontend closes the comm. */
+      onClose(
+        listener: (
+          data: Record<string, unknown>,
+          options: Required<CommMessageOptions>,
+        ) => void | Promise<void>,
+      ): void;
+    }
+
+    /**
+     * Open a comm from the kernel with the given target name.
+     *
+     * @example
+     * Create an ipywidgets slider and react to its changes.
+     * ```typescript
+     * const comm = await Deno.jupyter.openComm("jupyter.widget", {
+     *   state: {
+     *     _model_name: "IntSliderModel",
+     *     _model_module: "@jupyter-widgets/controls",
+     *     _model_module_version: "2.0.0",
+     *     _view_name: "IntSliderView",
+     *     _view_module: "@jupyter-widgets/controls",
+     *     _view_module_version: "2.0.0",
+     *     value: 50,
+     *     min: 0,
+     *     max: 100,
+     *   },
+     *   buffer_paths: [],
+     * }, { metadata: { version: "2.1.0" } });
+     *
+     * comm.onMessage((data) => {
+     *   if (data.method === "update") {
+     *     console.log(data.state);
+     *   }
+     * });
+     *
+     * await Deno.jupyter.display({
+     *   "application/vnd.jupyter.widget-view+json": {
+     *     model_id: comm.id,
+     *     version_major: 2,
+     *     version_minor: 0,
+     *   },
+     * }, { raw: true });
+     * ```
+     *
+     * @category Jupyter
+     * @tags unstable
+     */
+    export function openComm(
+      targetName: string,
+      data?: Record<string, unknown>,
+      options?: CommMessageOptions,
+    ): Promise<Comm>;
+
+    /**
+     * Register a callback for comms the frontend opens with the given target
+     * name. Comms the frontend opens for targets without a callback are
+     * closed right away.
+     *
+     * @category Jupyter
+     * @tags unstable
+     */
+    export function registerCommTarget(
+      targetName: string,
+      callback: (
+        comm: Comm,
+        data: Record<string, unknown>,
+        options: Required<CommMessageOptions>,
+      ) => void | Promise<void>,
+    ): void;
   }
 }
 
@@ -1,5 +1,6 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 
+use crate::args::CliOptions;
 use crate::args::Flags;
 use crate::colors;
 use crate::util::fs::canonicalize_path;
@@ -21,6 +22,7 @@ use notify::Watcher;
 use std::cell::RefCell;
 use std::collections::HashSet;
 use std::io::IsTerminal;
+use std::path::Path;
 use std::path::PathBuf;
 use std::rc::Rc;
 use std::sync::Arc;
@@ -187,6 +189,28 @@ impl WatcherCommunicator {
   }
 }
 
+/// Whether a file that the options are resolved from, such as the config
+/// file, the import map or the package.json, is in the changed paths of a
+/// restart.
+pub fn has_config_changed(
+  cli_options: &CliOptions,
+  changed_paths: &[PathBuf],
+) -> bool {
+  let is_changed = |path: &Path| {
+    canonicalize_path(path)
+      .map(|path| changed_paths.contains(&path))
+      .unwrap_or(false)
+  };
+  cli_options
+    .watch_paths()
+    .iter()
+    .any(|path| is_changed(path))
+    || cli_options
+      .maybe_package_json()
+      .as_ref()
+      .is_some_and(|package_json| is_changed(&package_json.path))
+}
+
 /// Creates a file watcher.
 ///
 /// - `operation` is the actual operation we want to run every time the watcher detects file
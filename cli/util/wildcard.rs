@@ -0,0 +1,34 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+use deno_core::error::AnyError;
+use regex::Regex;
+
+/// Converts a pattern where `*` matches any characters, and every other
+/// character matches itself, to a regex matching the whole text.
+pub fn wildcard_pattern_to_regex(pattern: &str) -> Result<Regex, AnyError> {
+  let pattern = pattern
+    .split('*')
+    .map(regex::escape)
+    .collect::<Vec<_>>()
+    .join(".*");
+  Ok(Regex::new(&format!("^{}$", pattern))?)
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn test_wildcard_pattern_to_regex() {
+    let regex = wildcard_pattern_to_regex("@scope/*").unwrap();
+    assert!(regex.is_match("@scope/lib"));
+    assert!(!regex.is_match("@other/lib"));
+    let regex = wildcard_pattern_to_regex("lib.x").unwrap();
+    assert!(regex.is_match("lib.x"));
+    assert!(!regex.is_match("lib_x"));
+    let regex =
+      wildcard_pattern_to_regex("https://deno.land/x/*/mod.ts").unwrap();
+    assert!(regex.is_match("https://deno.land/x/oak/mod.ts"));
+    assert!(!regex.is_match("https://deno.land/x/oak/deps.ts"));
+  }
+}
@@ -35,12 +35,25 @@ use std::rc::Rc;
 use std::thread;
 use tokio::net::TcpListener;
 use tokio::sync::broadcast;
+use tokio::sync::Notify;
 use uuid::Uuid;
 
+mod dap;
+
+/// The protocol spoken by clients of the `InspectorServer`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum InspectorProtocol {
+  /// Chrome DevTools Protocol over websockets.
+  Cdp,
+  /// Debug Adapter Protocol over a plain TCP socket.
+  Dap,
+}
+
 /// Websocket server that is used to proxy connections from
 /// devtools to the inspector.
 pub struct InspectorServer {
   pub host: SocketAddr,
+  pub protocol: InspectorProtocol,
   register_inspector_tx: UnboundedSender<InspectorInfo>,
   shutdown_server_tx: Option<broadcast::Sender<()>>,
   thread_handle: Option<thread::JoinHandle<()>>,
@@ -48,6 +61,14 @@ pub struct InspectorServer {
 
 impl InspectorServer {
   pub fn new(host: SocketAddr, name: &'static str) -> Result<Self, AnyError> {
+    Self::with_protocol(host, name, InspectorProtocol::Cdp)
+  }
+
+  pub fn with_protocol(
+    host: SocketAddr,
+    name: &'static str,
+    protocol: InspectorProtocol,
+  ) -> Result<Self, AnyError> {
     let (register_inspector_tx, register_inspector_rx) =
       mpsc::unbounded::<InspectorInfo>();
 
@@ -69,12 +90,14 @@ impl InspectorServer {
           register_inspector_rx,
           shutdown_server_rx,
           name,
+          protocol,
         ),
       )
     });
 
     Ok(Self {
       host,
+      protocol,
       register_inspector_tx,
       shutdown_server_tx: Some(shutdown_server_tx),
       thread_handle: Some(thread_handle),
@@ -237,24 +260,37 @@ async fn server(
   register_inspector_rx: UnboundedReceiver<InspectorInfo>,
   shutdown_server_rx: broadcast::Receiver<()>,
   name: &str,
+  protocol: InspectorProtocol,
 ) {
   let inspector_map_ =
     Rc::new(RefCell::new(HashMap::<Uuid, InspectorInfo>::new()));
 
+  // lets DAP connections wait for the first inspector to register
+  let inspector_registered = Rc::new(Notify::new());
+
   let inspector_map = Rc::clone(&inspector_map_);
+  let inspector_registered_ = Rc::clone(&inspector_registered);
   let mut register_inspector_handler = pin!(register_inspector_rx
     .map(|info| {
-      eprintln!(
-        "Debugger listening on {}",
-        info.get_websocket_debugger_url(&info.host.to_string())
-      );
-      eprintln!("Visit chrome://inspect to connect to the debugger.");
+      match protocol {
+        InspectorProtocol::Cdp => {
+          eprintln!(
+            "Debugger listening on {}",
+            info.get_websocket_debugger_url(&info.host.to_string())
+          );
+          eprintln!("Visit chrome://inspect to connect to the debugger.");
+        }
+        InspectorProtocol::Dap => {
+          eprintln!("Debug adapter listening on {}", info.host);
+        }
+      }
       if info.wait_for_session {
         eprintln!("Deno is waiting for debugger to connect.");
       }
       if inspector_map.borrow_mut().insert(info.uuid, info).is_some() {
         panic!("Inspector UUID already in map");
       }
+      inspector_registered_.notify_waiters();
     })
     .collect::<()>());
 
@@ -303,6 +339,21 @@ async fn server(
           break;
         }
       };
+
+      if protocol == InspectorProtocol::Dap {
+        let inspector_map = Rc::clone(&inspector_map_);
+        let inspector_registered = Rc::clone(&inspector_registered);
+        let mut shutdown_server_rx = shutdown_server_rx.resubscribe();
+        deno_core::unsync::spawn(async move {
+          let mut shutdown_rx = pin!(shutdown_server_rx.recv());
+          tokio::select! {
+            _ = dap::serve_connection(stream, inspector_map, inspector_registered) => {},
+            _ = &mut shutdown_rx => {},
+          }
+        });
+        continue;
+      }
+
       let io = TokioIo::new(stream);
 
       let inspector_map = Rc::clone(&inspector_map_);
@@ -0,0 +1,814 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+//! Debug Adapter Protocol (DAP) frontend for the inspector server.
+//!
+//! Editors speak DAP over a plain TCP socket using `Content-Length` framed
+//! JSON messages. Each connection is bridged to a regular inspector session
+//! and DAP requests are translated to their Chrome DevTools Protocol
+//! equivalents.
+
+use deno_core::futures::channel::mpsc;
+use deno_core::futures::channel::mpsc::UnboundedReceiver;
+use deno_core::futures::channel::mpsc::UnboundedSender;
+use deno_core::futures::channel::oneshot;
+use deno_core::futures::StreamExt;
+use deno_core::serde_json;
+use deno_core::serde_json::json;
+use deno_core::serde_json::Value;
+use deno_core::unsync::spawn;
+use deno_core::url::Url;
+use deno_core::InspectorMsg;
+use deno_core::InspectorSessionProxy;
+use std::cell::Cell;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use tokio::io::AsyncBufReadExt;
+use tokio::io::AsyncReadExt;
+use tokio::io::AsyncWriteExt;
+use tokio::io::BufReader;
+use tokio::net::tcp::OwnedWriteHalf;
+use tokio::net::TcpStream;
+use tokio::sync::Notify;
+use uuid::Uuid;
+
+use super::InspectorInfo;
+
+/// DAP only has a notion of threads, while the inspector session is bound to
+/// a single isolate. The main worker is always reported as thread `1`.
+const THREAD_ID: i64 = 1;
+
+type CdpResult = Result<Value, String>;
+
+/// Serves a single DAP client connection until either side disconnects.
+pub(super) async fn serve_connection(
+  stream: TcpStream,
+  inspector_map: Rc<RefCell<HashMap<Uuid, InspectorInfo>>>,
+  inspector_registered: Rc<Notify>,
+) {
+  let (read_half, write_half) = stream.into_split();
+
+  // Editors usually connect right after spawning the process, possibly
+  // before the main worker registered its inspector.
+  let new_session_tx = loop {
+    // created before the map is read, so a registration in between isn't
+    // missed
+    let registered = inspector_registered.notified();
+    let maybe_tx = inspector_map
+      .borrow()
+      .values()
+      .next()
+      .map(|info| info.new_session_tx.clone());
+    if let Some(tx) = maybe_tx {
+      break tx;
+    }
+    registered.await;
+  };
+
+  // The 'outbound' channel carries messages sent by the inspector.
+  let (outbound_tx, outbound_rx) = mpsc::unbounded();
+  // The 'inbound' channel carries messages sent to the inspector.
+  let (inbound_tx, inbound_rx) = mpsc::unbounded();
+  let inspector_session_proxy = InspectorSessionProxy {
+    tx: outbound_tx,
+    rx: inbound_rx,
+  };
+  if new_session_tx
+    .unbounded_send(inspector_session_proxy)
+    .is_err()
+  {
+    return;
+  }
+  eprintln!("Debugger session started.");
+
+  let cdp = CdpClient::new(inbound_tx);
+  let (events_tx, events_rx) = mpsc::unbounded();
+  spawn(pump_inspector_messages(outbound_rx, cdp.clone(), events_tx));
+
+  let (requests_tx, requests_rx) = mpsc::unbounded();
+  spawn(read_dap_messages(BufReader::new(read_half), requests_tx));
+
+  let mut session = DapSession::new(write_half, cdp);
+  session.run(requests_rx, events_rx).await;
+  eprintln!("Debugger session ended");
+}
+
+/// Reads `Content-Length` framed DAP messages from the socket and forwards
+/// them to the session. Resolves once the client disconnects or sends a
+/// malformed message.
+async fn read_dap_messages(
+  mut reader: BufReader<tokio::net::tcp::OwnedReadHalf>,
+  requests_tx: UnboundedSender<Value>,
+) {
+  loop {
+    let mut content_length = None;
+    loop {
+      let mut line = String::new();
+      match reader.read_line(&mut line).await {
+        Ok(0) | Err(_) => return,
+        Ok(_) => {}
+      }
+      let line = line.trim_end();
+      if line.is_empty() {
+        break;
+      }
+      if let Some((name, value)) = line.split_once(':') {
+        if name.trim().eq_ignore_ascii_case("content-length") {
+          content_length = value.trim().parse::<usize>().ok();
+        }
+      }
+    }
+    let Some(content_length) = content_length else {
+      return;
+    };
+    let mut body = vec![0; content_length];
+    if reader.read_exact(&mut body).await.is_err() {
+      return;
+    }
+    let Ok(message) = serde_json::from_slice::<Value>(&body) else {
+      return;
+    };
+    if requests_tx.unbounded_send(message).is_err() {
+      return;
+    }
+  }
+}
+
+/// Routes messages coming from the inspector: responses are delivered to the
+/// pending `CdpClient::call`, notifications are forwarded as events.
+async fn pump_inspector_messages(
+  mut outbound_rx: UnboundedReceiver<InspectorMsg>,
+  cdp: CdpClient,
+  events_tx: UnboundedSender<(String, Value)>,
+) {
+  while let Some(msg) = outbound_rx.next().await {
+    let Ok(message) = serde_json::from_str::<Value>(&msg.content) else {
+      continue;
+    };
+    if let Some(id) = message.get("id").and_then(|id| id.as_i64()) {
+      let result = match message.get("error") {
+        Some(error) => Err(
+          error
+            .get("message")
+            .and_then(|m| m.as_str())
+            .unwrap_or("Unknown inspector error")
+            .to_string(),
+        ),
+        None => Ok(message.get("result").cloned().unwrap_or(Value::Null)),
+      };
+      if let Some(tx) = cdp.pending.borrow_mut().remove(&id) {
+        let _ = tx.send(result);
+      }
+    } else if let Some(method) = message.get("method").and_then(|m| m.as_str())
+    {
+      let params = message.get("params").cloned().unwrap_or(Value::Null);
+      if events_tx
+        .unbounded_send((method.to_string(), params))
+        .is_err()
+      {
+        break;
+      }
+    }
+  }
+}
+
+#[derive(Clone)]
+struct CdpClient {
+  next_id: Rc<Cell<i64>>,
+  tx: UnboundedSender<String>,
+  pending: Rc<RefCell<HashMap<i64, oneshot::Sender<CdpResult>>>>,
+}
+
+impl CdpClient {
+  fn new(tx: UnboundedSender<String>) -> Self {
+    Self {
+      next_id: Rc::new(Cell::new(1)),
+      tx,
+      pending: Default::default(),
+    }
+  }
+
+  async fn call(&self, method: &str, params: Value) -> CdpResult {
+    let id = self.next_id.get();
+    self.next_id.set(id + 1);
+    let (tx, rx) = oneshot::channel();
+    self.pending.borrow_mut().insert(id, tx);
+    let message = json!({ "id": id, "method": method, "params": params });
+    if self.tx.unbounded_send(message.to_string()).is_err() {
+      self.pending.borrow_mut().remove(&id);
+      return Err("Inspector session closed".to_string());
+    }
+    rx.await
+      .unwrap_or_else(|_| Err("Inspector session closed".to_string()))
+  }
+}
+
+struct DapSession {
+  writer: OwnedWriteHalf,
+  cdp: CdpClient,
+  seq: i64,
+  configuration_done: bool,
+  stop_on_entry: bool,
+  seen_entry_pause: bool,
+  entry_pause_pending: bool,
+  /// Maps a source path to the inspector ids of its breakpoints.
+  breakpoints: HashMap<String, Vec<String>>,
+  /// Call frames of the current pause, empty while running.
+  call_frames: Vec<Value>,
+  /// Maps a DAP `variablesReference` to a remote object id. References are
+  /// only valid while paused.
+  object_refs: Vec<String>,
+}
+
+impl DapSession {
+  fn new(writer: OwnedWriteHalf, cdp: CdpClient) -> Self {
+    Self {
+      writer,
+      cdp,
+      seq: 0,
+      configuration_done: false,
+      stop_on_entry: false,
+      seen_entry_pause: false,
+      entry_pause_pending: false,
+      breakpoints: HashMap::new(),
+      call_frames: Vec::new(),
+      object_refs: Vec::new(),
+    }
+  }
+
+  async fn run(
+    &mut self,
+    mut requests_rx: UnboundedReceiver<Value>,
+    mut events_rx: UnboundedReceiver<(String, Value)>,
+  ) {
+    loop {
+      tokio::select! {
+        maybe_request = requests_rx.next() => {
+          let Some(request) = maybe_request else {
+            break;
+          };
+          let command = request
+            .get("command")
+            .and_then(|c| c.as_str())
+            .unwrap_or_default()
+            .to_string();
+          let arguments =
+            request.get("arguments").cloned().unwrap_or(Value::Null);
+          let request_seq =
+            request.get("seq").and_then(|s| s.as_i64()).unwrap_or(0);
+          let result = self.handle_request(&command, arguments).await;
+          let is_disconnect = command == "disconnect";
+          if self.respond(request_seq, &command, result).await.is_err() {
+            break;
+          }
+          if command == "initialize" {
+            let _ = self.send_event("initialized", Value::Null).await;
+          }
+          if is_disconnect {
+            break;
+          }
+        }
+        maybe_event = events_rx.next() => {
+          let Some((method, params)) = maybe_event else {
+            let _ = self
+              .send_event("terminated", Value::Null)
+              .await;
+            break;
+          };
+          if self.handle_event(&method, params).await.is_err() {
+            break;
+          }
+        }
+      }
+    }
+  }
+
+  async fn handle_request(&mut self, command: &str, args: Value) -> CdpResult {
+    match command {
+      "initialize" => {
+        self.cdp.call("Runtime.enable", json!({})).await?;
+        self.cdp.call("Debugger.enable", json!({})).await?;
+        Ok(json!({
+          "supportsConfigurationDoneRequest": true,
+          "supportsConditionalBreakpoints": true,
+          "supportsEvaluateForHovers": true,
+          "exceptionBreakpointFilters": [
+            { "filter": "all", "label": "All Exceptions", "default": false },
+            { "filter": "uncaught", "label": "Uncaught Exceptions", "default": false },
+          ],
+        }))
+      }
+      // the adapter runs inside the debugged process, so there's nothing to
+      // launch
+      "launch" => Err(
+        "Launching is not supported. Start the program with `deno run`, `deno test` or `deno serve` and `--inspect-dap`, then attach to it.".to_string(),
+      ),
+      "attach" => {
+        self.stop_on_entry = args
+          .get("stopOnEntry")
+          .and_then(|v| v.as_bool())
+          .unwrap_or(false);
+        Ok(Value::Null)
+      }
+      "setBreakpoints" => self.set_breakpoints(args).await,
+      "setExceptionBreakpoints" => {
+        let filters = args
+          .get("filters")
+          .and_then(|f| f.as_array())
+          .cloned()
+          .unwrap_or_default();
+        let has_filter =
+          |name: &str| filters.iter().any(|f| f.as_str() == Some(name));
+        let state = if has_filter("all") {
+          "all"
+        } else if has_filter("uncaught") {
+          "uncaught"
+        } else {
+          "none"
+        };
+        self
+          .cdp
+          .call("Debugger.setPauseOnExceptions", json!({ "state": state }))
+          .await?;
+        Ok(Value::Null)
+      }
+      "configurationDone" => {
+        self.configuration_done = true;
+        self
+          .cdp
+          .call("Runtime.runIfWaitingForDebugger", json!({}))
+          .await?;
+        if self.entry_pause_pending {
+          self.entry_pause_pending = false;
+          self.resolve_entry_pause().await?;
+        }
+        Ok(Value::Null)
+      }
+      "threads" => Ok(json!({
+        "threads": [{ "id": THREAD_ID, "name": "main" }],
+      })),
+      "stackTrace" => Ok(self.stack_trace()),
+      "scopes" => {
+        let frame_id = args.get("frameId").and_then(|f| f.as_u64());
+        let scopes = self.scopes(frame_id.unwrap_or(0) as usize);
+        Ok(json!({ "scopes": scopes }))
+      }
+      "variables" => self.variables(args).await,
+      "evaluate" => self.evaluate(args).await,
+      "continue" => {
+        self.cdp.call("Debugger.resume", json!({})).await?;
+        Ok(json!({ "allThreadsContinued": true }))
+      }
+      "next" => self.cdp.call("Debugger.stepOver", json!({})).await,
+      "stepIn" => self.cdp.call("Debugger.stepInto", json!({})).await,
+      "stepOut" => self.cdp.call("Debugger.stepOut", json!({})).await,
+      "pause" => self.cdp.call("Debugger.pause", json!({})).await,
+      "disconnect" => {
+        if !self.call_frames.is_empty() {
+          let _ = self.cdp.call("Debugger.resume", json!({})).await;
+        }
+        Ok(Value::Null)
+      }
+      _ => Err(format!("Unsupported command \"{command}\"")),
+    }
+  }
+
+  async fn handle_event(
+    &mut self,
+    method: &str,
+    params: Value,
+  ) -> Result<(), std::io::Error> {
+    match method {
+      "Debugger.paused" => {
+        self.call_frames = params
+          .get("callFrames")
+          .and_then(|f| f.as_array())
+          .cloned()
+          .unwrap_or_default();
+        self.object_refs.clear();
+        if !self.seen_entry_pause {
+          // The first pause is the break on the first statement requested
+          // by `--inspect-dap`, which lets us install breakpoints first.
+          self.seen_entry_pause = true;
+          if self.configuration_done {
+            // errors are reported by the editor once it inspects the state
+            let _ = self.resolve_entry_pause().await;
+          } else {
+            self.entry_pause_pending = true;
+          }
+          return Ok(());
+        }
+        let reason = match params.get("reason").and_then(|r| r.as_str()) {
+          Some("exception") | Some("promiseRejection") => "exception",
+          _ if params
+            .get("hitBreakpoints")
+            .and_then(|b| b.as_array())
+            .map(|b| !b.is_empty())
+            .unwrap_or(false) =>
+          {
+            "breakpoint"
+          }
+          _ => "step",
+        };
+        self
+          .send_event(
+            "stopped",
+            json!({
+              "reason": reason,
+              "threadId": THREAD_ID,
+              "allThreadsStopped": true,
+            }),
+          )
+          .await
+      }
+      "Debugger.resumed" => {
+        self.call_frames.clear();
+        self.object_refs.clear();
+        self
+          .send_event(
+            "continued",
+            json!({ "threadId": THREAD_ID, "allThreadsContinued": true }),
+          )
+          .await
+      }
+      _ => Ok(()),
+    }
+  }
+
+  async fn resolve_entry_pause(&mut self) -> Result<(), String> {
+    if self.stop_on_entry {
+      self
+        .send_event(
+          "stopped",
+          json!({
+            "reason": "entry",
+            "threadId": THREAD_ID,
+            "allThreadsStopped": true,
+          }),
+        )
+        .await
+        .map_err(|err| err.to_string())
+    } else {
+      self
+        .cdp
+        .call("Debugger.resume", json!({}))
+        .await
+        .map(|_| ())
+    }
+  }
+
+  async fn set_breakpoints(&mut self, args: Value) -> CdpResult {
+    let Some(path) = args
+      .get("source")
+      .and_then(|s| s.get("path"))
+      .and_then(|p| p.as_str())
+    else {
+      return Err("Missing source path".to_string());
+    };
+    let url = Url::from_file_path(path)
+      .map_err(|_| format!("Invalid source path \"{path}\""))?;
+
+    for id in self.breakpoints.remove(path).unwrap_or_default() {
+      self
+        .cdp
+        .call("Debugger.removeBreakpoint", json!({ "breakpointId": id }))
+        .await?;
+    }
+
+    let requested = args
+      .get("breakpoints")
+      .and_then(|b| b.as_array())
+      .cloned()
+      .unwrap_or_default();
+    let mut ids = Vec::with_capacity(requested.len());
+    let mut breakpoints = Vec::with_capacity(requested.len());
+    for breakpoint in requested {
+      let line = breakpoint.get("line").and_then(|l| l.as_i64()).unwrap_or(1);
+      let mut params = json!({
+        "url": url.as_str(),
+        "lineNumber": line - 1,
+      });
+      if let Some(column) = breakpoint.get("column").and_then(|c| c.as_i64()) {
+        params["columnNumber"] = json!(column - 1);
+      }
+      if let Some(condition) = breakpoint.get("condition") {
+        params["condition"] = condition.clone();
+      }
+      match self.cdp.call("Debugger.setBreakpointByUrl", params).await {
+        Ok(result) => {
+          if let Some(id) = result.get("breakpointId").and_then(|i| i.as_str())
+          {
+            ids.push(id.to_string());
+          }
+          breakpoints.push(json!({ "verified": true, "line": line }));
+        }
+        Err(message) => {
+          breakpoints.push(json!({
+            "verified": false,
+            "line": line,
+            "message": message,
+          }));
+        }
+      }
+    }
+    self.breakpoints.insert(path.to_string(), ids);
+    Ok(json!({ "breakpoints": breakpoints }))
+  }
+
+  fn stack_trace(&self) -> Value {
+    let frames = self
+      .call_frames
+      .iter()
+      .enumerate()
+      .map(|(index, frame)| {
+        let name = match frame.get("functionName").and_then(|n| n.as_str()) {
+          Some(name) if !name.is_empty() => name,
+          _ => "<anonymous>",
+        };
+        let location = frame.get("location").cloned().unwrap_or(Value::Null);
+        let line = location
+          .get("lineNumber")
+          .and_then(|l| l.as_i64())
+          .unwrap_or(0);
+        let column = location
+          .get("columnNumber")
+          .and_then(|c| c.as_i64())
+          .unwrap_or(0);
+        let url = frame.get("url").and_then(|u| u.as_str()).unwrap_or("");
+        json!({
+          "id": index,
+          "name": name,
+          "source": source_from_url(url),
+          "line": line + 1,
+          "column": column + 1,
+        })
+      })
+      .collect::<Vec<_>>();
+    json!({ "stackFrames": frames, "totalFrames": self.call_frames.len() })
+  }
+
+  fn scopes(&mut self, frame_id: usize) -> Vec<Value> {
+    let scope_chain = self
+      .call_frames
+      .get(frame_id)
+      .and_then(|frame| frame.get("scopeChain"))
+      .and_then(|s| s.as_array())
+      .cloned()
+      .unwrap_or_default();
+    scope_chain
+      .into_iter()
+      .filter_map(|scope| {
+        let kind = scope.get("type").and_then(|t| t.as_str())?.to_string();
+        let object_id = scope
+          .get("object")
+          .and_then(|o| o.get("objectId"))
+          .and_then(|o| o.as_str())?;
+        Some(json!({
+          "name": scope_name(&kind),
+          "variablesReference": self.register_object(object_id),
+          "expensive": kind == "global",
+        }))
+      })
+      .collect()
+  }
+
+  async fn variables(&mut self, args: Value) -> CdpResult {
+    let reference = args
+      .get("variablesReference")
+      .and_then(|r| r.as_u64())
+      .unwrap_or(0) as usize;
+    let Some(object_id) = reference
+      .checked_sub(1)
+      .and_then(|index| self.object_refs.get(index))
+      .cloned()
+    else {
+      return Ok(json!({ "variables": [] }));
+    };
+    let result = self
+      .cdp
+      .call(
+        "Runtime.getProperties",
+        json!({ "objectId": object_id, "ownProperties": true }),
+      )
+      .await?;
+    let properties = result
+      .get("result")
+      .and_then(|r| r.as_array())
+      .cloned()
+      .unwrap_or_default();
+    let variables = properties
+      .into_iter()
+      .filter_map(|property| {
+        let name = property.get("name").and_then(|n| n.as_str())?.to_string();
+        let value = property.get("value").cloned().unwrap_or(Value::Null);
+        Some(json!({
+          "name": name,
+          "value": describe_remote_object(&value),
+          "type": value.get("type").cloned().unwrap_or(Value::Null),
+          "variablesReference": self.register_remote_object(&value),
+        }))
+      })
+      .collect::<Vec<_>>();
+    Ok(json!({ "variables": variables }))
+  }
+
+  async fn evaluate(&mut self, args: Value) -> CdpResult {
+    let expression = args
+      .get("expression")
+      .and_then(|e| e.as_str())
+      .unwrap_or_default();
+    let maybe_call_frame_id = args
+      .get("frameId")
+      .and_then(|f| f.as_u64())
+      .and_then(|frame_id| self.call_frames.get(frame_id as usize))
+      .and_then(|frame| frame.get("callFrameId"))
+      .cloned();
+    let result = match maybe_call_frame_id {
+      Some(call_frame_id) => {
+        self
+          .cdp
+          .call(
+            "Debugger.evaluateOnCallFrame",
+            json!({ "callFrameId": call_frame_id, "expression": expression }),
+          )
+          .await?
+      }
+      None => {
+        self
+          .cdp
+          .call(
+            "Runtime.evaluate",
+            json!({ "expression": expression, "replMode": true }),
+          )
+          .await?
+      }
+    };
+    if let Some(details) = result.get("exceptionDetails") {
+      let message = details
+        .get("exception")
+        .map(describe_remote_object)
+        .unwrap_or_else(|| "Uncaught exception".to_string());
+      return Err(message);
+    }
+    let value = result.get("result").cloned().unwrap_or(Value::Null);
+    Ok(json!({
+      "result": describe_remote_object(&value),
+      "type": value.get("type").cloned().unwrap_or(Value::Null),
+      "variablesReference": self.register_remote_object(&value),
+    }))
+  }
+
+  fn register_object(&mut self, object_id: &str) -> usize {
+    self.object_refs.push(object_id.to_string());
+    self.object_refs.len()
+  }
+
+  fn register_remote_object(&mut self, value: &Value) -> usize {
+    match value.get("objectId").and_then(|o| o.as_str()) {
+      Some(object_id) => self.register_object(object_id),
+      None => 0,
+    }
+  }
+
+  async fn respond(
+    &mut self,
+    request_seq: i64,
+    command: &str,
+    result: CdpResult,
+  ) -> Result<(), std::io::Error> {
+    self.seq += 1;
+    let message = match result {
+      Ok(body) => json!({
+        "seq": self.seq,
+        "type": "response",
+        "request_seq": request_seq,
+        "success": true,
+        "command": command,
+        "body": body,
+      }),
+      Err(message) => json!({
+        "seq": self.seq,
+        "type": "response",
+        "request_seq": request_seq,
+        "success": false,
+        "command": command,
+        "message": message,
+      }),
+    };
+    self.write_message(&message).await
+  }
+
+  async fn send_event(
+    &mut self,
+    event: &str,
+    body: Value,
+  ) -> Result<(), std::io::Error> {
+    self.seq += 1;
+    let message = json!({
+      "seq": self.seq,
+      "type": "event",
+      "event": event,
+      "body": body,
+    });
+    self.write_message(&message).await
+  }
+
+  async fn write_message(
+    &mut self,
+    message: &Value,
+  ) -> Result<(), std::io::Error> {
+    let content = message.to_string();
+    let header = format!("Content-Length: {}\r\n\r\n", content.len());
+    self.writer.write_all(header.as_bytes()).await?;
+    self.writer.write_all(content.as_bytes()).await?;
+    self.writer.flush().await
+  }
+}
+
+fn source_from_url(url: &str) -> Value {
+  let maybe_path = Url::parse(url)
+    .ok()
+    .filter(|url| url.scheme() == "file")
+    .and_then(|url| url.to_file_path().ok());
+  match maybe_path {
+    Some(path) => json!({
+      "name": path.file_name().map(|n| n.to_string_lossy().to_string()),
+      "path": path.to_string_lossy(),
+    }),
+    None => json!({ "name": url }),
+  }
+}
+
+fn scope_name(kind: &str) -> &'static str {
+  match kind {
+    "local" => "Local",
+    "closure" => "Closure",
+    "block" => "Block",
+    "catch" => "Catch",
+    "module" => "Module",
+    "script" => "Script",
+    "with" => "With",
+    "eval" => "Eval",
+    "global" => "Global",
+    _ => "Scope",
+  }
+}
+
+fn describe_remote_object(value: &Value) -> String {
+  if let Some(description) = value.get("description").and_then(|d| d.as_str()) {
+    return description.to_string();
+  }
+  match value.get("value") {
+    Some(Value::String(s)) => format!("{s:?}"),
+    Some(value) => value.to_string(),
+    None => value
+      .get("type")
+      .and_then(|t| t.as_str())
+      .unwrap_or("undefined")
+      .to_string(),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_describe_remote_object() {
+    assert_eq!(
+      describe_remote_object(&json!({ "type": "string", "value": "a" })),
+      "\"a\""
+    );
+    assert_eq!(
+      describe_remote_object(&json!({ "type": "number", "value": 1 })),
+      "1"
+    );
+    assert_eq!(
+      describe_remote_object(
+        &json!({ "type": "object", "description": "Array(2)" })
+      ),
+      "Array(2)"
+    );
+    assert_eq!(
+      describe_remote_object(&json!({ "type": "undefined" })),
+      "undefined"
+    );
+  }
+
+  #[test]
+  fn test_source_from_url() {
+    assert_eq!(
+      source_from_url("ext:core/01_core.js"),
+      json!({ "name": "ext:core/01_core.js" })
+    );
+    let path = std::env::temp_dir().join("main.ts");
+    let url = Url::from_file_path(&path).unwrap();
+    assert_eq!(
+      source_from_url(url.as_str()),
+      json!({
+        "name": "main.ts",
+        "path": path.to_string_lossy(),
+      })
+    );
+  }
+}
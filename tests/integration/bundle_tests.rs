@@ -121,7 +121,7 @@ fn bundle_circular() {
   // check the output of the bundle program.
   assert_ends_with!(
     std::str::from_utf8(&output.stdout).unwrap().trim(),
-    "f2\nf1",
+    "f1\nf2",
   );
   assert_eq!(output.stderr, b"");
 }
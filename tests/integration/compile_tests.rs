@@ -715,6 +715,162 @@ fn dynamic_import_unanalyzable() {
     .assert_exit_code(0);
 }
 
+#[test]
+fn include_asset_dirs() {
+  let context = TestContextBuilder::new().use_temp_cwd().build();
+  let temp_dir = context.temp_dir();
+  temp_dir.write(
+    "main.ts",
+    concat!(
+      "const assets = new URL('./assets/', import.meta.url);\n",
+      "console.log(Deno.readTextFileSync(new URL('hello.txt', assets)));\n",
+      "const names = [...Deno.readDirSync(new URL('nested', assets))]\n",
+      "  .map((e) => e.name);\n",
+      "console.log(names);\n",
+    ),
+  );
+  let assets_dir = temp_dir.path().join("assets");
+  assets_dir.join("nested").create_dir_all();
+  assets_dir.join("hello.txt").write("Hello from an asset!");
+  assets_dir.join("nested/data.json").write("{}");
+  let binary_path = if cfg!(windows) {
+    temp_dir.path().join("binary.exe")
+  } else {
+    temp_dir.path().join("binary")
+  };
+
+  context
+    .new_command()
+    .args("compile --output binary --include assets main.ts")
+    .run()
+    .skip_output_check()
+    .assert_exit_code(0);
+  // the assets should be read from the executable
+  assets_dir.remove_dir_all();
+
+  context
+    .new_command()
+    .name(&binary_path)
+    .run()
+    .assert_matches_text("Hello from an asset!\n[ \"data.json\" ]\n")
+    .assert_exit_code(0);
+}
+
+#[test]
+fn info_standalone() {
+  let context = TestContextBuilder::new().use_temp_cwd().build();
+  let temp_dir = context.temp_dir();
+  temp_dir.write(
+    "main.ts",
+    "import { add } from './add.ts';\nconsole.log(add(1, 2));\n",
+  );
+  temp_dir.write(
+    "add.ts",
+    "export function add(a: number, b: number) { return a + b; }\n",
+  );
+  temp_dir.path().join("assets").create_dir_all();
+  temp_dir.write("assets/hello.txt", "Hello!");
+
+  context
+    .new_command()
+    .args("compile --output binary --allow-read --v8-flags=--max-old-space-size=100 --include assets main.ts")
+    .run()
+    .skip_output_check()
+    .assert_exit_code(0);
+
+  let binary_path = if cfg!(windows) {
+    "binary.exe"
+  } else {
+    "binary"
+  };
+  context
+    .new_command()
+    .args_vec(["info", "--standalone", binary_path])
+    .run()
+    .assert_matches_text(
+      "entrypoint: file:///[WILDCARD]/main.ts
+compressed: no
+code cache: none
+permissions: --allow-read
+v8 flags: --max-old-space-size=100
+unstable: none
+
+modules: (2)
+  file:///[WILDCARD]/add.ts (JavaScript, [WILDCARD])
+  file:///[WILDCARD]/main.ts (JavaScript, [WILDCARD])
+
+assets: ([WILDCARD])
+  assets/
+    hello.txt (6B)
+",
+    )
+    .assert_exit_code(0);
+
+  context
+    .new_command()
+    .args_vec(["info", "--standalone", "--extract", "out", binary_path])
+    .run()
+    .skip_output_check()
+    .assert_exit_code(0);
+  temp_dir
+    .path()
+    .join("out/assets/assets/hello.txt")
+    .assert_matches_text("Hello!");
+  assert!(temp_dir.path().join("out/metadata.json").exists());
+
+  context
+    .new_command()
+    .args("info --standalone main.ts")
+    .run()
+    .assert_matches_text(
+      "error: '[WILDCARD]main.ts' is not an executable produced by 'deno compile'.\n",
+    )
+    .assert_exit_code(1);
+}
+
+#[test]
+fn compress_and_code_cache() {
+  let context = TestContextBuilder::new().use_temp_cwd().build();
+  let temp_dir = context.temp_dir();
+  temp_dir.write(
+    "main.ts",
+    concat!(
+      "import { add } from './add.ts';\n",
+      "console.log(add(1, 2));\n",
+      "console.log(Deno.readTextFileSync(new URL('./assets/hello.txt', import.meta.url)));\n",
+    ),
+  );
+  temp_dir.write(
+    "add.ts",
+    "export function add(a: number, b: number) { return a + b; }\n",
+  );
+  let assets_dir = temp_dir.path().join("assets");
+  assets_dir.create_dir_all();
+  assets_dir
+    .join("hello.txt")
+    .write("Hello from a compressed asset!");
+  let binary_path = if cfg!(windows) {
+    temp_dir.path().join("binary.exe")
+  } else {
+    temp_dir.path().join("binary")
+  };
+
+  context
+    .new_command()
+    .args("compile --output binary --compress --code-cache --include assets main.ts")
+    .run()
+    .skip_output_check()
+    .assert_exit_code(0);
+  assets_dir.remove_dir_all();
+
+  context
+    .new_command()
+    .name(&binary_path)
+    .run()
+    .assert_matches_text("3\nHello from a compressed asset!\n")
+    .assert_exit_code(0);
+}
+
 #[test]
 fn compile_npm_specifiers() {
   let context = TestContextBuilder::for_npm().use_temp_cwd().build();
@@ -1,5 +1,6 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 
+use deno_core::serde_json;
 use test_util as util;
 use test_util::itest;
 use util::assert_contains;
@@ -142,3 +143,175 @@ fn deno_doc_html() {
     .join("~/MyClass.prototype.prop.html")
     .exists());
 }
+
+#[test]
+fn deno_doc_markdown() {
+  let context = TestContext::default();
+  let temp_dir = context.temp_dir();
+  let output = context
+    .new_command()
+    .env("NO_COLOR", "1")
+    .args_vec(vec![
+      "doc",
+      "--markdown",
+      "--name=MyLib",
+      &format!("--output={}", temp_dir.path().to_string_lossy()),
+      "doc/referenced_private_types_fixed.ts",
+    ])
+    .split_output()
+    .run();
+
+  output.assert_exit_code(0);
+  assert_contains!(output.stderr(), "Written 2 files to");
+  let index = temp_dir.read_to_string("index.md");
+  assert_contains!(index, "# MyLib");
+  assert_contains!(
+    index,
+    "- [doc/referenced_private_types_fixed.ts](doc_referenced_private_types_fixed.md)"
+  );
+  let module = temp_dir.read_to_string("doc_referenced_private_types_fixed.md");
+  assert_contains!(module, "## MyInterface");
+  assert_contains!(module, "## MyClass");
+  assert_contains!(module, "### MyClass.prop");
+}
+
+#[test]
+fn deno_doc_markdown_per_symbol() {
+  let context = TestContext::default();
+  let temp_dir = context.temp_dir();
+  // other files in the directory, such as the ones of a wiki, are kept
+  temp_dir.write("Home.md", "# Home");
+  let output = context
+    .new_command()
+    .env("NO_COLOR", "1")
+    .args_vec(vec![
+      "doc",
+      "--markdown=symbol",
+      &format!("--output={}", temp_dir.path().to_string_lossy()),
+      "doc/referenced_private_types_fixed.ts",
+    ])
+    .split_output()
+    .run();
+
+  output.assert_exit_code(0);
+  assert_contains!(output.stderr(), "Written 4 files to");
+  assert!(temp_dir.path().join("Home.md").exists());
+  assert!(temp_dir.path().join("index.md").exists());
+  let class =
+    temp_dir.read_to_string("doc_referenced_private_types_fixed.MyClass.md");
+  assert_contains!(class, "# MyClass");
+  assert_contains!(class, "prop: MyInterface");
+  assert!(temp_dir
+    .path()
+    .join("doc_referenced_private_types_fixed.MyInterface.md")
+    .exists());
+}
+
+#[test]
+fn deno_doc_man() {
+  let context = TestContext::default();
+  let temp_dir = context.temp_dir();
+  let output = context
+    .new_command()
+    .env("NO_COLOR", "1")
+    .args_vec(vec![
+      "doc",
+      "--man",
+      &format!("--output={}", temp_dir.path().to_string_lossy()),
+      "doc/man_page.ts",
+    ])
+    .split_output()
+    .run();
+
+  output.assert_exit_code(0);
+  assert_contains!(output.stderr(), "Written 2 files to");
+  let page = temp_dir.read_to_string("add.3");
+  assert_contains!(page, ".TH ADD 3");
+  assert_contains!(page, "add \\- Adds two numbers\n");
+  assert_contains!(page, ".B function add(a: number, b: number): number");
+  assert_contains!(page, ".TP\n.I a\nThe first number.\n");
+  assert_contains!(
+    page,
+    ".SH RETURN VALUE\nThe sum of \\fBa\\fR and \\fBb\\fR.\n"
+  );
+  assert_contains!(page, ".EX\nadd(1, 2);\n.EE\n");
+  let page = temp_dir.read_to_string("subtract.3");
+  assert_contains!(page, "see \\fBadd\\fR");
+}
+
+#[test]
+fn deno_doc_lint_coverage() {
+  let context = TestContext::default();
+  let output = context
+    .new_command()
+    .env("NO_COLOR", "1")
+    .args("doc --lint --coverage doc/man_page.ts")
+    .split_output()
+    .run();
+
+  output.assert_exit_code(0);
+  assert_contains!(
+    output.stdout(),
+    " doc/man_page.ts |     2/2 |        2/4 |     1/2 |    62.5 |"
+  );
+  assert_contains!(output.stdout(), " All files       |");
+
+  // documentation diagnostics don't fail the command when reporting coverage
+  let output = context
+    .new_command()
+    .env("NO_COLOR", "1")
+    .args("doc --lint --coverage doc/referenced_private_types.ts")
+    .split_output()
+    .run();
+  output.assert_exit_code(0);
+  assert_contains!(output.stderr(), "error[missing-jsdoc]");
+}
+
+#[test]
+fn deno_doc_lint_min_coverage() {
+  let context = TestContext::default();
+  let output = context
+    .new_command()
+    .env("NO_COLOR", "1")
+    .args("doc --lint --min-coverage=80 doc/man_page.ts")
+    .split_output()
+    .run();
+
+  output.assert_exit_code(1);
+  assert_contains!(
+    output.stderr(),
+    "Documentation coverage of 62.5% is below the minimum of 80%."
+  );
+
+  let output = context
+    .new_command()
+    .env("NO_COLOR", "1")
+    .args("doc --lint --min-coverage=60 doc/man_page.ts")
+    .split_output()
+    .run();
+  output.assert_exit_code(0);
+}
+
+#[test]
+fn deno_doc_lint_coverage_json() {
+  let context = TestContext::default();
+  let output = context
+    .new_command()
+    .args("doc --lint --coverage --json doc/man_page.ts")
+    .split_output()
+    .run();
+
+  output.assert_exit_code(0);
+  let report: serde_json::Value =
+    serde_json::from_str(output.stdout()).unwrap();
+  assert_eq!(report["modules"][0]["module"], "doc/man_page.ts");
+  assert_eq!(
+    report["total"],
+    serde_json::json!({
+      "symbols": { "documented": 2, "total": 2 },
+      "parameters": { "documented": 2, "total": 4 },
+      "returnValues": { "documented": 1, "total": 2 },
+      "percent": 62.5,
+    })
+  );
+}
@@ -169,3 +169,96 @@ Run these commands to get started
   output.assert_exit_code(0);
   output.assert_matches_text("Log from main.ts that already exists\n");
 }
+
+#[test]
+fn init_subcommand_with_template() {
+  let context = TestContextBuilder::for_jsr().use_temp_cwd().build();
+  let cwd = context.temp_dir().path();
+
+  let output = context
+    .new_command()
+    .args("init --template=serve --yes my_app")
+    .split_output()
+    .run();
+
+  output.assert_exit_code(0);
+
+  let stderr = output.stderr();
+  assert_contains!(stderr, "Project initialized");
+  assert_contains!(stderr, "cd my_app");
+  assert_contains!(stderr, "deno serve main.ts");
+  assert_contains!(stderr, "deno test");
+
+  assert!(cwd.join("my_app/deno.json").exists());
+  assert_contains!(cwd.join("my_app/main.ts").read_to_string(), "my_app");
+
+  let output = context
+    .new_command()
+    .env("NO_COLOR", "1")
+    .args("test my_app/main_test.ts")
+    .split_output()
+    .run();
+
+  output.assert_exit_code(0);
+  assert_contains!(output.stdout(), "2 passed");
+  output.skip_output_check();
+}
+
+#[test]
+fn init_subcommand_with_library_template() {
+  let context = TestContextBuilder::new().use_temp_cwd().build();
+  let cwd = context.temp_dir().path();
+
+  let output = context
+    .new_command()
+    .env("NO_COLOR", "1")
+    .args("init --template=library --yes my_lib")
+    .split_output()
+    .run();
+
+  output.assert_exit_code(0);
+  assert_contains!(
+    output.stderr(),
+    "Replace the placeholder scope '@your-scope'"
+  );
+  assert_contains!(
+    cwd.join("my_lib/deno.json").read_to_string(),
+    "\"name\": \"@your-scope/my-lib\""
+  );
+}
+
+#[test]
+fn init_subcommand_with_local_template() {
+  let context = TestContextBuilder::new().use_temp_cwd().build();
+  let cwd = context.temp_dir().path();
+
+  cwd.join("template/src").create_dir_all();
+  cwd
+    .join("template/src/main.ts")
+    .write("console.log('{{name}} by {{author}}');");
+
+  let output = context
+    .new_command()
+    .args("init --template=./template --yes my_app")
+    .split_output()
+    .run();
+
+  output.assert_exit_code(0);
+  assert_contains!(output.stderr(), "Project initialized");
+  let main_ts = cwd.join("my_app/src/main.ts").read_to_string();
+  assert_contains!(main_ts, "console.log('my_app by ");
+}
+
+#[test]
+fn init_subcommand_with_unknown_template() {
+  let context = TestContextBuilder::new().use_temp_cwd().build();
+
+  let output = context
+    .new_command()
+    .args("init --template=unknown")
+    .split_output()
+    .run();
+
+  output.assert_exit_code(1);
+  assert_contains!(output.stderr(), "Unknown template 'unknown'");
+}
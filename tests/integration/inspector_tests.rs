@@ -1438,3 +1438,153 @@ async fn inspector_wait() {
   assert!(temp_dir.path().join("hello.txt").exists());
   tester.child.kill().unwrap();
 }
+
+/// A client of the Debug Adapter Protocol server of `--inspect-dap`.
+struct DapClient {
+  reader: tokio::io::BufReader<tokio::net::tcp::OwnedReadHalf>,
+  writer: tokio::net::tcp::OwnedWriteHalf,
+  seq: i64,
+}
+
+impl DapClient {
+  async fn connect(addr: &str) -> Self {
+    let stream = TcpStream::connect(addr).await.unwrap();
+    let (reader, writer) = stream.into_split();
+    Self {
+      reader: tokio::io::BufReader::new(reader),
+      writer,
+      seq: 0,
+    }
+  }
+
+  async fn request(
+    &mut self,
+    command: &str,
+    arguments: serde_json::Value,
+  ) -> serde_json::Value {
+    use tokio::io::AsyncWriteExt;
+
+    self.seq += 1;
+    let seq = self.seq;
+    let content = json!({
+      "seq": seq,
+      "type": "request",
+      "command": command,
+      "arguments": arguments,
+    })
+    .to_string();
+    let message =
+      format!("Content-Length: {}\r\n\r\n{}", content.len(), content);
+    self.writer.write_all(message.as_bytes()).await.unwrap();
+    let response = self
+      .recv_until(|message| {
+        message["type"] == "response" && message["request_seq"] == seq
+      })
+      .await;
+    assert_eq!(response["success"], true, "{response}");
+    response
+  }
+
+  async fn recv_event(&mut self, event: &str) -> serde_json::Value {
+    self
+      .recv_until(|message| {
+        message["type"] == "event" && message["event"] == event
+      })
+      .await
+  }
+
+  async fn recv_until(
+    &mut self,
+    predicate: impl Fn(&serde_json::Value) -> bool,
+  ) -> serde_json::Value {
+    loop {
+      let message = timeout(Duration::from_secs(10), self.recv())
+        .await
+        .expect("timed out waiting for a DAP message");
+      if predicate(&message) {
+        return message;
+      }
+    }
+  }
+
+  async fn recv(&mut self) -> serde_json::Value {
+    use tokio::io::AsyncBufReadExt;
+    use tokio::io::AsyncReadExt;
+
+    let mut content_length = 0;
+    loop {
+      let mut line = String::new();
+      self.reader.read_line(&mut line).await.unwrap();
+      let line = line.trim_end();
+      if line.is_empty() {
+        break;
+      }
+      if let Some(length) = line.strip_prefix("Content-Length: ") {
+        content_length = length.parse().unwrap();
+      }
+    }
+    let mut content = vec![0; content_length];
+    self.reader.read_exact(&mut content).await.unwrap();
+    serde_json::from_slice(&content).unwrap()
+  }
+}
+
+#[tokio::test]
+async fn inspector_dap_breakpoint() {
+  let script = util::testdata_path().join("inspector/inspect_dap.js");
+  let mut child = util::deno_cmd()
+    .arg("run")
+    .arg(inspect_flag_with_unique_port("--inspect-dap"))
+    .arg(&script)
+    .piped_output()
+    .spawn()
+    .unwrap();
+
+  let stderr = child.stderr.as_mut().unwrap();
+  let mut stderr_lines =
+    std::io::BufReader::new(stderr).lines().map(|r| r.unwrap());
+  let line = skip_check_line(&mut stderr_lines);
+  let addr = line
+    .strip_prefix("Debug adapter listening on ")
+    .unwrap_or_else(|| panic!("unexpected line: {line}"))
+    .to_string();
+  assert_eq!(
+    &skip_check_line(&mut stderr_lines),
+    "Deno is waiting for debugger to connect."
+  );
+
+  let mut client = DapClient::connect(&addr).await;
+  client
+    .request("initialize", json!({ "adapterID": "deno" }))
+    .await;
+  client.recv_event("initialized").await;
+  client.request("launch", json!({})).await;
+  let response = client
+    .request(
+      "setBreakpoints",
+      json!({
+        "source": { "path": script.to_string() },
+        "breakpoints": [{ "line": 3 }],
+      }),
+    )
+    .await;
+  assert_eq!(response["body"]["breakpoints"][0]["verified"], true);
+  client.request("configurationDone", json!({})).await;
+
+  let stopped = client.recv_event("stopped").await;
+  assert_eq!(stopped["body"]["reason"], "breakpoint");
+
+  let response = client.request("stackTrace", json!({ "threadId": 1 })).await;
+  let frame = &response["body"]["stackFrames"][0];
+  assert_eq!(frame["line"], 3);
+  assert_eq!(frame["source"]["path"], script.to_string());
+
+  client.request("continue", json!({ "threadId": 1 })).await;
+  client.recv_event("continued").await;
+
+  let stdout = child.stdout.as_mut().unwrap();
+  let mut stdout_lines =
+    std::io::BufReader::new(stdout).lines().map(|r| r.unwrap());
+  assert_eq!(stdout_lines.next().unwrap(), "2");
+  child.wait().unwrap();
+}
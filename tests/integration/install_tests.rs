@@ -331,3 +331,66 @@ fn check_local_by_default2() {
     .skip_output_check()
     .assert_exit_code(0);
 }
+
+#[test]
+fn install_local_dependencies() {
+  let context = TestContextBuilder::for_npm().use_temp_cwd().build();
+  let temp_dir = context.temp_dir();
+  temp_dir.write(
+    "deno.json",
+    r#"{
+  "nodeModulesDir": true,
+  "imports": {
+    "@denotest/esm-basic": "npm:@denotest/esm-basic@1.0.0"
+  }
+}"#,
+  );
+
+  context
+    .new_command()
+    .args("install")
+    .run()
+    .skip_output_check()
+    .assert_exit_code(0);
+  assert!(temp_dir
+    .path()
+    .join("node_modules/@denotest/esm-basic/package.json")
+    .exists());
+  let lockfile = temp_dir.read_to_string("deno.lock");
+  assert_contains!(lockfile, "npm:@denotest/esm-basic@1.0.0");
+
+  // nothing changed, so a frozen install succeeds
+  context
+    .new_command()
+    .args("install --frozen")
+    .run()
+    .skip_output_check()
+    .assert_exit_code(0);
+
+  temp_dir.write(
+    "deno.json",
+    r#"{
+  "nodeModulesDir": true,
+  "imports": {
+    "@denotest/esm-basic": "npm:@denotest/esm-basic@1.0.0",
+    "@denotest/esm-import-cjs-default": "npm:@denotest/esm-import-cjs-default@1.0.0"
+  }
+}"#,
+  );
+  context
+    .new_command()
+    .args("install --frozen")
+    .run()
+    .assert_matches_text(
+      "[WILDCARD]error: The lockfile is out of date, \"npm:@denotest/esm-import-cjs-default@1.0.0\" is missing from it. Run `deno install` without --frozen to update it.
+    at [WILDCARD]deno.lock
+",
+    )
+    .assert_exit_code(1);
+  assert_eq!(temp_dir.read_to_string("deno.lock"), lockfile);
+  // nothing was installed
+  assert!(!temp_dir
+    .path()
+    .join("node_modules/@denotest/esm-import-cjs-default")
+    .exists());
+}
@@ -629,3 +629,214 @@ async fn jupyter_store_history_false() -> Result<()> {
 
   Ok(())
 }
+
+#[tokio::test]
+async fn jupyter_comms() -> Result<()> {
+  let (_ctx, client, _process) = setup().await;
+  client
+    .send(
+      Shell,
+      "execute_request",
+      json!({
+        "silent": false,
+        "store_history": true,
+        "code": r#"Deno.jupyter.registerCommTarget("test.echo", (comm) => {
+          comm.onMessage((data) => comm.send({ echo: data.value }));
+        });"#
+      }),
+    )
+    .await?;
+  let reply = client.recv(Shell).await?;
+  assert_json_subset(reply.content, json!({ "status": "ok" }));
+
+  client
+    .send(
+      Shell,
+      "comm_open",
+      json!({
+        "comm_id": "comm-1",
+        "target_name": "test.echo",
+        "data": {},
+      }),
+    )
+    .await?;
+  client
+    .send(
+      Shell,
+      "comm_msg",
+      json!({
+        "comm_id": "comm-1",
+        "data": { "value": 42 },
+      }),
+    )
+    .await?;
+  let comm_msg = loop {
+    let msg = client.recv(IoPub).await?;
+    if msg.header.msg_type == "comm_msg" {
+      break msg;
+    }
+  };
+  assert_json_subset(
+    comm_msg.content,
+    json!({
+      "comm_id": "comm-1",
+      "data": { "echo": 42 },
+    }),
+  );
+
+  client.send(Shell, "comm_info_request", json!({})).await?;
+  let reply = client.recv(Shell).await?;
+  assert_eq!(reply.header.msg_type, "comm_info_reply");
+  assert_json_subset(
+    reply.content,
+    json!({
+      "status": "ok",
+      "comms": {
+        "comm-1": { "target_name": "test.echo" },
+      },
+    }),
+  );
+
+  // comms for unregistered targets are closed
+  client
+    .send(
+      Shell,
+      "comm_open",
+      json!({
+        "comm_id": "comm-2",
+        "target_name": "test.unknown",
+        "data": {},
+      }),
+    )
+    .await?;
+  let comm_close = loop {
+    let msg = client.recv(IoPub).await?;
+    if msg.header.msg_type == "comm_close" {
+      break msg;
+    }
+  };
+  assert_json_subset(comm_close.content, json!({ "comm_id": "comm-2" }));
+
+  Ok(())
+}
+
+#[tokio::test]
+async fn jupyter_history_request() -> Result<()> {
+  let (_ctx, client, _process) = setup().await;
+  for code in ["const a = 1;", "a + 1"] {
+    client
+      .send(
+        Shell,
+        "execute_request",
+        json!({
+          "silent": false,
+          "store_history": true,
+          "code": code,
+        }),
+      )
+      .await?;
+    let reply = client.recv(Shell).await?;
+    assert_eq!(reply.header.msg_type, "execute_reply");
+  }
+
+  client
+    .send(
+      Shell,
+      "history_request",
+      json!({
+        "output": false,
+        "raw": true,
+        "hist_access_type": "tail",
+        "n": 10,
+      }),
+    )
+    .await?;
+  let reply = client.recv(Shell).await?;
+  assert_eq!(reply.header.msg_type, "history_reply");
+  assert_eq!(
+    reply.content,
+    json!({
+      "status": "ok",
+      "history": [[1, 1, "const a = 1;"], [1, 2, "a + 1"]],
+    }),
+  );
+
+  Ok(())
+}
+
+#[tokio::test]
+async fn jupyter_inspect_request() -> Result<()> {
+  let (_ctx, client, _process) = setup().await;
+  client
+    .send(
+      Shell,
+      "inspect_request",
+      json!({
+        "code": "Deno.readTextFile",
+        "cursor_pos": 8,
+        "detail_level": 0,
+      }),
+    )
+    .await?;
+  let reply = client.recv(Shell).await?;
+  assert_eq!(reply.header.msg_type, "inspect_reply");
+  assert_json_subset(
+    reply.content.clone(),
+    json!({
+      "status": "ok",
+      "found": true,
+    }),
+  );
+  let markdown = reply.content["data"]["text/markdown"].as_str().unwrap();
+  assert!(markdown.contains("readTextFile"), "{markdown}");
+
+  Ok(())
+}
+
+#[tokio::test]
+async fn jupyter_interrupt_request() -> Result<()> {
+  let (_ctx, client, _process) = setup().await;
+  client
+    .send(
+      Shell,
+      "execute_request",
+      json!({
+        "silent": false,
+        "store_history": true,
+        "code": "globalThis.value = 5; while (true) {}",
+      }),
+    )
+    .await?;
+  // give the cell time to start executing
+  tokio::time::sleep(Duration::from_millis(500)).await;
+  client.send(Control, "interrupt_request", json!({})).await?;
+  let reply = client.recv(Control).await?;
+  assert_eq!(reply.header.msg_type, "interrupt_reply");
+
+  let reply = client.recv(Shell).await?;
+  assert_eq!(reply.header.msg_type, "execute_reply");
+  assert_json_subset(
+    reply.content,
+    json!({
+      "status": "error",
+      "ename": "Interrupted",
+    }),
+  );
+
+  // the kernel keeps its state after an interrupt
+  client
+    .send(
+      Shell,
+      "execute_request",
+      json!({
+        "silent": false,
+        "store_history": true,
+        "code": "console.log(globalThis.value)",
+      }),
+    )
+    .await?;
+  let reply = client.recv(Shell).await?;
+  assert_json_subset(reply.content, json!({ "status": "ok" }));
+
+  Ok(())
+}
@@ -1162,6 +1162,40 @@ fn lsp_deno_task() {
   client.shutdown();
 }
 
+#[test]
+fn lsp_unused_exports() {
+  let context = TestContextBuilder::new().use_temp_cwd().build();
+  let mut client = context.new_lsp_command().build();
+  client.initialize_default();
+  client.did_open(json!({
+    "textDocument": {
+      "uri": "file:///a/lib.ts",
+      "languageId": "typescript",
+      "version": 1,
+      "text": "export function used() {\n  return 1;\n}\n\nexport const unused = 2;\n",
+    }
+  }));
+  client.did_open(json!({
+    "textDocument": {
+      "uri": "file:///a/main.ts",
+      "languageId": "typescript",
+      "version": 1,
+      "text": "import { used } from \"./lib.ts\";\n\nconsole.log(used());\n",
+    }
+  }));
+
+  let res = client.write_request("deno/unusedExports", json!(null));
+  let unused_exports = res
+    .as_array()
+    .unwrap()
+    .iter()
+    .map(|e| (e["uri"].as_str().unwrap(), e["name"].as_str().unwrap()))
+    .collect::<Vec<_>>();
+  assert_eq!(unused_exports, vec![("file:///a/lib.ts", "unused")]);
+  assert_eq!(res[0]["range"]["start"]["line"], 4);
+  client.shutdown();
+}
+
 #[test]
 fn lsp_reload_import_registries_command() {
   let context = TestContextBuilder::new().use_temp_cwd().build();
@@ -6886,6 +6920,54 @@ fn lsp_completions_auto_import() {
   client.shutdown();
 }
 
+#[test]
+fn lsp_completions_auto_import_embedded() {
+  let context = TestContextBuilder::new().use_temp_cwd().build();
+  let mut client = context.new_lsp_command().build();
+  client.initialize_default();
+  client.did_open(json!({
+    "textDocument": {
+      "uri": "file:///a/foo.ts",
+      "languageId": "typescript",
+      "version": 1,
+      "text": "export const foo = \"foo\";\n",
+    }
+  }));
+  client.did_open_raw(json!({
+    "textDocument": {
+      "uri": "file:///a/README.md",
+      "languageId": "markdown",
+      "version": 1,
+      "text": "# Title\n\n```ts\nexport {};\nfo\n```\n",
+    }
+  }));
+  let list = client.get_completion_list(
+    "file:///a/README.md",
+    (4, 2),
+    json!({ "triggerKind": 1 }),
+  );
+  let item = list
+    .items
+    .iter()
+    .find(|item| item.label == "foo")
+    .expect("completions items missing 'foo' symbol");
+  let res = client.write_request("completionItem/resolve", item);
+  // the import is added at the start of the code block, not of the file
+  assert_eq!(
+    res["additionalTextEdits"],
+    json!([
+      {
+        "range": {
+          "start": { "line": 3, "character": 0 },
+          "end": { "line": 3, "character": 0 }
+        },
+        "newText": "import { foo } from \"./foo.ts\";\n\n"
+      }
+    ])
+  );
+  client.shutdown();
+}
+
 #[test]
 fn lsp_npm_completions_auto_import_and_quick_fix_no_import_map() {
   let context = TestContextBuilder::new()
@@ -146,6 +146,83 @@ fn add_npm() {
   }));
 }
 
+#[test]
+fn remove_basic() {
+  let context = pm_context_builder().build();
+  let temp_dir = context.temp_dir().path();
+  temp_dir.join("deno.json").write_json(&json!({
+    "imports": {
+      "@denotest/add": "jsr:@denotest/add@^1.0.0",
+      "chalk": "npm:chalk@^4.1.2"
+    }
+  }));
+  temp_dir.join("package.json").write_json(&json!({
+    "dependencies": {
+      "chalk": "^4.1.2"
+    }
+  }));
+
+  let output = context
+    .new_command()
+    .args("remove @denotest/add npm:chalk")
+    .run();
+  output.assert_exit_code(0);
+  let output = output.combined_output();
+  assert_contains!(output, "Remove @denotest/add");
+  assert_contains!(output, "Remove chalk");
+  temp_dir.join("deno.json").assert_matches_json(json!({
+    "imports": {}
+  }));
+  temp_dir.join("package.json").assert_matches_json(json!({
+    "dependencies": {}
+  }));
+
+  let output = context.new_command().args("remove @denotest/add").run();
+  output.assert_exit_code(0);
+  assert_contains!(
+    output.combined_output(),
+    "@denotest/add was not found in the dependencies."
+  );
+}
+
+#[test]
+fn outdated_update_latest() {
+  let context = pm_context_builder().build();
+  let temp_dir = context.temp_dir().path();
+  temp_dir.join("deno.json").write_json(&json!({
+    "imports": {
+      "@denotest/add": "jsr:@denotest/add@^0.2.0"
+    }
+  }));
+
+  let output = context.new_command().args("outdated").run();
+  output.assert_exit_code(0);
+  let output = output.combined_output();
+  assert_contains!(output, "Package");
+  assert_contains!(output, "jsr:@denotest/add  -        0.2.0   1.0.0");
+
+  // the newest version allowed by the constraint is already used
+  let output = context.new_command().args("outdated --update").run();
+  output.assert_exit_code(0);
+  temp_dir.join("deno.json").assert_matches_json(json!({
+    "imports": {
+      "@denotest/add": "jsr:@denotest/add@^0.2.0"
+    }
+  }));
+
+  let output = context
+    .new_command()
+    .args("outdated --update --latest")
+    .run();
+  output.assert_exit_code(0);
+  assert_contains!(output.combined_output(), "Update @denotest/add to ^1.0.0");
+  temp_dir.join("deno.json").assert_matches_json(json!({
+    "imports": {
+      "@denotest/add": "jsr:@denotest/add@^1.0.0"
+    }
+  }));
+}
+
 fn pm_context_builder() -> TestContextBuilder {
   TestContextBuilder::new()
     .use_http_server()
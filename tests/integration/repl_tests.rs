@@ -1131,3 +1131,113 @@ fn eval_file_promise_error() {
   assert_contains!(out, "Uncaught undefined");
   assert!(err.is_empty());
 }
+
+#[test]
+fn repl_save_and_load_session() {
+  let context = TestContextBuilder::new().use_temp_cwd().build();
+  let temp_dir = context.temp_dir();
+  context
+    .new_command()
+    .args_vec(["repl", "-A"])
+    .with_pty(|mut console| {
+      console.write_line("const a = 1;");
+      console.expect("undefined");
+      console.write_line("throw new Error('oops');");
+      console.expect("Uncaught Error: oops");
+      console.write_line("const b = a + 1;");
+      console.expect("undefined");
+      console.write_line(".save session.ts");
+      console.expect("Session saved to: session.ts");
+    });
+  // failed inputs aren't saved
+  assert_eq!(
+    temp_dir.path().join("session.ts").read_to_string(),
+    "const a = 1;\nconst b = a + 1;\n"
+  );
+
+  context
+    .new_command()
+    .args_vec(["repl", "-A"])
+    .with_pty(|mut console| {
+      console.write_line(".load session.ts");
+      console.write_line("b + 1");
+      console.expect("3");
+    });
+}
+
+#[test]
+fn repl_type_check() {
+  util::with_pty(&["repl", "--check"], |mut console| {
+    console.write_line("const a: number = 1;");
+    console.expect("undefined");
+    console.write_line("const b: string = a;");
+    console.expect(
+      "TS2322 [ERROR]: Type 'number' is not assignable to type 'string'.",
+    );
+    console.expect("$deno$repl.ts:1:7");
+    // inputs with type errors aren't evaluated
+    console.write_line("typeof b");
+    console.expect("TS2304 [ERROR]: Cannot find name 'b'.");
+    console.write_line("({ value: a + 1 })");
+    console.expect("{ value: 2 }");
+    console.write_line(".check");
+    console.expect("Type checking disabled");
+    console.write_line("const c: string = a;");
+    console.expect("undefined");
+    console.write_line("c");
+    console.expect("1");
+  });
+}
+
+#[test]
+fn repl_type_check_redeclaration() {
+  util::with_pty(&["repl", "--check"], |mut console| {
+    console.write_line("let value = 1;");
+    console.expect("undefined");
+    // redeclaring a binding is allowed and changes its type
+    console.write_line("let value = \"a\";");
+    console.expect("undefined");
+    console.write_line("value.toUpperCase()");
+    console.expect("\"A\"");
+    // inputs that throw aren't used as context for later inputs
+    console.write_line(
+      "let thrown: number = (() => { throw new Error(\"boom\"); })();",
+    );
+    console.expect("Uncaught Error: boom");
+    console.write_line("let thrown = \"ok\";");
+    console.expect("undefined");
+  });
+}
+
+#[test]
+fn repl_editor_mode() {
+  util::with_pty(&["repl"], |mut console| {
+    console.write_line(".editor");
+    console.expect("Entering editor mode");
+    console.write_line("function add(a, b) {");
+    console.write_line("  return a + b;");
+    console.write_line("}");
+    console.write_raw("\u{4}");
+    console.write_line("add(1, 2)");
+    console.expect("3");
+  });
+}
+
+#[test]
+fn repl_project_history() {
+  let context = TestContextBuilder::new().use_temp_cwd().build();
+  context.temp_dir().write("deno.json", "{}");
+  context
+    .new_command()
+    .args_vec(["repl"])
+    .with_pty(|mut console| {
+      console.write_line("1 + 1");
+      console.expect("2");
+    });
+  let deno_dir = context.deno_dir().path();
+  assert!(!deno_dir.join("deno_history.txt").exists());
+  let history_files = std::fs::read_dir(deno_dir.join("repl_history"))
+    .unwrap()
+    .collect::<Vec<_>>();
+  assert_eq!(history_files.len(), 1);
+}
@@ -0,0 +1,6 @@
+try {
+  await import("node:fs");
+} catch (err) {
+  console.log((err as Error).message);
+}
+console.log("done");
@@ -0,0 +1,6 @@
+import { helper } from "./helper.ts";
+import { greet } from "./shared.ts";
+
+export function lazy() {
+  greet(helper());
+}
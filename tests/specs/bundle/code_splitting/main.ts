@@ -0,0 +1,5 @@
+import { greet } from "./shared.ts";
+
+greet("main");
+const { lazy } = await import("./lazy.ts");
+lazy();
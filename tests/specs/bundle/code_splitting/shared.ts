@@ -0,0 +1,3 @@
+export function greet(name: string) {
+  console.log(`Hello ${name}`);
+}
@@ -0,0 +1,5 @@
+import { count, increment } from "./main.bundle.js";
+
+console.log(count);
+increment();
+console.log(count);
@@ -0,0 +1,4 @@
+import { add } from "./math.ts";
+
+// this comment is removed
+console.log(add(1, 2));
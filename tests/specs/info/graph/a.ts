@@ -0,0 +1,7 @@
+import { b } from "./b.ts";
+
+export const a = "a";
+
+export function getB() {
+  return b;
+}
@@ -0,0 +1,7 @@
+import { getB } from "./a.ts";
+
+export const b = "b";
+
+export function getA() {
+  return getB;
+}
@@ -0,0 +1,5 @@
+import { a } from "./a.ts";
+
+console.log(a);
+const { c } = await import("./c.ts");
+console.log(c);
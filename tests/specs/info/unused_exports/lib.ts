@@ -0,0 +1,5 @@
+export function used() {
+  return 1;
+}
+
+export const unused = 2;
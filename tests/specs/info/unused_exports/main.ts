@@ -0,0 +1,3 @@
+import { used } from "./lib.ts";
+
+console.log(used());
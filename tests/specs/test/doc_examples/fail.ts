@@ -0,0 +1,10 @@
+/**
+ * ```ts
+ * import { double } from "./fail.ts";
+ *
+ * double(2); // => 5
+ * ```
+ */
+export function double(n: number): number {
+  return n * 2;
+}
@@ -0,0 +1,50 @@
+/**
+ * Adds two numbers.
+ *
+ * ```ts
+ * import { add } from "./mod.ts";
+ *
+ * add(1, 2); // => 3
+ * add(0.1, 0.2) > 0.3;
+ * // => true
+ * ```
+ */
+export function add(a: number, b: number): number {
+  return a + b;
+}
+
+/**
+ * Greets someone.
+ *
+ * ```ts
+ * import { greet } from "./mod.ts";
+ *
+ * console.log(greet("Deno"));
+ * ```
+ *
+ * ```output
+ * Hello, Deno!
+ * ```
+ */
+export function greet(name: string): string {
+  return `Hello, ${name}!`;
+}
+
+/**
+ * Parses JSON.
+ *
+ * ```ts throws
+ * import { parse } from "./mod.ts";
+ *
+ * parse("{");
+ * ```
+ *
+ * ```ts no_run
+ * import { parse } from "./mod.ts";
+ *
+ * parse(await Deno.readTextFile("config.json"));
+ * ```
+ */
+export function parse(text: string): unknown {
+  return JSON.parse(text);
+}
@@ -2,8 +2,13 @@
 // deno-lint-ignore-file
 // This code was bundled using `deno bundle` and it's not recommended to edit it manually
 
-const makeParagraph = ()=>jsx("p", {
-        children: "A paragraph!"
-    });
-export { makeParagraph as makeParagraph };
+const __bundle = [WILDCARD]
+[WILDCARD]
+const [__exports, __meta, __deps, __evaluated] = __bundle.enter(0, "file:///[WILDCARD]/main.tsx", [1], import.meta.main === true);
+__bundle.export(__exports, { "makeParagraph": () => makeParagraph });
+const [__d0] = await __deps();
+[WILDCARD]const makeParagraph = ()=>(0, __d0.jsx)("p", {
+[WILDCARD]
+export { makeParagraph };
+__evaluated();
 
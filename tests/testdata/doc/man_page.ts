@@ -0,0 +1,19 @@
+/**
+ * Adds two numbers. The result may lose precision for large numbers.
+ *
+ * ```ts
+ * add(1, 2);
+ * ```
+ *
+ * @param a The first number.
+ * @param b The second number.
+ * @returns The sum of `a` and `b`.
+ */
+export function add(a: number, b: number): number {
+  return a + b;
+}
+
+/** Subtracts `b` from `a`, see {@link add}. */
+export function subtract(a: number, b: number): number {
+  return a - b;
+}